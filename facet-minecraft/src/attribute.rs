@@ -15,9 +15,17 @@ facet::define_attr_grammar! {
     pub enum Attr {
         /// Marks a field as variably-sized.
         Variable,
+        /// Marks a field as encoded using network NBT.
+        Nbt,
+        /// Marks a string field as encoded using Java's Modified UTF-8.
+        Mutf8,
         /// Specifies custom serialization function for a field.
         Serialize(fn_ptr SerializeFn),
         /// Specifies custom deserialization function for a field.
         Deserialize(fn_ptr DeserializeFn),
+        /// The first protocol version a field or variant is present in.
+        Since(usize),
+        /// The last protocol version a field or variant is present in.
+        Until(usize),
     }
 }
@@ -0,0 +1,85 @@
+use core::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// An error that occurred while handling signed chat.
+#[derive(Debug)]
+pub struct ChatError {
+    kind: ChatErrorKind,
+}
+
+impl ChatError {
+    /// Create a new [`ChatError`].
+    #[inline]
+    #[must_use]
+    pub const fn new(kind: ChatErrorKind) -> Self { Self { kind } }
+
+    /// Get the kind of chat error.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> &ChatErrorKind { &self.kind }
+}
+
+/// The type of chat error.
+#[derive(Debug)]
+pub enum ChatErrorKind {
+    /// A list of last seen messages was longer than the window.
+    TooManyEntries(usize),
+    /// An update advanced the window by more messages than were tracked.
+    InvalidOffset {
+        /// The offset of the update.
+        offset: u32,
+        /// The largest allowed offset.
+        max: u32,
+    },
+    /// An update acknowledged a message outside of the window.
+    OutsideWindow,
+    /// An update acknowledged a message that is unknown or was ignored.
+    UnknownMessage(usize),
+    /// An update ignored a message that was previously acknowledged.
+    IgnoredMessage(usize),
+    /// The checksum of an update did not match the acknowledged messages.
+    ChecksumMismatch {
+        /// The checksum of the acknowledged messages.
+        expected: u8,
+        /// The checksum sent in the update.
+        found: u8,
+    },
+    /// A player's public key was longer than the vanilla limit.
+    KeyTooLong(usize),
+    /// A public key signature was longer than the vanilla limit.
+    SignatureTooLong(usize),
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Error for ChatError {}
+impl Display for ChatError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ChatErrorKind::TooManyEntries(count) => {
+                write!(f, "{count} last seen messages exceed the window")
+            }
+            ChatErrorKind::InvalidOffset { offset, max } => {
+                write!(f, "advanced last seen window by {offset} messages, expected at most {max}")
+            }
+            ChatErrorKind::OutsideWindow => {
+                f.write_str("last seen update acknowledged a message outside the window")
+            }
+            ChatErrorKind::UnknownMessage(index) => {
+                write!(f, "last seen update acknowledged an unknown message at index {index}")
+            }
+            ChatErrorKind::IgnoredMessage(index) => {
+                write!(f, "last seen update ignored an acknowledged message at index {index}")
+            }
+            ChatErrorKind::ChecksumMismatch { expected, found } => {
+                write!(f, "last seen checksum mismatch, expected {expected} but found {found}")
+            }
+            ChatErrorKind::KeyTooLong(len) => write!(f, "player public key is {len} bytes long"),
+            ChatErrorKind::SignatureTooLong(len) => {
+                write!(f, "player public key signature is {len} bytes long")
+            }
+        }
+    }
+}
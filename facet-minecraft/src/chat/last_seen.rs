@@ -0,0 +1,261 @@
+use alloc::{vec, vec::Vec};
+
+use facet::{Facet, Shape};
+
+use super::{ChatError, ChatErrorKind, MessageSignature, PackedSignature};
+use crate::{
+    codec::{McCodec, McReader, McWriter, codec_shape},
+    deserialize::DeserializeError,
+    packet::ProtocolVersion,
+    serialize::SerializeError,
+};
+
+/// The messages a client has seen, in the order they were received.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LastSeenMessages(pub Vec<MessageSignature>);
+
+impl LastSeenMessages {
+    /// The number of messages a client remembers.
+    pub const WINDOW: usize = 20;
+
+    /// Create a new, empty [`LastSeenMessages`].
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self { Self(Vec::new()) }
+
+    /// Get the checksum of the messages, as sent in a [`LastSeenUpdate`].
+    ///
+    /// The checksum is never [`LastSeenUpdate::IGNORE_CHECKSUM`].
+    #[must_use]
+    pub fn checksum(&self) -> u8 {
+        let hash = self
+            .0
+            .iter()
+            .fold(1i32, |hash, signature| hash.wrapping_mul(31).wrapping_add(signature.checksum()));
+        match hash.to_le_bytes()[0] {
+            0 => 1,
+            byte => byte,
+        }
+    }
+}
+
+/// The messages a client has seen, as references to cached signatures.
+///
+/// Sent in the Player Chat packet, prefixed by a `VarInt` count.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PackedLastSeenMessages(pub Vec<PackedSignature>);
+
+impl McCodec for PackedLastSeenMessages {
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        if self.0.len() > LastSeenMessages::WINDOW {
+            return Err(ChatError::new(ChatErrorKind::TooManyEntries(self.0.len())).into());
+        }
+        writer.write_var_u32(u32::try_from(self.0.len()).unwrap_or(u32::MAX))?;
+        self.0.iter().try_for_each(|signature| signature.encode(writer))
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        let len = reader.read_var_u32()? as usize;
+        if len > LastSeenMessages::WINDOW {
+            return Err(ChatError::new(ChatErrorKind::TooManyEntries(len)).into());
+        }
+        (0..len).map(|_| PackedSignature::decode(reader)).collect::<Result<_, _>>().map(Self)
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for PackedLastSeenMessages {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("PackedLastSeenMessages").build() };
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// A client's acknowledgement of the messages it has seen,
+/// sent with every chat message and command.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LastSeenUpdate {
+    /// The number of messages the window has advanced by.
+    pub offset: u32,
+    /// A bit for each message in the window, set if it was acknowledged.
+    pub acknowledged: u32,
+    /// The [`LastSeenMessages::checksum`] of the acknowledged messages,
+    /// or [`LastSeenUpdate::IGNORE_CHECKSUM`].
+    pub checksum: u8,
+}
+
+impl LastSeenUpdate {
+    /// The number of bytes used for the acknowledged bits.
+    const ACKNOWLEDGED_BYTES: usize = LastSeenMessages::WINDOW.div_ceil(8);
+    /// A checksum that is not checked.
+    pub const IGNORE_CHECKSUM: u8 = 0;
+
+    /// Returns `true` if the message at an index in the window
+    /// was acknowledged.
+    #[must_use]
+    pub const fn is_acknowledged(&self, index: usize) -> bool {
+        index < 32 && self.acknowledged & 1 << index != 0
+    }
+
+    /// Returns `true` if the checksum is ignored or matches the messages.
+    #[must_use]
+    pub fn verify_checksum(&self, messages: &LastSeenMessages) -> bool {
+        self.checksum == Self::IGNORE_CHECKSUM || self.checksum == messages.checksum()
+    }
+
+    /// Write the update for a protocol version.
+    ///
+    /// The checksum is only sent since 1.21.5.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer cannot be written to.
+    pub fn encode_for(
+        &self,
+        writer: &mut McWriter<'_>,
+        version: ProtocolVersion,
+    ) -> Result<(), SerializeError> {
+        writer.write_var_u32(self.offset)?;
+        writer.write_bytes(&self.acknowledged.to_le_bytes()[..Self::ACKNOWLEDGED_BYTES])?;
+        if version >= ProtocolVersion::V1_21_5 { writer.write_u8(self.checksum) } else { Ok(()) }
+    }
+
+    /// Read an update for a protocol version.
+    ///
+    /// Updates from before 1.21.5 use [`LastSeenUpdate::IGNORE_CHECKSUM`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input ends unexpectedly.
+    pub fn decode_for(
+        reader: &mut McReader<'_>,
+        version: ProtocolVersion,
+    ) -> Result<Self, DeserializeError> {
+        let offset = reader.read_var_u32()?;
+        let mut acknowledged = [0; 4];
+        acknowledged[..Self::ACKNOWLEDGED_BYTES]
+            .copy_from_slice(reader.read_bytes(Self::ACKNOWLEDGED_BYTES)?);
+        let checksum = if version >= ProtocolVersion::V1_21_5 {
+            reader.read_u8()?
+        } else {
+            Self::IGNORE_CHECKSUM
+        };
+        Ok(Self { offset, acknowledged: u32::from_le_bytes(acknowledged), checksum })
+    }
+}
+
+impl McCodec for LastSeenUpdate {
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        self.encode_for(writer, writer.version())
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        Self::decode_for(reader, reader.version())
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for LastSeenUpdate {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("LastSeenUpdate").build() };
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Tracks the messages sent to a client and validates its
+/// [`LastSeenUpdate`]s, as done by the vanilla server.
+///
+/// Call [`LastSeenTracker::add_pending`] for every signed message sent to the
+/// client, and [`LastSeenTracker::apply_update`] for every update received.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LastSeenTracker {
+    entries: Vec<Option<TrackedMessage>>,
+    last_pending: Option<MessageSignature>,
+}
+
+/// A message in a [`LastSeenTracker`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TrackedMessage {
+    signature: MessageSignature,
+    pending: bool,
+}
+
+impl LastSeenTracker {
+    /// Create a new [`LastSeenTracker`] with an empty window.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { entries: vec![None; LastSeenMessages::WINDOW], last_pending: None }
+    }
+
+    /// Get the number of messages in and after the window.
+    #[inline]
+    #[must_use]
+    pub fn tracked_count(&self) -> usize { self.entries.len() }
+
+    /// Track a message sent to the client.
+    ///
+    /// The same message is not tracked twice in a row.
+    pub fn add_pending(&mut self, signature: MessageSignature) {
+        if self.last_pending.as_ref() != Some(&signature) {
+            self.entries.push(Some(TrackedMessage { signature: signature.clone(), pending: true }));
+            self.last_pending = Some(signature);
+        }
+    }
+
+    /// Advance the window by a number of messages.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the window would advance past the tracked messages.
+    pub fn apply_offset(&mut self, offset: u32) -> Result<(), ChatError> {
+        let max = self.entries.len() - LastSeenMessages::WINDOW;
+        if offset as usize > max {
+            let max = u32::try_from(max).unwrap_or(u32::MAX);
+            return Err(ChatError::new(ChatErrorKind::InvalidOffset { offset, max }));
+        }
+        self.entries.drain(..offset as usize);
+        Ok(())
+    }
+
+    /// Apply an update from the client,
+    /// returning the messages it acknowledged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the offset is invalid, an unknown message is
+    /// acknowledged, a previously acknowledged message is ignored,
+    /// or the checksum does not match.
+    pub fn apply_update(&mut self, update: &LastSeenUpdate) -> Result<LastSeenMessages, ChatError> {
+        self.apply_offset(update.offset)?;
+        if update.acknowledged >> LastSeenMessages::WINDOW != 0 {
+            return Err(ChatError::new(ChatErrorKind::OutsideWindow));
+        }
+
+        let mut messages = LastSeenMessages::new();
+        for (index, entry) in self.entries.iter_mut().take(LastSeenMessages::WINDOW).enumerate() {
+            if update.is_acknowledged(index) {
+                let Some(message) = entry else {
+                    return Err(ChatError::new(ChatErrorKind::UnknownMessage(index)));
+                };
+                message.pending = false;
+                messages.0.push(message.signature.clone());
+            } else {
+                if entry.as_ref().is_some_and(|message| !message.pending) {
+                    return Err(ChatError::new(ChatErrorKind::IgnoredMessage(index)));
+                }
+                *entry = None;
+            }
+        }
+
+        if update.verify_checksum(&messages) {
+            Ok(messages)
+        } else {
+            Err(ChatError::new(ChatErrorKind::ChecksumMismatch {
+                expected: messages.checksum(),
+                found: update.checksum,
+            }))
+        }
+    }
+}
+
+impl Default for LastSeenTracker {
+    fn default() -> Self { Self::new() }
+}
@@ -0,0 +1,33 @@
+//! Types used by signed chat.
+//!
+//! Chat messages are signed with a [`MessageSignature`] using the key of the
+//! player's [`ChatSession`]. Clients acknowledge the messages they have seen
+//! in a window of [`LastSeenMessages::WINDOW`] messages, sending a
+//! [`LastSeenUpdate`] with every chat message and command. A
+//! [`LastSeenTracker`] validates these updates on the server.
+//!
+//! Signatures themselves are not verified.
+//!
+//! ```rust
+//! use facet_minecraft::chat::{LastSeenTracker, LastSeenUpdate, MessageSignature};
+//!
+//! let mut tracker = LastSeenTracker::new();
+//! tracker.add_pending(MessageSignature::new([1; 256]));
+//!
+//! // The client advances the window and acknowledges the message
+//! let update = LastSeenUpdate { offset: 1, acknowledged: 1 << 19, checksum: 0 };
+//! let seen = tracker.apply_update(&update).unwrap();
+//! assert_eq!(seen.0, [MessageSignature::new([1; 256])]);
+//! ```
+
+mod error;
+pub use error::{ChatError, ChatErrorKind};
+
+mod last_seen;
+pub use last_seen::{LastSeenMessages, LastSeenTracker, LastSeenUpdate, PackedLastSeenMessages};
+
+mod session;
+pub use session::{ChatSession, PlayerPublicKey};
+
+mod signature;
+pub use signature::{MessageSignature, PackedSignature};
@@ -0,0 +1,107 @@
+use alloc::vec::Vec;
+
+use facet::{Facet, Shape};
+use uuid::Uuid;
+
+use super::{ChatError, ChatErrorKind};
+use crate::{
+    codec::{McCodec, McReader, McWriter, codec_shape},
+    deserialize::DeserializeError,
+    serialize::SerializeError,
+};
+
+/// A player's chat session, sent in the Player Session packet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChatSession {
+    /// The ID of the session, chosen by the client.
+    pub session_id: Uuid,
+    /// The player's public key.
+    pub public_key: PlayerPublicKey,
+}
+
+impl McCodec for ChatSession {
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        writer.write_uuid(&self.session_id)?;
+        self.public_key.encode(writer)
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        Ok(Self { session_id: reader.read_uuid()?, public_key: PlayerPublicKey::decode(reader)? })
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for ChatSession {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("ChatSession").build() };
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// A player's public key, signed by Mojang.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerPublicKey {
+    /// When the key expires, in milliseconds since the Unix epoch.
+    pub expires_at: i64,
+    /// The RSA public key, in DER format.
+    pub key: Vec<u8>,
+    /// Mojang's signature over the key and its expiry.
+    pub signature: Vec<u8>,
+}
+
+impl PlayerPublicKey {
+    /// The longest public key accepted by the vanilla server, in bytes.
+    pub const MAX_KEY_LENGTH: usize = 512;
+    /// The longest signature accepted by the vanilla server, in bytes.
+    pub const MAX_SIGNATURE_LENGTH: usize = 4096;
+
+    /// Returns `true` if the key has expired at the given time,
+    /// in milliseconds since the Unix epoch.
+    #[inline]
+    #[must_use]
+    pub const fn is_expired(&self, now: i64) -> bool { now > self.expires_at }
+
+    /// Read a byte array, checking its length before reading it.
+    fn read_limited<'de>(
+        reader: &mut McReader<'de>,
+        max: usize,
+        error: fn(usize) -> ChatErrorKind,
+    ) -> Result<&'de [u8], DeserializeError> {
+        let len = reader.read_var_u32()? as usize;
+        if len > max {
+            return Err(ChatError::new(error(len)).into());
+        }
+        reader.read_bytes(len)
+    }
+}
+
+impl McCodec for PlayerPublicKey {
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        if self.key.len() > Self::MAX_KEY_LENGTH {
+            return Err(ChatError::new(ChatErrorKind::KeyTooLong(self.key.len())).into());
+        }
+        if self.signature.len() > Self::MAX_SIGNATURE_LENGTH {
+            let len = self.signature.len();
+            return Err(ChatError::new(ChatErrorKind::SignatureTooLong(len)).into());
+        }
+
+        writer.write_u64(self.expires_at.cast_unsigned())?;
+        writer.write_byte_array(&self.key)?;
+        writer.write_byte_array(&self.signature)
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        let expires_at = reader.read_u64()?.cast_signed();
+        let key = Self::read_limited(reader, Self::MAX_KEY_LENGTH, ChatErrorKind::KeyTooLong)?;
+        let signature = Self::read_limited(
+            reader,
+            Self::MAX_SIGNATURE_LENGTH,
+            ChatErrorKind::SignatureTooLong,
+        )?;
+        Ok(Self { expires_at, key: key.to_vec(), signature: signature.to_vec() })
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for PlayerPublicKey {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("PlayerPublicKey").build() };
+}
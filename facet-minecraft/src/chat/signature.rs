@@ -0,0 +1,57 @@
+use facet::{Facet, Shape};
+
+use crate::{
+    codec::{McCodec, McReader, McWriter, codec_shape},
+    deserialize::DeserializeError,
+    serialize::SerializeError,
+    types::IdOr,
+};
+
+/// The signature of a chat message, sent as 256 bytes without a length.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MessageSignature(pub [u8; MessageSignature::LENGTH]);
+
+/// A [`MessageSignature`], or the ID of a signature the client has cached.
+///
+/// Sent as a `VarInt` of the ID plus one,
+/// or `0` followed by the full signature.
+pub type PackedSignature = IdOr<MessageSignature>;
+
+impl MessageSignature {
+    /// The length of a signature, in bytes.
+    pub const LENGTH: usize = 256;
+
+    /// Create a new [`MessageSignature`].
+    #[inline]
+    #[must_use]
+    pub const fn new(bytes: [u8; Self::LENGTH]) -> Self { Self(bytes) }
+
+    /// Get the bytes of the signature.
+    #[inline]
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8; Self::LENGTH] { &self.0 }
+
+    /// Get the checksum of the signature,
+    /// the same as Java's `Arrays.hashCode` over signed bytes.
+    #[must_use]
+    pub fn checksum(&self) -> i32 {
+        self.0.iter().fold(1i32, |hash, byte| {
+            hash.wrapping_mul(31).wrapping_add(i32::from(byte.cast_signed()))
+        })
+    }
+}
+
+impl McCodec for MessageSignature {
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        writer.write_bytes(&self.0)
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        reader.read_array().map(Self)
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for MessageSignature {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("MessageSignature").build() };
+}
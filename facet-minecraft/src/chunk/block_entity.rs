@@ -0,0 +1,77 @@
+use alloc::vec::Vec;
+
+use facet::{Facet, Shape};
+
+use crate::{
+    codec::{McCodec, McReader, McWriter, codec_shape},
+    deserialize::DeserializeError,
+    nbt::{NbtCompound, NbtTag},
+    serialize::SerializeError,
+};
+
+/// A block entity in a chunk, such as a chest or a sign.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BlockEntity {
+    /// The `x` coordinate within the chunk, `0..16`.
+    pub x: u8,
+    /// The `z` coordinate within the chunk, `0..16`.
+    pub z: u8,
+    /// The `y` coordinate in the world.
+    pub y: i16,
+    /// The ID of the block entity type.
+    pub kind: u32,
+    /// The data sent to the client, if any.
+    pub data: Option<NbtCompound>,
+}
+
+impl BlockEntity {
+    /// Get the `x` and `z` coordinates packed into a single byte,
+    /// as `x << 4 | z`.
+    #[inline]
+    #[must_use]
+    pub const fn packed_xz(&self) -> u8 { (self.x & 0xF) << 4 | self.z & 0xF }
+}
+
+/// The block entities of a chunk, sent as a `VarInt`-prefixed array.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BlockEntities(pub Vec<BlockEntity>);
+
+impl McCodec for BlockEntities {
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        writer.write_var_u32(u32::try_from(self.0.len()).unwrap_or(u32::MAX))?;
+        for entity in &self.0 {
+            writer.write_u8(entity.packed_xz())?;
+            writer.write_bytes(&entity.y.to_be_bytes())?;
+            writer.write_var_u32(entity.kind)?;
+            match &entity.data {
+                Some(data) => writer.write(data)?,
+                None => writer.write_u8(NbtTag::End as u8)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        let len = reader.read_var_u32()? as usize;
+        let entities = (0..len)
+            .map(|_| {
+                let packed = reader.read_u8()?;
+                let y = i16::from_be_bytes(reader.read_array()?);
+                let kind = reader.read_var_u32()?;
+                let data = if reader.remaining().first() == Some(&(NbtTag::End as u8)) {
+                    reader.read_u8()?;
+                    None
+                } else {
+                    Some(reader.read::<NbtCompound>()?)
+                };
+                Ok(BlockEntity { x: packed >> 4, z: packed & 0xF, y, kind, data })
+            })
+            .collect::<Result<Vec<_>, DeserializeError>>()?;
+        Ok(Self(entities))
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for BlockEntities {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("BlockEntities").build() };
+}
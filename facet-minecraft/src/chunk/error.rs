@@ -0,0 +1,84 @@
+use alloc::string::String;
+use core::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// An error that occurred while encoding or decoding chunk data.
+#[derive(Debug)]
+pub struct ChunkError {
+    kind: ChunkErrorKind,
+}
+
+impl ChunkError {
+    /// Create a new [`ChunkError`].
+    #[inline]
+    #[must_use]
+    pub const fn new(kind: ChunkErrorKind) -> Self { Self { kind } }
+
+    /// Get the kind of chunk error.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> &ChunkErrorKind { &self.kind }
+}
+
+/// The type of chunk error.
+#[derive(Debug)]
+pub enum ChunkErrorKind {
+    /// A paletted container used more than 32 bits per entry.
+    InvalidBits(u8),
+    /// An entry referred to a palette index that does not exist.
+    InvalidPaletteIndex {
+        /// The palette index.
+        index: u64,
+        /// The length of the palette.
+        len: usize,
+    },
+    /// The number of longs in a paletted container did not match its size.
+    InvalidDataLength {
+        /// The expected number of longs.
+        expected: usize,
+        /// The number of longs found.
+        found: usize,
+    },
+    /// A heightmap kind ID was not recognized.
+    UnknownHeightmap(u32),
+    /// A heightmap in an NBT compound was not a long array.
+    InvalidHeightmap(String),
+    /// A light array was not 2048 bytes long.
+    InvalidLightLength(usize),
+    /// A value did not fit in the bits per entry of a direct palette.
+    ValueTooLarge {
+        /// The value.
+        value: u32,
+        /// The bits per entry.
+        bits: u8,
+    },
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Error for ChunkError {}
+impl Display for ChunkError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ChunkErrorKind::InvalidBits(bits) => write!(f, "invalid bits per entry {bits}"),
+            ChunkErrorKind::InvalidPaletteIndex { index, len } => {
+                write!(f, "palette index {index} out of range for palette of length {len}")
+            }
+            ChunkErrorKind::InvalidDataLength { expected, found } => {
+                write!(f, "expected {expected} longs of data, found {found}")
+            }
+            ChunkErrorKind::UnknownHeightmap(id) => write!(f, "unknown heightmap kind {id}"),
+            ChunkErrorKind::InvalidHeightmap(name) => {
+                write!(f, "heightmap `{name}` is not a long array")
+            }
+            ChunkErrorKind::InvalidLightLength(len) => {
+                write!(f, "light array has {len} bytes, expected 2048")
+            }
+            ChunkErrorKind::ValueTooLarge { value, bits } => {
+                write!(f, "value {value} does not fit in {bits} bits")
+            }
+        }
+    }
+}
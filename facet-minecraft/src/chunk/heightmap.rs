@@ -0,0 +1,197 @@
+use alloc::{string::String, vec::Vec};
+
+use facet::{Facet, Shape};
+
+use super::{ChunkError, ChunkErrorKind};
+use crate::{
+    codec::{McCodec, McReader, McWriter, codec_shape},
+    deserialize::DeserializeError,
+    nbt::{NbtCompound, NbtTag, NbtValue},
+    packet::ProtocolVersion,
+    serialize::SerializeError,
+};
+
+/// The kind of a [`Heightmap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HeightmapKind {
+    /// The highest non-air block, during world generation.
+    WorldSurfaceWorldgen,
+    /// The highest non-air block.
+    WorldSurface,
+    /// The highest motion-blocking block, during world generation.
+    OceanFloorWorldgen,
+    /// The highest motion-blocking block.
+    OceanFloor,
+    /// The highest motion-blocking or fluid block.
+    MotionBlocking,
+    /// The highest motion-blocking or fluid block, ignoring leaves.
+    MotionBlockingNoLeaves,
+}
+
+impl HeightmapKind {
+    /// Every kind of heightmap, ordered by ID.
+    pub const ALL: [Self; 6] = [
+        Self::WorldSurfaceWorldgen,
+        Self::WorldSurface,
+        Self::OceanFloorWorldgen,
+        Self::OceanFloor,
+        Self::MotionBlocking,
+        Self::MotionBlockingNoLeaves,
+    ];
+
+    /// Get the ID of this kind, as sent since 1.21.5.
+    #[inline]
+    #[must_use]
+    pub const fn id(self) -> u32 { self as u32 }
+
+    /// Get the kind with an ID.
+    #[must_use]
+    pub fn from_id(id: u32) -> Option<Self> { Self::ALL.get(id as usize).copied() }
+
+    /// Get the name of this kind, as sent before 1.21.5.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::WorldSurfaceWorldgen => "WORLD_SURFACE_WG",
+            Self::WorldSurface => "WORLD_SURFACE",
+            Self::OceanFloorWorldgen => "OCEAN_FLOOR_WG",
+            Self::OceanFloor => "OCEAN_FLOOR",
+            Self::MotionBlocking => "MOTION_BLOCKING",
+            Self::MotionBlockingNoLeaves => "MOTION_BLOCKING_NO_LEAVES",
+        }
+    }
+
+    /// Get the kind with a name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+}
+
+/// The height of the highest block of a kind in each column of a chunk.
+///
+/// Heights are packed into longs without spanning two longs,
+/// using as many bits as needed for the height of the world plus one.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Heightmap {
+    /// The kind of heightmap.
+    pub kind: HeightmapKind,
+    /// The packed heights.
+    pub data: Vec<u64>,
+}
+
+impl Heightmap {
+    /// Get the height of a column, relative to the bottom of the world.
+    ///
+    /// Returns `None` if the data is too short.
+    #[must_use]
+    pub fn get(&self, x: usize, z: usize, world_height: u32) -> Option<u32> {
+        let bits = (u32::BITS - world_height.leading_zeros()) as usize;
+        let per_long = 64 / bits.max(1);
+        let index = z * 16 + x;
+        let long = self.data.get(index / per_long)?;
+        #[expect(clippy::cast_possible_truncation, reason = "Masked to at most 32 bits")]
+        Some((long >> (index % per_long * bits) & ((1 << bits) - 1)) as u32)
+    }
+}
+
+/// The heightmaps of a chunk.
+///
+/// Sent as an array of kind IDs and longs since 1.21.5, and as an NBT
+/// compound of names and long arrays before. Both forms are accepted when
+/// decoding, as the NBT form always starts with a compound tag while the
+/// array never holds that many heightmaps. When used as a field, heightmaps
+/// are encoded for the [`version`](McWriter::version) of the writer,
+/// otherwise use [`Heightmaps::encode_for`].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Heightmaps(pub Vec<Heightmap>);
+
+impl Heightmaps {
+    /// Get the heightmap of a kind, if present.
+    #[must_use]
+    pub fn get(&self, kind: HeightmapKind) -> Option<&Heightmap> {
+        self.0.iter().find(|heightmap| heightmap.kind == kind)
+    }
+
+    /// Insert a heightmap, replacing any heightmap of the same kind.
+    pub fn insert(&mut self, heightmap: Heightmap) {
+        match self.0.iter_mut().find(|existing| existing.kind == heightmap.kind) {
+            Some(existing) => *existing = heightmap,
+            None => self.0.push(heightmap),
+        }
+    }
+
+    /// Write the heightmaps for a protocol version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer cannot be written to.
+    pub fn encode_for(
+        &self,
+        writer: &mut McWriter<'_>,
+        version: ProtocolVersion,
+    ) -> Result<(), SerializeError> {
+        if version < ProtocolVersion::V1_21_5 {
+            let mut compound = NbtCompound::new();
+            for heightmap in &self.0 {
+                let data = heightmap.data.iter().map(|long| long.cast_signed()).collect::<Vec<_>>();
+                compound.insert(heightmap.kind.name(), data);
+            }
+            return writer.write(&compound);
+        }
+
+        writer.write_var_u32(u32::try_from(self.0.len()).unwrap_or(u32::MAX))?;
+        for heightmap in &self.0 {
+            writer.write_var_u32(heightmap.kind.id())?;
+            writer.write_var_u32(u32::try_from(heightmap.data.len()).unwrap_or(u32::MAX))?;
+            heightmap.data.iter().try_for_each(|long| writer.write_u64(*long))?;
+        }
+        Ok(())
+    }
+
+    /// Read heightmaps sent as an NBT compound.
+    fn decode_nbt(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        let mut heightmaps = Self::default();
+        for (name, value) in &reader.read::<NbtCompound>()? {
+            let Some(kind) = HeightmapKind::from_name(name) else { continue };
+            let NbtValue::LongArray(data) = value else {
+                return Err(
+                    ChunkError::new(ChunkErrorKind::InvalidHeightmap(String::from(name))).into()
+                );
+            };
+            let data = data.iter().map(|long| long.cast_unsigned()).collect();
+            heightmaps.insert(Heightmap { kind, data });
+        }
+        Ok(heightmaps)
+    }
+}
+
+impl McCodec for Heightmaps {
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        self.encode_for(writer, writer.version())
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        if reader.remaining().first() == Some(&(NbtTag::Compound as u8)) {
+            return Self::decode_nbt(reader);
+        }
+
+        let len = reader.read_var_u32()? as usize;
+        let heightmaps = (0..len)
+            .map(|_| {
+                let id = reader.read_var_u32()?;
+                let kind = HeightmapKind::from_id(id)
+                    .ok_or_else(|| ChunkError::new(ChunkErrorKind::UnknownHeightmap(id)))?;
+                let len = reader.read_var_u32()? as usize;
+                let data = (0..len).map(|_| reader.read_u64()).collect::<Result<Vec<_>, _>>()?;
+                Ok(Heightmap { kind, data })
+            })
+            .collect::<Result<Vec<_>, DeserializeError>>()?;
+        Ok(Self(heightmaps))
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for Heightmaps {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("Heightmaps").build() };
+}
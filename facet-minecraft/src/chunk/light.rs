@@ -0,0 +1,189 @@
+use alloc::{borrow::Cow, boxed::Box, string::ToString};
+
+use facet::{
+    Attr, Def, Facet, OxPtrMut, OxPtrUninit, PtrConst, Shape, ShapeBuilder, TryFromOutcome, Type,
+    UserType, VTableIndirect,
+};
+
+use super::{ChunkError, ChunkErrorKind};
+use crate::{
+    codec::{CodecFn, McCodec, McReader, McWriter},
+    deserialize::DeserializeError,
+    serialize::SerializeError,
+    types::BitSet,
+};
+
+/// The light levels of a chunk section, one nibble per block.
+///
+/// Blocks are ordered by `y`, then `z`, then `x`,
+/// with even blocks in the low nibble of each byte.
+///
+/// When deserialized using
+/// [`from_slice_borrowed`](crate::Deserializable::from_slice_borrowed),
+/// the light array borrows from the input.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LightArray<'a>(Cow<'a, [u8; LightArray::LEN]>);
+
+impl<'a> LightArray<'a> {
+    /// The number of bytes in a light array.
+    pub const LEN: usize = 2048;
+
+    /// Create a new [`LightArray`] with every block at the same light level.
+    #[must_use]
+    pub fn new(level: u8) -> Self {
+        let level = level & 0xF;
+        Self(Cow::Owned([level << 4 | level; LightArray::LEN]))
+    }
+
+    /// Create a [`LightArray`] borrowing its bytes.
+    ///
+    /// Returns `None` if there are not exactly [`LightArray::LEN`] bytes.
+    #[must_use]
+    pub fn from_bytes(bytes: &'a [u8]) -> Option<Self> {
+        <&[u8; LightArray::LEN]>::try_from(bytes).ok().map(|bytes| Self(Cow::Borrowed(bytes)))
+    }
+
+    /// Get the bytes of the light array.
+    #[inline]
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8; LightArray::LEN] { &self.0 }
+
+    /// Returns `true` if the light array borrows its bytes.
+    #[inline]
+    #[must_use]
+    pub const fn is_borrowed(&self) -> bool { matches!(self.0, Cow::Borrowed(_)) }
+
+    /// Convert the light array into one that owns its bytes.
+    #[must_use]
+    pub fn into_owned(self) -> LightArray<'static> { LightArray(Cow::Owned(self.0.into_owned())) }
+
+    /// Get the light level of a block within the section.
+    #[must_use]
+    pub fn get(&self, x: usize, y: usize, z: usize) -> u8 {
+        let index = Self::index(x, y, z);
+        self.0[index / 2] >> (index % 2 * 4) & 0xF
+    }
+
+    /// Set the light level of a block within the section.
+    ///
+    /// Copies the bytes if they are borrowed.
+    pub fn set(&mut self, x: usize, y: usize, z: usize, level: u8) {
+        let index = Self::index(x, y, z);
+        let shift = index % 2 * 4;
+        let byte = &mut self.0.to_mut()[index / 2];
+        *byte = *byte & !(0xF << shift) | (level & 0xF) << shift;
+    }
+
+    /// Get the index of a block within the section.
+    const fn index(x: usize, y: usize, z: usize) -> usize {
+        (y & 0xF) << 8 | (z & 0xF) << 4 | x & 0xF
+    }
+}
+
+impl Default for LightArray<'_> {
+    #[inline]
+    fn default() -> Self { Self::new(0) }
+}
+
+impl McCodec for LightArray<'static> {
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        writer.write_byte_array(self.as_bytes())
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        let bytes = reader.read_byte_array()?;
+        LightArray::from_bytes(bytes)
+            .map(LightArray::into_owned)
+            .ok_or_else(|| ChunkError::new(ChunkErrorKind::InvalidLightLength(bytes.len())).into())
+    }
+}
+
+// SAFETY: The shape describes an opaque `LightArray`, built from a `Cow<[u8]>`.
+unsafe impl<'a> Facet<'a> for LightArray<'a> {
+    const SHAPE: &'static Shape = &const {
+        /// Check the length of a `Cow<[u8]>` and convert it into a light array.
+        unsafe fn try_from(
+            target: OxPtrUninit,
+            source: &'static Shape,
+            ptr: PtrConst,
+        ) -> TryFromOutcome {
+            if source.id != <Cow<'_, [u8]>>::SHAPE.id {
+                return TryFromOutcome::Unsupported;
+            }
+
+            let bytes = unsafe { ptr.read::<Cow<'static, [u8]>>() };
+            let array = match &bytes {
+                Cow::Borrowed(bytes) => LightArray::from_bytes(bytes),
+                Cow::Owned(bytes) => LightArray::from_bytes(bytes).map(LightArray::into_owned),
+            };
+            if let Some(array) = array {
+                unsafe { target.put(array) };
+                TryFromOutcome::Converted
+            } else {
+                let error = ChunkError::new(ChunkErrorKind::InvalidLightLength(bytes.len()));
+                TryFromOutcome::Failed(error.to_string().into())
+            }
+        }
+
+        /// Drop a value in place.
+        unsafe fn drop_in_place(ox: OxPtrMut) {
+            unsafe { core::ptr::drop_in_place(ox.ptr().as_ptr::<LightArray>().cast_mut()) };
+        }
+
+        /// Clone a value into uninitialized memory.
+        unsafe fn clone_into(src: facet::OxPtrConst, dst: OxPtrMut) {
+            let value = unsafe { src.get::<LightArray<'static>>() }.clone();
+            unsafe { dst.ptr().as_uninit().put(value) };
+        }
+
+        ShapeBuilder::for_sized::<Self>("LightArray")
+            .ty(Type::User(UserType::Opaque))
+            .def(Def::Scalar)
+            .builder_shape(<Cow<'a, [u8]>>::SHAPE)
+            .attributes(
+                &const {
+                    [Attr::new(
+                        Some("mc"),
+                        "codec",
+                        &const { CodecFn::of::<LightArray<'static>>() },
+                    )]
+                },
+            )
+            .vtable_indirect(
+                &const { VTableIndirect { try_from: Some(try_from), ..VTableIndirect::EMPTY } },
+            )
+            .type_ops_indirect(
+                &const {
+                    facet::TypeOpsIndirect {
+                        drop_in_place,
+                        default_in_place: None,
+                        clone_into: Some(clone_into),
+                        is_truthy: None,
+                    }
+                },
+            )
+            .build()
+    };
+}
+
+/// The sky and block light of a chunk.
+///
+/// Bit `n` of each mask refers to section `n - 1`,
+/// counting from the section below the bottom of the world.
+/// A light array is sent for each set bit of the matching light mask,
+/// and sections in the empty masks have no light at all.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Facet)]
+pub struct LightData<'a> {
+    /// The sections with sky light arrays.
+    pub sky_mask: BitSet,
+    /// The sections with block light arrays.
+    pub block_mask: BitSet,
+    /// The sections with no sky light.
+    pub empty_sky_mask: BitSet,
+    /// The sections with no block light.
+    pub empty_block_mask: BitSet,
+    /// The sky light arrays, in order of the set bits of the sky mask.
+    pub sky_light: Box<[LightArray<'a>]>,
+    /// The block light arrays, in order of the set bits of the block mask.
+    pub block_light: Box<[LightArray<'a>]>,
+}
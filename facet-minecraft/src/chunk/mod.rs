@@ -0,0 +1,124 @@
+//! Chunk data, as sent in the Chunk Data and Update Light packet.
+//!
+//! A [`ChunkDataAndLight`] can be decoded from the packet body with
+//! [`from_slice_borrowed`](crate::from_slice_borrowed), borrowing the encoded
+//! sections from the body. The sections are decoded separately with
+//! [`ChunkData::sections`], as their encoding depends on the protocol version.
+//!
+//! Block states and biomes are stored in [`PalettedContainer`]s,
+//! which pick the smallest palette for the values they contain.
+//!
+//! ```rust
+//! use facet_minecraft::{
+//!     chunk::{ChunkData, ChunkDataAndLight, ChunkSection},
+//!     packet::ProtocolVersion,
+//! };
+//!
+//! let mut section = ChunkSection::default();
+//! section.block_count = 1;
+//! section.block_states.set(0, 1);
+//!
+//! let mut chunk = ChunkData::default();
+//! chunk
+//!     .set_sections(&[section.clone(), ChunkSection::default()], ProtocolVersion::LATEST)
+//!     .unwrap();
+//! let packet = ChunkDataAndLight { x: 1, z: -1, chunk, ..Default::default() };
+//!
+//! let body = facet_minecraft::to_vec(&packet).unwrap();
+//! let (decoded, _) = facet_minecraft::from_slice_borrowed::<ChunkDataAndLight>(&body).unwrap();
+//! assert_eq!(decoded, packet);
+//!
+//! let sections = decoded.chunk.sections(ProtocolVersion::LATEST).unwrap();
+//! assert_eq!(sections, [section, ChunkSection::default()]);
+//! ```
+
+use alloc::{borrow::Cow, vec::Vec};
+
+use facet::Facet;
+
+use crate::{
+    codec::{McReader, McWriter},
+    deserialize::DeserializeError,
+    packet::ProtocolVersion,
+    serialize::SerializeError,
+};
+
+mod block_entity;
+pub use block_entity::{BlockEntities, BlockEntity};
+
+mod error;
+pub use error::{ChunkError, ChunkErrorKind};
+
+mod heightmap;
+pub use heightmap::{Heightmap, HeightmapKind, Heightmaps};
+
+mod light;
+pub use light::{LightArray, LightData};
+
+mod palette;
+pub use palette::{Biomes, BlockStates, PalettedContainer};
+
+mod section;
+pub use section::ChunkSection;
+
+/// The body of the Chunk Data and Update Light packet.
+#[derive(Debug, Default, Clone, PartialEq, Facet)]
+pub struct ChunkDataAndLight<'a> {
+    /// The `x` coordinate of the chunk.
+    pub x: i32,
+    /// The `z` coordinate of the chunk.
+    pub z: i32,
+    /// The contents of the chunk.
+    pub chunk: ChunkData<'a>,
+    /// The light of the chunk.
+    pub light: LightData<'a>,
+}
+
+/// The heightmaps, sections and block entities of a chunk.
+#[derive(Debug, Default, Clone, PartialEq, Facet)]
+pub struct ChunkData<'a> {
+    /// The heightmaps of the chunk.
+    pub heightmaps: Heightmaps,
+    /// The encoded sections, from the bottom of the world to the top.
+    ///
+    /// See [`ChunkData::sections`] and [`ChunkData::set_sections`].
+    pub data: Cow<'a, [u8]>,
+    /// The block entities in the chunk.
+    pub block_entities: BlockEntities,
+}
+
+impl ChunkData<'_> {
+    /// Decode the sections of the chunk for a protocol version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data does not contain valid sections.
+    pub fn sections(
+        &self,
+        version: ProtocolVersion,
+    ) -> Result<Vec<ChunkSection>, DeserializeError> {
+        let mut reader = McReader::new(&self.data);
+        let mut sections = Vec::new();
+        while !reader.remaining().is_empty() {
+            sections.push(ChunkSection::decode_for(&mut reader, version)?);
+        }
+        Ok(sections)
+    }
+
+    /// Encode the sections of the chunk for a protocol version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a section cannot be encoded.
+    pub fn set_sections(
+        &mut self,
+        sections: &[ChunkSection],
+        version: ProtocolVersion,
+    ) -> Result<(), SerializeError> {
+        let mut data = Vec::new();
+        let mut writer = McWriter::new(&mut data);
+        sections.iter().try_for_each(|section| section.encode_for(&mut writer, version))?;
+        self.data = Cow::Owned(data);
+        Ok(())
+    }
+}
@@ -0,0 +1,381 @@
+use alloc::{boxed::Box, vec, vec::Vec};
+
+use facet::{Facet, Shape};
+
+use super::{ChunkError, ChunkErrorKind};
+use crate::{
+    codec::{McCodec, McReader, McWriter, codec_shape},
+    deserialize::DeserializeError,
+    packet::ProtocolVersion,
+    serialize::SerializeError,
+};
+
+/// The block states of a chunk section, `16 x 16 x 16` entries.
+pub type BlockStates = PalettedContainer<4096>;
+/// The biomes of a chunk section, `4 x 4 x 4` entries.
+pub type Biomes = PalettedContainer<64>;
+
+/// A cube of `N` values, encoded using a palette.
+///
+/// On the wire a container starts with the number of bits per entry,
+/// which decides the palette:
+///
+/// - `0` bits: a single value, and no data.
+/// - Up to [`MAX_INDIRECT_BITS`](Self::MAX_INDIRECT_BITS): a `VarInt` palette
+///   of values, and entries indexing into the palette.
+/// - More bits: entries are the values themselves.
+///
+/// Entries are packed into big-endian longs, without spanning two longs.
+/// Before 1.21.5 the longs are prefixed by their count, see
+/// [`PalettedContainer::encode_for`]. When used as a field, the container is
+/// encoded for the [`version`](McWriter::version) of the writer and reader,
+/// which is the version given to
+/// [`to_vec_versioned`](crate::to_vec_versioned) or
+/// [`from_slice_versioned`](crate::from_slice_versioned), and
+/// [`ProtocolVersion::LATEST`] otherwise.
+///
+/// ```rust
+/// use facet_minecraft::chunk::BlockStates;
+///
+/// let mut states = BlockStates::new(0);
+/// states.set(BlockStates::index(1, 2, 3), 9);
+/// assert_eq!(states.get(BlockStates::index(1, 2, 3)), Some(9));
+/// assert_eq!(states.palette(), [0, 9]);
+///
+/// let bytes = facet_minecraft::to_vec(&states).unwrap();
+/// let (decoded, _) = facet_minecraft::from_slice::<BlockStates>(&bytes).unwrap();
+/// assert_eq!(decoded, states);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PalettedContainer<const N: usize> {
+    storage: Storage,
+    direct_bits: Option<u8>,
+}
+
+/// The values of a [`PalettedContainer`].
+#[derive(Debug, Clone)]
+enum Storage {
+    /// Every entry has the same value.
+    Single(u32),
+    /// A value for every entry.
+    Values(Box<[u32]>),
+}
+
+impl<const N: usize> PalettedContainer<N> {
+    /// The number of entries along each edge of the cube.
+    pub const EDGE: usize = {
+        let mut edge = 1;
+        while edge * edge * edge < N {
+            edge += 1;
+        }
+        edge
+    };
+    /// The largest number of bits per entry using a palette.
+    ///
+    /// Biomes use up to `3` bits, all other containers up to `8`.
+    pub const MAX_INDIRECT_BITS: u8 = if N == 64 { 3 } else { 8 };
+    /// The smallest number of bits per entry using a palette.
+    ///
+    /// Biomes use at least `1` bit, all other containers at least `4`.
+    pub const MIN_INDIRECT_BITS: u8 = if N == 64 { 1 } else { 4 };
+    /// The number of entries in the container.
+    pub const SIZE: usize = N;
+
+    /// Create a new [`PalettedContainer`] filled with a single value.
+    #[inline]
+    #[must_use]
+    pub const fn new(value: u32) -> Self {
+        Self { storage: Storage::Single(value), direct_bits: None }
+    }
+
+    /// Create a new [`PalettedContainer`] from a value for every entry.
+    ///
+    /// Returns `None` if there are not exactly `N` values.
+    #[must_use]
+    pub fn from_values(values: Vec<u32>) -> Option<Self> {
+        (values.len() == N).then(|| Self {
+            storage: Storage::Values(values.into_boxed_slice()),
+            direct_bits: None,
+        })
+    }
+
+    /// Set the bits per entry used when the values are written directly,
+    /// without a palette.
+    ///
+    /// This should be the number of bits needed for the largest ID in the
+    /// registry, such as `15` for block states. If not set, the number of
+    /// bits needed for the largest value in the container is used.
+    #[inline]
+    #[must_use]
+    pub const fn with_direct_bits(mut self, bits: u8) -> Self {
+        self.direct_bits = Some(bits);
+        self
+    }
+
+    /// Get the bits per entry used when the values are written directly.
+    #[inline]
+    #[must_use]
+    pub const fn direct_bits(&self) -> Option<u8> { self.direct_bits }
+
+    /// Get the index of the entry at a position within the cube.
+    ///
+    /// Entries are ordered by `y`, then `z`, then `x`.
+    #[inline]
+    #[must_use]
+    pub const fn index(x: usize, y: usize, z: usize) -> usize {
+        (y * Self::EDGE + z) * Self::EDGE + x
+    }
+
+    /// Get the value of an entry, or `None` if the index is out of range.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<u32> {
+        match &self.storage {
+            Storage::Single(value) => (index < N).then_some(*value),
+            Storage::Values(values) => values.get(index).copied(),
+        }
+    }
+
+    /// Set the value of an entry, returning the previous value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of range.
+    pub fn set(&mut self, index: usize, value: u32) -> u32 {
+        assert!(index < N, "index {index} out of range for container of size {N}");
+        match &mut self.storage {
+            Storage::Single(single) if *single == value => value,
+            Storage::Single(single) => {
+                let previous = *single;
+                let mut values = vec![previous; N].into_boxed_slice();
+                values[index] = value;
+                self.storage = Storage::Values(values);
+                previous
+            }
+            Storage::Values(values) => core::mem::replace(&mut values[index], value),
+        }
+    }
+
+    /// Set every entry to the same value.
+    #[inline]
+    pub fn fill(&mut self, value: u32) { self.storage = Storage::Single(value); }
+
+    /// Iterate over the value of every entry.
+    pub fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        (0..N).map(move |index| match &self.storage {
+            Storage::Single(value) => *value,
+            Storage::Values(values) => values[index],
+        })
+    }
+
+    /// Get the single value of every entry, if all entries are the same.
+    #[must_use]
+    pub fn single(&self) -> Option<u32> {
+        match &self.storage {
+            Storage::Single(value) => Some(*value),
+            Storage::Values(values) => {
+                values.iter().all(|value| *value == values[0]).then_some(values[0])
+            }
+        }
+    }
+
+    /// Get the distinct values in the container, in order of first appearance.
+    #[must_use]
+    pub fn palette(&self) -> Vec<u32> {
+        let mut palette = Vec::new();
+        for value in self.iter() {
+            if !palette.contains(&value) {
+                palette.push(value);
+            }
+        }
+        palette
+    }
+
+    // ---------------------------------------------------------------------------------------------
+
+    /// Returns `true` if the longs are prefixed by their count
+    /// in the protocol version.
+    ///
+    /// The prefix was removed in 1.21.5.
+    #[inline]
+    #[must_use]
+    pub const fn has_length_prefix(version: ProtocolVersion) -> bool {
+        version.protocol() < ProtocolVersion::V1_21_5.protocol()
+    }
+
+    /// Write this container for a protocol version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a value does not fit in the
+    /// [`direct_bits`](Self::with_direct_bits), or the buffer cannot be
+    /// written to.
+    pub fn encode_for(
+        &self,
+        writer: &mut McWriter<'_>,
+        version: ProtocolVersion,
+    ) -> Result<(), SerializeError> {
+        self.encode_with(writer, Self::has_length_prefix(version))
+    }
+
+    /// Read a container for a protocol version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input ends unexpectedly,
+    /// or does not contain a valid container.
+    pub fn decode_for(
+        reader: &mut McReader<'_>,
+        version: ProtocolVersion,
+    ) -> Result<Self, DeserializeError> {
+        Self::decode_with(reader, Self::has_length_prefix(version))
+    }
+
+    /// Write this container, optionally prefixing the data with its length.
+    fn encode_with(&self, writer: &mut McWriter<'_>, prefixed: bool) -> Result<(), SerializeError> {
+        let palette = self.palette();
+        if let [value] = palette[..] {
+            writer.write_u8(0)?;
+            writer.write_var_u32(value)?;
+            return if prefixed { writer.write_var_u32(0) } else { Ok(()) };
+        }
+
+        let bits = bits_for(palette.len() - 1).max(Self::MIN_INDIRECT_BITS);
+        if bits <= Self::MAX_INDIRECT_BITS {
+            writer.write_u8(bits)?;
+            writer.write_var_u32(u32::try_from(palette.len()).unwrap_or(u32::MAX))?;
+            for value in &palette {
+                writer.write_var_u32(*value)?;
+            }
+            #[expect(clippy::cast_possible_truncation, reason = "Palette has at most 256 values")]
+            let entries = self.iter().map(|value| {
+                palette.iter().position(|entry| *entry == value).unwrap_or_default() as u32
+            });
+            return Self::write_data(writer, bits, entries, prefixed);
+        }
+
+        let largest = palette.iter().copied().max().unwrap_or_default();
+        let needed = bits_for(largest as usize).max(Self::MAX_INDIRECT_BITS + 1);
+        let bits = self.direct_bits.unwrap_or(needed);
+        if bits > 32 {
+            return Err(ChunkError::new(ChunkErrorKind::InvalidBits(bits)).into());
+        } else if bits < needed {
+            return Err(
+                ChunkError::new(ChunkErrorKind::ValueTooLarge { value: largest, bits }).into()
+            );
+        }
+
+        writer.write_u8(bits)?;
+        Self::write_data(writer, bits, self.iter(), prefixed)
+    }
+
+    /// Pack entries into longs and write them.
+    fn write_data(
+        writer: &mut McWriter<'_>,
+        bits: u8,
+        entries: impl Iterator<Item = u32>,
+        prefixed: bool,
+    ) -> Result<(), SerializeError> {
+        let per_long = 64 / usize::from(bits);
+        let mut data = vec![0u64; N.div_ceil(per_long)];
+        for (index, entry) in entries.enumerate() {
+            let shift = (index % per_long) * usize::from(bits);
+            data[index / per_long] |= u64::from(entry) << shift;
+        }
+
+        if prefixed {
+            writer.write_var_u32(u32::try_from(data.len()).unwrap_or(u32::MAX))?;
+        }
+        data.into_iter().try_for_each(|long| writer.write_u64(long))
+    }
+
+    /// Read a container, optionally expecting the data to be prefixed with
+    /// its length.
+    fn decode_with(reader: &mut McReader<'_>, prefixed: bool) -> Result<Self, DeserializeError> {
+        let bits = reader.read_u8()?;
+        if bits == 0 {
+            let value = reader.read_var_u32()?;
+            if prefixed {
+                // The length should be zero, but any data is ignored.
+                let len = reader.read_var_u32()? as usize;
+                reader.read_bytes(len.saturating_mul(8))?;
+            }
+            return Ok(Self::new(value));
+        } else if bits > 32 {
+            return Err(ChunkError::new(ChunkErrorKind::InvalidBits(bits)).into());
+        }
+
+        let (bits, palette) = if bits <= Self::MAX_INDIRECT_BITS {
+            let len = reader.read_var_u32()? as usize;
+            let palette = (0..len).map(|_| reader.read_var_u32()).collect::<Result<Vec<_>, _>>()?;
+            (bits.max(Self::MIN_INDIRECT_BITS), Some(palette))
+        } else {
+            (bits, None)
+        };
+
+        let per_long = 64 / usize::from(bits);
+        let expected = N.div_ceil(per_long);
+        if prefixed {
+            let found = reader.read_var_u32()? as usize;
+            if found != expected {
+                return Err(
+                    ChunkError::new(ChunkErrorKind::InvalidDataLength { expected, found }).into()
+                );
+            }
+        }
+
+        let mask = (1u64 << bits) - 1;
+        let mut values = Vec::with_capacity(N);
+        for _ in 0..expected {
+            let long = reader.read_u64()?;
+            for slot in 0..per_long.min(N - values.len()) {
+                let entry = long >> (slot * usize::from(bits)) & mask;
+                #[expect(clippy::cast_possible_truncation, reason = "Entries are at most 32 bits")]
+                let value = match &palette {
+                    Some(palette) => *palette.get(entry as usize).ok_or_else(|| {
+                        ChunkError::new(ChunkErrorKind::InvalidPaletteIndex {
+                            index: entry,
+                            len: palette.len(),
+                        })
+                    })?,
+                    None => entry as u32,
+                };
+                values.push(value);
+            }
+        }
+
+        Ok(Self {
+            storage: Storage::Values(values.into_boxed_slice()),
+            direct_bits: palette.is_none().then_some(bits),
+        })
+    }
+}
+
+/// Get the number of bits needed to store a value.
+#[expect(clippy::cast_possible_truncation, reason = "At most 64 bits")]
+const fn bits_for(value: usize) -> u8 { (usize::BITS - value.leading_zeros()) as u8 }
+
+impl<const N: usize> Default for PalettedContainer<N> {
+    #[inline]
+    fn default() -> Self { Self::new(0) }
+}
+
+impl<const N: usize> PartialEq for PalettedContainer<N> {
+    fn eq(&self, other: &Self) -> bool { self.iter().eq(other.iter()) }
+}
+
+impl<const N: usize> Eq for PalettedContainer<N> {}
+
+impl<const N: usize> McCodec for PalettedContainer<N> {
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        self.encode_for(writer, writer.version())
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        Self::decode_for(reader, reader.version())
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl<const N: usize> Facet<'_> for PalettedContainer<N> {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("PalettedContainer").build() };
+}
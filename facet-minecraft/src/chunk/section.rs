@@ -0,0 +1,60 @@
+use facet::Facet;
+
+use super::{Biomes, BlockStates};
+use crate::{
+    codec::{McReader, McWriter},
+    deserialize::DeserializeError,
+    packet::ProtocolVersion,
+    serialize::SerializeError,
+};
+
+/// A `16 x 16 x 16` section of a chunk.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Facet)]
+pub struct ChunkSection {
+    /// The number of blocks that are not air.
+    pub block_count: i16,
+    /// The block state of every block.
+    pub block_states: BlockStates,
+    /// The biome of every `4 x 4 x 4` cell.
+    pub biomes: Biomes,
+}
+
+impl ChunkSection {
+    /// Returns `true` if the section only contains air.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool { self.block_count == 0 }
+
+    /// Write the section for a protocol version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a container cannot be encoded,
+    /// or the buffer cannot be written to.
+    pub fn encode_for(
+        &self,
+        writer: &mut McWriter<'_>,
+        version: ProtocolVersion,
+    ) -> Result<(), SerializeError> {
+        writer.write_bytes(&self.block_count.to_be_bytes())?;
+        self.block_states.encode_for(writer, version)?;
+        self.biomes.encode_for(writer, version)
+    }
+
+    /// Read a section for a protocol version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input ends unexpectedly,
+    /// or does not contain a valid section.
+    pub fn decode_for(
+        reader: &mut McReader<'_>,
+        version: ProtocolVersion,
+    ) -> Result<Self, DeserializeError> {
+        Ok(Self {
+            block_count: i16::from_be_bytes(reader.read_array()?),
+            block_states: BlockStates::decode_for(reader, version)?,
+            biomes: Biomes::decode_for(reader, version)?,
+        })
+    }
+}
@@ -0,0 +1,709 @@
+//! Hand-written wire encodings for types that cannot be described by their
+//! fields alone.
+//!
+//! Types implementing [`McCodec`] are exposed to [`facet`] as opaque scalars,
+//! which allows them to be used as fields of any other `Facet` type and still
+//! be handled by the [`McSerializer`](crate::serialize::McSerializer) and
+//! [`McDeserializer`](crate::deserialize::McDeserializer).
+
+use alloc::borrow::Cow;
+
+use facet::{Attr, Def, Facet, OxPtrMut, PtrConst, PtrUninit, ShapeBuilder, Type, UserType};
+use facet_format::{ScalarTypeHint, ScalarValue};
+use uuid::Uuid;
+
+use crate::{
+    common::TypeSerializeHint,
+    deserialize::{DeserializeError, DeserializeErrorKind, parse},
+    mutf8,
+    packet::ProtocolVersion,
+    serialize::{SerializeBuffer, SerializeError, SerializeErrorKind},
+    types::Identifier,
+};
+
+/// A type with a custom wire encoding.
+///
+/// Use [`codec_shape`] to implement [`Facet`] for the type.
+pub trait McCodec: Clone + Sized + 'static {
+    /// A hint for the size of an encoded value.
+    ///
+    /// Attached to the shape created by [`codec_shape`] and used by
+    /// [`Serializable::serialize_hint_versioned`](crate::Serializable::serialize_hint_versioned).
+    const SERIALIZE_HINT: TypeSerializeHint = TypeSerializeHint::None;
+
+    /// Encode the value into the writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value cannot be encoded,
+    /// or if the buffer cannot be written to.
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError>;
+
+    /// Decode a value from the reader.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is invalid or ends unexpectedly.
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError>;
+}
+
+/// Create a [`ShapeBuilder`] for a type implementing [`McCodec`].
+///
+/// The resulting [`Shape`](facet::Shape) describes an opaque scalar that
+/// carries its own encoding and decoding functions.
+///
+/// ```rust,ignore
+/// unsafe impl Facet<'_> for MyType {
+///     const SHAPE: &'static Shape = &const { codec_shape::<Self>("MyType").build() };
+/// }
+/// ```
+#[must_use]
+pub const fn codec_shape<T: McCodec>(identifier: &'static str) -> ShapeBuilder {
+    /// Drop a value in place.
+    unsafe fn drop_in_place<T>(ox: OxPtrMut) {
+        unsafe { core::ptr::drop_in_place(ox.ptr().as_ptr::<T>().cast_mut()) };
+    }
+
+    /// Clone a value into uninitialized memory.
+    unsafe fn clone_into<T: Clone>(src: facet::OxPtrConst, dst: OxPtrMut) {
+        let value = unsafe { src.get::<T>() }.clone();
+        unsafe { dst.ptr().as_uninit().put(value) };
+    }
+
+    ShapeBuilder::for_sized::<T>(identifier)
+        .ty(Type::User(UserType::Opaque))
+        .def(Def::Scalar)
+        .attributes(&const { [Attr::new(Some("mc"), "codec", &const { CodecFn::of::<T>() })] })
+        .type_ops_indirect(
+            &const {
+                facet::TypeOpsIndirect {
+                    drop_in_place: drop_in_place::<T>,
+                    default_in_place: None,
+                    clone_into: Some(clone_into::<T>),
+                    is_truthy: None,
+                }
+            },
+        )
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Type-erased function pointers for a type implementing [`McCodec`].
+#[derive(Debug, Clone, Copy, Facet)]
+#[facet(opaque)]
+pub struct CodecFn {
+    encode: unsafe fn(PtrConst, &mut McWriter<'_>) -> Result<(), SerializeError>,
+    decode: unsafe fn(&mut McReader<'_>, PtrUninit) -> Result<(), DeserializeError>,
+    hint: TypeSerializeHint,
+}
+
+impl CodecFn {
+    /// Create a new [`CodecFn`] for the given type.
+    #[must_use]
+    pub const fn of<T: McCodec>() -> Self {
+        /// Encode the value behind the pointer.
+        unsafe fn encode<T: McCodec>(
+            ptr: PtrConst,
+            writer: &mut McWriter<'_>,
+        ) -> Result<(), SerializeError> {
+            unsafe { ptr.get::<T>() }.encode(writer)
+        }
+
+        /// Decode a value into the uninitialized memory behind the pointer.
+        unsafe fn decode<T: McCodec>(
+            reader: &mut McReader<'_>,
+            ptr: PtrUninit,
+        ) -> Result<(), DeserializeError> {
+            let value = T::decode(reader)?;
+            unsafe { ptr.put(value) };
+            Ok(())
+        }
+
+        Self { encode: encode::<T>, decode: decode::<T>, hint: T::SERIALIZE_HINT }
+    }
+
+    /// Get the [`CodecFn`] attached to a [`Shape`](facet::Shape), if any.
+    #[must_use]
+    pub fn from_shape(shape: &facet::Shape) -> Option<&'static Self> {
+        shape
+            .attributes
+            .iter()
+            .find(|attr| attr.ns == Some("mc") && attr.key == "codec")
+            .and_then(Attr::get_as::<Self>)
+    }
+
+    /// Get the [`McCodec::SERIALIZE_HINT`] of the type.
+    #[inline]
+    #[must_use]
+    pub const fn hint(&self) -> TypeSerializeHint { self.hint }
+
+    /// Encode the value behind the pointer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value cannot be encoded,
+    /// or if the buffer cannot be written to.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must point to a valid value of the type this
+    /// [`CodecFn`] was created for.
+    #[inline]
+    pub unsafe fn encode(
+        &self,
+        ptr: PtrConst,
+        writer: &mut McWriter<'_>,
+    ) -> Result<(), SerializeError> {
+        unsafe { (self.encode)(ptr, writer) }
+    }
+
+    /// Decode a value into the uninitialized memory behind the pointer.
+    ///
+    /// Nothing is written if decoding fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is invalid or ends unexpectedly.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must point to memory suitable for a value of the type
+    /// this [`CodecFn`] was created for.
+    #[inline]
+    pub unsafe fn decode(
+        &self,
+        reader: &mut McReader<'_>,
+        ptr: PtrUninit,
+    ) -> Result<(), DeserializeError> {
+        unsafe { (self.decode)(reader, ptr) }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// A writer used by [`McCodec::encode`].
+///
+/// The writer carries the protocol version being written for,
+/// which defaults to [`ProtocolVersion::LATEST`].
+pub struct McWriter<'buffer> {
+    buffer: &'buffer mut dyn SerializeBuffer,
+    version: ProtocolVersion,
+}
+
+impl<'buffer> McWriter<'buffer> {
+    /// Create a new [`McWriter`].
+    #[inline]
+    #[must_use]
+    pub const fn new(buffer: &'buffer mut dyn SerializeBuffer) -> Self {
+        Self { buffer, version: ProtocolVersion::LATEST }
+    }
+
+    /// Write for a protocol version.
+    #[inline]
+    #[must_use]
+    pub const fn with_version(mut self, version: ProtocolVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Get the protocol version being written for.
+    #[inline]
+    #[must_use]
+    pub const fn version(&self) -> ProtocolVersion { self.version }
+
+    /// Get the underlying buffer.
+    #[inline]
+    pub(crate) const fn buffer(&mut self) -> &mut dyn SerializeBuffer { &mut *self.buffer }
+
+    /// Write raw bytes into the buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer cannot be written to.
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), SerializeError> {
+        if self.buffer.extend_buffer(bytes) {
+            Ok(())
+        } else {
+            Err(SerializeError::new(SerializeErrorKind::BufferError))
+        }
+    }
+
+    /// Write a `bool` as a single byte.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer cannot be written to.
+    #[inline]
+    pub fn write_bool(&mut self, value: bool) -> Result<(), SerializeError> {
+        self.write_bytes(&[u8::from(value)])
+    }
+
+    /// Write a `u8`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer cannot be written to.
+    #[inline]
+    pub fn write_u8(&mut self, value: u8) -> Result<(), SerializeError> {
+        self.write_bytes(&[value])
+    }
+
+    /// Write a big-endian `u16`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer cannot be written to.
+    #[inline]
+    pub fn write_u16(&mut self, value: u16) -> Result<(), SerializeError> {
+        self.write_bytes(&value.to_be_bytes())
+    }
+
+    /// Write a big-endian `u32`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer cannot be written to.
+    #[inline]
+    pub fn write_u32(&mut self, value: u32) -> Result<(), SerializeError> {
+        self.write_bytes(&value.to_be_bytes())
+    }
+
+    /// Write a big-endian `u64`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer cannot be written to.
+    #[inline]
+    pub fn write_u64(&mut self, value: u64) -> Result<(), SerializeError> {
+        self.write_bytes(&value.to_be_bytes())
+    }
+
+    /// Write a big-endian `f32`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer cannot be written to.
+    #[inline]
+    pub fn write_f32(&mut self, value: f32) -> Result<(), SerializeError> {
+        self.write_bytes(&value.to_be_bytes())
+    }
+
+    /// Write a big-endian `f64`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer cannot be written to.
+    #[inline]
+    pub fn write_f64(&mut self, value: f64) -> Result<(), SerializeError> {
+        self.write_bytes(&value.to_be_bytes())
+    }
+
+    /// Write a variable-length `u32`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer cannot be written to.
+    #[inline]
+    pub fn write_var_u32(&mut self, value: u32) -> Result<(), SerializeError> {
+        self.write_var_u64(u64::from(value))
+    }
+
+    /// Write a variable-length `i32`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer cannot be written to.
+    #[inline]
+    pub fn write_var_i32(&mut self, value: i32) -> Result<(), SerializeError> {
+        self.write_var_u32(value.cast_unsigned())
+    }
+
+    /// Write a variable-length `u64`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer cannot be written to.
+    pub fn write_var_u64(&mut self, mut value: u64) -> Result<(), SerializeError> {
+        let mut buffer = [0u8; 10];
+        let mut count = 0;
+        loop {
+            let mut byte = (value & 0b0111_1111) as u8;
+            value >>= 7;
+            if value != 0 {
+                byte |= 0b1000_0000;
+            }
+            buffer[count] = byte;
+            count += 1;
+            if value == 0 {
+                break;
+            }
+        }
+        self.write_bytes(&buffer[..count])
+    }
+
+    /// Write a variable-length `i64`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer cannot be written to.
+    #[inline]
+    pub fn write_var_i64(&mut self, value: i64) -> Result<(), SerializeError> {
+        self.write_var_u64(value.cast_unsigned())
+    }
+
+    /// Write a length-prefixed UTF-8 string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer cannot be written to.
+    #[inline]
+    pub fn write_str(&mut self, value: &str) -> Result<(), SerializeError> {
+        self.write_byte_array(value.as_bytes())
+    }
+
+    /// Write a `u16`-prefixed Modified UTF-8 string.
+    ///
+    /// See [`mutf8`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is longer than `u16::MAX` bytes once
+    /// encoded, or if the buffer cannot be written to.
+    pub fn write_mutf8(&mut self, value: &str) -> Result<(), SerializeError> {
+        let bytes = mutf8::encode(value);
+        let Ok(length) = u16::try_from(bytes.len()) else {
+            return Err(SerializeError::new(SerializeErrorKind::StringTooLong(bytes.len())));
+        };
+        self.write_u16(length)?;
+        self.write_bytes(&bytes)
+    }
+
+    /// Write a length-prefixed byte array.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer cannot be written to.
+    #[inline]
+    pub fn write_byte_array(&mut self, value: &[u8]) -> Result<(), SerializeError> {
+        self.write_var_u64(value.len() as u64)?;
+        self.write_bytes(value)
+    }
+
+    /// Write a [`Uuid`] as a big-endian `u128`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer cannot be written to.
+    #[inline]
+    pub fn write_uuid(&mut self, value: &Uuid) -> Result<(), SerializeError> {
+        self.write_bytes(value.as_bytes())
+    }
+
+    /// Write an [`Identifier`] as a length-prefixed string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the identifier is longer than
+    /// [`Identifier::MAX_LENGTH`], or if the buffer cannot be written to.
+    pub fn write_identifier(&mut self, value: &Identifier<'_>) -> Result<(), SerializeError> {
+        let value = value.as_str();
+        if value.len() > Identifier::MAX_LENGTH {
+            return Err(SerializeError::new(SerializeErrorKind::StringTooLong(value.len())));
+        }
+        self.write_str(value)
+    }
+
+    /// Write a value using its [`McCodec`] implementation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value cannot be encoded,
+    /// or if the buffer cannot be written to.
+    #[inline]
+    pub fn write<T: McCodec>(&mut self, value: &T) -> Result<(), SerializeError> {
+        value.encode(self)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// A reader used by [`McCodec::decode`].
+///
+/// The reader carries the protocol version being read for,
+/// which defaults to [`ProtocolVersion::LATEST`].
+#[derive(Debug, Clone, Copy)]
+pub struct McReader<'de> {
+    input: &'de [u8],
+    consumed: usize,
+    version: ProtocolVersion,
+}
+
+impl<'de> McReader<'de> {
+    /// Create a new [`McReader`] over the given input.
+    #[inline]
+    #[must_use]
+    pub const fn new(input: &'de [u8]) -> Self {
+        Self { input, consumed: 0, version: ProtocolVersion::LATEST }
+    }
+
+    /// Read for a protocol version.
+    #[inline]
+    #[must_use]
+    pub const fn with_version(mut self, version: ProtocolVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Get the protocol version being read for.
+    #[inline]
+    #[must_use]
+    pub const fn version(&self) -> ProtocolVersion { self.version }
+
+    /// Returns the number of bytes consumed so far.
+    #[inline]
+    #[must_use]
+    pub const fn consumed(&self) -> usize { self.consumed }
+
+    /// Returns the bytes that have not been consumed yet.
+    #[inline]
+    #[must_use]
+    pub fn remaining(&self) -> &'de [u8] { self.input.get(self.consumed..).unwrap_or_default() }
+
+    /// Read `len` raw bytes from the input.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input ends unexpectedly.
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'de [u8], DeserializeError> {
+        let expected = self.consumed + len;
+        if let Some(bytes) = self.input.get(self.consumed..expected) {
+            self.consumed = expected;
+            Ok(bytes)
+        } else {
+            Err(DeserializeError::new(DeserializeErrorKind::UnexpectedEndOfInput {
+                expected,
+                found: self.input.len(),
+            }))
+        }
+    }
+
+    /// Read a fixed number of raw bytes from the input.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input ends unexpectedly.
+    pub fn read_array<const N: usize>(&mut self) -> Result<[u8; N], DeserializeError> {
+        let mut array = [0u8; N];
+        array.copy_from_slice(self.read_bytes(N)?);
+        Ok(array)
+    }
+
+    /// Read a `bool` from a single byte.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the byte is not a valid boolean,
+    /// or if the input ends unexpectedly.
+    pub fn read_bool(&mut self) -> Result<bool, DeserializeError> {
+        match self.read_scalar(ScalarTypeHint::Bool, false)? {
+            ScalarValue::Bool(value) => Ok(value),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Read a `u8`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input ends unexpectedly.
+    #[inline]
+    pub fn read_u8(&mut self) -> Result<u8, DeserializeError> {
+        self.read_array::<1>().map(|[byte]| byte)
+    }
+
+    /// Read a big-endian `u16`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input ends unexpectedly.
+    #[inline]
+    pub fn read_u16(&mut self) -> Result<u16, DeserializeError> {
+        self.read_array().map(u16::from_be_bytes)
+    }
+
+    /// Read a big-endian `u32`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input ends unexpectedly.
+    #[inline]
+    pub fn read_u32(&mut self) -> Result<u32, DeserializeError> {
+        self.read_array().map(u32::from_be_bytes)
+    }
+
+    /// Read a big-endian `u64`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input ends unexpectedly.
+    #[inline]
+    pub fn read_u64(&mut self) -> Result<u64, DeserializeError> {
+        self.read_array().map(u64::from_be_bytes)
+    }
+
+    /// Read a big-endian `f32`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input ends unexpectedly.
+    #[inline]
+    pub fn read_f32(&mut self) -> Result<f32, DeserializeError> {
+        self.read_array().map(f32::from_be_bytes)
+    }
+
+    /// Read a big-endian `f64`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input ends unexpectedly.
+    #[inline]
+    pub fn read_f64(&mut self) -> Result<f64, DeserializeError> {
+        self.read_array().map(f64::from_be_bytes)
+    }
+
+    /// Read a variable-length `u32`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input ends unexpectedly.
+    #[expect(clippy::cast_possible_truncation, reason = "Parsed as a `u32`")]
+    pub fn read_var_u32(&mut self) -> Result<u32, DeserializeError> {
+        match self.read_scalar(ScalarTypeHint::U32, true)? {
+            ScalarValue::U64(value) => Ok(value as u32),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Read a variable-length `i32`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input ends unexpectedly.
+    #[inline]
+    pub fn read_var_i32(&mut self) -> Result<i32, DeserializeError> {
+        self.read_var_u32().map(u32::cast_signed)
+    }
+
+    /// Read a variable-length `u64`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input ends unexpectedly.
+    pub fn read_var_u64(&mut self) -> Result<u64, DeserializeError> {
+        match self.read_scalar(ScalarTypeHint::U64, true)? {
+            ScalarValue::U64(value) => Ok(value),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Read a variable-length `i64`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input ends unexpectedly.
+    #[inline]
+    pub fn read_var_i64(&mut self) -> Result<i64, DeserializeError> {
+        self.read_var_u64().map(u64::cast_signed)
+    }
+
+    /// Read a length-prefixed UTF-8 string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is not valid UTF-8,
+    /// or if the input ends unexpectedly.
+    pub fn read_str(&mut self) -> Result<&'de str, DeserializeError> {
+        match self.read_scalar(ScalarTypeHint::String, false)? {
+            ScalarValue::Str(Cow::Borrowed(value)) => Ok(value),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Read a `u16`-prefixed Modified UTF-8 string.
+    ///
+    /// See [`mutf8`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is not valid Modified UTF-8,
+    /// or if the input ends unexpectedly.
+    pub fn read_mutf8(&mut self) -> Result<Cow<'de, str>, DeserializeError> {
+        let length = self.read_u16()?;
+        let bytes = self.read_bytes(usize::from(length))?;
+        mutf8::decode(bytes).map_err(|_| DeserializeError::new(DeserializeErrorKind::InvalidUtf8))
+    }
+
+    /// Read a length-prefixed byte array.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input ends unexpectedly.
+    pub fn read_byte_array(&mut self) -> Result<&'de [u8], DeserializeError> {
+        match self.read_scalar(ScalarTypeHint::Bytes, false)? {
+            ScalarValue::Bytes(Cow::Borrowed(value)) => Ok(value),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Read a [`Uuid`] from a big-endian `u128`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input ends unexpectedly.
+    #[inline]
+    pub fn read_uuid(&mut self) -> Result<Uuid, DeserializeError> {
+        self.read_array().map(Uuid::from_bytes)
+    }
+
+    /// Read a length-prefixed [`Identifier`], borrowing from the input.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the identifier is too long, contains characters
+    /// not allowed in identifiers, or if the input ends unexpectedly.
+    pub fn read_identifier(&mut self) -> Result<Identifier<'de>, DeserializeError> {
+        Identifier::new(self.read_str()?).map_err(Into::into)
+    }
+
+    /// Read a value using its [`McCodec`] implementation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is invalid or ends unexpectedly.
+    #[inline]
+    pub fn read<T: McCodec>(&mut self) -> Result<T, DeserializeError> { T::decode(self) }
+
+    /// Parse a scalar using [`parse::parse_scalar`],
+    /// adjusting any errors to be relative to the start of the input.
+    fn read_scalar(
+        &mut self,
+        hint: ScalarTypeHint,
+        variable: bool,
+    ) -> Result<ScalarValue<'de>, DeserializeError> {
+        match parse::parse_scalar(self.remaining(), hint, variable) {
+            Ok((value, size)) => {
+                self.consumed += size;
+                Ok(value)
+            }
+            Err(err) => match *err.kind() {
+                DeserializeErrorKind::UnexpectedEndOfInput { expected, found } => {
+                    Err(DeserializeError::new(DeserializeErrorKind::UnexpectedEndOfInput {
+                        expected: self.consumed + expected,
+                        found: self.consumed + found,
+                    }))
+                }
+                _ => Err(err),
+            },
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use core::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+use crate::packet::ProtocolVersion;
+
+/// An error that occurred while encoding or decoding a command graph.
+#[derive(Debug)]
+pub struct CommandError {
+    kind: CommandErrorKind,
+}
+
+impl CommandError {
+    /// Create a new [`CommandError`].
+    #[inline]
+    #[must_use]
+    pub const fn new(kind: CommandErrorKind) -> Self { Self { kind } }
+
+    /// Get the kind of command error.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> &CommandErrorKind { &self.kind }
+}
+
+/// The type of command error.
+#[derive(Debug)]
+pub enum CommandErrorKind {
+    /// A node's flags used an unknown node type or unknown bits.
+    InvalidFlags(u8),
+    /// An argument parser ID was not recognized.
+    UnknownParser(u32),
+    /// An argument parser does not exist in the protocol version.
+    UnsupportedParser {
+        /// The name of the parser.
+        parser: &'static str,
+        /// The protocol version.
+        version: ProtocolVersion,
+    },
+    /// The protocol version is older than 1.21.4, whose parser IDs are
+    /// not known.
+    UnsupportedVersion(ProtocolVersion),
+    /// A string argument used an unknown kind.
+    InvalidStringKind(u32),
+    /// A node has a child index that does not exist.
+    InvalidChild {
+        /// The index of the node.
+        node: u32,
+        /// The index of the child.
+        child: u32,
+    },
+    /// A node redirects to an index that does not exist.
+    InvalidRedirect {
+        /// The index of the node.
+        node: u32,
+        /// The index of the redirect target.
+        redirect: u32,
+    },
+    /// The root index does not exist.
+    InvalidRoot(u32),
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Error for CommandError {}
+impl Display for CommandError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            CommandErrorKind::InvalidFlags(flags) => {
+                write!(f, "invalid command node flags {flags:#04x}")
+            }
+            CommandErrorKind::UnknownParser(id) => write!(f, "unknown argument parser {id}"),
+            CommandErrorKind::UnsupportedParser { parser, version } => {
+                write!(
+                    f,
+                    "argument parser `{parser}` does not exist in protocol {}",
+                    version.protocol()
+                )
+            }
+            CommandErrorKind::UnsupportedVersion(version) => {
+                write!(f, "command graphs are not supported in protocol {}", version.protocol())
+            }
+            CommandErrorKind::InvalidStringKind(kind) => {
+                write!(f, "invalid string argument kind {kind}")
+            }
+            CommandErrorKind::InvalidChild { node, child } => {
+                write!(f, "command node {node} has a missing child {child}")
+            }
+            CommandErrorKind::InvalidRedirect { node, redirect } => {
+                write!(f, "command node {node} redirects to a missing node {redirect}")
+            }
+            CommandErrorKind::InvalidRoot(index) => write!(f, "missing root command node {index}"),
+        }
+    }
+}
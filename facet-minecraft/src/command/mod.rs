@@ -0,0 +1,394 @@
+//! The command graph, as sent in the Commands packet.
+//!
+//! A [`CommandGraph`] is a flat list of [`CommandNode`]s that refer to their
+//! children and redirect targets by index, followed by the index of the root
+//! node. Indices are checked when the graph is encoded or decoded, so a graph
+//! never refers to a node that does not exist.
+//!
+//! Parser IDs and node flags depend on the protocol version. As a field, the
+//! graph is encoded for the version of the [`McWriter`] or [`McReader`],
+//! otherwise use [`CommandGraph::encode_for`] and [`CommandGraph::decode_for`].
+//! Graphs are only supported since 1.21.4, as older versions use parser IDs
+//! that are not known.
+//!
+//! ```rust
+//! use facet_minecraft::command::{ArgumentParser, CommandGraph, CommandNode};
+//!
+//! let mut graph = CommandGraph::new();
+//! let gamemode = graph.add(graph.root(), CommandNode::literal("gamemode"));
+//! graph.add(gamemode, CommandNode::argument("mode", ArgumentParser::Gamemode).executable());
+//!
+//! let bytes = facet_minecraft::to_vec(&graph).unwrap();
+//! assert_eq!(&bytes[..4], [3, 0, 1, 1]);
+//! let (decoded, _) = facet_minecraft::from_slice::<CommandGraph>(&bytes).unwrap();
+//! assert_eq!(decoded, graph);
+//! ```
+
+use alloc::{string::String, vec::Vec};
+
+use facet::{Facet, Shape};
+
+use crate::{
+    codec::{McCodec, McReader, McWriter, codec_shape},
+    deserialize::DeserializeError,
+    packet::ProtocolVersion,
+    serialize::SerializeError,
+    types::Identifier,
+};
+
+mod error;
+pub use error::{CommandError, CommandErrorKind};
+
+mod parser;
+pub use parser::{ArgumentParser, StringKind};
+
+/// A graph of command nodes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandGraph {
+    nodes: Vec<CommandNode>,
+    root: u32,
+}
+
+/// A node in a [`CommandGraph`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandNode {
+    /// The type of node.
+    pub kind: NodeKind,
+    /// Whether the command can be run when ending at this node.
+    pub executable: bool,
+    /// Whether the command requires elevated permissions.
+    ///
+    /// Only sent since 1.21.6, and left out for older versions.
+    pub restricted: bool,
+    /// The indices of the node's children.
+    pub children: Vec<u32>,
+    /// The index of the node to continue parsing from.
+    pub redirect: Option<u32>,
+}
+
+/// The type of a [`CommandNode`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodeKind {
+    /// The root of the graph.
+    Root,
+    /// A literal word.
+    Literal(String),
+    /// An argument parsed by an [`ArgumentParser`].
+    Argument {
+        /// The name of the argument.
+        name: String,
+        /// The parser of the argument.
+        parser: ArgumentParser,
+        /// The suggestion provider, such as `minecraft:ask_server`.
+        suggestions: Option<Identifier<'static>>,
+    },
+}
+
+impl CommandGraph {
+    /// Create a new [`CommandGraph`] containing only a root node.
+    #[must_use]
+    pub fn new() -> Self { Self { nodes: alloc::vec![CommandNode::root()], root: 0 } }
+
+    /// Create a [`CommandGraph`] from its nodes and the index of the root.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the root, a child or a redirect index
+    /// does not refer to a node.
+    pub fn from_parts(nodes: Vec<CommandNode>, root: u32) -> Result<Self, CommandError> {
+        let graph = Self { nodes, root };
+        graph.validate()?;
+        Ok(graph)
+    }
+
+    /// Get the index of the root node.
+    #[inline]
+    #[must_use]
+    pub const fn root(&self) -> u32 { self.root }
+
+    /// Get all nodes, ordered by index.
+    #[inline]
+    #[must_use]
+    pub fn nodes(&self) -> &[CommandNode] { &self.nodes }
+
+    /// Get the node at an index, if present.
+    #[must_use]
+    pub fn node(&self, index: u32) -> Option<&CommandNode> { self.nodes.get(index as usize) }
+
+    /// Get a mutable reference to the node at an index, if present.
+    #[must_use]
+    pub fn node_mut(&mut self, index: u32) -> Option<&mut CommandNode> {
+        self.nodes.get_mut(index as usize)
+    }
+
+    /// Add a node as a child of another node, returning its index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parent node does not exist.
+    pub fn add(&mut self, parent: u32, node: CommandNode) -> u32 {
+        let index = u32::try_from(self.nodes.len()).expect("too many command nodes");
+        self.nodes[parent as usize].children.push(index);
+        self.nodes.push(node);
+        index
+    }
+
+    /// Check that the root, children and redirects refer to existing nodes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for the first index that does not refer to a node.
+    pub fn validate(&self) -> Result<(), CommandError> {
+        let exists = |index: u32| (index as usize) < self.nodes.len();
+        if !exists(self.root) {
+            return Err(CommandError::new(CommandErrorKind::InvalidRoot(self.root)));
+        }
+
+        for (node, index) in self.nodes.iter().zip(0u32..) {
+            if let Some(&child) = node.children.iter().find(|child| !exists(**child)) {
+                return Err(CommandError::new(CommandErrorKind::InvalidChild {
+                    node: index,
+                    child,
+                }));
+            }
+            if let Some(redirect) = node.redirect.filter(|redirect| !exists(*redirect)) {
+                return Err(CommandError::new(CommandErrorKind::InvalidRedirect {
+                    node: index,
+                    redirect,
+                }));
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the graph for a protocol version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the protocol version is not supported, an index
+    /// does not refer to a node, a parser does not exist in the protocol
+    /// version, or the buffer cannot be written to.
+    pub fn encode_for(
+        &self,
+        writer: &mut McWriter<'_>,
+        version: ProtocolVersion,
+    ) -> Result<(), SerializeError> {
+        check_version(version)?;
+        self.validate()?;
+        writer.write_var_u32(u32::try_from(self.nodes.len()).unwrap_or(u32::MAX))?;
+        self.nodes.iter().try_for_each(|node| node.encode_for(writer, version))?;
+        writer.write_var_u32(self.root)
+    }
+
+    /// Read a graph for a protocol version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the protocol version is not supported, the input
+    /// ends unexpectedly, or does not contain a valid graph for the protocol
+    /// version.
+    pub fn decode_for(
+        reader: &mut McReader<'_>,
+        version: ProtocolVersion,
+    ) -> Result<Self, DeserializeError> {
+        check_version(version)?;
+        let len = reader.read_var_u32()?;
+        let nodes =
+            (0..len).map(|_| CommandNode::decode_for(reader, version)).collect::<Result<_, _>>()?;
+        Ok(Self::from_parts(nodes, reader.read_var_u32()?)?)
+    }
+}
+
+/// Reject protocol versions whose parser IDs are not known.
+fn check_version(version: ProtocolVersion) -> Result<(), CommandError> {
+    if version < ProtocolVersion::V1_21_4 {
+        Err(CommandError::new(CommandErrorKind::UnsupportedVersion(version)))
+    } else {
+        Ok(())
+    }
+}
+
+impl Default for CommandGraph {
+    fn default() -> Self { Self::new() }
+}
+
+impl McCodec for CommandGraph {
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        self.encode_for(writer, writer.version())
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        Self::decode_for(reader, reader.version())
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for CommandGraph {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("CommandGraph").build() };
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl CommandNode {
+    const ARGUMENT: u8 = 0x02;
+    const EXECUTABLE: u8 = 0x04;
+    const LITERAL: u8 = 0x01;
+    const REDIRECT: u8 = 0x08;
+    const RESTRICTED: u8 = 0x20;
+    const SUGGESTIONS: u8 = 0x10;
+    const TYPE_MASK: u8 = 0x03;
+
+    /// Create a new root node.
+    #[must_use]
+    pub const fn root() -> Self { Self::new(NodeKind::Root) }
+
+    /// Create a new literal node.
+    #[must_use]
+    pub fn literal(name: impl Into<String>) -> Self { Self::new(NodeKind::Literal(name.into())) }
+
+    /// Create a new argument node without suggestions.
+    #[must_use]
+    pub fn argument(name: impl Into<String>, parser: ArgumentParser) -> Self {
+        Self::new(NodeKind::Argument { name: name.into(), parser, suggestions: None })
+    }
+
+    /// Create a new node without children.
+    const fn new(kind: NodeKind) -> Self {
+        Self { kind, executable: false, restricted: false, children: Vec::new(), redirect: None }
+    }
+
+    /// Mark the node as executable.
+    #[must_use]
+    pub fn executable(mut self) -> Self {
+        self.executable = true;
+        self
+    }
+
+    /// Mark the node as requiring elevated permissions.
+    #[must_use]
+    pub fn restricted(mut self) -> Self {
+        self.restricted = true;
+        self
+    }
+
+    /// Redirect the node to another node.
+    #[must_use]
+    pub fn redirect(mut self, index: u32) -> Self {
+        self.redirect = Some(index);
+        self
+    }
+
+    /// Set the suggestion provider of an argument node.
+    ///
+    /// Has no effect on other nodes.
+    #[must_use]
+    pub fn suggestions(mut self, provider: Identifier<'static>) -> Self {
+        if let NodeKind::Argument { suggestions, .. } = &mut self.kind {
+            *suggestions = Some(provider);
+        }
+        self
+    }
+
+    /// Get the name of the node, if it is not the root.
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        match &self.kind {
+            NodeKind::Root => None,
+            NodeKind::Literal(name) | NodeKind::Argument { name, .. } => Some(name),
+        }
+    }
+
+    /// Returns `true` if nodes can be marked as restricted
+    /// in the protocol version.
+    ///
+    /// The flag was added in 1.21.6.
+    const fn has_restricted(version: ProtocolVersion) -> bool {
+        version.protocol() >= ProtocolVersion::V1_21_6.protocol()
+    }
+
+    /// Write the node for a protocol version.
+    fn encode_for(
+        &self,
+        writer: &mut McWriter<'_>,
+        version: ProtocolVersion,
+    ) -> Result<(), SerializeError> {
+        let mut flags = match &self.kind {
+            NodeKind::Root => 0,
+            NodeKind::Literal(_) => Self::LITERAL,
+            NodeKind::Argument { suggestions: None, .. } => Self::ARGUMENT,
+            NodeKind::Argument { suggestions: Some(_), .. } => Self::ARGUMENT | Self::SUGGESTIONS,
+        };
+        if self.executable {
+            flags |= Self::EXECUTABLE;
+        }
+        if self.redirect.is_some() {
+            flags |= Self::REDIRECT;
+        }
+        if self.restricted && Self::has_restricted(version) {
+            flags |= Self::RESTRICTED;
+        }
+
+        writer.write_u8(flags)?;
+        writer.write_var_u32(u32::try_from(self.children.len()).unwrap_or(u32::MAX))?;
+        self.children.iter().try_for_each(|child| writer.write_var_u32(*child))?;
+        if let Some(redirect) = self.redirect {
+            writer.write_var_u32(redirect)?;
+        }
+
+        match &self.kind {
+            NodeKind::Root => Ok(()),
+            NodeKind::Literal(name) => writer.write_str(name),
+            NodeKind::Argument { name, parser, suggestions } => {
+                writer.write_str(name)?;
+                parser.encode_for(writer, version)?;
+                suggestions.as_ref().map_or(Ok(()), |provider| writer.write_identifier(provider))
+            }
+        }
+    }
+
+    /// Read a node for a protocol version.
+    fn decode_for(
+        reader: &mut McReader<'_>,
+        version: ProtocolVersion,
+    ) -> Result<Self, DeserializeError> {
+        let flags = reader.read_u8()?;
+        let node_type = flags & Self::TYPE_MASK;
+        let mut known = Self::TYPE_MASK | Self::EXECUTABLE | Self::REDIRECT;
+        if Self::has_restricted(version) {
+            known |= Self::RESTRICTED;
+        }
+        let invalid = node_type == Self::TYPE_MASK
+            || flags & !(known | Self::SUGGESTIONS) != 0
+            || flags & Self::SUGGESTIONS != 0 && node_type != Self::ARGUMENT;
+        if invalid {
+            return Err(CommandError::new(CommandErrorKind::InvalidFlags(flags)).into());
+        }
+
+        let len = reader.read_var_u32()?;
+        let children = (0..len).map(|_| reader.read_var_u32()).collect::<Result<_, _>>()?;
+        let redirect =
+            if flags & Self::REDIRECT != 0 { Some(reader.read_var_u32()?) } else { None };
+
+        let kind = match node_type {
+            Self::LITERAL => NodeKind::Literal(reader.read_str()?.into()),
+            Self::ARGUMENT => NodeKind::Argument {
+                name: reader.read_str()?.into(),
+                parser: ArgumentParser::decode_for(reader, version)?,
+                suggestions: if flags & Self::SUGGESTIONS != 0 {
+                    Some(reader.read_identifier()?.into_owned())
+                } else {
+                    None
+                },
+            },
+            _ => NodeKind::Root,
+        };
+
+        Ok(Self {
+            kind,
+            executable: flags & Self::EXECUTABLE != 0,
+            restricted: flags & Self::RESTRICTED != 0,
+            children,
+            redirect,
+        })
+    }
+}
@@ -0,0 +1,570 @@
+use super::{CommandError, CommandErrorKind};
+use crate::{
+    codec::{McReader, McWriter},
+    deserialize::DeserializeError,
+    packet::ProtocolVersion,
+    serialize::SerializeError,
+    types::Identifier,
+};
+
+/// The parser of an argument node, with its properties.
+///
+/// Parser IDs follow the `command_argument_type` registry, which depends on
+/// the protocol version, see [`ArgumentParser::table`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArgumentParser {
+    /// `brigadier:bool`
+    Bool,
+    /// `brigadier:float`
+    Float {
+        /// The smallest allowed value.
+        min: Option<f32>,
+        /// The largest allowed value.
+        max: Option<f32>,
+    },
+    /// `brigadier:double`
+    Double {
+        /// The smallest allowed value.
+        min: Option<f64>,
+        /// The largest allowed value.
+        max: Option<f64>,
+    },
+    /// `brigadier:integer`
+    Integer {
+        /// The smallest allowed value.
+        min: Option<i32>,
+        /// The largest allowed value.
+        max: Option<i32>,
+    },
+    /// `brigadier:long`
+    Long {
+        /// The smallest allowed value.
+        min: Option<i64>,
+        /// The largest allowed value.
+        max: Option<i64>,
+    },
+    /// `brigadier:string`
+    String(StringKind),
+    /// `minecraft:entity`
+    Entity {
+        /// Whether only a single entity is allowed.
+        single: bool,
+        /// Whether only players are allowed.
+        players_only: bool,
+    },
+    /// `minecraft:game_profile`
+    GameProfile,
+    /// `minecraft:block_pos`
+    BlockPos,
+    /// `minecraft:column_pos`
+    ColumnPos,
+    /// `minecraft:vec3`
+    Vec3,
+    /// `minecraft:vec2`
+    Vec2,
+    /// `minecraft:block_state`
+    BlockState,
+    /// `minecraft:block_predicate`
+    BlockPredicate,
+    /// `minecraft:item_stack`
+    ItemStack,
+    /// `minecraft:item_predicate`
+    ItemPredicate,
+    /// `minecraft:color`
+    Color,
+    /// `minecraft:hex_color`, since 1.21.6
+    HexColor,
+    /// `minecraft:component`
+    Component,
+    /// `minecraft:style`
+    Style,
+    /// `minecraft:message`
+    Message,
+    /// `minecraft:nbt_compound_tag`
+    NbtCompoundTag,
+    /// `minecraft:nbt_tag`
+    NbtTag,
+    /// `minecraft:nbt_path`
+    NbtPath,
+    /// `minecraft:objective`
+    Objective,
+    /// `minecraft:objective_criteria`
+    ObjectiveCriteria,
+    /// `minecraft:operation`
+    Operation,
+    /// `minecraft:particle`
+    Particle,
+    /// `minecraft:angle`
+    Angle,
+    /// `minecraft:rotation`
+    Rotation,
+    /// `minecraft:scoreboard_slot`
+    ScoreboardSlot,
+    /// `minecraft:score_holder`
+    ScoreHolder {
+        /// Whether multiple score holders are allowed.
+        allow_multiple: bool,
+    },
+    /// `minecraft:swizzle`
+    Swizzle,
+    /// `minecraft:team`
+    Team,
+    /// `minecraft:item_slot`
+    ItemSlot,
+    /// `minecraft:item_slots`
+    ItemSlots,
+    /// `minecraft:resource_location`
+    ResourceLocation,
+    /// `minecraft:function`
+    Function,
+    /// `minecraft:entity_anchor`
+    EntityAnchor,
+    /// `minecraft:int_range`
+    IntRange,
+    /// `minecraft:float_range`
+    FloatRange,
+    /// `minecraft:dimension`
+    Dimension,
+    /// `minecraft:gamemode`
+    Gamemode,
+    /// `minecraft:time`
+    Time {
+        /// The smallest allowed number of ticks.
+        min: i32,
+    },
+    /// `minecraft:resource_or_tag`
+    ResourceOrTag {
+        /// The registry of the resource.
+        registry: Identifier<'static>,
+    },
+    /// `minecraft:resource_or_tag_key`
+    ResourceOrTagKey {
+        /// The registry of the resource.
+        registry: Identifier<'static>,
+    },
+    /// `minecraft:resource`
+    Resource {
+        /// The registry of the resource.
+        registry: Identifier<'static>,
+    },
+    /// `minecraft:resource_key`
+    ResourceKey {
+        /// The registry of the resource.
+        registry: Identifier<'static>,
+    },
+    /// `minecraft:resource_selector`
+    ResourceSelector {
+        /// The registry of the resource.
+        registry: Identifier<'static>,
+    },
+    /// `minecraft:template_mirror`
+    TemplateMirror,
+    /// `minecraft:template_rotation`
+    TemplateRotation,
+    /// `minecraft:heightmap`
+    Heightmap,
+    /// `minecraft:loot_table`
+    LootTable,
+    /// `minecraft:loot_predicate`
+    LootPredicate,
+    /// `minecraft:loot_modifier`
+    LootModifier,
+    /// `minecraft:dialog`, since 1.21.6
+    Dialog,
+    /// `minecraft:uuid`
+    Uuid,
+}
+
+/// The kind of a `brigadier:string` argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StringKind {
+    /// A single word.
+    SingleWord,
+    /// A single word, or a quoted phrase.
+    QuotablePhrase,
+    /// The rest of the input.
+    GreedyPhrase,
+}
+
+/// The flag set when a number parser has a minimum.
+const MIN_FLAG: u8 = 0x01;
+/// The flag set when a number parser has a maximum.
+const MAX_FLAG: u8 = 0x02;
+
+impl ArgumentParser {
+    /// The parsers of 1.21.4 and 1.21.5, ordered by ID.
+    const V1_21_4: &[&str] = &[
+        "brigadier:bool",
+        "brigadier:float",
+        "brigadier:double",
+        "brigadier:integer",
+        "brigadier:long",
+        "brigadier:string",
+        "minecraft:entity",
+        "minecraft:game_profile",
+        "minecraft:block_pos",
+        "minecraft:column_pos",
+        "minecraft:vec3",
+        "minecraft:vec2",
+        "minecraft:block_state",
+        "minecraft:block_predicate",
+        "minecraft:item_stack",
+        "minecraft:item_predicate",
+        "minecraft:color",
+        "minecraft:component",
+        "minecraft:style",
+        "minecraft:message",
+        "minecraft:nbt_compound_tag",
+        "minecraft:nbt_tag",
+        "minecraft:nbt_path",
+        "minecraft:objective",
+        "minecraft:objective_criteria",
+        "minecraft:operation",
+        "minecraft:particle",
+        "minecraft:angle",
+        "minecraft:rotation",
+        "minecraft:scoreboard_slot",
+        "minecraft:score_holder",
+        "minecraft:swizzle",
+        "minecraft:team",
+        "minecraft:item_slot",
+        "minecraft:item_slots",
+        "minecraft:resource_location",
+        "minecraft:function",
+        "minecraft:entity_anchor",
+        "minecraft:int_range",
+        "minecraft:float_range",
+        "minecraft:dimension",
+        "minecraft:gamemode",
+        "minecraft:time",
+        "minecraft:resource_or_tag",
+        "minecraft:resource_or_tag_key",
+        "minecraft:resource",
+        "minecraft:resource_key",
+        "minecraft:resource_selector",
+        "minecraft:template_mirror",
+        "minecraft:template_rotation",
+        "minecraft:heightmap",
+        "minecraft:loot_table",
+        "minecraft:loot_predicate",
+        "minecraft:loot_modifier",
+        "minecraft:uuid",
+    ];
+    /// The parsers of 1.21.6 and later, ordered by ID.
+    const V1_21_6: &[&str] = &[
+        "brigadier:bool",
+        "brigadier:float",
+        "brigadier:double",
+        "brigadier:integer",
+        "brigadier:long",
+        "brigadier:string",
+        "minecraft:entity",
+        "minecraft:game_profile",
+        "minecraft:block_pos",
+        "minecraft:column_pos",
+        "minecraft:vec3",
+        "minecraft:vec2",
+        "minecraft:block_state",
+        "minecraft:block_predicate",
+        "minecraft:item_stack",
+        "minecraft:item_predicate",
+        "minecraft:color",
+        "minecraft:hex_color",
+        "minecraft:component",
+        "minecraft:style",
+        "minecraft:message",
+        "minecraft:nbt_compound_tag",
+        "minecraft:nbt_tag",
+        "minecraft:nbt_path",
+        "minecraft:objective",
+        "minecraft:objective_criteria",
+        "minecraft:operation",
+        "minecraft:particle",
+        "minecraft:angle",
+        "minecraft:rotation",
+        "minecraft:scoreboard_slot",
+        "minecraft:score_holder",
+        "minecraft:swizzle",
+        "minecraft:team",
+        "minecraft:item_slot",
+        "minecraft:item_slots",
+        "minecraft:resource_location",
+        "minecraft:function",
+        "minecraft:entity_anchor",
+        "minecraft:int_range",
+        "minecraft:float_range",
+        "minecraft:dimension",
+        "minecraft:gamemode",
+        "minecraft:time",
+        "minecraft:resource_or_tag",
+        "minecraft:resource_or_tag_key",
+        "minecraft:resource",
+        "minecraft:resource_key",
+        "minecraft:resource_selector",
+        "minecraft:template_mirror",
+        "minecraft:template_rotation",
+        "minecraft:heightmap",
+        "minecraft:loot_table",
+        "minecraft:loot_predicate",
+        "minecraft:loot_modifier",
+        "minecraft:dialog",
+        "minecraft:uuid",
+    ];
+
+    /// Get the names of the parsers of a protocol version, ordered by ID.
+    ///
+    /// Snapshots use the table of the latest release,
+    /// and versions before 1.21.4 are not supported.
+    #[must_use]
+    pub fn table(version: ProtocolVersion) -> &'static [&'static str] {
+        if version >= ProtocolVersion::V1_21_6 {
+            Self::V1_21_6
+        } else if version >= ProtocolVersion::V1_21_4 {
+            Self::V1_21_4
+        } else {
+            &[]
+        }
+    }
+
+    /// Get the name of this parser in the `command_argument_type` registry.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Bool => "brigadier:bool",
+            Self::Float { .. } => "brigadier:float",
+            Self::Double { .. } => "brigadier:double",
+            Self::Integer { .. } => "brigadier:integer",
+            Self::Long { .. } => "brigadier:long",
+            Self::String(_) => "brigadier:string",
+            Self::Entity { .. } => "minecraft:entity",
+            Self::GameProfile => "minecraft:game_profile",
+            Self::BlockPos => "minecraft:block_pos",
+            Self::ColumnPos => "minecraft:column_pos",
+            Self::Vec3 => "minecraft:vec3",
+            Self::Vec2 => "minecraft:vec2",
+            Self::BlockState => "minecraft:block_state",
+            Self::BlockPredicate => "minecraft:block_predicate",
+            Self::ItemStack => "minecraft:item_stack",
+            Self::ItemPredicate => "minecraft:item_predicate",
+            Self::Color => "minecraft:color",
+            Self::HexColor => "minecraft:hex_color",
+            Self::Component => "minecraft:component",
+            Self::Style => "minecraft:style",
+            Self::Message => "minecraft:message",
+            Self::NbtCompoundTag => "minecraft:nbt_compound_tag",
+            Self::NbtTag => "minecraft:nbt_tag",
+            Self::NbtPath => "minecraft:nbt_path",
+            Self::Objective => "minecraft:objective",
+            Self::ObjectiveCriteria => "minecraft:objective_criteria",
+            Self::Operation => "minecraft:operation",
+            Self::Particle => "minecraft:particle",
+            Self::Angle => "minecraft:angle",
+            Self::Rotation => "minecraft:rotation",
+            Self::ScoreboardSlot => "minecraft:scoreboard_slot",
+            Self::ScoreHolder { .. } => "minecraft:score_holder",
+            Self::Swizzle => "minecraft:swizzle",
+            Self::Team => "minecraft:team",
+            Self::ItemSlot => "minecraft:item_slot",
+            Self::ItemSlots => "minecraft:item_slots",
+            Self::ResourceLocation => "minecraft:resource_location",
+            Self::Function => "minecraft:function",
+            Self::EntityAnchor => "minecraft:entity_anchor",
+            Self::IntRange => "minecraft:int_range",
+            Self::FloatRange => "minecraft:float_range",
+            Self::Dimension => "minecraft:dimension",
+            Self::Gamemode => "minecraft:gamemode",
+            Self::Time { .. } => "minecraft:time",
+            Self::ResourceOrTag { .. } => "minecraft:resource_or_tag",
+            Self::ResourceOrTagKey { .. } => "minecraft:resource_or_tag_key",
+            Self::Resource { .. } => "minecraft:resource",
+            Self::ResourceKey { .. } => "minecraft:resource_key",
+            Self::ResourceSelector { .. } => "minecraft:resource_selector",
+            Self::TemplateMirror => "minecraft:template_mirror",
+            Self::TemplateRotation => "minecraft:template_rotation",
+            Self::Heightmap => "minecraft:heightmap",
+            Self::LootTable => "minecraft:loot_table",
+            Self::LootPredicate => "minecraft:loot_predicate",
+            Self::LootModifier => "minecraft:loot_modifier",
+            Self::Dialog => "minecraft:dialog",
+            Self::Uuid => "minecraft:uuid",
+        }
+    }
+
+    /// Get the ID of this parser in a protocol version,
+    /// or `None` if the parser does not exist in that version.
+    #[must_use]
+    pub fn id(&self, version: ProtocolVersion) -> Option<u32> {
+        let index = Self::table(version).iter().position(|name| *name == self.name())?;
+        u32::try_from(index).ok()
+    }
+
+    /// Get the parser with a name and no properties,
+    /// or `None` if the parser has properties or the name is not recognized.
+    fn from_unit_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "brigadier:bool" => Self::Bool,
+            "minecraft:game_profile" => Self::GameProfile,
+            "minecraft:block_pos" => Self::BlockPos,
+            "minecraft:column_pos" => Self::ColumnPos,
+            "minecraft:vec3" => Self::Vec3,
+            "minecraft:vec2" => Self::Vec2,
+            "minecraft:block_state" => Self::BlockState,
+            "minecraft:block_predicate" => Self::BlockPredicate,
+            "minecraft:item_stack" => Self::ItemStack,
+            "minecraft:item_predicate" => Self::ItemPredicate,
+            "minecraft:color" => Self::Color,
+            "minecraft:hex_color" => Self::HexColor,
+            "minecraft:component" => Self::Component,
+            "minecraft:style" => Self::Style,
+            "minecraft:message" => Self::Message,
+            "minecraft:nbt_compound_tag" => Self::NbtCompoundTag,
+            "minecraft:nbt_tag" => Self::NbtTag,
+            "minecraft:nbt_path" => Self::NbtPath,
+            "minecraft:objective" => Self::Objective,
+            "minecraft:objective_criteria" => Self::ObjectiveCriteria,
+            "minecraft:operation" => Self::Operation,
+            "minecraft:particle" => Self::Particle,
+            "minecraft:angle" => Self::Angle,
+            "minecraft:rotation" => Self::Rotation,
+            "minecraft:scoreboard_slot" => Self::ScoreboardSlot,
+            "minecraft:swizzle" => Self::Swizzle,
+            "minecraft:team" => Self::Team,
+            "minecraft:item_slot" => Self::ItemSlot,
+            "minecraft:item_slots" => Self::ItemSlots,
+            "minecraft:resource_location" => Self::ResourceLocation,
+            "minecraft:function" => Self::Function,
+            "minecraft:entity_anchor" => Self::EntityAnchor,
+            "minecraft:int_range" => Self::IntRange,
+            "minecraft:float_range" => Self::FloatRange,
+            "minecraft:dimension" => Self::Dimension,
+            "minecraft:gamemode" => Self::Gamemode,
+            "minecraft:template_mirror" => Self::TemplateMirror,
+            "minecraft:template_rotation" => Self::TemplateRotation,
+            "minecraft:heightmap" => Self::Heightmap,
+            "minecraft:loot_table" => Self::LootTable,
+            "minecraft:loot_predicate" => Self::LootPredicate,
+            "minecraft:loot_modifier" => Self::LootModifier,
+            "minecraft:dialog" => Self::Dialog,
+            "minecraft:uuid" => Self::Uuid,
+            _ => return None,
+        })
+    }
+
+    /// Write the parser ID for a protocol version, followed by its properties.
+    pub(super) fn encode_for(
+        &self,
+        writer: &mut McWriter<'_>,
+        version: ProtocolVersion,
+    ) -> Result<(), SerializeError> {
+        let id = self.id(version).ok_or_else(|| {
+            CommandError::new(CommandErrorKind::UnsupportedParser { parser: self.name(), version })
+        })?;
+        writer.write_var_u32(id)?;
+        match self {
+            Self::Float { min, max } => write_bounds(writer, *min, *max, McWriter::write_f32),
+            Self::Double { min, max } => write_bounds(writer, *min, *max, McWriter::write_f64),
+            Self::Integer { min, max } => write_bounds(writer, *min, *max, |writer, value: i32| {
+                writer.write_u32(value.cast_unsigned())
+            }),
+            Self::Long { min, max } => write_bounds(writer, *min, *max, |writer, value: i64| {
+                writer.write_u64(value.cast_unsigned())
+            }),
+            Self::String(kind) => writer.write_var_u32(*kind as u32),
+            Self::Entity { single, players_only } => {
+                writer.write_u8(u8::from(*single) | u8::from(*players_only) << 1)
+            }
+            Self::ScoreHolder { allow_multiple } => writer.write_u8(u8::from(*allow_multiple)),
+            Self::Time { min } => writer.write_u32(min.cast_unsigned()),
+            Self::ResourceOrTag { registry }
+            | Self::ResourceOrTagKey { registry }
+            | Self::Resource { registry }
+            | Self::ResourceKey { registry }
+            | Self::ResourceSelector { registry } => writer.write_identifier(registry),
+            _ => Ok(()),
+        }
+    }
+
+    /// Read a parser ID for a protocol version, followed by its properties.
+    pub(super) fn decode_for(
+        reader: &mut McReader<'_>,
+        version: ProtocolVersion,
+    ) -> Result<Self, DeserializeError> {
+        let id = reader.read_var_u32()?;
+        let Some(name) = Self::table(version).get(id as usize).copied() else {
+            return Err(CommandError::new(CommandErrorKind::UnknownParser(id)).into());
+        };
+        if let Some(parser) = Self::from_unit_name(name) {
+            return Ok(parser);
+        }
+
+        Ok(match name {
+            "brigadier:float" => {
+                let (min, max) = read_bounds(reader, McReader::read_f32)?;
+                Self::Float { min, max }
+            }
+            "brigadier:double" => {
+                let (min, max) = read_bounds(reader, McReader::read_f64)?;
+                Self::Double { min, max }
+            }
+            "brigadier:integer" => {
+                let (min, max) =
+                    read_bounds(reader, |reader| reader.read_u32().map(u32::cast_signed))?;
+                Self::Integer { min, max }
+            }
+            "brigadier:long" => {
+                let (min, max) =
+                    read_bounds(reader, |reader| reader.read_u64().map(u64::cast_signed))?;
+                Self::Long { min, max }
+            }
+            "brigadier:string" => Self::String(match reader.read_var_u32()? {
+                0 => StringKind::SingleWord,
+                1 => StringKind::QuotablePhrase,
+                2 => StringKind::GreedyPhrase,
+                kind => {
+                    return Err(CommandError::new(CommandErrorKind::InvalidStringKind(kind)).into());
+                }
+            }),
+            "minecraft:entity" => {
+                let flags = reader.read_u8()?;
+                Self::Entity { single: flags & 0x01 != 0, players_only: flags & 0x02 != 0 }
+            }
+            "minecraft:score_holder" => {
+                Self::ScoreHolder { allow_multiple: reader.read_u8()? & 0x01 != 0 }
+            }
+            "minecraft:time" => Self::Time { min: reader.read_u32()?.cast_signed() },
+            _ => {
+                let registry = reader.read_identifier()?.into_owned();
+                match name {
+                    "minecraft:resource_or_tag" => Self::ResourceOrTag { registry },
+                    "minecraft:resource_or_tag_key" => Self::ResourceOrTagKey { registry },
+                    "minecraft:resource" => Self::Resource { registry },
+                    "minecraft:resource_key" => Self::ResourceKey { registry },
+                    _ => Self::ResourceSelector { registry },
+                }
+            }
+        })
+    }
+}
+
+/// Write the flags and bounds of a number parser.
+fn write_bounds<'a, T: Copy>(
+    writer: &mut McWriter<'a>,
+    min: Option<T>,
+    max: Option<T>,
+    write: impl Fn(&mut McWriter<'a>, T) -> Result<(), SerializeError>,
+) -> Result<(), SerializeError> {
+    let flags = if min.is_some() { MIN_FLAG } else { 0 } | if max.is_some() { MAX_FLAG } else { 0 };
+    writer.write_u8(flags)?;
+    min.into_iter().chain(max).try_for_each(|value| write(writer, value))
+}
+
+/// Read the flags and bounds of a number parser.
+fn read_bounds<'a, T>(
+    reader: &mut McReader<'a>,
+    read: impl Fn(&mut McReader<'a>) -> Result<T, DeserializeError>,
+) -> Result<(Option<T>, Option<T>), DeserializeError> {
+    let flags = reader.read_u8()?;
+    let min = if flags & MIN_FLAG != 0 { Some(read(reader)?) } else { None };
+    let max = if flags & MAX_FLAG != 0 { Some(read(reader)?) } else { None };
+    Ok((min, max))
+}
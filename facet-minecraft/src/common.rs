@@ -6,6 +6,8 @@ use facet::{
     SequenceType, SetDef, Shape, ShapeLayout, SliceDef, TextualType, Type, UserType,
 };
 
+use crate::codec::CodecFn;
+
 /// Indicates whether a type can be serialized and whether it can fail.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum TypeSerializeResult {
@@ -170,6 +172,27 @@ const VAR_U32_HINT: TypeSerializeHint = TypeSerializeHint::Range { min: 1, max:
 const VAR_U32_UNBOUNDED_HINT: TypeSerializeHint = TypeSerializeHint::Range { min: 1, max: None };
 const VAR_U64_HINT: TypeSerializeHint = TypeSerializeHint::Range { min: 1, max: Some(10) };
 const VAR_U128_HINT: TypeSerializeHint = TypeSerializeHint::Range { min: 1, max: Some(19) };
+const MUTF8_HINT: TypeSerializeHint =
+    TypeSerializeHint::Range { min: 2, max: Some(2 + u16::MAX as usize) };
+
+/// Returns `true` if the field attributes contain `mc::mutf8`.
+pub(crate) const fn is_mutf8(attrs: Option<&'static [FieldAttribute]>) -> bool {
+    let Some(field_attrs) = attrs else { return false };
+
+    let mut index: usize = 0;
+    while index < field_attrs.len() {
+        let attr = &field_attrs[index];
+        index += 1;
+
+        if let Some(name) = &attr.ns
+            && matches!(name.as_bytes(), b"mc")
+            && matches!(attr.key.as_bytes(), b"mutf8")
+        {
+            return true;
+        }
+    }
+    false
+}
 
 /// A helper function to calculate the [`TypeSerializeHint`] for a [`Shape`].
 pub(crate) const fn calculate_shape_hint(
@@ -302,8 +325,8 @@ const fn calculate_ty_hint(
                                 b"variable" => {
                                     variable_length = true;
                                 }
-                                // Custom functions cannot provide a size hint
-                                b"serialize" | b"deserialize" => {
+                                // Custom functions and NBT cannot provide a size hint
+                                b"serialize" | b"deserialize" | b"nbt" => {
                                     return TypeSerializeHint::None;
                                 }
                                 _ => {}
@@ -332,6 +355,8 @@ const fn calculate_ty_hint(
                 }
             }
             PrimitiveType::Textual(ty) => match ty {
+                // `str` as Modified UTF-8
+                TextualType::Str if is_mutf8(attrs) => MUTF8_HINT,
                 // `str`
                 TextualType::Str => VAR_U32_UNBOUNDED_HINT,
                 // `char` (not supported)
@@ -395,9 +420,14 @@ const fn calculate_ty_hint(
             }
             // `opaque`
             UserType::Opaque => {
-                // Essentially overrides for specific known types
+                // Essentially overrides for specific known types.
+                // Types with an `mc::codec` attribute provide their own hint,
+                // but attribute data cannot be read in a `const fn`,
+                // see `calculate_shape_hint_versioned`.
                 // TODO: Use `ConstTypeId`/`TypeId` instead of identifiers
                 match shape.type_identifier.as_bytes() {
+                    // `u16` length repr + up to `u16::MAX` bytes
+                    b"String" if is_mutf8(attrs) => MUTF8_HINT,
                     // VarInt length repr + unknown max
                     b"String" => VAR_U32_UNBOUNDED_HINT,
                     // `[u8; 16]`
@@ -441,3 +471,134 @@ const fn calculate_field_hint(fields: &[Field]) -> TypeSerializeHint {
 
     acc
 }
+
+// -------------------------------------------------------------------------------------------------
+
+/// Get the value of a version attribute, `mc::since` or `mc::until`.
+fn version_attr(attrs: &[FieldAttribute], key: &str) -> Option<usize> {
+    attrs
+        .iter()
+        .find(|attr| attr.ns == Some("mc") && attr.key == key)
+        .and_then(|attr| attr.get_as::<usize>().copied())
+}
+
+/// Returns `true` if a field or variant with the given attributes is
+/// present in a protocol version.
+///
+/// Everything is present if no version is given.
+pub(crate) fn is_present(attrs: &[FieldAttribute], version: Option<u32>) -> bool {
+    let Some(version) = version else { return true };
+    let version = version as usize;
+
+    version_attr(attrs, "since").is_none_or(|since| since <= version)
+        && version_attr(attrs, "until").is_none_or(|until| version <= until)
+}
+
+/// Returns `true` if a field or variant has a version attribute.
+pub(crate) fn is_versioned(attrs: &[FieldAttribute]) -> bool {
+    version_attr(attrs, "since").is_some() || version_attr(attrs, "until").is_some()
+}
+
+/// Returns `true` if a struct or enum has a version attribute,
+/// or contains one through its fields, a container or a pointer.
+pub(crate) fn contains_versioned(shape: &'static Shape) -> bool {
+    /// The shapes currently being visited, to stop at recursive types.
+    struct Visiting<'a> {
+        shape: &'static Shape,
+        parent: Option<&'a Visiting<'a>>,
+    }
+
+    fn contains(shape: &'static Shape, parent: Option<&Visiting<'_>>) -> bool {
+        let mut current = parent;
+        while let Some(visiting) = current {
+            if core::ptr::eq(visiting.shape, shape) {
+                return false;
+            }
+            current = visiting.parent;
+        }
+        let visiting = Visiting { shape, parent };
+
+        match shape.def {
+            Def::Option(def) => return contains(def.t(), Some(&visiting)),
+            Def::List(def) => return contains(def.t(), Some(&visiting)),
+            Def::Array(def) => return contains(def.t(), Some(&visiting)),
+            Def::Set(def) => return contains(def.t(), Some(&visiting)),
+            Def::Map(def) => {
+                return contains(def.k(), Some(&visiting)) || contains(def.v(), Some(&visiting));
+            }
+            Def::Pointer(def) => {
+                return def.pointee().is_some_and(|t| contains(t, Some(&visiting)));
+            }
+            _ => {}
+        }
+
+        match shape.ty {
+            Type::User(UserType::Struct(ty)) => ty.fields.iter().any(|field| {
+                is_versioned(field.attributes) || contains(field.shape(), Some(&visiting))
+            }),
+            Type::User(UserType::Enum(ty)) => ty.variants.iter().any(|variant| {
+                is_versioned(variant.attributes)
+                    || variant
+                        .data
+                        .fields
+                        .iter()
+                        .any(|field| contains(field.shape(), Some(&visiting)))
+            }),
+            _ => false,
+        }
+    }
+
+    contains(shape, None)
+}
+
+/// A version-aware [`calculate_shape_hint`].
+///
+/// Fields and variants not present in the protocol version are ignored.
+/// Unlike [`calculate_shape_hint`], this runs at runtime and can inspect
+/// the fields of structs and enums, and the [`CodecFn`] of codec types.
+pub(crate) fn calculate_shape_hint_versioned(
+    shape: &'static Shape,
+    attrs: Option<&'static [FieldAttribute]>,
+    version: u32,
+) -> TypeSerializeHint {
+    /// Sum the hints of all fields present in the version.
+    fn fields_hint(fields: &'static [Field], version: u32) -> TypeSerializeHint {
+        fields.iter().filter(|field| is_present(field.attributes, Some(version))).fold(
+            TypeSerializeHint::Exact { size: 0 },
+            |acc, field| {
+                acc.add(calculate_shape_hint_versioned(
+                    field.shape(),
+                    Some(field.attributes),
+                    version,
+                ))
+            },
+        )
+    }
+
+    if let Some(codec) = CodecFn::from_shape(shape) {
+        return codec.hint();
+    }
+
+    match shape.ty {
+        Type::User(UserType::Struct(ty)) => fields_hint(ty.fields, version),
+        Type::User(UserType::Enum(ty)) => {
+            let mut variants = ty
+                .variants
+                .iter()
+                .filter(|variant| is_present(variant.attributes, Some(version)))
+                .map(|variant| fields_hint(variant.data.fields, version));
+
+            let Some(first) = variants.next() else { return TypeSerializeHint::None };
+            let (min, max) =
+                variants.fold((first.minimum(), first.maximum()), |(min, max), hint| {
+                    (
+                        min.zip(hint.minimum()).map(|(a, b)| a.min(b)),
+                        max.zip(hint.maximum()).map(|(a, b)| a.max(b)),
+                    )
+                });
+
+            VAR_U32_HINT.add(TypeSerializeHint::from_min_max(min, max))
+        }
+        _ => calculate_shape_hint(shape, attrs),
+    }
+}
@@ -0,0 +1,81 @@
+use core::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+use super::Transition;
+use crate::packet::{Direction, ProtocolState};
+
+/// An error that occurred while tracking the state of a connection.
+#[derive(Debug)]
+pub struct ConnectionError {
+    kind: ConnectionErrorKind,
+}
+
+impl ConnectionError {
+    /// Create a new [`ConnectionError`].
+    #[inline]
+    #[must_use]
+    pub const fn new(kind: ConnectionErrorKind) -> Self { Self { kind } }
+
+    /// Get the kind of connection error.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> &ConnectionErrorKind { &self.kind }
+}
+
+/// The type of connection error.
+#[derive(Debug)]
+pub enum ConnectionErrorKind {
+    /// A packet was sent or received in the wrong state or direction.
+    UnexpectedPacket {
+        /// The current state of the connection.
+        current: ProtocolState,
+        /// The state the packet belongs to.
+        state: ProtocolState,
+        /// The direction the packet belongs to.
+        direction: Direction,
+        /// The packet ID.
+        id: u32,
+    },
+    /// A packet reported a transition that is not possible
+    /// in its state and direction.
+    InvalidTransition {
+        /// The transition.
+        transition: Transition,
+        /// The state the packet belongs to.
+        state: ProtocolState,
+        /// The direction the packet belongs to.
+        direction: Direction,
+    },
+    /// A packet was sent by the side waiting for a transition
+    /// to be acknowledged.
+    AwaitingAcknowledgement(Transition),
+    /// A transition was acknowledged without being started.
+    UnexpectedAcknowledgement(Transition),
+    /// Encryption was enabled twice.
+    AlreadyEncrypted,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Error for ConnectionError {}
+impl Display for ConnectionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ConnectionErrorKind::UnexpectedPacket { current, state, direction, id } => {
+                write!(f, "unexpected {direction} {state} packet {id:#04x} in {current} state")
+            }
+            ConnectionErrorKind::InvalidTransition { transition, state, direction } => {
+                write!(f, "{direction} {state} packets cannot cause a {transition:?} transition")
+            }
+            ConnectionErrorKind::AwaitingAcknowledgement(transition) => {
+                write!(f, "waiting for {transition:?} to be acknowledged")
+            }
+            ConnectionErrorKind::UnexpectedAcknowledgement(transition) => {
+                write!(f, "unexpected acknowledgement {transition:?}")
+            }
+            ConnectionErrorKind::AlreadyEncrypted => f.write_str("encryption is already enabled"),
+        }
+    }
+}
@@ -0,0 +1,325 @@
+//! A sans-IO state machine tracking the state of a connection.
+//!
+//! A [`Connection`] is fed every packet sent and received on one side of a
+//! connection. It checks each packet is legal in the current
+//! [`ProtocolState`] and [`Direction`], follows the transitions between
+//! states, and returns an [`Action`] when the
+//! [`PacketCodec`](crate::frame::PacketCodec) should enable compression or
+//! encryption.
+//!
+//! Packets report which [`Transition`] they cause through
+//! [`Packet::transition`]. Transitions started by the server, such as
+//! Login Success, only take effect once the client acknowledges them:
+//!
+//! | Started by           | Acknowledged by                  | Next state      |
+//! |----------------------|----------------------------------|-----------------|
+//! | Intention            |                                  | Status or Login |
+//! | Login Success        | Login Acknowledged               | Configuration   |
+//! | Finish Configuration | Acknowledge Finish Configuration | Play            |
+//! | Start Configuration  | Configuration Acknowledged       | Configuration   |
+//!
+//! While waiting for an acknowledgement the server may not send any packets.
+
+use crate::packet::{Direction, Packet, PacketSet, ProtocolState};
+
+mod error;
+pub use error::{ConnectionError, ConnectionErrorKind};
+
+/// A change to the state of a connection caused by a packet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Transition {
+    /// Intention with the status intent, switching to
+    /// [`ProtocolState::Status`].
+    Status,
+    /// Intention with the login or transfer intent,
+    /// switching to [`ProtocolState::Login`].
+    Login,
+    /// Set Compression with a threshold,
+    /// where a negative threshold disables compression.
+    SetCompression(i32),
+    /// Encryption Response, after which encryption is enabled.
+    EnableEncryption,
+    /// Login Success, acknowledged by [`Transition::LoginAcknowledged`].
+    LoginSuccess,
+    /// Login Acknowledged, switching to [`ProtocolState::Configuration`].
+    LoginAcknowledged,
+    /// Finish Configuration,
+    /// acknowledged by [`Transition::AcknowledgeFinishConfiguration`].
+    FinishConfiguration,
+    /// Acknowledge Finish Configuration, switching to [`ProtocolState::Play`].
+    AcknowledgeFinishConfiguration,
+    /// Start Configuration,
+    /// acknowledged by [`Transition::ConfigurationAcknowledged`].
+    StartConfiguration,
+    /// Configuration Acknowledged,
+    /// switching to [`ProtocolState::Configuration`].
+    ConfigurationAcknowledged,
+}
+
+impl Transition {
+    /// Get the state and direction of the packets causing this transition.
+    #[must_use]
+    pub const fn source(self) -> (ProtocolState, Direction) {
+        match self {
+            Self::Status | Self::Login => (ProtocolState::Handshake, Direction::Serverbound),
+            Self::SetCompression(_) | Self::LoginSuccess => {
+                (ProtocolState::Login, Direction::Clientbound)
+            }
+            Self::EnableEncryption | Self::LoginAcknowledged => {
+                (ProtocolState::Login, Direction::Serverbound)
+            }
+            Self::FinishConfiguration => (ProtocolState::Configuration, Direction::Clientbound),
+            Self::AcknowledgeFinishConfiguration => {
+                (ProtocolState::Configuration, Direction::Serverbound)
+            }
+            Self::StartConfiguration => (ProtocolState::Play, Direction::Clientbound),
+            Self::ConfigurationAcknowledged => (ProtocolState::Play, Direction::Serverbound),
+        }
+    }
+
+    /// Get the transition acknowledging this one, if it must be acknowledged.
+    #[must_use]
+    pub const fn acknowledgement(self) -> Option<Self> {
+        match self {
+            Self::LoginSuccess => Some(Self::LoginAcknowledged),
+            Self::FinishConfiguration => Some(Self::AcknowledgeFinishConfiguration),
+            Self::StartConfiguration => Some(Self::ConfigurationAcknowledged),
+            _ => None,
+        }
+    }
+}
+
+/// A change the [`PacketCodec`](crate::frame::PacketCodec) must make
+/// before the next packet is sent or received.
+#[must_use]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// Enable compression with the given threshold, or disable it.
+    ///
+    /// See [`PacketCodec::set_compression`](crate::frame::PacketCodec).
+    SetCompression(Option<usize>),
+    /// Enable encryption using the shared secret.
+    ///
+    /// The server enables encryption after receiving the Encryption Response,
+    /// and the client after sending it.
+    EnableEncryption,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// The state of one side of a connection.
+///
+/// ```rust
+/// use facet_minecraft::{
+///     connection::{Connection, Transition},
+///     packet::{Direction, Packet, ProtocolState},
+/// };
+///
+/// struct LoginSuccess;
+///
+/// impl Packet for LoginSuccess {
+///     const DIRECTION: Direction = Direction::Clientbound;
+///     const ID: u32 = 0x02;
+///     const STATE: ProtocolState = ProtocolState::Login;
+///
+///     fn transition(&self) -> Option<Transition> { Some(Transition::LoginSuccess) }
+/// }
+///
+/// struct LoginAcknowledged;
+///
+/// impl Packet for LoginAcknowledged {
+///     const DIRECTION: Direction = Direction::Serverbound;
+///     const ID: u32 = 0x03;
+///     const STATE: ProtocolState = ProtocolState::Login;
+///
+///     fn transition(&self) -> Option<Transition> { Some(Transition::LoginAcknowledged) }
+/// }
+///
+/// let mut client = Connection::client_in(ProtocolState::Login);
+/// assert_eq!(client.receive_packet(&LoginSuccess).unwrap(), None);
+/// assert_eq!(client.state(), ProtocolState::Login);
+///
+/// assert_eq!(client.send(&LoginAcknowledged).unwrap(), None);
+/// assert_eq!(client.state(), ProtocolState::Configuration);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Connection {
+    outgoing: Direction,
+    state: ProtocolState,
+    pending: Option<Transition>,
+    compression: Option<usize>,
+    encrypted: bool,
+}
+
+impl Connection {
+    /// Create a new [`Connection`] sending packets in the given direction,
+    /// starting in [`ProtocolState::Handshake`].
+    #[inline]
+    #[must_use]
+    pub const fn new(outgoing: Direction) -> Self {
+        Self {
+            outgoing,
+            state: ProtocolState::Handshake,
+            pending: None,
+            compression: None,
+            encrypted: false,
+        }
+    }
+
+    /// Create a new client [`Connection`].
+    #[inline]
+    #[must_use]
+    pub const fn client() -> Self { Self::new(Direction::Serverbound) }
+
+    /// Create a new server [`Connection`].
+    #[inline]
+    #[must_use]
+    pub const fn server() -> Self { Self::new(Direction::Clientbound) }
+
+    /// Create a new client [`Connection`] starting in the given state.
+    #[inline]
+    #[must_use]
+    pub const fn client_in(state: ProtocolState) -> Self { Self { state, ..Self::client() } }
+
+    /// Create a new server [`Connection`] starting in the given state.
+    #[inline]
+    #[must_use]
+    pub const fn server_in(state: ProtocolState) -> Self { Self { state, ..Self::server() } }
+
+    /// Get the current state of the connection.
+    #[inline]
+    #[must_use]
+    pub const fn state(&self) -> ProtocolState { self.state }
+
+    /// Get the direction of packets sent by this side.
+    #[inline]
+    #[must_use]
+    pub const fn outgoing(&self) -> Direction { self.outgoing }
+
+    /// Get the direction of packets received by this side.
+    #[inline]
+    #[must_use]
+    pub const fn incoming(&self) -> Direction { self.outgoing.opposite() }
+
+    /// Get the transition waiting to be acknowledged, if any.
+    #[inline]
+    #[must_use]
+    pub const fn pending(&self) -> Option<Transition> { self.pending }
+
+    /// Get the compression threshold, if compression is enabled.
+    #[inline]
+    #[must_use]
+    pub const fn compression(&self) -> Option<usize> { self.compression }
+
+    /// Returns `true` if encryption is enabled.
+    #[inline]
+    #[must_use]
+    pub const fn is_encrypted(&self) -> bool { self.encrypted }
+
+    // ---------------------------------------------------------------------------------------------
+
+    /// Track a packet sent by this side.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the packet cannot be sent in the current state.
+    pub fn send<P: Packet>(&mut self, packet: &P) -> Result<Option<Action>, ConnectionError> {
+        self.handle(self.outgoing, P::STATE, P::DIRECTION, P::ID, packet.transition())
+    }
+
+    /// Track a packet received by this side.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the packet cannot be received in the current state.
+    pub fn receive<'de, S: PacketSet<'de>>(
+        &mut self,
+        packet: &S,
+    ) -> Result<Option<Action>, ConnectionError> {
+        self.handle(self.incoming(), S::STATE, S::DIRECTION, packet.id(), packet.transition())
+    }
+
+    /// Track a single packet received by this side.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the packet cannot be received in the current state.
+    pub fn receive_packet<P: Packet>(
+        &mut self,
+        packet: &P,
+    ) -> Result<Option<Action>, ConnectionError> {
+        self.handle(self.incoming(), P::STATE, P::DIRECTION, P::ID, packet.transition())
+    }
+
+    /// Track a packet travelling in the `expected` direction.
+    fn handle(
+        &mut self,
+        expected: Direction,
+        state: ProtocolState,
+        direction: Direction,
+        id: u32,
+        transition: Option<Transition>,
+    ) -> Result<Option<Action>, ConnectionError> {
+        if state != self.state || direction != expected {
+            return Err(ConnectionError::new(ConnectionErrorKind::UnexpectedPacket {
+                current: self.state,
+                state,
+                direction,
+                id,
+            }));
+        }
+        if let Some(pending) = self.pending
+            && direction == pending.source().1
+        {
+            return Err(ConnectionError::new(ConnectionErrorKind::AwaitingAcknowledgement(
+                pending,
+            )));
+        }
+
+        let Some(transition) = transition else { return Ok(None) };
+        if transition.source() != (state, direction) {
+            return Err(ConnectionError::new(ConnectionErrorKind::InvalidTransition {
+                transition,
+                state,
+                direction,
+            }));
+        }
+
+        match transition {
+            Transition::Status => self.state = ProtocolState::Status,
+            Transition::Login => self.state = ProtocolState::Login,
+            Transition::SetCompression(threshold) => {
+                self.compression = usize::try_from(threshold).ok();
+                return Ok(Some(Action::SetCompression(self.compression)));
+            }
+            Transition::EnableEncryption => {
+                if self.encrypted {
+                    return Err(ConnectionError::new(ConnectionErrorKind::AlreadyEncrypted));
+                }
+                self.encrypted = true;
+                return Ok(Some(Action::EnableEncryption));
+            }
+            Transition::LoginSuccess
+            | Transition::FinishConfiguration
+            | Transition::StartConfiguration => self.pending = Some(transition),
+            Transition::LoginAcknowledged
+            | Transition::AcknowledgeFinishConfiguration
+            | Transition::ConfigurationAcknowledged => {
+                match self.pending.and_then(Transition::acknowledgement) {
+                    Some(expected) if expected == transition => self.pending = None,
+                    _ => {
+                        return Err(ConnectionError::new(
+                            ConnectionErrorKind::UnexpectedAcknowledgement(transition),
+                        ));
+                    }
+                }
+                self.state = match transition {
+                    Transition::AcknowledgeFinishConfiguration => ProtocolState::Play,
+                    _ => ProtocolState::Configuration,
+                };
+            }
+        }
+
+        Ok(None)
+    }
+}
@@ -3,6 +3,21 @@ use core::{
     fmt::{self, Display, Formatter},
 };
 
+use facet::Shape;
+
+use crate::{
+    chat::ChatError,
+    chunk::ChunkError,
+    command::CommandError,
+    entity::EntityError,
+    frame::FrameError,
+    item::ItemError,
+    nbt::{NbtError, NbtErrorKind},
+    profile::ProfileError,
+    text::TextError,
+    types::IdentifierError,
+};
+
 /// An error that occurred during deserialization.
 #[derive(Debug)]
 #[repr(transparent)]
@@ -31,6 +46,11 @@ pub enum DeserializeErrorKind {
     InvalidVariant(usize),
     /// An invalid UTF-8 sequence was encountered.
     InvalidUtf8,
+    /// An invalid identifier was encountered.
+    InvalidIdentifier(IdentifierError),
+
+    /// Attempted to deserialize a type that is not supported.
+    UnsupportedType(&'static Shape),
 
     /// The input ended unexpectedly.
     UnexpectedEndOfInput {
@@ -40,6 +60,25 @@ pub enum DeserializeErrorKind {
         found: usize,
     },
 
+    /// An error occurred while decoding a packet frame.
+    Frame(FrameError),
+    /// An error occurred while decoding NBT.
+    Nbt(NbtError),
+    /// An error occurred while decoding a text component.
+    Text(TextError),
+    /// An error occurred while decoding chunk data.
+    Chunk(ChunkError),
+    /// An error occurred while decoding entity metadata.
+    Entity(EntityError),
+    /// An error occurred while decoding an item stack.
+    Item(ItemError),
+    /// An error occurred while decoding a command graph.
+    Command(CommandError),
+    /// An error occurred while decoding signed chat data.
+    Chat(ChatError),
+    /// An error occurred while decoding a game profile.
+    Profile(ProfileError),
+
     /// An I/O error occurred.
     #[cfg(feature = "std")]
     Io(std::io::Error),
@@ -52,6 +91,67 @@ impl Display for DeserializeError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result { core::fmt::Debug::fmt(self, f) }
 }
 
+impl From<FrameError> for DeserializeError {
+    #[inline]
+    fn from(err: FrameError) -> Self { Self { kind: DeserializeErrorKind::Frame(err) } }
+}
+
+impl From<NbtError> for DeserializeError {
+    /// Running out of input is reported as
+    /// [`DeserializeErrorKind::UnexpectedEndOfInput`],
+    /// so streams read more input and try again.
+    fn from(err: NbtError) -> Self {
+        match *err.kind() {
+            NbtErrorKind::UnexpectedEndOfInput { expected, found } => {
+                Self::new(DeserializeErrorKind::UnexpectedEndOfInput { expected, found })
+            }
+            _ => Self { kind: DeserializeErrorKind::Nbt(err) },
+        }
+    }
+}
+
+impl From<ChunkError> for DeserializeError {
+    #[inline]
+    fn from(err: ChunkError) -> Self { Self { kind: DeserializeErrorKind::Chunk(err) } }
+}
+
+impl From<EntityError> for DeserializeError {
+    #[inline]
+    fn from(err: EntityError) -> Self { Self { kind: DeserializeErrorKind::Entity(err) } }
+}
+
+impl From<ItemError> for DeserializeError {
+    #[inline]
+    fn from(err: ItemError) -> Self { Self { kind: DeserializeErrorKind::Item(err) } }
+}
+
+impl From<CommandError> for DeserializeError {
+    #[inline]
+    fn from(err: CommandError) -> Self { Self { kind: DeserializeErrorKind::Command(err) } }
+}
+
+impl From<ChatError> for DeserializeError {
+    #[inline]
+    fn from(err: ChatError) -> Self { Self { kind: DeserializeErrorKind::Chat(err) } }
+}
+
+impl From<ProfileError> for DeserializeError {
+    #[inline]
+    fn from(err: ProfileError) -> Self { Self { kind: DeserializeErrorKind::Profile(err) } }
+}
+
+impl From<TextError> for DeserializeError {
+    #[inline]
+    fn from(err: TextError) -> Self { Self { kind: DeserializeErrorKind::Text(err) } }
+}
+
+impl From<IdentifierError> for DeserializeError {
+    #[inline]
+    fn from(err: IdentifierError) -> Self {
+        Self { kind: DeserializeErrorKind::InvalidIdentifier(err) }
+    }
+}
+
 #[cfg(feature = "std")]
 impl From<std::io::Error> for DeserializeError {
     fn from(err: std::io::Error) -> Self { Self { kind: DeserializeErrorKind::Io(err) } }
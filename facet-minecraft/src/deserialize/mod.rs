@@ -11,6 +11,8 @@ use facet_format::{
 use facet_reflect::Span;
 use uuid::Uuid;
 
+use crate::codec::McReader;
+
 mod error;
 pub use error::{DeserializeError, DeserializeErrorKind};
 
@@ -19,11 +21,18 @@ mod jit;
 #[cfg(feature = "jit")]
 pub use jit::McJitFormat;
 
-mod parse;
+pub(crate) mod parse;
 
 mod stack;
 use stack::{DeserializerStack, StackEntry};
 
+mod version;
+pub(crate) use version::decode_versioned;
+pub use version::from_slice_versioned;
+
+mod walk;
+use walk::DirectParser;
+
 #[cfg(feature = "streaming")]
 pub(crate) mod stream;
 #[cfg(feature = "streaming")]
@@ -138,8 +147,11 @@ impl<'de> McDeserializer<'de> {
         let Some(entry) = self.stack.next_mut() else { return Ok(None) };
 
         match entry {
-            StackEntry::Struct { remaining } => {
-                if *remaining == 0 {
+            StackEntry::Struct { started, remaining } => {
+                if !*started {
+                    *started = true;
+                    Ok(Some(ParseEvent::StructStart(ContainerKind::Object)))
+                } else if *remaining == 0 {
                     let _ = self.stack.pop();
                     Ok(Some(ParseEvent::StructEnd))
                 } else {
@@ -287,7 +299,10 @@ impl<'de> FormatParser<'de> for McDeserializer<'de> {
         }
     }
 
-    fn hint_byte_sequence(&mut self) -> bool { true }
+    fn hint_byte_sequence(&mut self) -> bool {
+        self.hint_scalar_type(ScalarTypeHint::Bytes);
+        true
+    }
 
     fn hint_sequence(&mut self) { self.stack.push_sequence_hint(None); }
 
@@ -304,6 +319,18 @@ impl<'de> FormatParser<'de> for McDeserializer<'de> {
     }
 }
 
+impl<'de> DirectParser<'de> for McDeserializer<'de> {
+    fn read_direct<T>(
+        &mut self,
+        mut read: impl FnMut(&mut McReader<'_>) -> Result<T, DeserializeError>,
+    ) -> Result<T, DeserializeError> {
+        let mut reader = McReader::new(self.input.get(self.counter..).unwrap_or_default());
+        let value = read(&mut reader)?;
+        self.counter += reader.consumed();
+        Ok(value)
+    }
+}
+
 /// A deserializer probe that implements [`ProbeStream`].
 pub struct McDeserializerProbe;
 
@@ -336,7 +363,7 @@ pub fn from_slice<T: Deserializable<'static>>(
 
     let mut format = FormatDeserializer::new_owned(McDeserializer::new(input));
 
-    format.deserialize_root::<T>().and_then(|val| {
+    walk::deserialize_owned::<T, _>(&mut format, None).and_then(|val| {
         let consumed = format.parser_mut().consumed();
         if let Some(remaining) = input.get(consumed..) {
             Ok((val, remaining))
@@ -374,7 +401,7 @@ pub fn from_slice_borrowed<'input: 'facet, 'facet, T: Deserializable<'facet>>(
 
     let mut format = FormatDeserializer::new(McDeserializer::new(input));
 
-    format.deserialize_root::<T>().and_then(|val| {
+    walk::deserialize_borrowed::<T, _>(&mut format, None).and_then(|val| {
         let consumed = format.parser_mut().consumed();
         if let Some(remaining) = input.get(consumed..) {
             Ok((val, remaining))
@@ -432,7 +459,7 @@ pub fn from_slice_borrowed<'input: 'facet, 'facet, T: Deserializable<'facet>>(
 
         let mut format = FormatDeserializer::new(format);
 
-        format.deserialize_root::<T>().and_then(|val| {
+        walk::deserialize_borrowed::<T, _>(&mut format, None).and_then(|val| {
             let consumed = format.parser_mut().consumed();
             if let Some(remaining) = input.get(consumed..) {
                 Ok((val, remaining))
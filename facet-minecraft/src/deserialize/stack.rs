@@ -10,7 +10,7 @@ pub(super) struct DeserializerStack {
 
 #[derive(Debug)]
 pub(super) enum StackEntry {
-    Struct { remaining: usize },
+    Struct { started: bool, remaining: usize },
     Enum { variants: Vec<EnumVariantHint>, variant: Option<usize>, remaining: Option<usize> },
 
     Sequence { remaining: Option<usize> },
@@ -41,7 +41,7 @@ impl DeserializerStack {
     pub(super) fn pop(&mut self) -> Option<StackEntry> { self.stack.pop() }
 
     pub(super) fn push_struct_hint(&mut self, fields: usize) {
-        self.stack.push(StackEntry::Struct { remaining: fields });
+        self.stack.push(StackEntry::Struct { started: false, remaining: fields });
     }
 
     pub(super) fn push_enum_hint(&mut self, variants: &[EnumVariantHint]) {
@@ -10,9 +10,13 @@ use facet_format::{
 };
 use facet_reflect::Span;
 
-use crate::deserialize::{
-    Deserializable, DeserializeError, DeserializeErrorKind, DeserializerStack, McDeserializerProbe,
-    StackEntry, parse,
+use crate::{
+    codec::McReader,
+    deserialize::{
+        Deserializable, DeserializeError, DeserializeErrorKind, DeserializerStack,
+        McDeserializerProbe, StackEntry, parse,
+        walk::{self, DirectParser},
+    },
 };
 
 /// A wrapper around a [`Coroutine`] for deserializing a value of type `T`.
@@ -33,8 +37,8 @@ impl<T: Deserializable<'static>> CoWrapper<T> {
         let cobuffer = Rc::clone(&buffer);
         let coroutine =
             Coroutine::new(move |yielder, ()| -> Result<T, FDError<DeserializeError>> {
-                FormatDeserializer::new_owned(McStreamDeserializer::new(cobuffer, yielder))
-                    .deserialize_root::<T>()
+                let parser = McStreamDeserializer::new(cobuffer, yielder);
+                walk::deserialize_owned::<T, _>(&mut FormatDeserializer::new_owned(parser), None)
             });
 
         Self { buffer, coroutine }
@@ -43,7 +47,7 @@ impl<T: Deserializable<'static>> CoWrapper<T> {
     /// Complete the deserialization synchronously.
     fn complete(
         mut self,
-        mut f: impl FnMut(&mut Cursor<Vec<u8>>) -> Result<(), FDError<DeserializeError>>,
+        mut f: impl FnMut(&mut [u8]) -> Result<(), FDError<DeserializeError>>,
     ) -> Result<T, FDError<DeserializeError>> {
         loop {
             match self.coroutine.resume(()) {
@@ -52,12 +56,13 @@ impl<T: Deserializable<'static>> CoWrapper<T> {
 
                     // Grow the buffer if needed
                     let buffer = cursor.get_mut();
+                    let length = buffer.len();
                     if let Some(growth) = growth {
-                        buffer.resize(buffer.len() + growth.get(), 0);
+                        buffer.resize(length + growth.get(), 0);
                     }
 
-                    // Read more data into the buffer
-                    f(&mut cursor)?;
+                    // Read more data into the new space in the buffer
+                    f(&mut buffer[length..])?;
                 }
                 CoroutineResult::Return(result) => {
                     return result;
@@ -71,7 +76,7 @@ impl<T: Deserializable<'static>> CoWrapper<T> {
     #[expect(clippy::await_holding_refcell_ref, reason = "Necessary for coroutine")]
     async fn complete_async(
         mut self,
-        mut f: impl AsyncFnMut(&mut Cursor<Vec<u8>>) -> Result<(), FDError<DeserializeError>>,
+        mut f: impl AsyncFnMut(&mut [u8]) -> Result<(), FDError<DeserializeError>>,
     ) -> Result<T, FDError<DeserializeError>> {
         loop {
             match self.coroutine.resume(()) {
@@ -80,12 +85,13 @@ impl<T: Deserializable<'static>> CoWrapper<T> {
 
                     // Grow the buffer if needed
                     let buffer = cursor.get_mut();
+                    let length = buffer.len();
                     if let Some(growth) = growth {
-                        buffer.resize(buffer.len() + growth.get(), 0);
+                        buffer.resize(length + growth.get(), 0);
                     }
 
-                    // Read more data into the buffer
-                    f(&mut cursor).await?;
+                    // Read more data into the new space in the buffer
+                    f(&mut buffer[length..]).await?;
                 }
                 CoroutineResult::Return(result) => {
                     return result;
@@ -101,16 +107,14 @@ impl<T: Deserializable<'static>> CoWrapper<T> {
 ///
 /// This function will return an error if deserialization fails,
 /// or the reader encounters an I/O error.
-#[expect(clippy::cast_possible_truncation, reason = "")]
 pub fn from_reader<T: Deserializable<'static>, R: std::io::Read>(
     reader: &mut R,
 ) -> Result<T, FDError<DeserializeError>> {
     // const { assert!(T::DESERIALIZABLE.possible(), "This type is not
     // deserializable!") };
 
-    CoWrapper::<T>::new().complete(|cursor| {
-        let index = cursor.position() as usize;
-        std::io::Read::read_exact(reader, &mut cursor.get_mut()[index..])
+    CoWrapper::<T>::new().complete(|buffer| {
+        std::io::Read::read_exact(reader, buffer)
             .map_err(|err| FDError::Parser(DeserializeError::from(err)))
     })
 }
@@ -123,7 +127,6 @@ pub fn from_reader<T: Deserializable<'static>, R: std::io::Read>(
 /// This function will return an error if deserialization fails,
 /// or the reader encounters an I/O error.
 #[cfg(feature = "futures-lite")]
-#[expect(clippy::cast_possible_truncation, reason = "")]
 pub async fn from_async_reader<T: Deserializable<'static>, R: futures_lite::AsyncRead + Unpin>(
     reader: &mut R,
 ) -> Result<T, FDError<DeserializeError>> {
@@ -131,9 +134,8 @@ pub async fn from_async_reader<T: Deserializable<'static>, R: futures_lite::Asyn
     // deserializable!") };
 
     CoWrapper::<T>::new()
-        .complete_async(async |cursor| {
-            let index = cursor.position() as usize;
-            futures_lite::io::AsyncReadExt::read_exact(reader, &mut cursor.get_mut()[index..])
+        .complete_async(async |buffer| {
+            futures_lite::io::AsyncReadExt::read_exact(reader, buffer)
                 .await
                 .map_err(|err| FDError::Parser(DeserializeError::from(err)))
         })
@@ -148,7 +150,6 @@ pub async fn from_async_reader<T: Deserializable<'static>, R: futures_lite::Asyn
 /// This function will return an error if deserialization fails,
 /// or the reader encounters an I/O error.
 #[cfg(feature = "tokio")]
-#[expect(clippy::cast_possible_truncation, reason = "")]
 pub async fn from_tokio_reader<T: Deserializable<'static>, R: tokio::io::AsyncRead + Unpin>(
     reader: &mut R,
 ) -> Result<T, FDError<DeserializeError>> {
@@ -156,9 +157,8 @@ pub async fn from_tokio_reader<T: Deserializable<'static>, R: tokio::io::AsyncRe
     // deserializable!") };
 
     CoWrapper::<T>::new()
-        .complete_async(async |cursor| {
-            let index = cursor.position() as usize;
-            tokio::io::AsyncReadExt::read_exact(reader, &mut cursor.get_mut()[index..])
+        .complete_async(async |buffer| {
+            tokio::io::AsyncReadExt::read_exact(reader, buffer)
                 .await
                 .map_or_else(|err| Err(FDError::Parser(DeserializeError::from(err))), |_| Ok(()))
         })
@@ -191,7 +191,7 @@ impl<'de, 'y> McStreamDeserializer<'de, 'y> {
     fn parse_next(&mut self) -> Result<Option<ParseEvent<'de>>, DeserializeError> {
         let Some(entry) = self.stack.next_mut() else { return Ok(None) };
         match entry {
-            StackEntry::Struct { remaining } => todo!(),
+            StackEntry::Struct { started, remaining } => todo!(),
             StackEntry::Enum { variants, variant, remaining } => todo!(),
             StackEntry::Sequence { remaining } => todo!(),
             StackEntry::Map { remaining } => todo!(),
@@ -304,3 +304,36 @@ impl<'de> FormatParser<'de> for McStreamDeserializer<'de, '_> {
         }
     }
 }
+
+impl<'de> DirectParser<'de> for McStreamDeserializer<'de, '_> {
+    fn read_direct<T>(
+        &mut self,
+        mut read: impl FnMut(&mut McReader<'_>) -> Result<T, DeserializeError>,
+    ) -> Result<T, DeserializeError> {
+        loop {
+            let cursor = self.buffer.borrow();
+            #[expect(clippy::cast_possible_truncation, reason = "")]
+            let position = cursor.position() as usize;
+
+            let mut reader = McReader::new(cursor.get_ref().get(position..).unwrap_or_default());
+            let result = read(&mut reader).map(|value| (value, reader.consumed()));
+            drop(cursor);
+
+            match result {
+                Ok((value, consumed)) => {
+                    self.buffer.borrow_mut().set_position((position + consumed) as u64);
+                    return Ok(value);
+                }
+                // If we hit an unexpected end of input, grow the buffer and try again
+                Err(err)
+                    if let DeserializeErrorKind::UnexpectedEndOfInput { expected, found } =
+                        *err.kind()
+                        && found < expected =>
+                {
+                    self.yielder.suspend(Some(NonZeroUsize::new(expected - found).unwrap()));
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
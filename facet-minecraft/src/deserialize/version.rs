@@ -0,0 +1,74 @@
+//! Deserialization of fields and variants gated by protocol version.
+
+use facet::Facet;
+use facet_format::{DeserializeError as FDError, FormatDeserializer};
+
+use super::{Deserializable, DeserializeError, DeserializeErrorKind, McDeserializer, walk};
+use crate::codec::McReader;
+
+/// Deserialize a value of type `T` for a protocol version from a byte slice,
+/// returning any remaining bytes.
+///
+/// Fields and enum variants marked with `#[facet(mc::since = ..)]` or
+/// `#[facet(mc::until = ..)]` are only read if the version is within their
+/// range, inclusive. Absent fields are filled using their `#[facet(default)]`
+/// or [`Default`] implementation.
+///
+/// Versions are followed through structs, enums, containers such as
+/// [`Option`] and [`Vec`](alloc::vec::Vec), and pointers such as
+/// [`Box`](alloc::boxed::Box).
+/// Enum variants are numbered by their position among those present in the
+/// version.
+///
+/// ```rust
+/// use facet::Facet;
+/// use facet_minecraft as mc;
+///
+/// #[derive(Debug, PartialEq, Facet)]
+/// struct Example {
+///     id: u8,
+///     #[facet(mc::since = 767)]
+///     added: u8,
+///     #[facet(mc::until = 766)]
+///     removed: u8,
+/// }
+///
+/// let (value, _) = mc::from_slice_versioned::<Example>(&[1, 3], 766).unwrap();
+/// assert_eq!(value, Example { id: 1, added: 0, removed: 3 });
+/// let (value, _) = mc::from_slice_versioned::<Example>(&[1, 2], 767).unwrap();
+/// assert_eq!(value, Example { id: 1, added: 2, removed: 0 });
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if deserialization fails,
+/// or if an absent field has no default value.
+pub fn from_slice_versioned<'input: 'facet, 'facet, T: Deserializable<'facet>>(
+    input: &'input [u8],
+    version: u32,
+) -> Result<(T, &'input [u8]), FDError<DeserializeError>> {
+    let mut format = FormatDeserializer::new(McDeserializer::new(input));
+    let value = walk::deserialize_borrowed::<T, _>(&mut format, Some(version))?;
+
+    let consumed = format.parser_mut().consumed();
+    Ok((value, input.get(consumed..).unwrap_or_default()))
+}
+
+/// Deserialize a value of type `T` from a [`McReader`],
+/// for the reader's protocol version.
+///
+/// Used by [`McCodec`](crate::codec::McCodec) implementations containing
+/// values described by their fields.
+pub(crate) fn decode_versioned<T: Facet<'static>>(
+    reader: &mut McReader<'_>,
+) -> Result<T, DeserializeError> {
+    let mut format = FormatDeserializer::new_owned(McDeserializer::new(reader.remaining()));
+    let value = walk::deserialize_owned::<T, _>(&mut format, Some(reader.version().protocol()))
+        .map_err(|err| match err {
+            FDError::Parser(err) => err,
+            _ => DeserializeError::new(DeserializeErrorKind::UnsupportedType(T::SHAPE)),
+        })?;
+
+    reader.read_bytes(format.parser_mut().consumed())?;
+    Ok(value)
+}
@@ -0,0 +1,312 @@
+//! Deserialization by walking the shape of a value.
+//!
+//! Containers, structs and enums are built here, so that values with a custom
+//! wire encoding are decoded exactly once and versions are followed through
+//! every level. Fields marked with `mc::nbt` or `mc::mutf8` are read the same
+//! way they are written. Everything else is passed to the
+//! [`FormatDeserializer`].
+
+use alloc::{borrow::Cow, string::String};
+
+use facet::{Def, Facet, Field, KnownPointer, PtrUninit, ShapeLayout, Type, UserType};
+use facet_format::{DeserializeError as FDError, FormatDeserializer, FormatParser};
+use facet_reflect::{HeapValue, Partial, ReflectError};
+
+use super::{DeserializeError, DeserializeErrorKind};
+use crate::{
+    codec::{CodecFn, McReader},
+    common::{is_mutf8, is_present},
+    nbt,
+    packet::ProtocolVersion,
+};
+
+/// A [`FormatParser`] whose remaining input can also be read directly.
+pub(crate) trait DirectParser<'de>: FormatParser<'de, Error = DeserializeError> {
+    /// Read from the remaining input, consuming the bytes read.
+    ///
+    /// The function may be called again with more input if it fails with
+    /// [`DeserializeErrorKind::UnexpectedEndOfInput`].
+    ///
+    /// # Errors
+    ///
+    /// Returns any error returned by the function.
+    fn read_direct<T>(
+        &mut self,
+        read: impl FnMut(&mut McReader<'_>) -> Result<T, DeserializeError>,
+    ) -> Result<T, DeserializeError>;
+}
+
+/// Deserialize a value of type `T` that may borrow from the input.
+pub(crate) fn deserialize_borrowed<'de, T: Facet<'de>, P: DirectParser<'de>>(
+    format: &mut FormatDeserializer<'de, true, P>,
+    version: Option<u32>,
+) -> Result<T, FDError<DeserializeError>> {
+    let wip = Partial::alloc::<T>().map_err(FDError::reflect)?;
+    let wip = deserialize_value(format, wip, version)?;
+    wip.build().map_err(FDError::reflect)?.materialize::<T>().map_err(FDError::reflect)
+}
+
+/// Deserialize a value of type `T` that does not borrow from the input.
+pub(crate) fn deserialize_owned<'de, T: Facet<'static>, P: DirectParser<'de>>(
+    format: &mut FormatDeserializer<'de, false, P>,
+    version: Option<u32>,
+) -> Result<T, FDError<DeserializeError>> {
+    let wip = Partial::alloc_owned::<T>().map_err(FDError::reflect)?;
+    // SAFETY: Owned values never borrow from the input,
+    // so only the lifetime marker changes.
+    let wip = unsafe { core::mem::transmute::<Partial<'static, false>, Partial<'de, false>>(wip) };
+
+    let value = deserialize_value(format, wip, version)?.build().map_err(FDError::reflect)?;
+    // SAFETY: See above.
+    let value =
+        unsafe { core::mem::transmute::<HeapValue<'de, false>, HeapValue<'static, false>>(value) };
+    value.materialize::<T>().map_err(FDError::reflect)
+}
+
+/// Deserialize a value, skipping fields and variants not present in the
+/// version, if any.
+fn deserialize_value<'de, const BORROW: bool, P: DirectParser<'de>>(
+    format: &mut FormatDeserializer<'de, BORROW, P>,
+    mut wip: Partial<'de, BORROW>,
+    version: Option<u32>,
+) -> Result<Partial<'de, BORROW>, FDError<DeserializeError>> {
+    // Types built from another shape, such as identifiers borrowing from the
+    // input, are left to the `FormatDeserializer`.
+    let shape = wip.shape();
+    if shape.builder_shape.is_none()
+        && let Some(codec) = CodecFn::from_shape(shape)
+    {
+        return deserialize_codec(format, wip, codec, version);
+    }
+
+    match shape.def {
+        Def::Option(_) => {
+            return if read_bool(format)? {
+                wip = wip.begin_some().map_err(FDError::reflect)?;
+                wip = deserialize_value(format, wip, version)?;
+                wip.end().map_err(FDError::reflect)
+            } else {
+                wip.set_default().map_err(FDError::reflect)
+            };
+        }
+        // Byte sequences are read as a whole.
+        Def::List(def) if def.t().is_type::<u8>() => return format.deserialize_into(wip),
+        Def::Array(def) if def.t().is_type::<u8>() => return format.deserialize_into(wip),
+        Def::List(_) => {
+            wip = wip.init_list().map_err(FDError::reflect)?;
+            return deserialize_items(format, wip, version);
+        }
+        Def::Array(def) => {
+            wip = wip.init_array().map_err(FDError::reflect)?;
+            for index in 0..def.n {
+                wip = wip.begin_nth_field(index).map_err(FDError::reflect)?;
+                wip = deserialize_value(format, wip, version)?;
+                wip = wip.end().map_err(FDError::reflect)?;
+            }
+            return Ok(wip);
+        }
+        Def::Set(_) => {
+            let len = read_var_u32(format)?;
+            wip = wip.init_set().map_err(FDError::reflect)?;
+            for _ in 0..len {
+                wip = wip.begin_set_item().map_err(FDError::reflect)?;
+                wip = deserialize_value(format, wip, version)?;
+                wip = wip.end().map_err(FDError::reflect)?;
+            }
+            return Ok(wip);
+        }
+        Def::Map(_) => {
+            let len = read_var_u32(format)?;
+            wip = wip.init_map().map_err(FDError::reflect)?;
+            for _ in 0..len {
+                wip = wip.begin_key().map_err(FDError::reflect)?;
+                wip = deserialize_value(format, wip, version)?;
+                wip = wip.end().map_err(FDError::reflect)?;
+                wip = wip.begin_value().map_err(FDError::reflect)?;
+                wip = deserialize_value(format, wip, version)?;
+                wip = wip.end().map_err(FDError::reflect)?;
+            }
+            return Ok(wip);
+        }
+        Def::Pointer(def)
+            if matches!(
+                def.known,
+                Some(KnownPointer::Box | KnownPointer::Rc | KnownPointer::Arc)
+            ) && def.pointee().is_some_and(|t| matches!(t.layout, ShapeLayout::Sized(_))) =>
+        {
+            wip = wip.begin_smart_ptr().map_err(FDError::reflect)?;
+            wip = deserialize_value(format, wip, version)?;
+            return wip.end().map_err(FDError::reflect);
+        }
+        Def::Pointer(def)
+            if matches!(
+                def.known,
+                Some(KnownPointer::Box | KnownPointer::Rc | KnownPointer::Arc)
+            ) && def.pointee().is_some_and(|t| matches!(t.def, Def::Slice(_))) =>
+        {
+            wip = wip.begin_smart_ptr().map_err(FDError::reflect)?;
+            wip = deserialize_items(format, wip, version)?;
+            return wip.end().map_err(FDError::reflect);
+        }
+        _ => {}
+    }
+
+    match (shape.def, shape.ty) {
+        (Def::Undefined, Type::User(UserType::Struct(ty))) => {
+            deserialize_fields(format, wip, ty.fields, version)
+        }
+        (Def::Undefined, Type::User(UserType::Enum(ty))) => {
+            // Variants are numbered by their position in the version.
+            let index = read_var_u32(format)? as usize;
+            let position = ty
+                .variants
+                .iter()
+                .enumerate()
+                .filter(|(_, variant)| is_present(variant.attributes, version))
+                .nth(index)
+                .map(|(position, _)| position)
+                .ok_or_else(|| {
+                    FDError::Parser(DeserializeError::new(DeserializeErrorKind::InvalidVariant(
+                        index,
+                    )))
+                })?;
+
+            wip = wip.select_nth_variant(position).map_err(FDError::reflect)?;
+            deserialize_fields(format, wip, ty.variants[position].data.fields, version)
+        }
+        _ => format.deserialize_into(wip),
+    }
+}
+
+/// Decode a value using its [`CodecFn`].
+fn deserialize_codec<'de, const BORROW: bool, P: DirectParser<'de>>(
+    format: &mut FormatDeserializer<'de, BORROW, P>,
+    wip: Partial<'de, BORROW>,
+    codec: &CodecFn,
+    version: Option<u32>,
+) -> Result<Partial<'de, BORROW>, FDError<DeserializeError>> {
+    // SAFETY: The `CodecFn` was created for the type being decoded.
+    deserialize_direct(format, wip, version, |reader, ptr| unsafe { codec.decode(reader, ptr) })
+}
+
+/// Decode a value directly from the input, keeping the error of the decoder
+/// instead of the one reported by `facet`.
+///
+/// The decoder reads for the version, or [`ProtocolVersion::LATEST`] if none,
+/// and must only initialize the pointer if it succeeds.
+fn deserialize_direct<'de, const BORROW: bool, P: DirectParser<'de>>(
+    format: &mut FormatDeserializer<'de, BORROW, P>,
+    wip: Partial<'de, BORROW>,
+    version: Option<u32>,
+    mut decode: impl FnMut(&mut McReader<'_>, PtrUninit) -> Result<(), DeserializeError>,
+) -> Result<Partial<'de, BORROW>, FDError<DeserializeError>> {
+    let shape = wip.shape();
+    let version = version.map_or(ProtocolVersion::LATEST, ProtocolVersion::new);
+    let mut error = None;
+    // SAFETY: The decoder only initializes the pointer if it succeeds.
+    let result = unsafe {
+        wip.set_from_function(|ptr| {
+            let decode = |reader: &mut McReader<'_>| {
+                *reader = reader.with_version(version);
+                decode(reader, ptr)
+            };
+            format.parser_mut().read_direct(decode).map_err(|err| {
+                error = Some(err);
+                ReflectError::OperationFailed { shape, operation: "failed to decode value" }
+            })
+        })
+    };
+    match error {
+        Some(err) => Err(FDError::Parser(err)),
+        None => result.map_err(FDError::reflect),
+    }
+}
+
+/// Deserialize a `VarInt`-prefixed sequence of items into a list or a slice.
+fn deserialize_items<'de, const BORROW: bool, P: DirectParser<'de>>(
+    format: &mut FormatDeserializer<'de, BORROW, P>,
+    mut wip: Partial<'de, BORROW>,
+    version: Option<u32>,
+) -> Result<Partial<'de, BORROW>, FDError<DeserializeError>> {
+    let len = read_var_u32(format)?;
+    for _ in 0..len {
+        wip = wip.begin_list_item().map_err(FDError::reflect)?;
+        wip = deserialize_value(format, wip, version)?;
+        wip = wip.end().map_err(FDError::reflect)?;
+    }
+    Ok(wip)
+}
+
+/// Deserialize the fields of a struct or enum variant in order,
+/// filling fields not present in the version with their default.
+fn deserialize_fields<'de, const BORROW: bool, P: DirectParser<'de>>(
+    format: &mut FormatDeserializer<'de, BORROW, P>,
+    mut wip: Partial<'de, BORROW>,
+    fields: &'static [Field],
+    version: Option<u32>,
+) -> Result<Partial<'de, BORROW>, FDError<DeserializeError>> {
+    for (index, field) in fields.iter().enumerate() {
+        if is_present(field.attributes, version) {
+            wip = wip.begin_nth_field(index).map_err(FDError::reflect)?;
+            wip = if field.has_attr(Some("mc"), "nbt") {
+                let shape = wip.shape();
+                // SAFETY: The pointer is for the type described by `shape`.
+                deserialize_direct(format, wip, version, |reader, ptr| unsafe {
+                    nbt::read_raw(ptr, shape, reader)
+                })?
+            } else if is_mutf8(Some(field.attributes)) {
+                deserialize_mutf8(format, wip)?
+            } else {
+                deserialize_value(format, wip, version)?
+            };
+            wip = wip.end().map_err(FDError::reflect)?;
+        } else {
+            wip = wip.set_nth_field_to_default(index).map_err(FDError::reflect)?;
+        }
+    }
+    Ok(wip)
+}
+
+/// Deserialize a `u16`-prefixed Modified UTF-8 string,
+/// or an optional one.
+fn deserialize_mutf8<'de, const BORROW: bool, P: DirectParser<'de>>(
+    format: &mut FormatDeserializer<'de, BORROW, P>,
+    mut wip: Partial<'de, BORROW>,
+) -> Result<Partial<'de, BORROW>, FDError<DeserializeError>> {
+    if let Def::Option(_) = wip.shape().def {
+        return if read_bool(format)? {
+            wip = wip.begin_some().map_err(FDError::reflect)?;
+            wip = deserialize_mutf8(format, wip)?;
+            wip.end().map_err(FDError::reflect)
+        } else {
+            wip.set_default().map_err(FDError::reflect)
+        };
+    }
+
+    let value = format
+        .parser_mut()
+        .read_direct(|reader| reader.read_mutf8().map(Cow::into_owned))
+        .map_err(FDError::Parser)?;
+    if wip.shape().is_type::<String>() {
+        wip.set(value).map_err(FDError::reflect)
+    } else {
+        wip.parse_from_str(&value).map_err(FDError::reflect)
+    }
+}
+
+/// Read a `bool` directly from the parser of a [`FormatDeserializer`].
+#[expect(clippy::redundant_closure_for_method_calls, reason = "Not general over the lifetime")]
+fn read_bool<'de, const BORROW: bool, P: DirectParser<'de>>(
+    format: &mut FormatDeserializer<'de, BORROW, P>,
+) -> Result<bool, FDError<DeserializeError>> {
+    format.parser_mut().read_direct(|reader| reader.read_bool()).map_err(FDError::Parser)
+}
+
+/// Read a variable-length `u32`, such as a length or variant index,
+/// directly from the parser of a [`FormatDeserializer`].
+#[expect(clippy::redundant_closure_for_method_calls, reason = "Not general over the lifetime")]
+fn read_var_u32<'de, const BORROW: bool, P: DirectParser<'de>>(
+    format: &mut FormatDeserializer<'de, BORROW, P>,
+) -> Result<u32, FDError<DeserializeError>> {
+    format.parser_mut().read_direct(|reader| reader.read_var_u32()).map_err(FDError::Parser)
+}
@@ -0,0 +1,354 @@
+//! Stream encryption, used after the Encryption Response.
+//!
+//! Once encryption begins, all traffic in both directions is encrypted using
+//! AES-128 in CFB8 mode, with the shared secret as both the key and the IV.
+//!
+//! A [`CipherStream`] wraps a reader and/or writer and encrypts or decrypts
+//! bytes as they pass through. Because it does not buffer anything itself,
+//! encryption can be enabled between two packets without losing any data,
+//! even if the inner stream has already read ahead.
+//!
+//! ```rust
+//! use std::io::{Cursor, Read};
+//!
+//! use facet_minecraft::encryption::CipherStream;
+//!
+//! let mut stream = CipherStream::new(Vec::new());
+//! facet_minecraft::to_writer(&"Encryption Response", &mut stream).unwrap();
+//!
+//! stream.enable(&[0; 16]);
+//! facet_minecraft::to_writer(&"Login Success", &mut stream).unwrap();
+//!
+//! let mut stream = CipherStream::new(Cursor::new(stream.into_inner()));
+//! let mut buffer = [0; 20];
+//! stream.read_exact(&mut buffer).unwrap();
+//! assert_eq!(&buffer, b"\x13Encryption Response");
+//!
+//! stream.enable(&[0; 16]);
+//! let mut buffer = [0; 14];
+//! stream.read_exact(&mut buffer).unwrap();
+//! assert_eq!(&buffer, b"\x0DLogin Success");
+//! ```
+
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+#[cfg(any(feature = "futures-lite", feature = "tokio"))]
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use aes::{
+    Aes128,
+    cipher::{BlockEncrypt, KeyInit},
+};
+
+/// AES-128 in CFB8 mode.
+///
+/// Each byte is encrypted by XOR-ing it with the first byte of the encrypted
+/// shift register, after which the ciphertext byte is shifted into the
+/// register. Separate instances must be used for encrypting and decrypting.
+#[derive(Clone)]
+pub struct Cfb8 {
+    cipher: Aes128,
+    register: [u8; 16],
+}
+
+impl Cfb8 {
+    /// Create a new [`Cfb8`] cipher from a key and an IV.
+    #[must_use]
+    pub fn new(key: &[u8; 16], iv: &[u8; 16]) -> Self {
+        Self { cipher: Aes128::new(key.into()), register: *iv }
+    }
+
+    /// Create a new [`Cfb8`] cipher using the shared secret
+    /// as both the key and the IV.
+    #[inline]
+    #[must_use]
+    pub fn from_secret(secret: &[u8; 16]) -> Self { Self::new(secret, secret) }
+
+    /// Encrypt data in place.
+    pub fn encrypt(&mut self, data: &mut [u8]) {
+        for byte in data {
+            *byte ^= self.keystream();
+            self.shift(*byte);
+        }
+    }
+
+    /// Decrypt data in place.
+    pub fn decrypt(&mut self, data: &mut [u8]) {
+        for byte in data {
+            let ciphertext = *byte;
+            *byte ^= self.keystream();
+            self.shift(ciphertext);
+        }
+    }
+
+    /// Get the next byte of the keystream.
+    fn keystream(&self) -> u8 {
+        let mut block = self.register.into();
+        self.cipher.encrypt_block(&mut block);
+        block[0]
+    }
+
+    /// Shift a ciphertext byte into the register.
+    fn shift(&mut self, ciphertext: u8) {
+        self.register.rotate_left(1);
+        self.register[15] = ciphertext;
+    }
+
+    /// Replace the register with the state after `ciphertext` was processed,
+    /// starting from `register`.
+    #[cfg(feature = "std")]
+    fn rewind(&mut self, register: [u8; 16], ciphertext: &[u8]) {
+        self.register = register;
+        if let Some(tail) = ciphertext.last_chunk::<16>() {
+            self.register = *tail;
+        } else {
+            self.register.rotate_left(ciphertext.len());
+            self.register[16 - ciphertext.len()..].copy_from_slice(ciphertext);
+        }
+    }
+}
+
+impl Debug for Cfb8 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cfb8").finish_non_exhaustive()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// A stream adapter that encrypts written bytes and decrypts read bytes.
+///
+/// Encryption starts disabled, so the stream can be used for the unencrypted
+/// part of the login sequence and then enabled with [`CipherStream::enable`].
+///
+/// Implements [`Read`](std::io::Read) and [`Write`](std::io::Write),
+/// as well as the `futures-lite` and `tokio` async traits when enabled.
+pub struct CipherStream<S> {
+    inner: S,
+    encryptor: Option<Cfb8>,
+    decryptor: Option<Cfb8>,
+    #[cfg(feature = "std")]
+    scratch: Vec<u8>,
+}
+
+impl<S> CipherStream<S> {
+    /// Create a new [`CipherStream`] with encryption disabled.
+    #[must_use]
+    pub const fn new(inner: S) -> Self {
+        Self {
+            inner,
+            encryptor: None,
+            decryptor: None,
+            #[cfg(feature = "std")]
+            scratch: Vec::new(),
+        }
+    }
+
+    /// Create a new [`CipherStream`] with encryption enabled.
+    #[must_use]
+    pub fn encrypted(inner: S, secret: &[u8; 16]) -> Self {
+        let mut stream = Self::new(inner);
+        stream.enable(secret);
+        stream
+    }
+
+    /// Enable encryption in both directions using the shared secret.
+    ///
+    /// Bytes read or written after this call are decrypted or encrypted.
+    pub fn enable(&mut self, secret: &[u8; 16]) {
+        self.encryptor = Some(Cfb8::from_secret(secret));
+        self.decryptor = Some(Cfb8::from_secret(secret));
+    }
+
+    /// Enable encryption, decrypting bytes that were already read from this
+    /// stream but not yet used.
+    ///
+    /// The `buffered` bytes must directly precede the next bytes read from the
+    /// stream, such as the remainder of a read buffer that was filled before
+    /// encryption was enabled.
+    pub fn enable_with_buffered(&mut self, secret: &[u8; 16], buffered: &mut [u8]) {
+        self.enable(secret);
+        if let Some(decryptor) = &mut self.decryptor {
+            decryptor.decrypt(buffered);
+        }
+    }
+
+    /// Returns `true` if encryption is enabled.
+    #[inline]
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool { self.encryptor.is_some() }
+
+    /// Get a reference to the inner stream.
+    #[inline]
+    #[must_use]
+    pub const fn get_ref(&self) -> &S { &self.inner }
+
+    /// Get a mutable reference to the inner stream.
+    ///
+    /// Reading from or writing to the inner stream directly
+    /// will desynchronize the cipher.
+    #[inline]
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut S { &mut self.inner }
+
+    /// Consume the [`CipherStream`] and return the inner stream.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> S { self.inner }
+
+    /// Encrypt `buf` into the scratch buffer,
+    /// returning the register to restore after a partial write.
+    #[cfg(feature = "std")]
+    fn encrypt_scratch(&mut self, buf: &[u8]) -> Option<[u8; 16]> {
+        let encryptor = self.encryptor.as_mut()?;
+        let register = encryptor.register;
+
+        self.scratch.clear();
+        self.scratch.extend_from_slice(buf);
+        encryptor.encrypt(&mut self.scratch);
+
+        Some(register)
+    }
+
+    /// Restore the encryptor to only include the bytes that were written.
+    #[cfg(feature = "std")]
+    fn finish_write(&mut self, register: [u8; 16], written: usize) {
+        if written < self.scratch.len()
+            && let Some(encryptor) = &mut self.encryptor
+        {
+            encryptor.rewind(register, &self.scratch[..written]);
+        }
+    }
+
+    /// Decrypt bytes that were read from the inner stream.
+    #[cfg(feature = "std")]
+    fn finish_read(&mut self, buf: &mut [u8]) {
+        if let Some(decryptor) = &mut self.decryptor {
+            decryptor.decrypt(buf);
+        }
+    }
+}
+
+impl<S: Debug> Debug for CipherStream<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CipherStream")
+            .field("inner", &self.inner)
+            .field("enabled", &self.is_enabled())
+            .finish_non_exhaustive()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> std::io::Read for CipherStream<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.finish_read(&mut buf[..read]);
+        Ok(read)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> std::io::Write for CipherStream<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let Some(register) = self.encrypt_scratch(buf) else { return self.inner.write(buf) };
+
+        let result = self.inner.write(&self.scratch);
+        self.finish_write(register, *result.as_ref().unwrap_or(&0));
+        result
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> { self.inner.flush() }
+}
+
+#[cfg(feature = "futures-lite")]
+impl<R: futures_lite::AsyncRead + Unpin> futures_lite::AsyncRead for CipherStream<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        let result = Pin::new(&mut this.inner).poll_read(cx, buf);
+        if let Poll::Ready(Ok(read)) = result {
+            this.finish_read(&mut buf[..read]);
+        }
+        result
+    }
+}
+
+#[cfg(feature = "futures-lite")]
+impl<W: futures_lite::AsyncWrite + Unpin> futures_lite::AsyncWrite for CipherStream<W> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        let Some(register) = this.encrypt_scratch(buf) else {
+            return Pin::new(&mut this.inner).poll_write(cx, buf);
+        };
+
+        let result = Pin::new(&mut this.inner).poll_write(cx, &this.scratch);
+        let written = if let Poll::Ready(Ok(written)) = result { written } else { 0 };
+        this.finish_write(register, written);
+        result
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_close(cx)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<R: tokio::io::AsyncRead + Unpin> tokio::io::AsyncRead for CipherStream<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        let filled = buf.filled().len();
+        let result = Pin::new(&mut this.inner).poll_read(cx, buf);
+        if let Poll::Ready(Ok(())) = result {
+            this.finish_read(&mut buf.filled_mut()[filled..]);
+        }
+        result
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<W: tokio::io::AsyncWrite + Unpin> tokio::io::AsyncWrite for CipherStream<W> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        let Some(register) = this.encrypt_scratch(buf) else {
+            return Pin::new(&mut this.inner).poll_write(cx, buf);
+        };
+
+        let result = Pin::new(&mut this.inner).poll_write(cx, &this.scratch);
+        let written = if let Poll::Ready(Ok(written)) = result { written } else { 0 };
+        this.finish_write(register, written);
+        result
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
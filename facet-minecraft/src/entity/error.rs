@@ -0,0 +1,102 @@
+use alloc::string::String;
+use core::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+use super::{MetadataType, ParticleKind};
+use crate::packet::ProtocolVersion;
+
+/// An error that occurred while encoding or decoding entity data.
+#[derive(Debug)]
+pub struct EntityError {
+    kind: EntityErrorKind,
+}
+
+impl EntityError {
+    /// Create a new [`EntityError`].
+    #[inline]
+    #[must_use]
+    pub const fn new(kind: EntityErrorKind) -> Self { Self { kind } }
+
+    /// Get the kind of entity error.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> &EntityErrorKind { &self.kind }
+}
+
+/// The type of entity error.
+#[derive(Debug)]
+pub enum EntityErrorKind {
+    /// A metadata type ID was not recognized for the protocol version.
+    UnknownType {
+        /// The type ID.
+        id: u32,
+        /// The protocol version.
+        version: ProtocolVersion,
+    },
+    /// A metadata type does not exist in the protocol version,
+    /// or its data cannot be encoded or decoded.
+    UnsupportedType {
+        /// The metadata type.
+        kind: MetadataType,
+        /// The protocol version.
+        version: ProtocolVersion,
+    },
+    /// An enum value inside a metadata entry was not recognized.
+    InvalidValue {
+        /// The metadata type.
+        kind: MetadataType,
+        /// The value.
+        value: u32,
+    },
+    /// A name inside a metadata entry was not recognized.
+    InvalidName {
+        /// The metadata type.
+        kind: MetadataType,
+        /// The name.
+        name: String,
+    },
+    /// The options of a particle did not match its type.
+    InvalidParticle(ParticleKind),
+    /// A particle type does not exist in the protocol version.
+    UnsupportedParticle {
+        /// The particle type.
+        kind: ParticleKind,
+        /// The protocol version.
+        version: ProtocolVersion,
+    },
+    /// A metadata entry used the index reserved for the terminator.
+    ReservedIndex,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Error for EntityError {}
+impl Display for EntityError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            EntityErrorKind::UnknownType { id, version } => {
+                write!(f, "unknown entity metadata type {id} in version {version}")
+            }
+            EntityErrorKind::UnsupportedType { kind, version } => {
+                write!(f, "unsupported entity metadata type {kind:?} in version {version}")
+            }
+            EntityErrorKind::InvalidValue { kind, value } => {
+                write!(f, "invalid value {value} for entity metadata type {kind:?}")
+            }
+            EntityErrorKind::InvalidName { kind, name } => {
+                write!(f, "invalid name `{name}` for entity metadata type {kind:?}")
+            }
+            EntityErrorKind::InvalidParticle(kind) => {
+                write!(f, "invalid options for particle type {}", kind.name())
+            }
+            EntityErrorKind::UnsupportedParticle { kind, version } => {
+                write!(f, "unsupported particle type {} in version {version}", kind.name())
+            }
+            EntityErrorKind::ReservedIndex => {
+                f.write_str("entity metadata index 255 is reserved for the terminator")
+            }
+        }
+    }
+}
@@ -0,0 +1,651 @@
+use alloc::{string::String, vec::Vec};
+
+use uuid::Uuid;
+
+use super::{EntityError, EntityErrorKind, Particle};
+use crate::{
+    codec::{McCodec, McReader, McWriter},
+    deserialize::DeserializeError,
+    item::OptionalItemStack,
+    nbt::NbtValue,
+    packet::ProtocolVersion,
+    profile::ResolvableProfile,
+    serialize::SerializeError,
+    text::{TextComponent, TextEncoding},
+    types::{BlockPos, GlobalPos},
+};
+
+/// The type of a [`MetadataValue`], as registered in
+/// `entity_data_serializers`.
+///
+/// Type IDs depend on the protocol version, see [`MetadataType::table`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[expect(missing_docs, reason = "Variants are named after their values")]
+pub enum MetadataType {
+    Byte,
+    VarInt,
+    VarLong,
+    Float,
+    String,
+    Text,
+    OptionalText,
+    Slot,
+    Boolean,
+    Rotations,
+    Position,
+    OptionalPosition,
+    Direction,
+    OptionalUuid,
+    BlockState,
+    OptionalBlockState,
+    Nbt,
+    Particle,
+    Particles,
+    VillagerData,
+    OptionalVarInt,
+    Pose,
+    CatVariant,
+    CowVariant,
+    WolfVariant,
+    WolfSoundVariant,
+    FrogVariant,
+    PigVariant,
+    ChickenVariant,
+    OptionalGlobalPosition,
+    PaintingVariant,
+    SnifferState,
+    ArmadilloState,
+    CopperGolemState,
+    WeatheringCopperState,
+    Vector3,
+    Quaternion,
+    ResolvableProfile,
+    HumanoidArm,
+}
+
+impl MetadataType {
+    /// The types of 1.20.2 and 1.20.3, ordered by ID.
+    const V1_20_2: &[Self] = &[
+        Self::Byte,
+        Self::VarInt,
+        Self::VarLong,
+        Self::Float,
+        Self::String,
+        Self::Text,
+        Self::OptionalText,
+        Self::Slot,
+        Self::Boolean,
+        Self::Rotations,
+        Self::Position,
+        Self::OptionalPosition,
+        Self::Direction,
+        Self::OptionalUuid,
+        Self::BlockState,
+        Self::OptionalBlockState,
+        Self::Nbt,
+        Self::Particle,
+        Self::VillagerData,
+        Self::OptionalVarInt,
+        Self::Pose,
+        Self::CatVariant,
+        Self::FrogVariant,
+        Self::OptionalGlobalPosition,
+        Self::PaintingVariant,
+        Self::SnifferState,
+        Self::Vector3,
+        Self::Quaternion,
+    ];
+    /// The types of 1.20.5 until 1.21.4, ordered by ID.
+    const V1_20_5: &[Self] = &[
+        Self::Byte,
+        Self::VarInt,
+        Self::VarLong,
+        Self::Float,
+        Self::String,
+        Self::Text,
+        Self::OptionalText,
+        Self::Slot,
+        Self::Boolean,
+        Self::Rotations,
+        Self::Position,
+        Self::OptionalPosition,
+        Self::Direction,
+        Self::OptionalUuid,
+        Self::BlockState,
+        Self::OptionalBlockState,
+        Self::Nbt,
+        Self::Particle,
+        Self::Particles,
+        Self::VillagerData,
+        Self::OptionalVarInt,
+        Self::Pose,
+        Self::CatVariant,
+        Self::WolfVariant,
+        Self::FrogVariant,
+        Self::OptionalGlobalPosition,
+        Self::PaintingVariant,
+        Self::SnifferState,
+        Self::ArmadilloState,
+        Self::Vector3,
+        Self::Quaternion,
+    ];
+    /// The types of 1.21.5 until 1.21.8, ordered by ID.
+    const V1_21_5: &[Self] = &[
+        Self::Byte,
+        Self::VarInt,
+        Self::VarLong,
+        Self::Float,
+        Self::String,
+        Self::Text,
+        Self::OptionalText,
+        Self::Slot,
+        Self::Boolean,
+        Self::Rotations,
+        Self::Position,
+        Self::OptionalPosition,
+        Self::Direction,
+        Self::OptionalUuid,
+        Self::BlockState,
+        Self::OptionalBlockState,
+        Self::Nbt,
+        Self::Particle,
+        Self::Particles,
+        Self::VillagerData,
+        Self::OptionalVarInt,
+        Self::Pose,
+        Self::CatVariant,
+        Self::CowVariant,
+        Self::WolfVariant,
+        Self::WolfSoundVariant,
+        Self::FrogVariant,
+        Self::PigVariant,
+        Self::ChickenVariant,
+        Self::OptionalGlobalPosition,
+        Self::PaintingVariant,
+        Self::SnifferState,
+        Self::ArmadilloState,
+        Self::Vector3,
+        Self::Quaternion,
+    ];
+    /// The types of 1.21.9 and later, ordered by ID.
+    const V1_21_9: &[Self] = &[
+        Self::Byte,
+        Self::VarInt,
+        Self::VarLong,
+        Self::Float,
+        Self::String,
+        Self::Text,
+        Self::OptionalText,
+        Self::Slot,
+        Self::Boolean,
+        Self::Rotations,
+        Self::Position,
+        Self::OptionalPosition,
+        Self::Direction,
+        Self::OptionalUuid,
+        Self::BlockState,
+        Self::OptionalBlockState,
+        Self::Particle,
+        Self::Particles,
+        Self::VillagerData,
+        Self::OptionalVarInt,
+        Self::Pose,
+        Self::CatVariant,
+        Self::CowVariant,
+        Self::WolfVariant,
+        Self::WolfSoundVariant,
+        Self::FrogVariant,
+        Self::PigVariant,
+        Self::ChickenVariant,
+        Self::OptionalGlobalPosition,
+        Self::PaintingVariant,
+        Self::SnifferState,
+        Self::ArmadilloState,
+        Self::CopperGolemState,
+        Self::WeatheringCopperState,
+        Self::Vector3,
+        Self::Quaternion,
+        Self::ResolvableProfile,
+        Self::HumanoidArm,
+    ];
+
+    /// Get the types of a protocol version, ordered by ID.
+    ///
+    /// Snapshots use the table of the latest release.
+    #[must_use]
+    pub fn table(version: ProtocolVersion) -> &'static [Self] {
+        if version >= ProtocolVersion::V1_21_9 {
+            Self::V1_21_9
+        } else if version >= ProtocolVersion::V1_21_5 {
+            Self::V1_21_5
+        } else if version >= ProtocolVersion::V1_20_5 {
+            Self::V1_20_5
+        } else {
+            Self::V1_20_2
+        }
+    }
+
+    /// Get the type with an ID in a protocol version.
+    #[must_use]
+    pub fn from_id(id: u32, version: ProtocolVersion) -> Option<Self> {
+        Self::table(version).get(id as usize).copied()
+    }
+
+    /// Get the ID of this type in a protocol version,
+    /// or `None` if the type does not exist in that version.
+    #[must_use]
+    pub fn id(self, version: ProtocolVersion) -> Option<u32> {
+        let index = Self::table(version).iter().position(|kind| *kind == self)?;
+        u32::try_from(index).ok()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// The direction a block or entity faces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[expect(missing_docs, reason = "Variants are named after their directions")]
+pub enum Facing {
+    Down,
+    Up,
+    North,
+    South,
+    West,
+    East,
+}
+
+impl Facing {
+    /// Every direction, ordered by ID.
+    pub const ALL: [Self; 6] =
+        [Self::Down, Self::Up, Self::North, Self::South, Self::West, Self::East];
+
+    /// Get the direction with an ID.
+    #[must_use]
+    pub fn from_id(id: u32) -> Option<Self> { Self::ALL.get(id as usize).copied() }
+}
+
+/// The pose of an entity.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[expect(missing_docs, reason = "Variants are named after their poses")]
+pub enum Pose {
+    #[default]
+    Standing,
+    FallFlying,
+    Sleeping,
+    Swimming,
+    SpinAttack,
+    Crouching,
+    LongJumping,
+    Dying,
+    Croaking,
+    UsingTongue,
+    Sitting,
+    Roaring,
+    Sniffing,
+    Emerging,
+    Digging,
+    Sliding,
+    Shooting,
+    Inhaling,
+}
+
+impl Pose {
+    /// Every pose, ordered by ID.
+    pub const ALL: [Self; 18] = [
+        Self::Standing,
+        Self::FallFlying,
+        Self::Sleeping,
+        Self::Swimming,
+        Self::SpinAttack,
+        Self::Crouching,
+        Self::LongJumping,
+        Self::Dying,
+        Self::Croaking,
+        Self::UsingTongue,
+        Self::Sitting,
+        Self::Roaring,
+        Self::Sniffing,
+        Self::Emerging,
+        Self::Digging,
+        Self::Sliding,
+        Self::Shooting,
+        Self::Inhaling,
+    ];
+
+    /// Get the pose with an ID.
+    #[must_use]
+    pub fn from_id(id: u32) -> Option<Self> { Self::ALL.get(id as usize).copied() }
+}
+
+/// The type, profession and level of a villager.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VillagerData {
+    /// The ID of the villager type, such as `minecraft:plains`.
+    pub kind: u32,
+    /// The ID of the villager profession.
+    pub profession: u32,
+    /// The level of the villager, from `1` to `5`.
+    pub level: u32,
+}
+
+/// A typed value of an entity metadata entry.
+///
+/// Registry entries, such as [`MetadataValue::CatVariant`],
+/// are sent by their registry ID.
+/// Particles use the particle registry of the version, see [`Particle`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetadataValue {
+    /// A signed byte, often used for bit flags.
+    Byte(i8),
+    /// A `VarInt`.
+    VarInt(i32),
+    /// A `VarLong`.
+    VarLong(i64),
+    /// A float.
+    Float(f32),
+    /// A string.
+    String(String),
+    /// A text component.
+    Text(TextComponent),
+    /// An optional text component.
+    OptionalText(Option<TextComponent>),
+    /// An item stack, sent since 1.20.5.
+    Slot(OptionalItemStack),
+    /// A boolean.
+    Boolean(bool),
+    /// Rotations around the `x`, `y` and `z` axes, in degrees.
+    Rotations([f32; 3]),
+    /// A block position.
+    Position(BlockPos),
+    /// An optional block position.
+    OptionalPosition(Option<BlockPos>),
+    /// A direction.
+    Direction(Facing),
+    /// An optional UUID.
+    OptionalUuid(Option<Uuid>),
+    /// A block state ID.
+    BlockState(u32),
+    /// An optional block state ID, sent as `0` (air) if absent.
+    OptionalBlockState(Option<u32>),
+    /// An NBT value, removed in 1.21.9.
+    Nbt(NbtValue),
+    /// A particle.
+    Particle(Particle),
+    /// A list of particles.
+    Particles(Vec<Particle>),
+    /// The data of a villager.
+    VillagerData(VillagerData),
+    /// An optional non-negative `VarInt`, sent as the value plus one.
+    OptionalVarInt(Option<u32>),
+    /// The pose of an entity.
+    Pose(Pose),
+    /// A `cat_variant` registry ID.
+    CatVariant(u32),
+    /// A `cow_variant` registry ID, sent since 1.21.5.
+    CowVariant(u32),
+    /// A `wolf_variant` registry ID, sent since 1.20.5.
+    WolfVariant(u32),
+    /// A `wolf_sound_variant` registry ID, sent since 1.21.5.
+    WolfSoundVariant(u32),
+    /// A `frog_variant` registry ID.
+    FrogVariant(u32),
+    /// A `pig_variant` registry ID, sent since 1.21.5.
+    PigVariant(u32),
+    /// A `chicken_variant` registry ID, sent since 1.21.5.
+    ChickenVariant(u32),
+    /// An optional position in a dimension.
+    OptionalGlobalPosition(Option<GlobalPos>),
+    /// A `painting_variant` registry ID.
+    PaintingVariant(u32),
+    /// The state of a sniffer.
+    SnifferState(u32),
+    /// The state of an armadillo, sent since 1.20.5.
+    ArmadilloState(u32),
+    /// The state of a copper golem, sent since 1.21.9.
+    CopperGolemState(u32),
+    /// The oxidation of a copper golem, sent since 1.21.9.
+    WeatheringCopperState(u32),
+    /// A vector.
+    Vector3([f32; 3]),
+    /// A rotation quaternion, as `x`, `y`, `z` and `w`.
+    Quaternion([f32; 4]),
+    /// The profile of a mannequin, sent since 1.21.9.
+    ResolvableProfile(ResolvableProfile),
+    /// The main arm of a mannequin, sent since 1.21.9.
+    HumanoidArm(u32),
+}
+
+impl MetadataValue {
+    /// Get the type of this value.
+    #[must_use]
+    pub const fn kind(&self) -> MetadataType {
+        match self {
+            Self::Byte(_) => MetadataType::Byte,
+            Self::VarInt(_) => MetadataType::VarInt,
+            Self::VarLong(_) => MetadataType::VarLong,
+            Self::Float(_) => MetadataType::Float,
+            Self::String(_) => MetadataType::String,
+            Self::Text(_) => MetadataType::Text,
+            Self::OptionalText(_) => MetadataType::OptionalText,
+            Self::Slot(_) => MetadataType::Slot,
+            Self::Boolean(_) => MetadataType::Boolean,
+            Self::Rotations(_) => MetadataType::Rotations,
+            Self::Position(_) => MetadataType::Position,
+            Self::OptionalPosition(_) => MetadataType::OptionalPosition,
+            Self::Direction(_) => MetadataType::Direction,
+            Self::OptionalUuid(_) => MetadataType::OptionalUuid,
+            Self::BlockState(_) => MetadataType::BlockState,
+            Self::OptionalBlockState(_) => MetadataType::OptionalBlockState,
+            Self::Nbt(_) => MetadataType::Nbt,
+            Self::Particle(_) => MetadataType::Particle,
+            Self::Particles(_) => MetadataType::Particles,
+            Self::VillagerData(_) => MetadataType::VillagerData,
+            Self::OptionalVarInt(_) => MetadataType::OptionalVarInt,
+            Self::Pose(_) => MetadataType::Pose,
+            Self::CatVariant(_) => MetadataType::CatVariant,
+            Self::CowVariant(_) => MetadataType::CowVariant,
+            Self::WolfVariant(_) => MetadataType::WolfVariant,
+            Self::WolfSoundVariant(_) => MetadataType::WolfSoundVariant,
+            Self::FrogVariant(_) => MetadataType::FrogVariant,
+            Self::PigVariant(_) => MetadataType::PigVariant,
+            Self::ChickenVariant(_) => MetadataType::ChickenVariant,
+            Self::OptionalGlobalPosition(_) => MetadataType::OptionalGlobalPosition,
+            Self::PaintingVariant(_) => MetadataType::PaintingVariant,
+            Self::SnifferState(_) => MetadataType::SnifferState,
+            Self::ArmadilloState(_) => MetadataType::ArmadilloState,
+            Self::CopperGolemState(_) => MetadataType::CopperGolemState,
+            Self::WeatheringCopperState(_) => MetadataType::WeatheringCopperState,
+            Self::Vector3(_) => MetadataType::Vector3,
+            Self::Quaternion(_) => MetadataType::Quaternion,
+            Self::ResolvableProfile(_) => MetadataType::ResolvableProfile,
+            Self::HumanoidArm(_) => MetadataType::HumanoidArm,
+        }
+    }
+
+    /// Write the value, without its type, for a protocol version.
+    pub(super) fn encode_for(
+        &self,
+        writer: &mut McWriter<'_>,
+        version: ProtocolVersion,
+    ) -> Result<(), SerializeError> {
+        let encoding = TextEncoding::for_protocol(version.protocol());
+        match self {
+            Self::Byte(value) => writer.write_u8(value.cast_unsigned()),
+            Self::VarInt(value) => writer.write_var_i32(*value),
+            Self::VarLong(value) => writer.write_var_i64(*value),
+            Self::Float(value) => writer.write_f32(*value),
+            Self::String(value) => writer.write_str(value),
+            Self::Text(text) => text.encode_with(writer, encoding),
+            Self::OptionalText(text) => {
+                writer.write_bool(text.is_some())?;
+                text.as_ref().map_or(Ok(()), |text| text.encode_with(writer, encoding))
+            }
+            Self::Slot(_) if version < ProtocolVersion::V1_20_5 => {
+                Err(EntityError::new(EntityErrorKind::UnsupportedType {
+                    kind: MetadataType::Slot,
+                    version,
+                })
+                .into())
+            }
+            Self::Slot(stack) => {
+                stack.encode(&mut McWriter::new(writer.buffer()).with_version(version))
+            }
+            Self::Boolean(value) => writer.write_bool(*value),
+            Self::Rotations(values) | Self::Vector3(values) => {
+                values.iter().try_for_each(|value| writer.write_f32(*value))
+            }
+            Self::Quaternion(values) => {
+                values.iter().try_for_each(|value| writer.write_f32(*value))
+            }
+            Self::Position(position) => writer.write(position),
+            Self::OptionalPosition(position) => {
+                writer.write_bool(position.is_some())?;
+                position.as_ref().map_or(Ok(()), |position| writer.write(position))
+            }
+            Self::Direction(facing) => writer.write_var_u32(*facing as u32),
+            Self::OptionalUuid(uuid) => {
+                writer.write_bool(uuid.is_some())?;
+                uuid.as_ref().map_or(Ok(()), |uuid| writer.write_uuid(uuid))
+            }
+            Self::OptionalBlockState(id) => writer.write_var_u32(id.unwrap_or_default()),
+            Self::Nbt(nbt) => writer.write(nbt),
+            Self::Particle(particle) => particle.encode(writer, version),
+            Self::Particles(particles) => {
+                writer.write_var_u32(u32::try_from(particles.len()).unwrap_or(u32::MAX))?;
+                particles.iter().try_for_each(|particle| particle.encode(writer, version))
+            }
+            Self::ResolvableProfile(profile) => writer.write(profile),
+            Self::VillagerData(data) => {
+                writer.write_var_u32(data.kind)?;
+                writer.write_var_u32(data.profession)?;
+                writer.write_var_u32(data.level)
+            }
+            Self::OptionalVarInt(value) => {
+                writer.write_var_u32(value.map_or(0, |value| value.wrapping_add(1)))
+            }
+            Self::Pose(pose) => writer.write_var_u32(*pose as u32),
+            Self::OptionalGlobalPosition(position) => {
+                writer.write_bool(position.is_some())?;
+                position.as_ref().map_or(Ok(()), |position| writer.write(position))
+            }
+            Self::BlockState(id)
+            | Self::CatVariant(id)
+            | Self::CowVariant(id)
+            | Self::WolfVariant(id)
+            | Self::WolfSoundVariant(id)
+            | Self::FrogVariant(id)
+            | Self::PigVariant(id)
+            | Self::ChickenVariant(id)
+            | Self::PaintingVariant(id)
+            | Self::SnifferState(id)
+            | Self::ArmadilloState(id)
+            | Self::CopperGolemState(id)
+            | Self::WeatheringCopperState(id)
+            | Self::HumanoidArm(id) => writer.write_var_u32(*id),
+        }
+    }
+
+    /// Read a value of the given type for a protocol version.
+    pub(super) fn decode_for(
+        kind: MetadataType,
+        reader: &mut McReader<'_>,
+        version: ProtocolVersion,
+    ) -> Result<Self, DeserializeError> {
+        type Kind = MetadataType;
+
+        let encoding = TextEncoding::for_protocol(version.protocol());
+        let unsupported = || EntityError::new(EntityErrorKind::UnsupportedType { kind, version });
+        let invalid = |value| EntityError::new(EntityErrorKind::InvalidValue { kind, value });
+        let floats = |reader: &mut McReader<'_>| {
+            Ok::<_, DeserializeError>([reader.read_f32()?, reader.read_f32()?, reader.read_f32()?])
+        };
+
+        Ok(match kind {
+            Kind::Byte => Self::Byte(reader.read_u8()?.cast_signed()),
+            Kind::VarInt => Self::VarInt(reader.read_var_i32()?),
+            Kind::VarLong => Self::VarLong(reader.read_var_i64()?),
+            Kind::Float => Self::Float(reader.read_f32()?),
+            Kind::String => Self::String(String::from(reader.read_str()?)),
+            Kind::Text => Self::Text(TextComponent::decode_with(reader, encoding)?),
+            Kind::OptionalText => Self::OptionalText(if reader.read_bool()? {
+                Some(TextComponent::decode_with(reader, encoding)?)
+            } else {
+                None
+            }),
+            Kind::Slot if version < ProtocolVersion::V1_20_5 => return Err(unsupported().into()),
+            Kind::Slot => {
+                let mut versioned = reader.with_version(version);
+                let stack = versioned.read()?;
+                *reader = versioned.with_version(reader.version());
+                Self::Slot(stack)
+            }
+            Kind::Boolean => Self::Boolean(reader.read_bool()?),
+            Kind::Rotations => Self::Rotations(floats(reader)?),
+            Kind::Position => Self::Position(reader.read()?),
+            Kind::OptionalPosition => Self::OptionalPosition(if reader.read_bool()? {
+                Some(reader.read()?)
+            } else {
+                None
+            }),
+            Kind::Direction => {
+                let id = reader.read_var_u32()?;
+                Self::Direction(Facing::from_id(id).ok_or_else(|| invalid(id))?)
+            }
+            Kind::OptionalUuid => Self::OptionalUuid(if reader.read_bool()? {
+                Some(reader.read_uuid()?)
+            } else {
+                None
+            }),
+            Kind::BlockState => Self::BlockState(reader.read_var_u32()?),
+            Kind::OptionalBlockState => {
+                Self::OptionalBlockState(Some(reader.read_var_u32()?).filter(|id| *id != 0))
+            }
+            Kind::Nbt => Self::Nbt(reader.read()?),
+            Kind::Particle => Self::Particle(Particle::decode(reader, version)?),
+            Kind::Particles => {
+                let count = reader.read_var_u32()?;
+                Self::Particles(
+                    (0..count)
+                        .map(|_| Particle::decode(reader, version))
+                        .collect::<Result<_, _>>()?,
+                )
+            }
+            Kind::ResolvableProfile => Self::ResolvableProfile(reader.read()?),
+            Kind::VillagerData => Self::VillagerData(VillagerData {
+                kind: reader.read_var_u32()?,
+                profession: reader.read_var_u32()?,
+                level: reader.read_var_u32()?,
+            }),
+            Kind::OptionalVarInt => Self::OptionalVarInt(reader.read_var_u32()?.checked_sub(1)),
+            Kind::Pose => {
+                let id = reader.read_var_u32()?;
+                Self::Pose(Pose::from_id(id).ok_or_else(|| invalid(id))?)
+            }
+            Kind::CatVariant => Self::CatVariant(reader.read_var_u32()?),
+            Kind::CowVariant => Self::CowVariant(reader.read_var_u32()?),
+            Kind::WolfVariant => Self::WolfVariant(reader.read_var_u32()?),
+            Kind::WolfSoundVariant => Self::WolfSoundVariant(reader.read_var_u32()?),
+            Kind::FrogVariant => Self::FrogVariant(reader.read_var_u32()?),
+            Kind::PigVariant => Self::PigVariant(reader.read_var_u32()?),
+            Kind::ChickenVariant => Self::ChickenVariant(reader.read_var_u32()?),
+            Kind::OptionalGlobalPosition => Self::OptionalGlobalPosition(if reader.read_bool()? {
+                Some(reader.read()?)
+            } else {
+                None
+            }),
+            Kind::PaintingVariant => Self::PaintingVariant(reader.read_var_u32()?),
+            Kind::SnifferState => Self::SnifferState(reader.read_var_u32()?),
+            Kind::ArmadilloState => Self::ArmadilloState(reader.read_var_u32()?),
+            Kind::CopperGolemState => Self::CopperGolemState(reader.read_var_u32()?),
+            Kind::WeatheringCopperState => Self::WeatheringCopperState(reader.read_var_u32()?),
+            Kind::Vector3 => Self::Vector3(floats(reader)?),
+            Kind::Quaternion => Self::Quaternion([
+                reader.read_f32()?,
+                reader.read_f32()?,
+                reader.read_f32()?,
+                reader.read_f32()?,
+            ]),
+            Kind::HumanoidArm => Self::HumanoidArm(reader.read_var_u32()?),
+        })
+    }
+}
@@ -0,0 +1,150 @@
+//! Entity metadata, as sent in the Set Entity Metadata packet.
+//!
+//! [`EntityMetadata`] is a list of indexed, typed [`MetadataValue`]s ended by
+//! a `0xFF` byte. Each value is prefixed by the ID of its [`MetadataType`],
+//! which depends on the protocol version. As a field, metadata is encoded for
+//! the version of the [`McWriter`] or
+//! [`McReader`], otherwise use
+//! [`EntityMetadata::encode_for`] and [`EntityMetadata::decode_for`].
+//!
+//! Values are not prefixed by their length, so a list containing an
+//! unsupported type, such as a [`MetadataType::Slot`] before 1.20.5,
+//! cannot be decoded.
+//!
+//! ```rust
+//! use facet_minecraft::entity::{EntityMetadata, MetadataValue, Pose};
+//!
+//! let mut metadata = EntityMetadata::new();
+//! metadata.insert(0, MetadataValue::Byte(0x02));
+//! metadata.insert(6, MetadataValue::Pose(Pose::Crouching));
+//!
+//! let bytes = facet_minecraft::to_vec(&metadata).unwrap();
+//! assert_eq!(bytes, [0, 0, 0x02, 6, 20, 5, 0xFF]);
+//! let (decoded, _) = facet_minecraft::from_slice::<EntityMetadata>(&bytes).unwrap();
+//! assert_eq!(decoded, metadata);
+//! ```
+
+use alloc::vec::Vec;
+
+use facet::{Facet, Shape};
+
+use crate::{
+    codec::{McCodec, McReader, McWriter, codec_shape},
+    deserialize::DeserializeError,
+    packet::ProtocolVersion,
+    serialize::SerializeError,
+};
+
+mod error;
+pub use error::{EntityError, EntityErrorKind};
+
+mod metadata;
+pub use metadata::{Facing, MetadataType, MetadataValue, Pose, VillagerData};
+
+mod particle;
+pub use particle::{Particle, ParticleKind, ParticleOptions, PositionSource};
+
+/// The metadata of an entity, as a list of indexed values.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct EntityMetadata(pub Vec<MetadataEntry>);
+
+/// A single value in [`EntityMetadata`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetadataEntry {
+    /// The index of the value, specific to the type of entity.
+    pub index: u8,
+    /// The value.
+    pub value: MetadataValue,
+}
+
+impl EntityMetadata {
+    /// The byte ending the list, in place of an index.
+    pub const TERMINATOR: u8 = 0xFF;
+
+    /// Create a new, empty [`EntityMetadata`].
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self { Self(Vec::new()) }
+
+    /// Get the value at an index, if present.
+    #[must_use]
+    pub fn get(&self, index: u8) -> Option<&MetadataValue> {
+        self.0.iter().find(|entry| entry.index == index).map(|entry| &entry.value)
+    }
+
+    /// Set the value at an index, replacing any previous value.
+    pub fn insert(&mut self, index: u8, value: MetadataValue) {
+        match self.0.iter_mut().find(|entry| entry.index == index) {
+            Some(entry) => entry.value = value,
+            None => self.0.push(MetadataEntry { index, value }),
+        }
+    }
+
+    /// Write the metadata for a protocol version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an entry uses the [`EntityMetadata::TERMINATOR`]
+    /// index, a value's type does not exist in the protocol version,
+    /// or the buffer cannot be written to.
+    pub fn encode_for(
+        &self,
+        writer: &mut McWriter<'_>,
+        version: ProtocolVersion,
+    ) -> Result<(), SerializeError> {
+        for MetadataEntry { index, value } in &self.0 {
+            if *index == Self::TERMINATOR {
+                return Err(EntityError::new(EntityErrorKind::ReservedIndex).into());
+            }
+
+            let kind = value.kind();
+            let id = kind.id(version).ok_or_else(|| {
+                EntityError::new(EntityErrorKind::UnsupportedType { kind, version })
+            })?;
+            writer.write_u8(*index)?;
+            writer.write_var_u32(id)?;
+            value.encode_for(writer, version)?;
+        }
+        writer.write_u8(Self::TERMINATOR)
+    }
+
+    /// Read metadata for a protocol version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a type ID is not recognized, a value's type is not
+    /// supported, or the input ends before the terminator.
+    pub fn decode_for(
+        reader: &mut McReader<'_>,
+        version: ProtocolVersion,
+    ) -> Result<Self, DeserializeError> {
+        let mut metadata = Self::new();
+        loop {
+            let index = reader.read_u8()?;
+            if index == Self::TERMINATOR {
+                return Ok(metadata);
+            }
+
+            let id = reader.read_var_u32()?;
+            let kind = MetadataType::from_id(id, version)
+                .ok_or_else(|| EntityError::new(EntityErrorKind::UnknownType { id, version }))?;
+            let value = MetadataValue::decode_for(kind, reader, version)?;
+            metadata.0.push(MetadataEntry { index, value });
+        }
+    }
+}
+
+impl McCodec for EntityMetadata {
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        self.encode_for(writer, writer.version())
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        Self::decode_for(reader, reader.version())
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for EntityMetadata {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("EntityMetadata").build() };
+}
@@ -0,0 +1,610 @@
+use alloc::string::ToString;
+
+use super::{EntityError, EntityErrorKind, MetadataType};
+use crate::{
+    codec::{McCodec, McReader, McWriter},
+    deserialize::DeserializeError,
+    item::ItemStack,
+    packet::ProtocolVersion,
+    serialize::SerializeError,
+    types::{BlockPos, Identifier},
+};
+
+/// Define [`ParticleKind`] from its variants and names,
+/// in order of their registry IDs.
+macro_rules! particle_kinds {
+    ($($variant:ident => $name:literal,)*) => {
+        /// The type of a [`Particle`].
+        ///
+        /// Variants are ordered as in the `particle_type` registry,
+        /// with types removed in older versions where they used to be.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[expect(missing_docs, reason = "Variants are named after their particles")]
+        pub enum ParticleKind {
+            $($variant,)*
+        }
+
+        impl ParticleKind {
+            /// Every type of particle, from every version.
+            pub const ALL: &'static [Self] = &[$(Self::$variant,)*];
+
+            /// Get the name of this particle type, without the namespace.
+            #[must_use]
+            pub const fn name(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $name,)*
+                }
+            }
+        }
+    };
+}
+
+particle_kinds! {
+    AmbientEntityEffect => "ambient_entity_effect",
+    AngryVillager => "angry_villager",
+    Block => "block",
+    BlockMarker => "block_marker",
+    Bubble => "bubble",
+    Cloud => "cloud",
+    Crit => "crit",
+    DamageIndicator => "damage_indicator",
+    DragonBreath => "dragon_breath",
+    DrippingLava => "dripping_lava",
+    FallingLava => "falling_lava",
+    LandingLava => "landing_lava",
+    DrippingWater => "dripping_water",
+    FallingWater => "falling_water",
+    Dust => "dust",
+    DustColorTransition => "dust_color_transition",
+    Effect => "effect",
+    ElderGuardian => "elder_guardian",
+    EnchantedHit => "enchanted_hit",
+    Enchant => "enchant",
+    EndRod => "end_rod",
+    EntityEffect => "entity_effect",
+    ExplosionEmitter => "explosion_emitter",
+    Explosion => "explosion",
+    Gust => "gust",
+    GustEmitter => "gust_emitter",
+    SmallGust => "small_gust",
+    GustEmitterLarge => "gust_emitter_large",
+    GustEmitterSmall => "gust_emitter_small",
+    SonicBoom => "sonic_boom",
+    FallingDust => "falling_dust",
+    Firework => "firework",
+    Fishing => "fishing",
+    Flame => "flame",
+    Infested => "infested",
+    CherryLeaves => "cherry_leaves",
+    PaleOakLeaves => "pale_oak_leaves",
+    TintedLeaves => "tinted_leaves",
+    SculkSoul => "sculk_soul",
+    SculkCharge => "sculk_charge",
+    SculkChargePop => "sculk_charge_pop",
+    SoulFireFlame => "soul_fire_flame",
+    CopperFireFlame => "copper_fire_flame",
+    Soul => "soul",
+    Flash => "flash",
+    HappyVillager => "happy_villager",
+    Composter => "composter",
+    Heart => "heart",
+    InstantEffect => "instant_effect",
+    Item => "item",
+    Vibration => "vibration",
+    Trail => "trail",
+    ItemSlime => "item_slime",
+    ItemCobweb => "item_cobweb",
+    ItemSnowball => "item_snowball",
+    LargeSmoke => "large_smoke",
+    Lava => "lava",
+    Mycelium => "mycelium",
+    Note => "note",
+    Poof => "poof",
+    Portal => "portal",
+    Rain => "rain",
+    Smoke => "smoke",
+    WhiteSmoke => "white_smoke",
+    Sneeze => "sneeze",
+    Spit => "spit",
+    SquidInk => "squid_ink",
+    SweepAttack => "sweep_attack",
+    TotemOfUndying => "totem_of_undying",
+    Underwater => "underwater",
+    Splash => "splash",
+    Witch => "witch",
+    BubblePop => "bubble_pop",
+    CurrentDown => "current_down",
+    BubbleColumnUp => "bubble_column_up",
+    Nautilus => "nautilus",
+    Dolphin => "dolphin",
+    CampfireCosySmoke => "campfire_cosy_smoke",
+    CampfireSignalSmoke => "campfire_signal_smoke",
+    DrippingHoney => "dripping_honey",
+    FallingHoney => "falling_honey",
+    LandingHoney => "landing_honey",
+    FallingNectar => "falling_nectar",
+    FallingSporeBlossom => "falling_spore_blossom",
+    Ash => "ash",
+    CrimsonSpore => "crimson_spore",
+    WarpedSpore => "warped_spore",
+    SporeBlossomAir => "spore_blossom_air",
+    DrippingObsidianTear => "dripping_obsidian_tear",
+    FallingObsidianTear => "falling_obsidian_tear",
+    LandingObsidianTear => "landing_obsidian_tear",
+    ReversePortal => "reverse_portal",
+    WhiteAsh => "white_ash",
+    SmallFlame => "small_flame",
+    Snowflake => "snowflake",
+    DrippingDripstoneLava => "dripping_dripstone_lava",
+    FallingDripstoneLava => "falling_dripstone_lava",
+    DrippingDripstoneWater => "dripping_dripstone_water",
+    FallingDripstoneWater => "falling_dripstone_water",
+    GlowSquidInk => "glow_squid_ink",
+    Glow => "glow",
+    WaxOn => "wax_on",
+    WaxOff => "wax_off",
+    ElectricSpark => "electric_spark",
+    Scrape => "scrape",
+    Shriek => "shriek",
+    EggCrack => "egg_crack",
+    DustPlume => "dust_plume",
+    GustDust => "gust_dust",
+    TrialSpawnerDetectedPlayer => "trial_spawner_detected_player",
+    TrialSpawnerDetectedPlayerOminous => "trial_spawner_detected_player_ominous",
+    VaultConnection => "vault_connection",
+    DustPillar => "dust_pillar",
+    OminousSpawning => "ominous_spawning",
+    RaidOmen => "raid_omen",
+    TrialOmen => "trial_omen",
+    BlockCrumble => "block_crumble",
+    Firefly => "firefly",
+}
+
+impl ParticleKind {
+    /// Check whether this particle type exists in a protocol version.
+    ///
+    /// Snapshots use the registry of the latest release.
+    #[must_use]
+    pub fn exists_in(self, version: ProtocolVersion) -> bool {
+        let since = match self {
+            Self::Gust
+            | Self::GustEmitter
+            | Self::WhiteSmoke
+            | Self::DustPlume
+            | Self::GustDust
+            | Self::TrialSpawnerDetectedPlayer => ProtocolVersion::V1_20_3,
+            Self::SmallGust
+            | Self::GustEmitterLarge
+            | Self::GustEmitterSmall
+            | Self::Infested
+            | Self::ItemCobweb
+            | Self::TrialSpawnerDetectedPlayerOminous
+            | Self::VaultConnection
+            | Self::DustPillar
+            | Self::OminousSpawning
+            | Self::RaidOmen
+            | Self::TrialOmen => ProtocolVersion::V1_20_5,
+            Self::Trail | Self::BlockCrumble => ProtocolVersion::V1_21_2,
+            Self::PaleOakLeaves => ProtocolVersion::V1_21_4,
+            Self::TintedLeaves | Self::Firefly => ProtocolVersion::V1_21_5,
+            Self::CopperFireFlame => ProtocolVersion::V1_21_9,
+            _ => ProtocolVersion::V1_20_2,
+        };
+        let removed = match self {
+            Self::AmbientEntityEffect | Self::GustEmitter | Self::GustDust => {
+                ProtocolVersion::V1_20_5
+            }
+            _ => return version >= since,
+        };
+        version >= since && version < removed
+    }
+
+    /// Get the registry ID of this particle type in a protocol version,
+    /// or `None` if the type does not exist in that version.
+    #[must_use]
+    pub fn id(self, version: ProtocolVersion) -> Option<u32> {
+        if !self.exists_in(version) {
+            return None;
+        }
+        let index = Self::ALL
+            .iter()
+            .take_while(|kind| **kind != self)
+            .filter(|kind| kind.exists_in(version))
+            .count();
+        u32::try_from(index).ok()
+    }
+
+    /// Get the particle type with a registry ID in a protocol version.
+    #[must_use]
+    pub fn from_id(id: u32, version: ProtocolVersion) -> Option<Self> {
+        Self::ALL.iter().copied().filter(|kind| kind.exists_in(version)).nth(id as usize)
+    }
+
+    /// Get the particle type with a name, with or without the
+    /// `minecraft` namespace.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.strip_prefix("minecraft:").unwrap_or(name);
+        Self::ALL.iter().copied().find(|kind| kind.name() == name)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// A particle, as its type and the options of that type.
+///
+/// Particles are encoded for the protocol version of their metadata.
+/// Dust colors sent as floats before 1.21.2 are rounded to `0xRRGGBB`,
+/// and trails read from 1.21.2 have a `duration` of `0`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Particle {
+    /// The type of the particle.
+    pub kind: ParticleKind,
+    /// The options of the particle, which must match its type.
+    pub options: ParticleOptions,
+}
+
+/// The options of a [`Particle`], depending on its [`ParticleKind`].
+///
+/// Colors are packed as `0xAARRGGBB`, or `0xRRGGBB` when opaque.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub enum ParticleOptions {
+    /// No options.
+    #[default]
+    None,
+    /// A block state ID, for [`ParticleKind::Block`],
+    /// [`ParticleKind::BlockMarker`], [`ParticleKind::FallingDust`],
+    /// [`ParticleKind::DustPillar`] and [`ParticleKind::BlockCrumble`].
+    Block(u32),
+    /// A colored dust, for [`ParticleKind::Dust`].
+    Dust {
+        /// The color.
+        color: u32,
+        /// The size of the particle.
+        scale: f32,
+    },
+    /// A dust fading between colors,
+    /// for [`ParticleKind::DustColorTransition`].
+    DustColorTransition {
+        /// The starting color.
+        from: u32,
+        /// The final color.
+        to: u32,
+        /// The size of the particle.
+        scale: f32,
+    },
+    /// A color, for [`ParticleKind::EntityEffect`],
+    /// [`ParticleKind::TintedLeaves`] and [`ParticleKind::Flash`].
+    Color(u32),
+    /// A colored spell, for [`ParticleKind::Effect`] and
+    /// [`ParticleKind::InstantEffect`].
+    Spell {
+        /// The color.
+        color: u32,
+        /// The strength of the effect.
+        power: f32,
+    },
+    /// The strength of [`ParticleKind::DragonBreath`].
+    Power(f32),
+    /// An item, for [`ParticleKind::Item`].
+    Item(ItemStack),
+    /// A vibration moving towards a source, for [`ParticleKind::Vibration`].
+    Vibration {
+        /// The destination of the vibration.
+        destination: PositionSource,
+        /// The time to reach the destination, in ticks.
+        ticks: u32,
+    },
+    /// A trail moving towards a target, for [`ParticleKind::Trail`].
+    Trail {
+        /// The position of the target.
+        target: [f64; 3],
+        /// The color.
+        color: u32,
+        /// The time to reach the target, in ticks.
+        duration: u32,
+    },
+    /// The roll of [`ParticleKind::SculkCharge`], in radians.
+    SculkCharge(f32),
+    /// The delay of [`ParticleKind::Shriek`], in ticks.
+    Shriek(u32),
+}
+
+/// The destination of a vibration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PositionSource {
+    /// A block.
+    Block(BlockPos),
+    /// An entity.
+    Entity {
+        /// The ID of the entity.
+        id: u32,
+        /// The height above the entity's feet.
+        y_offset: f32,
+    },
+}
+
+impl Particle {
+    /// Create a new [`Particle`] without options.
+    #[inline]
+    #[must_use]
+    pub const fn new(kind: ParticleKind) -> Self { Self { kind, options: ParticleOptions::None } }
+
+    /// Write the particle type and its options for a protocol version.
+    pub(super) fn encode(
+        &self,
+        writer: &mut McWriter<'_>,
+        version: ProtocolVersion,
+    ) -> Result<(), SerializeError> {
+        let Some(id) = self.kind.id(version) else {
+            return Err(EntityError::new(EntityErrorKind::UnsupportedParticle {
+                kind: self.kind,
+                version,
+            })
+            .into());
+        };
+        if !self.options.fits_for(self.kind, version) {
+            return Err(EntityError::new(EntityErrorKind::InvalidParticle(self.kind)).into());
+        }
+
+        let packed = version >= ProtocolVersion::V1_21_2;
+        writer.write_var_u32(id)?;
+        match &self.options {
+            ParticleOptions::None => Ok(()),
+            ParticleOptions::Block(id) | ParticleOptions::Shriek(id) => writer.write_var_u32(*id),
+            ParticleOptions::Dust { color, scale } => {
+                write_color(writer, *color, packed)?;
+                writer.write_f32(*scale)
+            }
+            ParticleOptions::DustColorTransition { from, to, scale } => {
+                write_color(writer, *from, packed)?;
+                if version < ProtocolVersion::V1_20_5 {
+                    writer.write_f32(*scale)?;
+                    return write_color(writer, *to, packed);
+                }
+                write_color(writer, *to, packed)?;
+                writer.write_f32(*scale)
+            }
+            ParticleOptions::Color(color) => writer.write_u32(*color),
+            ParticleOptions::Spell { color, power } => {
+                writer.write_u32(*color)?;
+                writer.write_f32(*power)
+            }
+            ParticleOptions::Power(value) | ParticleOptions::SculkCharge(value) => {
+                writer.write_f32(*value)
+            }
+            ParticleOptions::Item(stack) => {
+                stack.encode(&mut McWriter::new(writer.buffer()).with_version(version))
+            }
+            ParticleOptions::Vibration { destination, ticks } => {
+                let named = version < ProtocolVersion::V1_20_5;
+                match destination {
+                    PositionSource::Block(position) if named => {
+                        writer.write_identifier(&Identifier::new_unchecked("minecraft:block"))?;
+                        writer.write(position)?;
+                    }
+                    PositionSource::Block(position) => {
+                        writer.write_var_u32(0)?;
+                        writer.write(position)?;
+                    }
+                    PositionSource::Entity { id, y_offset } => {
+                        if named {
+                            writer
+                                .write_identifier(&Identifier::new_unchecked("minecraft:entity"))?;
+                        } else {
+                            writer.write_var_u32(1)?;
+                        }
+                        writer.write_var_u32(*id)?;
+                        writer.write_f32(*y_offset)?;
+                    }
+                }
+                writer.write_var_u32(*ticks)
+            }
+            ParticleOptions::Trail { target, color, duration } => {
+                target.iter().try_for_each(|value| writer.write_f64(*value))?;
+                writer.write_u32(*color)?;
+                if version >= ProtocolVersion::V1_21_4 {
+                    writer.write_var_u32(*duration)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Read a particle type and its options for a protocol version.
+    pub(super) fn decode(
+        reader: &mut McReader<'_>,
+        version: ProtocolVersion,
+    ) -> Result<Self, DeserializeError> {
+        type Kind = ParticleKind;
+
+        let invalid = |value| {
+            EntityError::new(EntityErrorKind::InvalidValue { kind: MetadataType::Particle, value })
+        };
+
+        let id = reader.read_var_u32()?;
+        let kind = Kind::from_id(id, version).ok_or_else(|| invalid(id))?;
+
+        let packed = version >= ProtocolVersion::V1_21_2;
+        let options = match kind {
+            Kind::Block
+            | Kind::BlockMarker
+            | Kind::FallingDust
+            | Kind::DustPillar
+            | Kind::BlockCrumble => ParticleOptions::Block(reader.read_var_u32()?),
+            Kind::Dust => ParticleOptions::Dust {
+                color: read_color(reader, packed)?,
+                scale: reader.read_f32()?,
+            },
+            Kind::DustColorTransition if version < ProtocolVersion::V1_20_5 => {
+                let from = read_color(reader, packed)?;
+                let scale = reader.read_f32()?;
+                ParticleOptions::DustColorTransition {
+                    from,
+                    to: read_color(reader, packed)?,
+                    scale,
+                }
+            }
+            Kind::DustColorTransition => ParticleOptions::DustColorTransition {
+                from: read_color(reader, packed)?,
+                to: read_color(reader, packed)?,
+                scale: reader.read_f32()?,
+            },
+            Kind::EntityEffect | Kind::TintedLeaves | Kind::Flash
+                if ParticleOptions::Color(0).fits_for(kind, version) =>
+            {
+                ParticleOptions::Color(reader.read_u32()?)
+            }
+            Kind::Effect | Kind::InstantEffect if version >= ProtocolVersion::V1_21_9 => {
+                ParticleOptions::Spell { color: reader.read_u32()?, power: reader.read_f32()? }
+            }
+            Kind::DragonBreath if version >= ProtocolVersion::V1_21_9 => {
+                ParticleOptions::Power(reader.read_f32()?)
+            }
+            Kind::Item => {
+                let mut versioned = reader.with_version(version);
+                let stack = versioned.read()?;
+                *reader = versioned.with_version(reader.version());
+                ParticleOptions::Item(stack)
+            }
+            Kind::Vibration => {
+                let source = if version < ProtocolVersion::V1_20_5 {
+                    let name = reader.read_identifier()?;
+                    match name.as_str() {
+                        "minecraft:block" => 0,
+                        "minecraft:entity" => 1,
+                        _ => {
+                            return Err(EntityError::new(EntityErrorKind::InvalidName {
+                                kind: MetadataType::Particle,
+                                name: name.as_str().to_string(),
+                            })
+                            .into());
+                        }
+                    }
+                } else {
+                    reader.read_var_u32()?
+                };
+                let destination = match source {
+                    0 => PositionSource::Block(reader.read()?),
+                    1 => PositionSource::Entity {
+                        id: reader.read_var_u32()?,
+                        y_offset: reader.read_f32()?,
+                    },
+                    other => return Err(invalid(other).into()),
+                };
+                ParticleOptions::Vibration { destination, ticks: reader.read_var_u32()? }
+            }
+            Kind::Trail => ParticleOptions::Trail {
+                target: [reader.read_f64()?, reader.read_f64()?, reader.read_f64()?],
+                color: reader.read_u32()?,
+                duration: if version >= ProtocolVersion::V1_21_4 {
+                    reader.read_var_u32()?
+                } else {
+                    0
+                },
+            },
+            Kind::SculkCharge => ParticleOptions::SculkCharge(reader.read_f32()?),
+            Kind::Shriek => ParticleOptions::Shriek(reader.read_var_u32()?),
+            _ => ParticleOptions::None,
+        };
+
+        Ok(Self { kind, options })
+    }
+}
+
+/// Write a `0xRRGGBB` color, either packed or as floats between `0.0` and
+/// `1.0`.
+fn write_color(writer: &mut McWriter<'_>, color: u32, packed: bool) -> Result<(), SerializeError> {
+    if packed {
+        return writer.write_u32(color);
+    }
+    color.to_be_bytes()[1..]
+        .iter()
+        .try_for_each(|component| writer.write_f32(f32::from(*component) / 255.0))
+}
+
+/// Read a `0xRRGGBB` color, either packed or as floats between `0.0` and `1.0`.
+fn read_color(reader: &mut McReader<'_>, packed: bool) -> Result<u32, DeserializeError> {
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        reason = "The component is clamped between 0 and 255"
+    )]
+    fn component(value: f32) -> u8 { (value.clamp(0.0, 1.0) * 255.0 + 0.5) as u8 }
+
+    if packed {
+        return reader.read_u32();
+    }
+    let [red, green, blue] = [reader.read_f32()?, reader.read_f32()?, reader.read_f32()?];
+    Ok(u32::from_be_bytes([0, component(red), component(green), component(blue)]))
+}
+
+impl ParticleOptions {
+    /// Check whether these options belong to a particle type
+    /// in the latest version.
+    #[must_use]
+    pub const fn fits(&self, kind: ParticleKind) -> bool {
+        self.fits_for(kind, ProtocolVersion::LATEST)
+    }
+
+    /// Check whether these options belong to a particle type
+    /// in a protocol version.
+    ///
+    /// [`ParticleKind::EntityEffect`] takes a color since 1.20.5,
+    /// and [`ParticleKind::Flash`], [`ParticleKind::Effect`],
+    /// [`ParticleKind::InstantEffect`] and [`ParticleKind::DragonBreath`]
+    /// take options since 1.21.9.
+    #[must_use]
+    pub const fn fits_for(&self, kind: ParticleKind, version: ProtocolVersion) -> bool {
+        type Kind = ParticleKind;
+        let since_1_20_5 = version.protocol() >= ProtocolVersion::V1_20_5.protocol();
+        let since_1_21_9 = version.protocol() >= ProtocolVersion::V1_21_9.protocol();
+        match self {
+            Self::Block(_) => matches!(
+                kind,
+                Kind::Block
+                    | Kind::BlockMarker
+                    | Kind::FallingDust
+                    | Kind::DustPillar
+                    | Kind::BlockCrumble
+            ),
+            Self::Dust { .. } => matches!(kind, Kind::Dust),
+            Self::DustColorTransition { .. } => matches!(kind, Kind::DustColorTransition),
+            Self::Color(_) => match kind {
+                Kind::TintedLeaves => true,
+                Kind::EntityEffect => since_1_20_5,
+                Kind::Flash => since_1_21_9,
+                _ => false,
+            },
+            Self::Spell { .. } => {
+                matches!(kind, Kind::Effect | Kind::InstantEffect) && since_1_21_9
+            }
+            Self::Power(_) => matches!(kind, Kind::DragonBreath) && since_1_21_9,
+            Self::Item(_) => matches!(kind, Kind::Item),
+            Self::Vibration { .. } => matches!(kind, Kind::Vibration),
+            Self::Trail { .. } => matches!(kind, Kind::Trail),
+            Self::SculkCharge(_) => matches!(kind, Kind::SculkCharge),
+            Self::Shriek(_) => matches!(kind, Kind::Shriek),
+            Self::None => match kind {
+                Kind::Block
+                | Kind::BlockMarker
+                | Kind::FallingDust
+                | Kind::DustPillar
+                | Kind::BlockCrumble
+                | Kind::Dust
+                | Kind::DustColorTransition
+                | Kind::TintedLeaves
+                | Kind::Item
+                | Kind::Vibration
+                | Kind::Trail
+                | Kind::SculkCharge
+                | Kind::Shriek => false,
+                Kind::EntityEffect => !since_1_20_5,
+                Kind::Flash | Kind::Effect | Kind::InstantEffect | Kind::DragonBreath => {
+                    !since_1_21_9
+                }
+                _ => true,
+            },
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use core::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// An error that occurred while encoding or decoding a packet frame.
+#[derive(Debug)]
+pub struct FrameError {
+    kind: FrameErrorKind,
+}
+
+impl FrameError {
+    /// Create a new [`FrameError`].
+    #[inline]
+    #[must_use]
+    pub const fn new(kind: FrameErrorKind) -> Self { Self { kind } }
+
+    /// Get the kind of frame error.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> &FrameErrorKind { &self.kind }
+}
+
+/// The type of frame error.
+#[derive(Debug)]
+pub enum FrameErrorKind {
+    /// An error occurred while writing to the buffer.
+    BufferError,
+    /// A `VarInt` was longer than allowed.
+    InvalidVarInt,
+    /// The frame was longer than
+    /// [`MAX_FRAME_LENGTH`](super::PacketCodec::MAX_FRAME_LENGTH).
+    FrameTooLarge(usize),
+    /// The uncompressed packet was longer than
+    /// [`MAX_DATA_LENGTH`](super::PacketCodec::MAX_DATA_LENGTH).
+    DataTooLarge(usize),
+    /// A compressed packet was smaller than the compression threshold.
+    BelowThreshold(usize),
+    /// A compressed packet did not decompress to its declared length.
+    LengthMismatch {
+        /// The declared length.
+        expected: usize,
+        /// The length after decompressing, up to one byte past `expected`.
+        found: usize,
+    },
+    /// A compressed packet could not be decompressed.
+    InvalidCompression,
+    /// Bytes were left over after decoding the packet body.
+    TrailingBytes(usize),
+
+    /// An I/O error occurred.
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Error for FrameError {}
+impl Display for FrameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            FrameErrorKind::BufferError => f.write_str("failed to write to the buffer"),
+            FrameErrorKind::InvalidVarInt => f.write_str("VarInt is too long"),
+            FrameErrorKind::FrameTooLarge(len) => write!(f, "frame of {len} bytes is too large"),
+            FrameErrorKind::DataTooLarge(len) => write!(f, "packet of {len} bytes is too large"),
+            FrameErrorKind::BelowThreshold(len) => {
+                write!(f, "compressed packet of {len} bytes is below the threshold")
+            }
+            FrameErrorKind::LengthMismatch { expected, found } => {
+                write!(f, "compressed packet declared {expected} bytes but contained {found}")
+            }
+            FrameErrorKind::InvalidCompression => f.write_str("invalid zlib data"),
+            FrameErrorKind::TrailingBytes(len) => write!(f, "{len} bytes left after packet body"),
+            #[cfg(feature = "std")]
+            FrameErrorKind::Io(err) => write!(f, "I/O error: {err}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for FrameError {
+    #[inline]
+    fn from(err: std::io::Error) -> Self { Self { kind: FrameErrorKind::Io(err) } }
+}
@@ -0,0 +1,524 @@
+//! Packet frames, the outermost layer of the Java protocol.
+//!
+//! Every packet is sent as a frame prefixed by its length as a `VarInt`.
+//! Before compression is enabled a frame contains the packet ID and body:
+//!
+//! | Field       | Type     |
+//! |-------------|----------|
+//! | Length      | `VarInt` |
+//! | Packet ID   | `VarInt` |
+//! | Body        | Bytes    |
+//!
+//! Once the server sends Set Compression, packets at or above the threshold
+//! are compressed using zlib, and smaller packets are sent with a data
+//! length of zero:
+//!
+//! | Field       | Type     |                                       |
+//! |-------------|----------|---------------------------------------|
+//! | Length      | `VarInt` |                                       |
+//! | Data Length | `VarInt` | Uncompressed length, or `0`           |
+//! | Packet ID   | `VarInt` | Compressed together with the body     |
+//! | Body        | Bytes    | unless the data length is `0`         |
+//!
+//! A [`PacketCodec`] writes and reads frames from byte slices, buffers and
+//! readers. Decoded [`Frame`]s borrow from the input when possible,
+//! and can be deserialized with [`Frame::decode`].
+
+use alloc::borrow::Cow;
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+
+use facet_format::{DeserializeError as FDError, SerializeError as FSError};
+
+use crate::{
+    Deserializable, Serializable,
+    deserialize::DeserializeError,
+    packet::{PacketError, PacketSet, ProtocolVersion},
+    serialize::{SerializeBuffer, SerializeError},
+};
+
+mod error;
+pub use error::{FrameError, FrameErrorKind};
+
+/// The number of bytes the frame length may occupy.
+const LENGTH_BYTES: usize = 3;
+
+/// Encodes and decodes packet frames,
+/// optionally compressing packets above a threshold.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PacketCodec {
+    #[cfg(feature = "compression")]
+    threshold: Option<usize>,
+}
+
+impl PacketCodec {
+    /// The maximum length of a packet after decompression.
+    pub const MAX_DATA_LENGTH: usize = 8_388_608;
+    /// The maximum length of a frame, the largest three-byte `VarInt`.
+    pub const MAX_FRAME_LENGTH: usize = 2_097_151;
+
+    /// Create a new [`PacketCodec`] without compression.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            #[cfg(feature = "compression")]
+            threshold: None,
+        }
+    }
+
+    /// Create a new [`PacketCodec`] that compresses packets of at least
+    /// `threshold` bytes.
+    #[inline]
+    #[must_use]
+    #[cfg(feature = "compression")]
+    pub const fn with_compression(threshold: usize) -> Self { Self { threshold: Some(threshold) } }
+
+    /// Get the compression threshold, if compression is enabled.
+    #[inline]
+    #[must_use]
+    #[cfg(feature = "compression")]
+    pub const fn compression(&self) -> Option<usize> { self.threshold }
+
+    /// Enable compression with the given threshold, or disable it.
+    ///
+    /// A negative threshold in Set Compression disables compression.
+    #[inline]
+    #[cfg(feature = "compression")]
+    pub const fn set_compression(&mut self, threshold: Option<usize>) {
+        self.threshold = threshold;
+    }
+
+    // ---------------------------------------------------------------------------------------------
+
+    /// Write a frame containing a packet ID and an already encoded body.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the packet is too large,
+    /// or if the buffer cannot be written to.
+    pub fn encode_frame<B: SerializeBuffer + ?Sized>(
+        &self,
+        id: u32,
+        body: &[u8],
+        buffer: &mut B,
+    ) -> Result<(), FrameError> {
+        let (id_bytes, id_len) = var_bytes(id);
+        let id = &id_bytes[..id_len];
+
+        #[cfg(feature = "compression")]
+        if let Some(threshold) = self.threshold {
+            let data_len = id.len() + body.len();
+            if data_len > Self::MAX_DATA_LENGTH {
+                return Err(FrameError::new(FrameErrorKind::DataTooLarge(data_len)));
+            }
+
+            if data_len >= threshold {
+                let compressed = compress(id, body)?;
+                #[expect(clippy::cast_possible_truncation, reason = "Checked against the maximum")]
+                let (len_bytes, len_len) = var_bytes(data_len as u32);
+                return write_frame(buffer, &[&len_bytes[..len_len], &compressed]);
+            }
+
+            return write_frame(buffer, &[&[0], id, body]);
+        }
+
+        write_frame(buffer, &[id, body])
+    }
+
+    /// Serialize a packet body and write it in a frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails, if the packet is too large,
+    /// or if the buffer cannot be written to.
+    pub fn encode_packet<'facet, T, B>(
+        &self,
+        id: u32,
+        value: &T,
+        buffer: &mut B,
+    ) -> Result<(), FSError<SerializeError>>
+    where
+        T: Serializable<'facet> + ?Sized,
+        B: SerializeBuffer + ?Sized,
+    {
+        let body = crate::to_vec(value)?;
+        self.encode_frame(id, &body, buffer).map_err(|err| FSError::Backend(err.into()))
+    }
+
+    /// Decode a frame from the start of the input.
+    ///
+    /// Returns the frame and the number of bytes it occupied,
+    /// or `None` if the input does not yet contain a whole frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the frame is too large or malformed.
+    pub fn decode_frame<'a>(
+        &self,
+        input: &'a [u8],
+    ) -> Result<Option<(Frame<'a>, usize)>, FrameError> {
+        let mut length = 0;
+        let mut prefix = 0;
+        loop {
+            let Some(&byte) = input.get(prefix) else { return Ok(None) };
+            prefix += 1;
+            if let Some(length) = length_byte(&mut length, prefix, byte)? {
+                let Some(payload) = input.get(prefix..prefix + length) else { return Ok(None) };
+                return self
+                    .decode_payload(Cow::Borrowed(payload))
+                    .map(|f| Some((f, prefix + length)));
+            }
+        }
+    }
+
+    /// Decode the contents of a frame, after the length prefix.
+    fn decode_payload<'a>(&self, payload: Cow<'a, [u8]>) -> Result<Frame<'a>, FrameError> {
+        #[cfg(feature = "compression")]
+        if let Some(threshold) = self.threshold {
+            let (data_len, size) = read_var(&payload)?;
+            let data_len = data_len as usize;
+
+            if data_len == 0 {
+                let (id, id_size) = read_var(&payload[size..])?;
+                return Ok(Frame { id, data: payload, body: size + id_size });
+            } else if data_len < threshold {
+                return Err(FrameError::new(FrameErrorKind::BelowThreshold(data_len)));
+            } else if data_len > Self::MAX_DATA_LENGTH {
+                return Err(FrameError::new(FrameErrorKind::DataTooLarge(data_len)));
+            }
+
+            let data = decompress(&payload[size..], data_len)?;
+            let (id, id_size) = read_var(&data)?;
+            return Ok(Frame { id, data: Cow::Owned(data), body: id_size });
+        }
+
+        let (id, size) = read_var(&payload)?;
+        Ok(Frame { id, data: payload, body: size })
+    }
+
+    // ---------------------------------------------------------------------------------------------
+
+    /// Read a frame from a [`Reader`](std::io::Read).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the frame is too large or malformed,
+    /// or the reader encounters an I/O error.
+    #[cfg(feature = "std")]
+    pub fn read_frame<R: std::io::Read>(
+        &self,
+        reader: &mut R,
+    ) -> Result<Frame<'static>, FrameError> {
+        let mut length = 0;
+        let mut index = 0;
+        let length = loop {
+            let mut byte = [0];
+            reader.read_exact(&mut byte)?;
+            index += 1;
+            if let Some(length) = length_byte(&mut length, index, byte[0])? {
+                break length;
+            }
+        };
+
+        let mut payload = alloc::vec![0; length];
+        reader.read_exact(&mut payload)?;
+        self.decode_payload(Cow::Owned(payload))
+    }
+
+    /// Write a frame to a [`Writer`](std::io::Write).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the packet is too large,
+    /// or the writer encounters an I/O error.
+    #[cfg(feature = "std")]
+    pub fn write_frame<W: std::io::Write>(
+        &self,
+        id: u32,
+        body: &[u8],
+        writer: &mut W,
+    ) -> Result<(), FrameError> {
+        let mut buffer = Vec::new();
+        self.encode_frame(id, body, &mut buffer)?;
+        writer.write_all(&buffer).map_err(Into::into)
+    }
+
+    /// Read a frame from an asynchronous
+    /// [`AsyncRead`](futures_lite::AsyncRead).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the frame is too large or malformed,
+    /// or the reader encounters an I/O error.
+    #[cfg(feature = "futures-lite")]
+    pub async fn read_async_frame<R: futures_lite::AsyncRead + Unpin>(
+        &self,
+        reader: &mut R,
+    ) -> Result<Frame<'static>, FrameError> {
+        use futures_lite::AsyncReadExt;
+
+        let mut length = 0;
+        let mut index = 0;
+        let length = loop {
+            let mut byte = [0];
+            reader.read_exact(&mut byte).await?;
+            index += 1;
+            if let Some(length) = length_byte(&mut length, index, byte[0])? {
+                break length;
+            }
+        };
+
+        let mut payload = alloc::vec![0; length];
+        reader.read_exact(&mut payload).await?;
+        self.decode_payload(Cow::Owned(payload))
+    }
+
+    /// Write a frame to an asynchronous
+    /// [`AsyncWrite`](futures_lite::AsyncWrite).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the packet is too large,
+    /// or the writer encounters an I/O error.
+    #[cfg(feature = "futures-lite")]
+    pub async fn write_async_frame<W: futures_lite::AsyncWrite + Unpin>(
+        &self,
+        id: u32,
+        body: &[u8],
+        writer: &mut W,
+    ) -> Result<(), FrameError> {
+        let mut buffer = Vec::new();
+        self.encode_frame(id, body, &mut buffer)?;
+        futures_lite::AsyncWriteExt::write_all(writer, &buffer).await.map_err(Into::into)
+    }
+
+    /// Read a frame from an asynchronous
+    /// [`AsyncRead`](tokio::io::AsyncRead).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the frame is too large or malformed,
+    /// or the reader encounters an I/O error.
+    #[cfg(feature = "tokio")]
+    pub async fn read_tokio_frame<R: tokio::io::AsyncRead + Unpin>(
+        &self,
+        reader: &mut R,
+    ) -> Result<Frame<'static>, FrameError> {
+        use tokio::io::AsyncReadExt;
+
+        let mut length = 0;
+        let mut index = 0;
+        let length = loop {
+            let mut byte = [0];
+            reader.read_exact(&mut byte).await?;
+            index += 1;
+            if let Some(length) = length_byte(&mut length, index, byte[0])? {
+                break length;
+            }
+        };
+
+        let mut payload = alloc::vec![0; length];
+        reader.read_exact(&mut payload).await?;
+        self.decode_payload(Cow::Owned(payload))
+    }
+
+    /// Write a frame to an asynchronous
+    /// [`AsyncWrite`](tokio::io::AsyncWrite).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the packet is too large,
+    /// or the writer encounters an I/O error.
+    #[cfg(feature = "tokio")]
+    pub async fn write_tokio_frame<W: tokio::io::AsyncWrite + Unpin>(
+        &self,
+        id: u32,
+        body: &[u8],
+        writer: &mut W,
+    ) -> Result<(), FrameError> {
+        let mut buffer = Vec::new();
+        self.encode_frame(id, body, &mut buffer)?;
+        tokio::io::AsyncWriteExt::write_all(writer, &buffer).await.map_err(Into::into)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// A packet ID and its encoded body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame<'a> {
+    id: u32,
+    data: Cow<'a, [u8]>,
+    body: usize,
+}
+
+impl<'a> Frame<'a> {
+    /// Create a new [`Frame`] from a packet ID and an encoded body.
+    #[inline]
+    #[must_use]
+    pub fn new(id: u32, body: impl Into<Cow<'a, [u8]>>) -> Self {
+        Self { id, data: body.into(), body: 0 }
+    }
+
+    /// Get the packet ID.
+    #[inline]
+    #[must_use]
+    pub const fn id(&self) -> u32 { self.id }
+
+    /// Get the encoded packet body.
+    #[inline]
+    #[must_use]
+    pub fn body(&self) -> &[u8] { &self.data[self.body..] }
+
+    /// Returns `true` if the body borrows from the input.
+    ///
+    /// Compressed packets are always owned.
+    #[inline]
+    #[must_use]
+    pub const fn is_borrowed(&self) -> bool { matches!(self.data, Cow::Borrowed(_)) }
+
+    /// Convert this frame into one that owns its body.
+    #[must_use]
+    pub fn into_owned(self) -> Frame<'static> {
+        Frame { id: self.id, data: Cow::Owned(self.data.into_owned()), body: self.body }
+    }
+
+    /// Deserialize the packet body, which must be consumed entirely.
+    ///
+    /// The value may borrow from the frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if deserialization fails,
+    /// or if bytes are left over after the body.
+    pub fn decode<'facet, T: Deserializable<'facet>>(
+        &'facet self,
+    ) -> Result<T, FDError<DeserializeError>> {
+        let (value, remaining) = crate::from_slice_borrowed::<T>(self.body())?;
+        if remaining.is_empty() {
+            Ok(value)
+        } else {
+            let error = FrameError::new(FrameErrorKind::TrailingBytes(remaining.len()));
+            Err(FDError::Parser(error.into()))
+        }
+    }
+
+    /// Decode the frame into one of a set of packets.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the packet ID is unknown or the body is invalid.
+    #[inline]
+    pub fn decode_packet<'de, S: PacketSet<'de>>(&'de self) -> Result<S, PacketError> {
+        S::decode_packet(self.id, self.body())
+    }
+
+    /// Decode the frame into one of a set of packets in a protocol version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the packet ID is unknown in the version
+    /// or the body is invalid.
+    #[inline]
+    pub fn decode_packet_versioned<'de, S: PacketSet<'de>>(
+        &'de self,
+        version: ProtocolVersion,
+    ) -> Result<S, PacketError> {
+        S::decode_packet_versioned(version, self.id, self.body())
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Add the `index`th byte (starting from `1`) to a frame length,
+/// returning the length once the last byte has been read.
+fn length_byte(length: &mut usize, index: usize, byte: u8) -> Result<Option<usize>, FrameError> {
+    *length |= usize::from(byte & 0x7F) << (7 * (index - 1));
+    if byte & 0x80 == 0 {
+        Ok(Some(*length))
+    } else if index == LENGTH_BYTES {
+        Err(FrameError::new(FrameErrorKind::InvalidVarInt))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Read a `VarInt` from the start of a complete payload.
+fn read_var(input: &[u8]) -> Result<(u32, usize), FrameError> {
+    let mut value = 0;
+    for (index, &byte) in input.iter().take(5).enumerate() {
+        value |= u32::from(byte & 0x7F) << (7 * index);
+        if byte & 0x80 == 0 {
+            return Ok((value, index + 1));
+        }
+    }
+    Err(FrameError::new(FrameErrorKind::InvalidVarInt))
+}
+
+/// Encode a `VarInt`, returning the bytes and how many are used.
+#[expect(clippy::cast_possible_truncation, reason = "Values are masked")]
+const fn var_bytes(mut value: u32) -> ([u8; 5], usize) {
+    let mut bytes = [0; 5];
+    let mut len = 0;
+    loop {
+        if value < 0x80 {
+            bytes[len] = value as u8;
+            return (bytes, len + 1);
+        }
+        bytes[len] = (value as u8 & 0x7F) | 0x80;
+        value >>= 7;
+        len += 1;
+    }
+}
+
+/// Write a length-prefixed frame made of the given parts.
+fn write_frame<B: SerializeBuffer + ?Sized>(
+    buffer: &mut B,
+    parts: &[&[u8]],
+) -> Result<(), FrameError> {
+    let length: usize = parts.iter().map(|part| part.len()).sum();
+    if length > PacketCodec::MAX_FRAME_LENGTH {
+        return Err(FrameError::new(FrameErrorKind::FrameTooLarge(length)));
+    }
+
+    #[expect(clippy::cast_possible_truncation, reason = "Checked against the maximum")]
+    let (len_bytes, len_len) = var_bytes(length as u32);
+    if core::iter::once(&len_bytes[..len_len])
+        .chain(parts.iter().copied())
+        .all(|part| buffer.extend_buffer(part))
+    {
+        Ok(())
+    } else {
+        Err(FrameError::new(FrameErrorKind::BufferError))
+    }
+}
+
+/// Compress a packet ID and body using zlib.
+#[cfg(feature = "compression")]
+fn compress(id: &[u8], body: &[u8]) -> Result<Vec<u8>, FrameError> {
+    use std::io::Write;
+
+    let mut encoder = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(id)?;
+    encoder.write_all(body)?;
+    encoder.finish().map_err(Into::into)
+}
+
+/// Decompress a packet, reading at most one byte past its declared length.
+#[cfg(feature = "compression")]
+fn decompress(input: &[u8], expected: usize) -> Result<Vec<u8>, FrameError> {
+    use std::io::Read;
+
+    let mut data = Vec::with_capacity(expected);
+    flate2::read::ZlibDecoder::new(input)
+        .take(expected as u64 + 1)
+        .read_to_end(&mut data)
+        .map_err(|_| FrameError::new(FrameErrorKind::InvalidCompression))?;
+
+    if data.len() == expected {
+        Ok(data)
+    } else {
+        Err(FrameError::new(FrameErrorKind::LengthMismatch { expected, found: data.len() }))
+    }
+}
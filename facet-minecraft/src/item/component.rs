@@ -0,0 +1,959 @@
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use super::{ItemError, ItemErrorKind, ItemStack, OptionalItemStack, read_kind, write_kind};
+use crate::{
+    codec::{McReader, McWriter},
+    deserialize::DeserializeError,
+    nbt::{NbtCompound, NbtValue},
+    packet::ProtocolVersion,
+    serialize::SerializeError,
+    text::{TextComponent, TextEncoding},
+    types::Identifier,
+};
+
+/// Define [`DataComponentKind`] from its variants and names.
+macro_rules! component_kinds {
+    ($($variant:ident => $name:literal,)*) => {
+        /// The type of a [`DataComponent`], as registered in
+        /// `data_component_type`.
+        ///
+        /// Type IDs depend on the protocol version,
+        /// see [`DataComponentKind::table`].
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[expect(missing_docs, reason = "Variants are named after their components")]
+        pub enum DataComponentKind {
+            $($variant,)*
+        }
+
+        impl DataComponentKind {
+            /// Every type of data component in any protocol version.
+            pub const ALL: &'static [Self] = &[$(Self::$variant,)*];
+
+            /// Get the name of this component type, without the namespace.
+            #[must_use]
+            pub const fn name(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $name,)*
+                }
+            }
+        }
+    };
+}
+
+component_kinds! {
+    CustomData => "custom_data",
+    MaxStackSize => "max_stack_size",
+    MaxDamage => "max_damage",
+    Damage => "damage",
+    Unbreakable => "unbreakable",
+    CustomName => "custom_name",
+    ItemName => "item_name",
+    ItemModel => "item_model",
+    Lore => "lore",
+    Rarity => "rarity",
+    Enchantments => "enchantments",
+    CanPlaceOn => "can_place_on",
+    CanBreak => "can_break",
+    AttributeModifiers => "attribute_modifiers",
+    CustomModelData => "custom_model_data",
+    HideAdditionalTooltip => "hide_additional_tooltip",
+    HideTooltip => "hide_tooltip",
+    TooltipDisplay => "tooltip_display",
+    RepairCost => "repair_cost",
+    CreativeSlotLock => "creative_slot_lock",
+    EnchantmentGlintOverride => "enchantment_glint_override",
+    IntangibleProjectile => "intangible_projectile",
+    Food => "food",
+    Consumable => "consumable",
+    UseRemainder => "use_remainder",
+    UseCooldown => "use_cooldown",
+    FireResistant => "fire_resistant",
+    DamageResistant => "damage_resistant",
+    Tool => "tool",
+    Weapon => "weapon",
+    Enchantable => "enchantable",
+    Equippable => "equippable",
+    Repairable => "repairable",
+    Glider => "glider",
+    TooltipStyle => "tooltip_style",
+    DeathProtection => "death_protection",
+    BlocksAttacks => "blocks_attacks",
+    StoredEnchantments => "stored_enchantments",
+    DyedColor => "dyed_color",
+    MapColor => "map_color",
+    MapId => "map_id",
+    MapDecorations => "map_decorations",
+    MapPostProcessing => "map_post_processing",
+    ChargedProjectiles => "charged_projectiles",
+    BundleContents => "bundle_contents",
+    PotionContents => "potion_contents",
+    PotionDurationScale => "potion_duration_scale",
+    SuspiciousStewEffects => "suspicious_stew_effects",
+    WritableBookContent => "writable_book_content",
+    WrittenBookContent => "written_book_content",
+    Trim => "trim",
+    DebugStickState => "debug_stick_state",
+    EntityData => "entity_data",
+    BucketEntityData => "bucket_entity_data",
+    BlockEntityData => "block_entity_data",
+    Instrument => "instrument",
+    ProvidesTrimMaterial => "provides_trim_material",
+    OminousBottleAmplifier => "ominous_bottle_amplifier",
+    JukeboxPlayable => "jukebox_playable",
+    ProvidesBannerPatterns => "provides_banner_patterns",
+    Recipes => "recipes",
+    LodestoneTracker => "lodestone_tracker",
+    FireworkExplosion => "firework_explosion",
+    Fireworks => "fireworks",
+    Profile => "profile",
+    NoteBlockSound => "note_block_sound",
+    BannerPatterns => "banner_patterns",
+    BaseColor => "base_color",
+    PotDecorations => "pot_decorations",
+    Container => "container",
+    BlockState => "block_state",
+    Bees => "bees",
+    Lock => "lock",
+    ContainerLoot => "container_loot",
+    BreakSound => "break_sound",
+    VillagerVariant => "villager/variant",
+    WolfVariant => "wolf/variant",
+    WolfSoundVariant => "wolf/sound_variant",
+    WolfCollar => "wolf/collar",
+    FoxVariant => "fox/variant",
+    SalmonSize => "salmon/size",
+    ParrotVariant => "parrot/variant",
+    TropicalFishPattern => "tropical_fish/pattern",
+    TropicalFishBaseColor => "tropical_fish/base_color",
+    TropicalFishPatternColor => "tropical_fish/pattern_color",
+    MooshroomVariant => "mooshroom/variant",
+    RabbitVariant => "rabbit/variant",
+    PigVariant => "pig/variant",
+    CowVariant => "cow/variant",
+    ChickenVariant => "chicken/variant",
+    FrogVariant => "frog/variant",
+    HorseVariant => "horse/variant",
+    PaintingVariant => "painting/variant",
+    LlamaVariant => "llama/variant",
+    AxolotlVariant => "axolotl/variant",
+    CatVariant => "cat/variant",
+    CatCollar => "cat/collar",
+    SheepColor => "sheep/color",
+    ShulkerColor => "shulker/color",
+}
+
+impl DataComponentKind {
+    /// The types of 1.20.5 and 1.20.6, ordered by ID.
+    const V1_20_5: &[Self] = &[
+        Self::CustomData,
+        Self::MaxStackSize,
+        Self::MaxDamage,
+        Self::Damage,
+        Self::Unbreakable,
+        Self::CustomName,
+        Self::ItemName,
+        Self::Lore,
+        Self::Rarity,
+        Self::Enchantments,
+        Self::CanPlaceOn,
+        Self::CanBreak,
+        Self::AttributeModifiers,
+        Self::CustomModelData,
+        Self::HideAdditionalTooltip,
+        Self::HideTooltip,
+        Self::RepairCost,
+        Self::CreativeSlotLock,
+        Self::EnchantmentGlintOverride,
+        Self::IntangibleProjectile,
+        Self::Food,
+        Self::FireResistant,
+        Self::Tool,
+        Self::StoredEnchantments,
+        Self::DyedColor,
+        Self::MapColor,
+        Self::MapId,
+        Self::MapDecorations,
+        Self::MapPostProcessing,
+        Self::ChargedProjectiles,
+        Self::BundleContents,
+        Self::PotionContents,
+        Self::SuspiciousStewEffects,
+        Self::WritableBookContent,
+        Self::WrittenBookContent,
+        Self::Trim,
+        Self::DebugStickState,
+        Self::EntityData,
+        Self::BucketEntityData,
+        Self::BlockEntityData,
+        Self::Instrument,
+        Self::OminousBottleAmplifier,
+        Self::Recipes,
+        Self::LodestoneTracker,
+        Self::FireworkExplosion,
+        Self::Fireworks,
+        Self::Profile,
+        Self::NoteBlockSound,
+        Self::BannerPatterns,
+        Self::BaseColor,
+        Self::PotDecorations,
+        Self::Container,
+        Self::BlockState,
+        Self::Bees,
+        Self::Lock,
+        Self::ContainerLoot,
+    ];
+    /// The types of 1.21 and 1.21.1, ordered by ID.
+    const V1_21: &[Self] = &[
+        Self::CustomData,
+        Self::MaxStackSize,
+        Self::MaxDamage,
+        Self::Damage,
+        Self::Unbreakable,
+        Self::CustomName,
+        Self::ItemName,
+        Self::Lore,
+        Self::Rarity,
+        Self::Enchantments,
+        Self::CanPlaceOn,
+        Self::CanBreak,
+        Self::AttributeModifiers,
+        Self::CustomModelData,
+        Self::HideAdditionalTooltip,
+        Self::HideTooltip,
+        Self::RepairCost,
+        Self::CreativeSlotLock,
+        Self::EnchantmentGlintOverride,
+        Self::IntangibleProjectile,
+        Self::Food,
+        Self::FireResistant,
+        Self::Tool,
+        Self::StoredEnchantments,
+        Self::DyedColor,
+        Self::MapColor,
+        Self::MapId,
+        Self::MapDecorations,
+        Self::MapPostProcessing,
+        Self::ChargedProjectiles,
+        Self::BundleContents,
+        Self::PotionContents,
+        Self::SuspiciousStewEffects,
+        Self::WritableBookContent,
+        Self::WrittenBookContent,
+        Self::Trim,
+        Self::DebugStickState,
+        Self::EntityData,
+        Self::BucketEntityData,
+        Self::BlockEntityData,
+        Self::Instrument,
+        Self::OminousBottleAmplifier,
+        Self::JukeboxPlayable,
+        Self::Recipes,
+        Self::LodestoneTracker,
+        Self::FireworkExplosion,
+        Self::Fireworks,
+        Self::Profile,
+        Self::NoteBlockSound,
+        Self::BannerPatterns,
+        Self::BaseColor,
+        Self::PotDecorations,
+        Self::Container,
+        Self::BlockState,
+        Self::Bees,
+        Self::Lock,
+        Self::ContainerLoot,
+    ];
+    /// The types of 1.21.2 until 1.21.4, ordered by ID.
+    const V1_21_2: &[Self] = &[
+        Self::CustomData,
+        Self::MaxStackSize,
+        Self::MaxDamage,
+        Self::Damage,
+        Self::Unbreakable,
+        Self::CustomName,
+        Self::ItemName,
+        Self::ItemModel,
+        Self::Lore,
+        Self::Rarity,
+        Self::Enchantments,
+        Self::CanPlaceOn,
+        Self::CanBreak,
+        Self::AttributeModifiers,
+        Self::CustomModelData,
+        Self::HideAdditionalTooltip,
+        Self::HideTooltip,
+        Self::RepairCost,
+        Self::CreativeSlotLock,
+        Self::EnchantmentGlintOverride,
+        Self::IntangibleProjectile,
+        Self::Food,
+        Self::Consumable,
+        Self::UseRemainder,
+        Self::UseCooldown,
+        Self::DamageResistant,
+        Self::Tool,
+        Self::Enchantable,
+        Self::Equippable,
+        Self::Repairable,
+        Self::Glider,
+        Self::TooltipStyle,
+        Self::DeathProtection,
+        Self::StoredEnchantments,
+        Self::DyedColor,
+        Self::MapColor,
+        Self::MapId,
+        Self::MapDecorations,
+        Self::MapPostProcessing,
+        Self::ChargedProjectiles,
+        Self::BundleContents,
+        Self::PotionContents,
+        Self::SuspiciousStewEffects,
+        Self::WritableBookContent,
+        Self::WrittenBookContent,
+        Self::Trim,
+        Self::DebugStickState,
+        Self::EntityData,
+        Self::BucketEntityData,
+        Self::BlockEntityData,
+        Self::Instrument,
+        Self::OminousBottleAmplifier,
+        Self::JukeboxPlayable,
+        Self::Recipes,
+        Self::LodestoneTracker,
+        Self::FireworkExplosion,
+        Self::Fireworks,
+        Self::Profile,
+        Self::NoteBlockSound,
+        Self::BannerPatterns,
+        Self::BaseColor,
+        Self::PotDecorations,
+        Self::Container,
+        Self::BlockState,
+        Self::Bees,
+        Self::Lock,
+        Self::ContainerLoot,
+    ];
+    /// The types of 1.21.5 and later, ordered by ID.
+    const V1_21_5: &[Self] = &[
+        Self::CustomData,
+        Self::MaxStackSize,
+        Self::MaxDamage,
+        Self::Damage,
+        Self::Unbreakable,
+        Self::CustomName,
+        Self::ItemName,
+        Self::ItemModel,
+        Self::Lore,
+        Self::Rarity,
+        Self::Enchantments,
+        Self::CanPlaceOn,
+        Self::CanBreak,
+        Self::AttributeModifiers,
+        Self::CustomModelData,
+        Self::TooltipDisplay,
+        Self::RepairCost,
+        Self::CreativeSlotLock,
+        Self::EnchantmentGlintOverride,
+        Self::IntangibleProjectile,
+        Self::Food,
+        Self::Consumable,
+        Self::UseRemainder,
+        Self::UseCooldown,
+        Self::DamageResistant,
+        Self::Tool,
+        Self::Weapon,
+        Self::Enchantable,
+        Self::Equippable,
+        Self::Repairable,
+        Self::Glider,
+        Self::TooltipStyle,
+        Self::DeathProtection,
+        Self::BlocksAttacks,
+        Self::StoredEnchantments,
+        Self::DyedColor,
+        Self::MapColor,
+        Self::MapId,
+        Self::MapDecorations,
+        Self::MapPostProcessing,
+        Self::ChargedProjectiles,
+        Self::BundleContents,
+        Self::PotionContents,
+        Self::PotionDurationScale,
+        Self::SuspiciousStewEffects,
+        Self::WritableBookContent,
+        Self::WrittenBookContent,
+        Self::Trim,
+        Self::DebugStickState,
+        Self::EntityData,
+        Self::BucketEntityData,
+        Self::BlockEntityData,
+        Self::Instrument,
+        Self::ProvidesTrimMaterial,
+        Self::OminousBottleAmplifier,
+        Self::JukeboxPlayable,
+        Self::ProvidesBannerPatterns,
+        Self::Recipes,
+        Self::LodestoneTracker,
+        Self::FireworkExplosion,
+        Self::Fireworks,
+        Self::Profile,
+        Self::NoteBlockSound,
+        Self::BannerPatterns,
+        Self::BaseColor,
+        Self::PotDecorations,
+        Self::Container,
+        Self::BlockState,
+        Self::Bees,
+        Self::Lock,
+        Self::ContainerLoot,
+        Self::BreakSound,
+        Self::VillagerVariant,
+        Self::WolfVariant,
+        Self::WolfSoundVariant,
+        Self::WolfCollar,
+        Self::FoxVariant,
+        Self::SalmonSize,
+        Self::ParrotVariant,
+        Self::TropicalFishPattern,
+        Self::TropicalFishBaseColor,
+        Self::TropicalFishPatternColor,
+        Self::MooshroomVariant,
+        Self::RabbitVariant,
+        Self::PigVariant,
+        Self::CowVariant,
+        Self::ChickenVariant,
+        Self::FrogVariant,
+        Self::HorseVariant,
+        Self::PaintingVariant,
+        Self::LlamaVariant,
+        Self::AxolotlVariant,
+        Self::CatVariant,
+        Self::CatCollar,
+        Self::SheepColor,
+        Self::ShulkerColor,
+    ];
+
+    /// Get the types of a protocol version, ordered by ID.
+    ///
+    /// Versions before 1.20.5 have no data components.
+    /// Snapshots use the table of the latest release.
+    #[must_use]
+    pub fn table(version: ProtocolVersion) -> &'static [Self] {
+        if version >= ProtocolVersion::V1_21_5 {
+            Self::V1_21_5
+        } else if version >= ProtocolVersion::V1_21_2 {
+            Self::V1_21_2
+        } else if version >= ProtocolVersion::V1_21 {
+            Self::V1_21
+        } else if version >= ProtocolVersion::V1_20_5 {
+            Self::V1_20_5
+        } else {
+            &[]
+        }
+    }
+
+    /// Get the component type with a registry ID in a protocol version.
+    #[must_use]
+    pub fn from_id(id: u32, version: ProtocolVersion) -> Option<Self> {
+        Self::table(version).get(id as usize).copied()
+    }
+
+    /// Get the registry ID of this component type in a protocol version,
+    /// or `None` if the type does not exist in that version.
+    #[must_use]
+    pub fn id(self, version: ProtocolVersion) -> Option<u32> {
+        let index = Self::table(version).iter().position(|kind| *kind == self)?;
+        u32::try_from(index).ok()
+    }
+
+    /// Get the component type with a name, with or without the
+    /// `minecraft` namespace.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.strip_prefix("minecraft:").unwrap_or(name);
+        Self::ALL.iter().copied().find(|kind| kind.name() == name)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// The rarity of an item, which colors its name.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rarity {
+    /// White.
+    #[default]
+    Common,
+    /// Yellow.
+    Uncommon,
+    /// Aqua.
+    Rare,
+    /// Light purple.
+    Epic,
+}
+
+impl Rarity {
+    /// Get the rarity with an ID.
+    #[must_use]
+    pub const fn from_id(id: u32) -> Option<Self> {
+        match id {
+            0 => Some(Self::Common),
+            1 => Some(Self::Uncommon),
+            2 => Some(Self::Rare),
+            3 => Some(Self::Epic),
+            _ => None,
+        }
+    }
+
+    /// Get the name of this rarity.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Common => "common",
+            Self::Uncommon => "uncommon",
+            Self::Rare => "rare",
+            Self::Epic => "epic",
+        }
+    }
+}
+
+/// Enchantments and their levels.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Enchantments {
+    /// The enchantment registry IDs and levels.
+    pub levels: Vec<(u32, u32)>,
+    /// Whether the enchantments are shown in the tooltip.
+    ///
+    /// Only sent before 1.21.5, and `true` when read for later versions.
+    pub show_in_tooltip: bool,
+}
+
+/// Values used by the item model.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CustomModelData {
+    /// Values for `range_dispatch` properties.
+    pub floats: Vec<f32>,
+    /// Values for `condition` properties.
+    pub flags: Vec<bool>,
+    /// Values for `select` properties.
+    pub strings: Vec<String>,
+    /// Values for tint sources.
+    pub colors: Vec<i32>,
+}
+
+/// The effect of eating an item.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Food {
+    /// The food points restored.
+    pub nutrition: u32,
+    /// The saturation restored.
+    pub saturation: f32,
+    /// Whether the item can be eaten with a full hunger bar.
+    pub can_always_eat: bool,
+}
+
+/// A typed component of an [`ItemStack`].
+///
+/// Components are encoded for the protocol version of the writer or reader.
+/// Component types with more complex data, such as
+/// [`DataComponentKind::AttributeModifiers`], are not supported.
+///
+/// Since 1.21.5, `show_in_tooltip` flags are no longer sent,
+/// and are `true` when read. Hidden components are listed in
+/// [`DataComponent::TooltipDisplay`] instead.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataComponent {
+    /// Custom data, not used by the game.
+    CustomData(NbtCompound),
+    /// The maximum number of items in a stack.
+    MaxStackSize(u32),
+    /// The durability of the item.
+    MaxDamage(u32),
+    /// The damage taken by the item.
+    Damage(u32),
+    /// The item does not lose durability.
+    Unbreakable {
+        /// Whether this is shown in the tooltip, before 1.21.5.
+        show_in_tooltip: bool,
+    },
+    /// The name given to the item, such as by an anvil.
+    CustomName(TextComponent),
+    /// The default name of the item.
+    ItemName(TextComponent),
+    /// The item model to use.
+    ItemModel(Identifier<'static>),
+    /// Additional lines in the tooltip.
+    Lore(Vec<TextComponent>),
+    /// The rarity of the item.
+    Rarity(Rarity),
+    /// The enchantments on the item.
+    Enchantments(Enchantments),
+    /// Values used by the item model.
+    CustomModelData(CustomModelData),
+    /// Hide additional information in the tooltip.
+    HideAdditionalTooltip,
+    /// Hide the tooltip entirely.
+    HideTooltip,
+    /// Hide the tooltip, or parts of it, since 1.21.5.
+    TooltipDisplay {
+        /// Whether the tooltip is hidden entirely.
+        hide_tooltip: bool,
+        /// The component types hidden from the tooltip.
+        hidden_components: Vec<DataComponentKind>,
+    },
+    /// The experience cost of repairing the item in an anvil.
+    RepairCost(u32),
+    /// The item cannot be taken from the creative inventory.
+    CreativeSlotLock,
+    /// Override whether the item has an enchantment glint.
+    EnchantmentGlintOverride(bool),
+    /// The item can be eaten.
+    Food(Food),
+    /// The item left behind after using the item.
+    UseRemainder(Box<ItemStack>),
+    /// The cooldown after using the item.
+    UseCooldown {
+        /// The cooldown, in seconds.
+        seconds: f32,
+        /// The group sharing the cooldown, the item itself if `None`.
+        group: Option<Identifier<'static>>,
+    },
+    /// How well the item can be enchanted.
+    Enchantable(u32),
+    /// The item allows gliding when equipped.
+    Glider,
+    /// The tooltip style to use.
+    TooltipStyle(Identifier<'static>),
+    /// The enchantments stored in an enchanted book.
+    StoredEnchantments(Enchantments),
+    /// The color of dyed leather armor.
+    DyedColor {
+        /// The color, as `0xRRGGBB`.
+        rgb: i32,
+        /// Whether this is shown in the tooltip, before 1.21.5.
+        show_in_tooltip: bool,
+    },
+    /// The color of the markings on a map.
+    MapColor(i32),
+    /// The ID of a filled map.
+    MapId(u32),
+    /// Decorations shown on a map.
+    MapDecorations(NbtValue),
+    /// Pending processing of a map, such as locking or scaling.
+    MapPostProcessing(u32),
+    /// The projectiles loaded in a crossbow.
+    ChargedProjectiles(Vec<ItemStack>),
+    /// The items in a bundle.
+    BundleContents(Vec<ItemStack>),
+    /// The properties of a debug stick.
+    DebugStickState(NbtValue),
+    /// Data for the entity created from the item.
+    EntityData(NbtCompound),
+    /// Data for the entity released from a bucket.
+    BucketEntityData(NbtCompound),
+    /// Data for the block entity created from the item.
+    BlockEntityData(NbtCompound),
+    /// The amplifier of the effect given by an ominous bottle.
+    OminousBottleAmplifier(u32),
+    /// The recipes unlocked by a knowledge book.
+    Recipes(NbtValue),
+    /// The sound played by a note block with a player head.
+    NoteBlockSound(Identifier<'static>),
+    /// The base color of a banner or shield.
+    BaseColor(u32),
+    /// The item IDs of the sherds on a decorated pot.
+    PotDecorations(Vec<u32>),
+    /// The items in a container.
+    Container(Vec<OptionalItemStack>),
+    /// Block state properties applied when placing the block.
+    BlockState(Vec<(String, String)>),
+    /// The loot table of an unopened container.
+    ContainerLoot(NbtValue),
+}
+
+impl DataComponent {
+    /// Get the type of this component.
+    #[must_use]
+    pub const fn kind(&self) -> DataComponentKind {
+        match self {
+            Self::CustomData(_) => DataComponentKind::CustomData,
+            Self::MaxStackSize(_) => DataComponentKind::MaxStackSize,
+            Self::MaxDamage(_) => DataComponentKind::MaxDamage,
+            Self::Damage(_) => DataComponentKind::Damage,
+            Self::Unbreakable { .. } => DataComponentKind::Unbreakable,
+            Self::CustomName(_) => DataComponentKind::CustomName,
+            Self::ItemName(_) => DataComponentKind::ItemName,
+            Self::ItemModel(_) => DataComponentKind::ItemModel,
+            Self::Lore(_) => DataComponentKind::Lore,
+            Self::Rarity(_) => DataComponentKind::Rarity,
+            Self::Enchantments(_) => DataComponentKind::Enchantments,
+            Self::CustomModelData(_) => DataComponentKind::CustomModelData,
+            Self::HideAdditionalTooltip => DataComponentKind::HideAdditionalTooltip,
+            Self::HideTooltip => DataComponentKind::HideTooltip,
+            Self::TooltipDisplay { .. } => DataComponentKind::TooltipDisplay,
+            Self::RepairCost(_) => DataComponentKind::RepairCost,
+            Self::CreativeSlotLock => DataComponentKind::CreativeSlotLock,
+            Self::EnchantmentGlintOverride(_) => DataComponentKind::EnchantmentGlintOverride,
+            Self::Food(_) => DataComponentKind::Food,
+            Self::UseRemainder(_) => DataComponentKind::UseRemainder,
+            Self::UseCooldown { .. } => DataComponentKind::UseCooldown,
+            Self::Enchantable(_) => DataComponentKind::Enchantable,
+            Self::Glider => DataComponentKind::Glider,
+            Self::TooltipStyle(_) => DataComponentKind::TooltipStyle,
+            Self::StoredEnchantments(_) => DataComponentKind::StoredEnchantments,
+            Self::DyedColor { .. } => DataComponentKind::DyedColor,
+            Self::MapColor(_) => DataComponentKind::MapColor,
+            Self::MapId(_) => DataComponentKind::MapId,
+            Self::MapDecorations(_) => DataComponentKind::MapDecorations,
+            Self::MapPostProcessing(_) => DataComponentKind::MapPostProcessing,
+            Self::ChargedProjectiles(_) => DataComponentKind::ChargedProjectiles,
+            Self::BundleContents(_) => DataComponentKind::BundleContents,
+            Self::DebugStickState(_) => DataComponentKind::DebugStickState,
+            Self::EntityData(_) => DataComponentKind::EntityData,
+            Self::BucketEntityData(_) => DataComponentKind::BucketEntityData,
+            Self::BlockEntityData(_) => DataComponentKind::BlockEntityData,
+            Self::OminousBottleAmplifier(_) => DataComponentKind::OminousBottleAmplifier,
+            Self::Recipes(_) => DataComponentKind::Recipes,
+            Self::NoteBlockSound(_) => DataComponentKind::NoteBlockSound,
+            Self::BaseColor(_) => DataComponentKind::BaseColor,
+            Self::PotDecorations(_) => DataComponentKind::PotDecorations,
+            Self::Container(_) => DataComponentKind::Container,
+            Self::BlockState(_) => DataComponentKind::BlockState,
+            Self::ContainerLoot(_) => DataComponentKind::ContainerLoot,
+        }
+    }
+
+    /// Write the data of this component, without its type.
+    pub(super) fn encode_data(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        let version = writer.version();
+        let encoding = TextEncoding::for_protocol(version.protocol());
+        let tooltip_flags = version < ProtocolVersion::V1_21_5;
+        let unsupported =
+            || ItemError::new(ItemErrorKind::UnsupportedComponent { kind: self.kind(), version });
+
+        match self {
+            Self::CustomData(nbt)
+            | Self::EntityData(nbt)
+            | Self::BucketEntityData(nbt)
+            | Self::BlockEntityData(nbt) => writer.write(nbt),
+            Self::MapDecorations(nbt)
+            | Self::DebugStickState(nbt)
+            | Self::Recipes(nbt)
+            | Self::ContainerLoot(nbt) => writer.write(nbt),
+            Self::MaxStackSize(value)
+            | Self::MaxDamage(value)
+            | Self::Damage(value)
+            | Self::RepairCost(value)
+            | Self::Enchantable(value)
+            | Self::MapId(value)
+            | Self::MapPostProcessing(value)
+            | Self::OminousBottleAmplifier(value)
+            | Self::BaseColor(value) => writer.write_var_u32(*value),
+            Self::Unbreakable { show_in_tooltip: shown } if tooltip_flags => {
+                writer.write_bool(*shown)
+            }
+            Self::EnchantmentGlintOverride(value) => writer.write_bool(*value),
+            Self::CustomName(text) | Self::ItemName(text) => text.encode_with(writer, encoding),
+            Self::ItemModel(identifier)
+            | Self::TooltipStyle(identifier)
+            | Self::NoteBlockSound(identifier) => writer.write_identifier(identifier),
+            Self::Lore(lines) => {
+                write_list(writer, lines, |writer, line| line.encode_with(writer, encoding))
+            }
+            Self::Rarity(rarity) => writer.write_var_u32(*rarity as u32),
+            Self::Enchantments(enchantments) | Self::StoredEnchantments(enchantments) => {
+                write_list(writer, &enchantments.levels, |writer, (id, level)| {
+                    writer.write_var_u32(*id)?;
+                    writer.write_var_u32(*level)
+                })?;
+                if tooltip_flags { writer.write_bool(enchantments.show_in_tooltip) } else { Ok(()) }
+            }
+            Self::CustomModelData(_) if version < ProtocolVersion::V1_21_4 => {
+                Err(unsupported().into())
+            }
+            Self::CustomModelData(data) => {
+                write_list(writer, &data.floats, |writer, value| writer.write_f32(*value))?;
+                write_list(writer, &data.flags, |writer, value| writer.write_bool(*value))?;
+                write_list(writer, &data.strings, |writer, value| writer.write_str(value))?;
+                write_list(writer, &data.colors, |writer, value| {
+                    writer.write_u32(value.cast_unsigned())
+                })
+            }
+            Self::Unbreakable { .. }
+            | Self::HideAdditionalTooltip
+            | Self::HideTooltip
+            | Self::CreativeSlotLock
+            | Self::Glider => Ok(()),
+            Self::TooltipDisplay { hide_tooltip, hidden_components } => {
+                writer.write_bool(*hide_tooltip)?;
+                write_list(writer, hidden_components, |writer, kind| write_kind(writer, *kind))
+            }
+            Self::Food(_) if version < ProtocolVersion::V1_21_2 => Err(unsupported().into()),
+            Self::Food(food) => {
+                writer.write_var_u32(food.nutrition)?;
+                writer.write_f32(food.saturation)?;
+                writer.write_bool(food.can_always_eat)
+            }
+            Self::UseRemainder(stack) => stack.encode_stack(writer),
+            Self::UseCooldown { seconds, group } => {
+                writer.write_f32(*seconds)?;
+                writer.write_bool(group.is_some())?;
+                group.as_ref().map_or(Ok(()), |group| writer.write_identifier(group))
+            }
+            Self::DyedColor { rgb, show_in_tooltip: shown } => {
+                writer.write_u32(rgb.cast_unsigned())?;
+                if tooltip_flags { writer.write_bool(*shown) } else { Ok(()) }
+            }
+            Self::MapColor(rgb) => writer.write_u32(rgb.cast_unsigned()),
+            Self::ChargedProjectiles(stacks) | Self::BundleContents(stacks) => {
+                write_list(writer, stacks, |writer, stack| stack.encode_stack(writer))
+            }
+            Self::PotDecorations(items) => {
+                write_list(writer, items, |writer, item| writer.write_var_u32(*item))
+            }
+            Self::Container(stacks) => {
+                write_list(writer, stacks, |writer, stack| stack.encode_stack(writer))
+            }
+            Self::BlockState(properties) => {
+                write_list(writer, properties, |writer, (key, value)| {
+                    writer.write_str(key)?;
+                    writer.write_str(value)
+                })
+            }
+        }
+    }
+
+    /// Read the data of a component of the given type.
+    #[expect(clippy::too_many_lines, reason = "One arm per component type")]
+    pub(super) fn decode_data(
+        kind: DataComponentKind,
+        reader: &mut McReader<'_>,
+        depth: usize,
+    ) -> Result<Self, DeserializeError> {
+        type Kind = DataComponentKind;
+
+        let version = reader.version();
+        let encoding = TextEncoding::for_protocol(version.protocol());
+        let unsupported = || ItemError::new(ItemErrorKind::UnsupportedComponent { kind, version });
+        let show_in_tooltip = |reader: &mut McReader<'_>| {
+            if version < ProtocolVersion::V1_21_5 { reader.read_bool() } else { Ok(true) }
+        };
+        let text = |reader: &mut McReader<'_>| TextComponent::decode_with(reader, encoding);
+        let identifier =
+            |reader: &mut McReader<'_>| reader.read_identifier().map(Identifier::into_owned);
+        let enchantments = |reader: &mut McReader<'_>| {
+            Ok::<_, DeserializeError>(Enchantments {
+                levels: read_list(reader, |reader| {
+                    Ok((reader.read_var_u32()?, reader.read_var_u32()?))
+                })?,
+                show_in_tooltip: show_in_tooltip(reader)?,
+            })
+        };
+
+        Ok(match kind {
+            Kind::CustomData => Self::CustomData(reader.read()?),
+            Kind::MaxStackSize => Self::MaxStackSize(reader.read_var_u32()?),
+            Kind::MaxDamage => Self::MaxDamage(reader.read_var_u32()?),
+            Kind::Damage => Self::Damage(reader.read_var_u32()?),
+            Kind::Unbreakable => Self::Unbreakable { show_in_tooltip: show_in_tooltip(reader)? },
+            Kind::CustomName => Self::CustomName(text(reader)?),
+            Kind::ItemName => Self::ItemName(text(reader)?),
+            Kind::ItemModel => Self::ItemModel(identifier(reader)?),
+            Kind::Lore => Self::Lore(read_list(reader, text)?),
+            Kind::Rarity => {
+                let value = reader.read_var_u32()?;
+                Self::Rarity(Rarity::from_id(value).ok_or_else(|| {
+                    ItemError::new(ItemErrorKind::InvalidValue { component: kind, value })
+                })?)
+            }
+            Kind::Enchantments => Self::Enchantments(enchantments(reader)?),
+            Kind::CustomModelData if version < ProtocolVersion::V1_21_4 => {
+                return Err(unsupported().into());
+            }
+            Kind::CustomModelData => Self::CustomModelData(CustomModelData {
+                floats: read_list(reader, McReader::read_f32)?,
+                flags: read_list(reader, McReader::read_bool)?,
+                strings: read_list(reader, |reader| reader.read_str().map(String::from))?,
+                colors: read_list(reader, |reader| reader.read_u32().map(u32::cast_signed))?,
+            }),
+            Kind::HideAdditionalTooltip => Self::HideAdditionalTooltip,
+            Kind::HideTooltip => Self::HideTooltip,
+            Kind::TooltipDisplay => Self::TooltipDisplay {
+                hide_tooltip: reader.read_bool()?,
+                hidden_components: read_list(reader, read_kind)?,
+            },
+            Kind::RepairCost => Self::RepairCost(reader.read_var_u32()?),
+            Kind::CreativeSlotLock => Self::CreativeSlotLock,
+            Kind::EnchantmentGlintOverride => Self::EnchantmentGlintOverride(reader.read_bool()?),
+            Kind::Food if version < ProtocolVersion::V1_21_2 => return Err(unsupported().into()),
+            Kind::Food => Self::Food(Food {
+                nutrition: reader.read_var_u32()?,
+                saturation: reader.read_f32()?,
+                can_always_eat: reader.read_bool()?,
+            }),
+            Kind::UseRemainder => {
+                Self::UseRemainder(Box::new(ItemStack::decode_stack(reader, depth + 1)?))
+            }
+            Kind::UseCooldown => Self::UseCooldown {
+                seconds: reader.read_f32()?,
+                group: if reader.read_bool()? { Some(identifier(reader)?) } else { None },
+            },
+            Kind::Enchantable => Self::Enchantable(reader.read_var_u32()?),
+            Kind::Glider => Self::Glider,
+            Kind::TooltipStyle => Self::TooltipStyle(identifier(reader)?),
+            Kind::StoredEnchantments => Self::StoredEnchantments(enchantments(reader)?),
+            Kind::DyedColor => Self::DyedColor {
+                rgb: reader.read_u32()?.cast_signed(),
+                show_in_tooltip: show_in_tooltip(reader)?,
+            },
+            Kind::MapColor => Self::MapColor(reader.read_u32()?.cast_signed()),
+            Kind::MapId => Self::MapId(reader.read_var_u32()?),
+            Kind::MapDecorations => Self::MapDecorations(reader.read()?),
+            Kind::MapPostProcessing => Self::MapPostProcessing(reader.read_var_u32()?),
+            Kind::ChargedProjectiles => Self::ChargedProjectiles(read_list(reader, |reader| {
+                ItemStack::decode_stack(reader, depth + 1)
+            })?),
+            Kind::BundleContents => Self::BundleContents(read_list(reader, |reader| {
+                ItemStack::decode_stack(reader, depth + 1)
+            })?),
+            Kind::DebugStickState => Self::DebugStickState(reader.read()?),
+            Kind::EntityData => Self::EntityData(reader.read()?),
+            Kind::BucketEntityData => Self::BucketEntityData(reader.read()?),
+            Kind::BlockEntityData => Self::BlockEntityData(reader.read()?),
+            Kind::OminousBottleAmplifier => Self::OminousBottleAmplifier(reader.read_var_u32()?),
+            Kind::Recipes => Self::Recipes(reader.read()?),
+            Kind::NoteBlockSound => Self::NoteBlockSound(identifier(reader)?),
+            Kind::BaseColor => Self::BaseColor(reader.read_var_u32()?),
+            Kind::PotDecorations => {
+                Self::PotDecorations(read_list(reader, McReader::read_var_u32)?)
+            }
+            Kind::Container => Self::Container(read_list(reader, |reader| {
+                OptionalItemStack::decode_stack(reader, depth + 1)
+            })?),
+            Kind::BlockState => Self::BlockState(read_list(reader, |reader| {
+                Ok((String::from(reader.read_str()?), String::from(reader.read_str()?)))
+            })?),
+            Kind::ContainerLoot => Self::ContainerLoot(reader.read()?),
+            _ => return Err(unsupported().into()),
+        })
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Write a `VarInt`-prefixed list of values.
+pub(super) fn write_list<T>(
+    writer: &mut McWriter<'_>,
+    values: &[T],
+    mut write: impl FnMut(&mut McWriter<'_>, &T) -> Result<(), SerializeError>,
+) -> Result<(), SerializeError> {
+    writer.write_var_u32(u32::try_from(values.len()).unwrap_or(u32::MAX))?;
+    values.iter().try_for_each(|value| write(writer, value))
+}
+
+/// Read a `VarInt`-prefixed list of values.
+pub(super) fn read_list<'de, T>(
+    reader: &mut McReader<'de>,
+    mut read: impl FnMut(&mut McReader<'de>) -> Result<T, DeserializeError>,
+) -> Result<Vec<T>, DeserializeError> {
+    let len = reader.read_var_u32()? as usize;
+    (0..len).map(|_| read(reader)).collect()
+}
@@ -0,0 +1,90 @@
+use core::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+use super::DataComponentKind;
+use crate::packet::ProtocolVersion;
+
+/// An error that occurred while encoding or decoding an item stack.
+#[derive(Debug)]
+pub struct ItemError {
+    kind: ItemErrorKind,
+}
+
+impl ItemError {
+    /// Create a new [`ItemError`].
+    #[inline]
+    #[must_use]
+    pub const fn new(kind: ItemErrorKind) -> Self { Self { kind } }
+
+    /// Get the kind of item error.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> &ItemErrorKind { &self.kind }
+}
+
+/// The type of item error.
+#[derive(Debug)]
+pub enum ItemErrorKind {
+    /// An [`ItemStack`](super::ItemStack) had a count of zero or less.
+    EmptyStack,
+    /// Item stacks with data components, or their hashed form,
+    /// do not exist in the protocol version.
+    UnsupportedVersion(ProtocolVersion),
+    /// A component type ID was not recognized for the protocol version.
+    UnknownComponent {
+        /// The type ID.
+        id: u32,
+        /// The protocol version.
+        version: ProtocolVersion,
+    },
+    /// A component type does not exist in the protocol version,
+    /// or its data cannot be encoded or decoded.
+    UnsupportedComponent {
+        /// The component type.
+        kind: DataComponentKind,
+        /// The protocol version.
+        version: ProtocolVersion,
+    },
+    /// A component type cannot be hashed for a
+    /// [`HashedItemStack`](super::HashedItemStack).
+    UnhashableComponent(DataComponentKind),
+    /// An enum value inside a component was not recognized.
+    InvalidValue {
+        /// The component type.
+        component: DataComponentKind,
+        /// The value.
+        value: u32,
+    },
+    /// Item stacks were nested deeper than
+    /// [`ItemStack::MAX_DEPTH`](super::ItemStack::MAX_DEPTH).
+    DepthLimit,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Error for ItemError {}
+impl Display for ItemError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ItemErrorKind::EmptyStack => f.write_str("item stack is empty"),
+            ItemErrorKind::UnsupportedVersion(version) => {
+                write!(f, "item stacks are not supported in version {version}")
+            }
+            ItemErrorKind::UnknownComponent { id, version } => {
+                write!(f, "unknown data component type {id} in version {version}")
+            }
+            ItemErrorKind::UnsupportedComponent { kind, version } => {
+                write!(f, "unsupported data component `{}` in version {version}", kind.name())
+            }
+            ItemErrorKind::UnhashableComponent(kind) => {
+                write!(f, "data component `{}` cannot be hashed", kind.name())
+            }
+            ItemErrorKind::InvalidValue { component, value } => {
+                write!(f, "invalid value {value} in data component `{}`", component.name())
+            }
+            ItemErrorKind::DepthLimit => f.write_str("maximum item nesting depth exceeded"),
+        }
+    }
+}
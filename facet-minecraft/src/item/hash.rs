@@ -0,0 +1,524 @@
+use alloc::{format, vec::Vec};
+
+use facet::{Facet, Shape};
+
+use super::{
+    ComponentPatch, DataComponent, DataComponentKind, ItemError, ItemErrorKind, ItemStack,
+    OptionalItemStack,
+    component::{read_list, write_list},
+    read_kind, write_kind,
+};
+use crate::{
+    codec::{McCodec, McReader, McWriter, codec_shape},
+    deserialize::DeserializeError,
+    nbt::{NbtCompound, NbtValue},
+    packet::ProtocolVersion,
+    serialize::SerializeError,
+    text::{TextComponent, TextEncoding},
+    types::Identifier,
+};
+
+/// An [`ItemStack`] with hashes in place of its component data,
+/// sent by clients when clicking in a container since 1.21.5.
+///
+/// Always sent as present, use an [`OptionalHashedItemStack`] for slots that
+/// may be empty.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HashedItemStack {
+    /// The ID of the item in the `item` registry.
+    pub item: u32,
+    /// The number of items.
+    pub count: u32,
+    /// The hashed changes to the default components of the item.
+    pub components: HashedComponentPatch,
+}
+
+impl HashedItemStack {
+    /// Hash the components of an [`ItemStack`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a component cannot be hashed.
+    pub fn new(stack: &ItemStack) -> Result<Self, ItemError> {
+        Ok(Self {
+            item: stack.item,
+            count: stack.count,
+            components: HashedComponentPatch::new(&stack.components)?,
+        })
+    }
+
+    /// Returns `true` if this is the hashed form of an [`ItemStack`].
+    ///
+    /// Stacks with components that cannot be hashed never match.
+    #[must_use]
+    pub fn matches(&self, stack: &ItemStack) -> bool {
+        self.item == stack.item
+            && self.count == stack.count
+            && self.components.matches(&stack.components)
+    }
+
+    /// Write the stack, without the presence flag.
+    fn encode_stack(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        writer.write_var_u32(self.item)?;
+        writer.write_var_u32(self.count)?;
+        write_list(writer, &self.components.added, |writer, (kind, hash)| {
+            write_kind(writer, *kind)?;
+            writer.write_u32(*hash)
+        })?;
+        write_list(writer, &self.components.removed, |writer, kind| write_kind(writer, *kind))
+    }
+
+    /// Read the stack, without the presence flag.
+    fn decode_stack(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        Ok(Self {
+            item: reader.read_var_u32()?,
+            count: reader.read_var_u32()?,
+            components: HashedComponentPatch {
+                added: read_list(reader, |reader| Ok((read_kind(reader)?, reader.read_u32()?)))?,
+                removed: read_list(reader, read_kind)?,
+            },
+        })
+    }
+}
+
+impl McCodec for HashedItemStack {
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        check_version(writer.version())?;
+        writer.write_bool(true)?;
+        self.encode_stack(writer)
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        OptionalHashedItemStack::decode(reader)?
+            .0
+            .ok_or_else(|| ItemError::new(ItemErrorKind::EmptyStack).into())
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for HashedItemStack {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("HashedItemStack").build() };
+}
+
+/// A [`HashedItemStack`] that may be empty, sent with a presence flag.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct OptionalHashedItemStack(pub Option<HashedItemStack>);
+
+impl OptionalHashedItemStack {
+    /// An empty slot.
+    pub const EMPTY: Self = Self(None);
+
+    /// Hash the components of an [`OptionalItemStack`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a component cannot be hashed.
+    pub fn new(stack: &OptionalItemStack) -> Result<Self, ItemError> {
+        stack.0.as_ref().map(HashedItemStack::new).transpose().map(Self)
+    }
+
+    /// Returns `true` if the slot is empty.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool { self.0.is_none() }
+
+    /// Returns `true` if this is the hashed form of an [`OptionalItemStack`].
+    #[must_use]
+    pub fn matches(&self, stack: &OptionalItemStack) -> bool {
+        match (&self.0, &stack.0) {
+            (Some(hashed), Some(stack)) => hashed.matches(stack),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl From<HashedItemStack> for OptionalHashedItemStack {
+    #[inline]
+    fn from(stack: HashedItemStack) -> Self { Self(Some(stack)) }
+}
+
+impl McCodec for OptionalHashedItemStack {
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        check_version(writer.version())?;
+        match &self.0 {
+            Some(stack) => stack.encode(writer),
+            None => writer.write_bool(false),
+        }
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        check_version(reader.version())?;
+        if reader.read_bool()? {
+            HashedItemStack::decode_stack(reader).map(Self::from)
+        } else {
+            Ok(Self::EMPTY)
+        }
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for OptionalHashedItemStack {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("OptionalHashedItemStack").build() };
+}
+
+/// A [`ComponentPatch`] with hashes in place of component data.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct HashedComponentPatch {
+    /// The types and hashes of the components added or replaced.
+    pub added: Vec<(DataComponentKind, u32)>,
+    /// Default components removed.
+    pub removed: Vec<DataComponentKind>,
+}
+
+impl HashedComponentPatch {
+    /// Hash the components of a [`ComponentPatch`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a component cannot be hashed,
+    /// or a removed component type does not exist since 1.21.5.
+    pub fn new(patch: &ComponentPatch) -> Result<Self, ItemError> {
+        if let Some(kind) = patch.removed.iter().find(|kind| kind.id(HASH_VERSION).is_none()) {
+            return Err(unsupported(*kind));
+        }
+
+        Ok(Self {
+            added: patch
+                .added
+                .iter()
+                .map(|component| Ok((component.kind(), component.hash()?)))
+                .collect::<Result<_, ItemError>>()?,
+            removed: patch.removed.clone(),
+        })
+    }
+
+    /// Returns `true` if this is the hashed form of a [`ComponentPatch`],
+    /// ignoring the order of components.
+    #[must_use]
+    pub fn matches(&self, patch: &ComponentPatch) -> bool {
+        let Ok(mut expected) = Self::new(patch) else { return false };
+        let mut found = self.clone();
+        for patch in [&mut expected, &mut found] {
+            patch.added.sort_unstable();
+            patch.removed.sort_unstable();
+        }
+        found == expected
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl DataComponent {
+    /// Compute the hash of this component, as sent in a [`HashedItemStack`].
+    ///
+    /// Hashes follow the CRC32C of the component's persistent encoding as of
+    /// 1.21.5. Fields removed in 1.21.5, such as `show_in_tooltip`, do not
+    /// affect the hash.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the component refers to registry entries by ID,
+    /// such as enchantments and nested item stacks,
+    /// is never saved, such as [`DataComponent::CreativeSlotLock`],
+    /// or no longer exists, such as [`DataComponent::HideTooltip`].
+    #[expect(clippy::too_many_lines, reason = "One arm per component type")]
+    pub fn hash(&self) -> Result<u32, ItemError> {
+        Ok(match self {
+            Self::CustomData(nbt)
+            | Self::EntityData(nbt)
+            | Self::BucketEntityData(nbt)
+            | Self::BlockEntityData(nbt) => hash_compound(nbt, false),
+            Self::MapDecorations(nbt) | Self::ContainerLoot(nbt) => hash_nbt(nbt, false),
+            Self::MaxStackSize(value)
+            | Self::MaxDamage(value)
+            | Self::Damage(value)
+            | Self::RepairCost(value)
+            | Self::MapId(value)
+            | Self::OminousBottleAmplifier(value) => hash_int(value.cast_signed()),
+            Self::Unbreakable { .. } | Self::Glider => hash_map(Vec::new()),
+            Self::Enchantable(value) => hash_fields(&[("value", hash_int(value.cast_signed()))]),
+            Self::CustomName(text) | Self::ItemName(text) => hash_text(text),
+            Self::ItemModel(identifier)
+            | Self::TooltipStyle(identifier)
+            | Self::NoteBlockSound(identifier) => hash_identifier(identifier),
+            Self::Lore(lines) => hash_list(lines.iter().map(hash_text)),
+            Self::Rarity(rarity) => hash_string(rarity.name()),
+            Self::CustomModelData(data) => {
+                let mut fields = Vec::new();
+                if !data.floats.is_empty() {
+                    fields.push(("floats", hash_list(data.floats.iter().copied().map(hash_float))));
+                }
+                if !data.flags.is_empty() {
+                    fields.push(("flags", hash_list(data.flags.iter().copied().map(hash_bool))));
+                }
+                if !data.strings.is_empty() {
+                    let strings = data.strings.iter().map(|value| hash_string(value));
+                    fields.push(("strings", hash_list(strings)));
+                }
+                if !data.colors.is_empty() {
+                    fields.push(("colors", hash_list(data.colors.iter().copied().map(hash_int))));
+                }
+                hash_fields(&fields)
+            }
+            Self::EnchantmentGlintOverride(value) => hash_bool(*value),
+            Self::TooltipDisplay { hide_tooltip, hidden_components } => {
+                let mut fields = Vec::new();
+                if *hide_tooltip {
+                    fields.push(("hide_tooltip", hash_bool(true)));
+                }
+                if !hidden_components.is_empty() {
+                    let names = hidden_components
+                        .iter()
+                        .map(|kind| hash_string(&format!("minecraft:{}", kind.name())));
+                    fields.push(("hidden_components", hash_list(names)));
+                }
+                hash_fields(&fields)
+            }
+            Self::Food(food) => {
+                let mut fields = Vec::from([
+                    ("nutrition", hash_int(food.nutrition.cast_signed())),
+                    ("saturation", hash_float(food.saturation)),
+                ]);
+                if food.can_always_eat {
+                    fields.push(("can_always_eat", hash_bool(true)));
+                }
+                hash_fields(&fields)
+            }
+            Self::UseCooldown { seconds, group } => {
+                let mut fields = Vec::from([("seconds", hash_float(*seconds))]);
+                if let Some(group) = group {
+                    fields.push(("cooldown_group", hash_identifier(group)));
+                }
+                hash_fields(&fields)
+            }
+            Self::DyedColor { rgb, .. } | Self::MapColor(rgb) => hash_int(*rgb),
+            Self::BaseColor(id) => {
+                let Some(name) = DYE_COLORS.get(*id as usize) else {
+                    return Err(ItemError::new(ItemErrorKind::InvalidValue {
+                        component: self.kind(),
+                        value: *id,
+                    }));
+                };
+                hash_string(name)
+            }
+            Self::BlockState(properties) => hash_map(
+                properties
+                    .iter()
+                    .map(|(key, value)| (hash_string(key), hash_string(value)))
+                    .collect(),
+            ),
+            Self::Enchantments(_)
+            | Self::StoredEnchantments(_)
+            | Self::CreativeSlotLock
+            | Self::UseRemainder(_)
+            | Self::MapPostProcessing(_)
+            | Self::ChargedProjectiles(_)
+            | Self::BundleContents(_)
+            | Self::DebugStickState(_)
+            | Self::Recipes(_)
+            | Self::PotDecorations(_)
+            | Self::Container(_) => {
+                return Err(ItemError::new(ItemErrorKind::UnhashableComponent(self.kind())));
+            }
+            Self::HideAdditionalTooltip | Self::HideTooltip => {
+                return Err(unsupported(self.kind()));
+            }
+        })
+    }
+}
+
+/// The first protocol version with hashed item stacks,
+/// whose component types and encodings are hashed.
+const HASH_VERSION: ProtocolVersion = ProtocolVersion::V1_21_5;
+
+/// Check that hashed item stacks exist in a protocol version.
+fn check_version(version: ProtocolVersion) -> Result<(), ItemError> {
+    if version < HASH_VERSION {
+        Err(ItemError::new(ItemErrorKind::UnsupportedVersion(version)))
+    } else {
+        Ok(())
+    }
+}
+
+/// Reject a component type that does not exist since 1.21.5.
+fn unsupported(kind: DataComponentKind) -> ItemError {
+    ItemError::new(ItemErrorKind::UnsupportedComponent { kind, version: HASH_VERSION })
+}
+
+/// The names of dye colors, ordered by ID.
+const DYE_COLORS: [&str; 16] = [
+    "white",
+    "orange",
+    "magenta",
+    "light_blue",
+    "yellow",
+    "lime",
+    "pink",
+    "gray",
+    "light_gray",
+    "cyan",
+    "purple",
+    "blue",
+    "brown",
+    "green",
+    "red",
+    "black",
+];
+
+// -------------------------------------------------------------------------------------------------
+
+// Tags written before each value, so values of different types hash
+// differently. Tag `1` marks an empty value, which components never contain.
+const TAG_MAP_START: u8 = 2;
+const TAG_MAP_END: u8 = 3;
+const TAG_LIST_START: u8 = 4;
+const TAG_LIST_END: u8 = 5;
+const TAG_BYTE: u8 = 6;
+const TAG_SHORT: u8 = 7;
+const TAG_INT: u8 = 8;
+const TAG_LONG: u8 = 9;
+const TAG_FLOAT: u8 = 10;
+const TAG_DOUBLE: u8 = 11;
+const TAG_STRING: u8 = 12;
+const TAG_BOOLEAN: u8 = 13;
+const TAG_BYTE_ARRAY_START: u8 = 14;
+const TAG_BYTE_ARRAY_END: u8 = 15;
+const TAG_INT_ARRAY_START: u8 = 16;
+const TAG_INT_ARRAY_END: u8 = 17;
+const TAG_LONG_ARRAY_START: u8 = 18;
+const TAG_LONG_ARRAY_END: u8 = 19;
+
+/// A CRC32C (Castagnoli) checksum in progress.
+struct Crc32c(u32);
+
+impl Crc32c {
+    /// The lookup table for the reflected Castagnoli polynomial.
+    const TABLE: [u32; 256] = {
+        let mut table = [0; 256];
+        let mut index = 0u32;
+        while index < 256 {
+            let mut crc = index;
+            let mut bit = 0;
+            while bit < 8 {
+                crc = if crc & 1 == 1 { (crc >> 1) ^ 0x82F6_3B78 } else { crc >> 1 };
+                bit += 1;
+            }
+            table[index as usize] = crc;
+            index += 1;
+        }
+        table
+    };
+
+    /// Start a checksum with a tag.
+    const fn new(tag: u8) -> Self { Self(u32::MAX).update(&[tag]) }
+
+    /// Add bytes to the checksum.
+    const fn update(mut self, bytes: &[u8]) -> Self {
+        let mut index = 0;
+        while index < bytes.len() {
+            self.0 = (self.0 >> 8) ^ Self::TABLE[((self.0 ^ bytes[index] as u32) & 0xFF) as usize];
+            index += 1;
+        }
+        self
+    }
+
+    /// Finish the checksum.
+    const fn finish(self) -> u32 { !self.0 }
+}
+
+/// Hash a primitive value.
+const fn hash_tagged(tag: u8, bytes: &[u8]) -> u32 { Crc32c::new(tag).update(bytes).finish() }
+
+const fn hash_bool(value: bool) -> u32 { hash_tagged(TAG_BOOLEAN, &[value as u8]) }
+
+const fn hash_int(value: i32) -> u32 { hash_tagged(TAG_INT, &value.to_le_bytes()) }
+
+const fn hash_float(value: f32) -> u32 { hash_tagged(TAG_FLOAT, &value.to_bits().to_le_bytes()) }
+
+/// Hash a string as its length and UTF-16 code units.
+fn hash_string(value: &str) -> u32 {
+    let len = u32::try_from(value.encode_utf16().count()).unwrap_or(u32::MAX);
+    let crc = Crc32c::new(TAG_STRING).update(&len.to_le_bytes());
+    value.encode_utf16().fold(crc, |crc, unit| crc.update(&unit.to_le_bytes())).finish()
+}
+
+fn hash_identifier(identifier: &Identifier<'_>) -> u32 {
+    hash_string(&format!("{}:{}", identifier.namespace(), identifier.path()))
+}
+
+/// Hash a sequence of hashes between two tags.
+fn hash_sequence(start: u8, hashes: impl IntoIterator<Item = u32>, end: u8) -> u32 {
+    let crc =
+        hashes.into_iter().fold(Crc32c::new(start), |crc, hash| crc.update(&hash.to_le_bytes()));
+    crc.update(&[end]).finish()
+}
+
+fn hash_list(hashes: impl IntoIterator<Item = u32>) -> u32 {
+    hash_sequence(TAG_LIST_START, hashes, TAG_LIST_END)
+}
+
+/// Hash a map from the hashes of its keys and values.
+///
+/// Entries are sorted by their hashes, so the order of entries does not matter.
+fn hash_map(mut entries: Vec<(u32, u32)>) -> u32 {
+    entries.sort_unstable();
+    let crc = entries.into_iter().fold(Crc32c::new(TAG_MAP_START), |crc, (key, value)| {
+        crc.update(&key.to_le_bytes()).update(&value.to_le_bytes())
+    });
+    crc.update(&[TAG_MAP_END]).finish()
+}
+
+/// Hash a map of named fields.
+fn hash_fields(fields: &[(&str, u32)]) -> u32 {
+    hash_map(fields.iter().map(|(name, hash)| (hash_string(name), *hash)).collect())
+}
+
+/// Hash a text component from its NBT form.
+fn hash_text(text: &TextComponent) -> u32 { hash_nbt(&text.to_nbt(TextEncoding::Nbt), true) }
+
+fn hash_compound(compound: &NbtCompound, bytes_as_bools: bool) -> u32 {
+    hash_map(
+        compound
+            .iter()
+            .map(|(key, value)| (hash_string(key), hash_nbt(value, bytes_as_bools)))
+            .collect(),
+    )
+}
+
+/// Hash an NBT value.
+///
+/// NBT has no booleans, so values saved as booleans are saved as bytes.
+/// If `bytes_as_bools` is set, bytes are hashed as the booleans they stand for.
+fn hash_nbt(value: &NbtValue, bytes_as_bools: bool) -> u32 {
+    match value {
+        NbtValue::Byte(value) if bytes_as_bools => hash_bool(*value != 0),
+        NbtValue::Byte(value) => hash_tagged(TAG_BYTE, &value.to_le_bytes()),
+        NbtValue::Short(value) => hash_tagged(TAG_SHORT, &value.to_le_bytes()),
+        NbtValue::Int(value) => hash_int(*value),
+        NbtValue::Long(value) => hash_tagged(TAG_LONG, &value.to_le_bytes()),
+        NbtValue::Float(value) => hash_float(*value),
+        NbtValue::Double(value) => hash_tagged(TAG_DOUBLE, &value.to_bits().to_le_bytes()),
+        NbtValue::String(value) => hash_string(value),
+        NbtValue::List(list) => hash_list(list.iter().map(|value| hash_nbt(value, bytes_as_bools))),
+        NbtValue::Compound(compound) => hash_compound(compound, bytes_as_bools),
+        NbtValue::ByteArray(values) => {
+            let crc = values.iter().fold(Crc32c::new(TAG_BYTE_ARRAY_START), |crc, value| {
+                crc.update(&value.to_le_bytes())
+            });
+            crc.update(&[TAG_BYTE_ARRAY_END]).finish()
+        }
+        NbtValue::IntArray(values) => {
+            let crc = values.iter().fold(Crc32c::new(TAG_INT_ARRAY_START), |crc, value| {
+                crc.update(&value.to_le_bytes())
+            });
+            crc.update(&[TAG_INT_ARRAY_END]).finish()
+        }
+        NbtValue::LongArray(values) => {
+            let crc = values.iter().fold(Crc32c::new(TAG_LONG_ARRAY_START), |crc, value| {
+                crc.update(&value.to_le_bytes())
+            });
+            crc.update(&[TAG_LONG_ARRAY_END]).finish()
+        }
+    }
+}
@@ -0,0 +1,279 @@
+//! Item stacks and their data components, as sent since 1.20.5.
+//!
+//! An [`ItemStack`] is a count, an item ID and a [`ComponentPatch`] of
+//! [`DataComponent`]s added to or removed from the item's defaults.
+//! Components are not prefixed by their length, so a component whose data is
+//! not understood cannot be skipped. Decoding fails with
+//! [`ItemErrorKind::UnknownComponent`] or
+//! [`ItemErrorKind::UnsupportedComponent`] instead.
+//!
+//! Component type IDs and encodings depend on the protocol version,
+//! see [`DataComponentKind::table`]. Versions before 1.20.5 fail with
+//! [`ItemErrorKind::UnsupportedVersion`].
+//!
+//! An [`ItemStack`] is never empty,
+//! use an [`OptionalItemStack`] for slots that may be empty.
+//!
+//! Since 1.21.5, clients send a [`HashedItemStack`] when clicking in a
+//! container, with a CRC32C hash of each component in place of its data.
+//! Use [`HashedItemStack::matches`] to check it against the server's stack.
+//!
+//! ```rust
+//! use facet_minecraft::item::{DataComponent, DataComponentKind, ItemStack, OptionalItemStack};
+//!
+//! let mut stack = ItemStack::new(1, 64);
+//! stack.components.insert(DataComponent::MaxStackSize(16));
+//! stack.components.remove(DataComponentKind::Food);
+//!
+//! let bytes = facet_minecraft::to_vec(&stack).unwrap();
+//! assert_eq!(bytes, [64, 1, 1, 1, 1, 16, 20]);
+//! let (decoded, _) = facet_minecraft::from_slice::<ItemStack>(&bytes).unwrap();
+//! assert_eq!(decoded, stack);
+//!
+//! let bytes = facet_minecraft::to_vec(&OptionalItemStack::EMPTY).unwrap();
+//! assert_eq!(bytes, [0]);
+//! ```
+
+use alloc::vec::Vec;
+
+use facet::{Facet, Shape};
+
+use crate::{
+    codec::{McCodec, McReader, McWriter, codec_shape},
+    deserialize::DeserializeError,
+    packet::ProtocolVersion,
+    serialize::SerializeError,
+};
+
+mod component;
+pub use component::{
+    CustomModelData, DataComponent, DataComponentKind, Enchantments, Food, Rarity,
+};
+
+mod error;
+pub use error::{ItemError, ItemErrorKind};
+
+mod hash;
+pub use hash::{HashedComponentPatch, HashedItemStack, OptionalHashedItemStack};
+
+/// A non-empty stack of items.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemStack {
+    /// The number of items, at least `1`.
+    pub count: u32,
+    /// The ID of the item in the `item` registry.
+    pub item: u32,
+    /// The changes to the default components of the item.
+    pub components: ComponentPatch,
+}
+
+impl ItemStack {
+    /// The deepest item stacks may be nested inside each other,
+    /// such as in bundles.
+    pub const MAX_DEPTH: usize = 64;
+
+    /// Create a new [`ItemStack`] with no component changes.
+    #[inline]
+    #[must_use]
+    pub const fn new(item: u32, count: u32) -> Self {
+        Self { count, item, components: ComponentPatch::new() }
+    }
+
+    /// Write this stack.
+    fn encode_stack(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        if self.count == 0 {
+            return Err(ItemError::new(ItemErrorKind::EmptyStack).into());
+        }
+
+        writer.write_var_u32(self.count)?;
+        writer.write_var_u32(self.item)?;
+        self.components.encode(writer)
+    }
+
+    /// Read a stack nested `depth` stacks deep.
+    fn decode_stack(reader: &mut McReader<'_>, depth: usize) -> Result<Self, DeserializeError> {
+        OptionalItemStack::decode_stack(reader, depth)?
+            .0
+            .ok_or_else(|| ItemError::new(ItemErrorKind::EmptyStack).into())
+    }
+}
+
+impl McCodec for ItemStack {
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        check_version(writer.version())?;
+        self.encode_stack(writer)
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        check_version(reader.version())?;
+        Self::decode_stack(reader, 0)
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for ItemStack {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("ItemStack").build() };
+}
+
+/// An [`ItemStack`] that may be empty, sent with a count of zero.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct OptionalItemStack(pub Option<ItemStack>);
+
+impl OptionalItemStack {
+    /// An empty slot.
+    pub const EMPTY: Self = Self(None);
+
+    /// Returns `true` if the slot is empty.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool { self.0.is_none() }
+
+    /// Write this stack, or a count of zero if empty.
+    fn encode_stack(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        match &self.0 {
+            Some(stack) => stack.encode_stack(writer),
+            None => writer.write_var_u32(0),
+        }
+    }
+
+    /// Read a stack nested `depth` stacks deep.
+    fn decode_stack(reader: &mut McReader<'_>, depth: usize) -> Result<Self, DeserializeError> {
+        if depth > ItemStack::MAX_DEPTH {
+            return Err(ItemError::new(ItemErrorKind::DepthLimit).into());
+        }
+
+        let count = reader.read_var_i32()?;
+        if count <= 0 {
+            return Ok(Self::EMPTY);
+        }
+
+        Ok(Self(Some(ItemStack {
+            count: count.cast_unsigned(),
+            item: reader.read_var_u32()?,
+            components: ComponentPatch::decode(reader, depth)?,
+        })))
+    }
+}
+
+impl From<ItemStack> for OptionalItemStack {
+    #[inline]
+    fn from(stack: ItemStack) -> Self { Self(Some(stack)) }
+}
+
+impl From<Option<ItemStack>> for OptionalItemStack {
+    #[inline]
+    fn from(stack: Option<ItemStack>) -> Self { Self(stack) }
+}
+
+impl McCodec for OptionalItemStack {
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        check_version(writer.version())?;
+        self.encode_stack(writer)
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        check_version(reader.version())?;
+        Self::decode_stack(reader, 0)
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for OptionalItemStack {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("OptionalItemStack").build() };
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Components added to and removed from the defaults of an item.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ComponentPatch {
+    /// Components added or replaced.
+    pub added: Vec<DataComponent>,
+    /// Default components removed.
+    pub removed: Vec<DataComponentKind>,
+}
+
+impl ComponentPatch {
+    /// Create a new, empty [`ComponentPatch`].
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self { Self { added: Vec::new(), removed: Vec::new() } }
+
+    /// Returns `true` if no components are added or removed.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool { self.added.is_empty() && self.removed.is_empty() }
+
+    /// Get the added component of a type, if any.
+    #[must_use]
+    pub fn get(&self, kind: DataComponentKind) -> Option<&DataComponent> {
+        self.added.iter().find(|component| component.kind() == kind)
+    }
+
+    /// Add a component, replacing any component of the same type.
+    pub fn insert(&mut self, component: DataComponent) {
+        let kind = component.kind();
+        self.removed.retain(|removed| *removed != kind);
+        match self.added.iter_mut().find(|existing| existing.kind() == kind) {
+            Some(existing) => *existing = component,
+            None => self.added.push(component),
+        }
+    }
+
+    /// Remove a component type from the item.
+    pub fn remove(&mut self, kind: DataComponentKind) {
+        self.added.retain(|component| component.kind() != kind);
+        if !self.removed.contains(&kind) {
+            self.removed.push(kind);
+        }
+    }
+
+    /// Write the patch.
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        writer.write_var_u32(u32::try_from(self.added.len()).unwrap_or(u32::MAX))?;
+        writer.write_var_u32(u32::try_from(self.removed.len()).unwrap_or(u32::MAX))?;
+        for component in &self.added {
+            write_kind(writer, component.kind())?;
+            component.encode_data(writer)?;
+        }
+        self.removed.iter().try_for_each(|kind| write_kind(writer, *kind))
+    }
+
+    /// Read a patch of a stack nested `depth` stacks deep.
+    fn decode(reader: &mut McReader<'_>, depth: usize) -> Result<Self, DeserializeError> {
+        let added = reader.read_var_u32()? as usize;
+        let removed = reader.read_var_u32()? as usize;
+
+        let added = (0..added)
+            .map(|_| DataComponent::decode_data(read_kind(reader)?, reader, depth))
+            .collect::<Result<Vec<_>, _>>()?;
+        let removed = (0..removed).map(|_| read_kind(reader)).collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { added, removed })
+    }
+}
+
+/// Check that item stacks with data components exist in a protocol version.
+fn check_version(version: ProtocolVersion) -> Result<(), ItemError> {
+    if version < ProtocolVersion::V1_20_5 {
+        Err(ItemError::new(ItemErrorKind::UnsupportedVersion(version)))
+    } else {
+        Ok(())
+    }
+}
+
+/// Write a component type ID for the writer's protocol version.
+fn write_kind(writer: &mut McWriter<'_>, kind: DataComponentKind) -> Result<(), SerializeError> {
+    let version = writer.version();
+    let id = kind
+        .id(version)
+        .ok_or_else(|| ItemError::new(ItemErrorKind::UnsupportedComponent { kind, version }))?;
+    writer.write_var_u32(id)
+}
+
+/// Read a component type ID for the reader's protocol version.
+fn read_kind(reader: &mut McReader<'_>) -> Result<DataComponentKind, DeserializeError> {
+    let version = reader.version();
+    let id = reader.read_var_u32()?;
+    DataComponentKind::from_id(id, version)
+        .ok_or_else(|| ItemError::new(ItemErrorKind::UnknownComponent { id, version }).into())
+}
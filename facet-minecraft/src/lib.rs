@@ -7,7 +7,26 @@ extern crate alloc;
 extern crate std;
 
 pub mod attribute;
+pub mod chat;
+pub mod chunk;
+pub mod codec;
+pub mod command;
 pub mod common;
+pub mod connection;
+#[cfg(feature = "encryption")]
+pub mod encryption;
+pub mod entity;
+pub mod frame;
+pub mod item;
+#[cfg(feature = "login")]
+pub mod login;
+pub mod mutf8;
+pub mod nbt;
+pub mod packet;
+pub mod payload;
+pub mod profile;
+pub mod text;
+pub mod types;
 
 pub mod deserialize;
 #[cfg(feature = "futures-lite")]
@@ -16,7 +35,7 @@ pub use deserialize::from_async_reader;
 pub use deserialize::from_reader;
 #[cfg(feature = "tokio")]
 pub use deserialize::from_tokio_reader;
-pub use deserialize::{Deserializable, from_slice, from_slice_borrowed};
+pub use deserialize::{Deserializable, from_slice, from_slice_borrowed, from_slice_versioned};
 
 pub mod serialize;
 #[cfg(feature = "futures-lite")]
@@ -25,4 +44,4 @@ pub use serialize::to_async_writer;
 pub use serialize::to_tokio_writer;
 #[cfg(feature = "streaming")]
 pub use serialize::to_writer;
-pub use serialize::{Serializable, to_buffer, to_vec};
+pub use serialize::{Serializable, to_buffer, to_vec, to_vec_versioned};
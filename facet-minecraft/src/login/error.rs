@@ -0,0 +1,68 @@
+use core::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// An error that occurred during the login encryption handshake.
+#[derive(Debug)]
+pub struct LoginError {
+    kind: LoginErrorKind,
+}
+
+impl LoginError {
+    /// Create a new [`LoginError`].
+    #[inline]
+    #[must_use]
+    pub const fn new(kind: LoginErrorKind) -> Self { Self { kind } }
+
+    /// Get the kind of login error.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> &LoginErrorKind { &self.kind }
+}
+
+/// The type of login error.
+#[derive(Debug)]
+pub enum LoginErrorKind {
+    /// An RSA operation failed.
+    Rsa(rsa::Error),
+    /// A key could not be encoded or decoded.
+    InvalidKey(rsa::pkcs8::spki::Error),
+    /// The decrypted shared secret was not 16 bytes long.
+    InvalidSecretLength(usize),
+    /// The decrypted verify token did not match the one sent.
+    VerifyTokenMismatch,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Error for LoginError {}
+impl Display for LoginError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            LoginErrorKind::Rsa(err) => write!(f, "RSA error: {err}"),
+            LoginErrorKind::InvalidKey(err) => write!(f, "invalid key: {err}"),
+            LoginErrorKind::InvalidSecretLength(len) => {
+                write!(f, "shared secret is {len} bytes long, expected 16")
+            }
+            LoginErrorKind::VerifyTokenMismatch => f.write_str("verify token does not match"),
+        }
+    }
+}
+
+impl From<rsa::Error> for LoginError {
+    #[inline]
+    fn from(err: rsa::Error) -> Self { Self { kind: LoginErrorKind::Rsa(err) } }
+}
+
+impl From<rsa::pkcs8::spki::Error> for LoginError {
+    #[inline]
+    fn from(err: rsa::pkcs8::spki::Error) -> Self { Self { kind: LoginErrorKind::InvalidKey(err) } }
+}
+
+impl From<rsa::pkcs8::Error> for LoginError {
+    #[inline]
+    fn from(err: rsa::pkcs8::Error) -> Self {
+        Self { kind: LoginErrorKind::Rsa(rsa::Error::Pkcs8(err)) }
+    }
+}
@@ -0,0 +1,258 @@
+//! Helpers for the login encryption handshake.
+//!
+//! 1. The server sends an [`EncryptionRequest`] containing its RSA public key,
+//!    encoded as an X.509 `SubjectPublicKeyInfo`, and a random verify token.
+//! 2. The client generates a shared secret, encrypts it and the verify token
+//!    with the server's public key using PKCS#1 v1.5, and sends back an
+//!    [`EncryptionResponse`].
+//! 3. Both sides compute the [`server_hash`] for authentication with the
+//!    session server, and enable
+//!    [`CipherStream`](crate::encryption::CipherStream) encryption using the
+//!    shared secret.
+//!
+//! A [`ServerKey`] handles the server side of the handshake and
+//! [`EncryptionRequest::respond`] handles the client side.
+//! No requests are made to the session server.
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Debug, Formatter, Write};
+
+use facet::Facet;
+use rsa::{
+    Pkcs1v15Encrypt, RsaPrivateKey, RsaPublicKey,
+    pkcs8::{DecodePrivateKey, DecodePublicKey, EncodePublicKey},
+    rand_core::CryptoRngCore,
+};
+use sha1::{Digest, Sha1};
+
+use crate::{
+    connection::Transition,
+    packet::{Direction, Packet, ProtocolState},
+};
+
+mod error;
+pub use error::{LoginError, LoginErrorKind};
+
+/// Sent by the server to begin encryption.
+#[derive(Debug, Clone, PartialEq, Eq, Facet)]
+pub struct EncryptionRequest {
+    /// The server ID, which is empty on vanilla servers.
+    pub server_id: String,
+    /// The server's public key, in DER format.
+    pub public_key: Vec<u8>,
+    /// A random token the client must encrypt and send back.
+    pub verify_token: Vec<u8>,
+    /// Whether the client should authenticate with the session server.
+    pub should_authenticate: bool,
+}
+
+/// Sent by the client in response to an [`EncryptionRequest`].
+#[derive(Debug, Clone, PartialEq, Eq, Facet)]
+pub struct EncryptionResponse {
+    /// The shared secret, encrypted with the server's public key.
+    pub shared_secret: Vec<u8>,
+    /// The verify token, encrypted with the server's public key.
+    pub verify_token: Vec<u8>,
+}
+
+impl Packet for EncryptionRequest {
+    const DIRECTION: Direction = Direction::Clientbound;
+    const ID: u32 = 0x01;
+    const STATE: ProtocolState = ProtocolState::Login;
+}
+
+impl Packet for EncryptionResponse {
+    const DIRECTION: Direction = Direction::Serverbound;
+    const ID: u32 = 0x01;
+    const STATE: ProtocolState = ProtocolState::Login;
+
+    fn transition(&self) -> Option<Transition> { Some(Transition::EnableEncryption) }
+}
+
+impl EncryptionRequest {
+    /// Encrypt the shared secret and verify token
+    /// using the server's public key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the public key is invalid or encryption fails.
+    pub fn respond<R: CryptoRngCore>(
+        &self,
+        rng: &mut R,
+        shared_secret: &[u8; 16],
+    ) -> Result<EncryptionResponse, LoginError> {
+        let key = RsaPublicKey::from_public_key_der(&self.public_key)?;
+        Ok(EncryptionResponse {
+            shared_secret: key.encrypt(rng, Pkcs1v15Encrypt, shared_secret)?,
+            verify_token: key.encrypt(rng, Pkcs1v15Encrypt, &self.verify_token)?,
+        })
+    }
+
+    /// Compute the [`server_hash`] for this request and a shared secret.
+    #[inline]
+    #[must_use]
+    pub fn server_hash(&self, shared_secret: &[u8]) -> String {
+        server_hash(&self.server_id, shared_secret, &self.public_key)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// A server's RSA keypair.
+#[derive(Clone)]
+pub struct ServerKey {
+    private: RsaPrivateKey,
+    public: Vec<u8>,
+}
+
+impl ServerKey {
+    /// The size of the key used by vanilla servers, in bits.
+    pub const BITS: usize = 1024;
+
+    /// Generate a new [`ServerKey`] of [`ServerKey::BITS`] bits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if key generation fails.
+    pub fn generate<R: CryptoRngCore + ?Sized>(rng: &mut R) -> Result<Self, LoginError> {
+        Self::from_private_key(RsaPrivateKey::new(rng, Self::BITS)?)
+    }
+
+    /// Create a [`ServerKey`] from a private key in PKCS#8 DER format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is invalid.
+    pub fn from_pkcs8_der(der: &[u8]) -> Result<Self, LoginError> {
+        Self::from_private_key(RsaPrivateKey::from_pkcs8_der(der)?)
+    }
+
+    /// Create a [`ServerKey`] from an [`RsaPrivateKey`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the public key cannot be encoded.
+    pub fn from_private_key(private: RsaPrivateKey) -> Result<Self, LoginError> {
+        let public = private.to_public_key().to_public_key_der()?.into_vec();
+        Ok(Self { private, public })
+    }
+
+    /// Get the public key in DER format, as sent in an [`EncryptionRequest`].
+    #[inline]
+    #[must_use]
+    pub fn public_key_der(&self) -> &[u8] { &self.public }
+
+    /// Get the private key.
+    #[inline]
+    #[must_use]
+    pub const fn private_key(&self) -> &RsaPrivateKey { &self.private }
+
+    /// Create an [`EncryptionRequest`] containing this key.
+    #[must_use]
+    pub fn encryption_request(
+        &self,
+        server_id: impl Into<String>,
+        verify_token: impl Into<Vec<u8>>,
+        should_authenticate: bool,
+    ) -> EncryptionRequest {
+        EncryptionRequest {
+            server_id: server_id.into(),
+            public_key: self.public.clone(),
+            verify_token: verify_token.into(),
+            should_authenticate,
+        }
+    }
+
+    /// Decrypt data encrypted with this key using PKCS#1 v1.5.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if decryption fails.
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, LoginError> {
+        Ok(self.private.decrypt(Pkcs1v15Encrypt, data)?)
+    }
+
+    /// Decrypt an [`EncryptionResponse`], check its verify token
+    /// and return the shared secret.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if decryption fails, the verify token does not match,
+    /// or the shared secret is not 16 bytes long.
+    pub fn decrypt_response(
+        &self,
+        response: &EncryptionResponse,
+        verify_token: &[u8],
+    ) -> Result<[u8; 16], LoginError> {
+        if self.decrypt(&response.verify_token)? != verify_token {
+            return Err(LoginError::new(LoginErrorKind::VerifyTokenMismatch));
+        }
+
+        let secret = self.decrypt(&response.shared_secret)?;
+        secret
+            .as_slice()
+            .try_into()
+            .map_err(|_| LoginError::new(LoginErrorKind::InvalidSecretLength(secret.len())))
+    }
+}
+
+impl Debug for ServerKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServerKey").field("public", &self.public).finish_non_exhaustive()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Generate a random shared secret.
+#[must_use]
+pub fn generate_secret<R: CryptoRngCore + ?Sized>(rng: &mut R) -> [u8; 16] {
+    let mut secret = [0; 16];
+    rng.fill_bytes(&mut secret);
+    secret
+}
+
+/// Compute the server hash sent to the session server.
+///
+/// This is the SHA-1 digest of the server ID, shared secret and public key,
+/// formatted as a signed two's complement number in hexadecimal without
+/// leading zeros.
+///
+/// ```rust
+/// use facet_minecraft::login::server_hash;
+///
+/// assert_eq!(server_hash("Notch", &[], &[]), "4ed1f46bbe04bc756bcb17c0c7ce3e4632f06a48");
+/// assert_eq!(server_hash("jeb_", &[], &[]), "-7c9d5b0044c130109a5d7b5fb5c317c02b4e28c1");
+/// ```
+#[must_use]
+pub fn server_hash(server_id: &str, shared_secret: &[u8], public_key: &[u8]) -> String {
+    let mut digest: [u8; 20] = Sha1::new()
+        .chain_update(server_id)
+        .chain_update(shared_secret)
+        .chain_update(public_key)
+        .finalize()
+        .into();
+
+    let negative = digest[0] & 0x80 != 0;
+    if negative {
+        // Negate the digest, inverting all bits and adding one
+        let mut carry = true;
+        for byte in digest.iter_mut().rev() {
+            (*byte, carry) = (!*byte).overflowing_add(u8::from(carry));
+        }
+    }
+
+    let mut hash = String::with_capacity(41);
+    if negative {
+        hash.push('-');
+    }
+    for byte in digest {
+        let _ = write!(hash, "{byte:02x}");
+    }
+
+    // Remove leading zeros, keeping at least one digit
+    let start = usize::from(negative);
+    let zeros = hash[start..].bytes().take_while(|&b| b == b'0').count().min(39);
+    hash.replace_range(start..start + zeros, "");
+    hash
+}
@@ -0,0 +1,140 @@
+//! Java's Modified UTF-8, as used by NBT and `DataOutput.writeUTF`.
+//!
+//! Modified UTF-8 differs from standard UTF-8 in two ways:
+//! - `U+0000` is encoded as the two bytes `0xC0 0x80`, so encoded strings never
+//!   contain a zero byte,
+//! - characters outside of the Basic Multilingual Plane are encoded as a UTF-16
+//!   surrogate pair, with each surrogate encoded as three bytes.
+//!
+//! Strings without either are encoded identically to standard UTF-8,
+//! and are borrowed instead of copied.
+//!
+//! Fields can be encoded as a `u16`-prefixed Modified UTF-8 string using
+//! `#[facet(mc::mutf8)]`.
+
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// Encode a string as Modified UTF-8.
+#[must_use]
+pub fn encode(value: &str) -> Cow<'_, [u8]> {
+    if is_standard(value.as_bytes()) {
+        return Cow::Borrowed(value.as_bytes());
+    }
+
+    let mut bytes = Vec::with_capacity(encoded_len(value));
+    for unit in value.encode_utf16() {
+        #[expect(clippy::cast_possible_truncation, reason = "Values are masked")]
+        match unit {
+            0x0001..=0x007F => bytes.push(unit as u8),
+            0x0000 | 0x0080..=0x07FF => {
+                bytes.extend_from_slice(&[0xC0 | (unit >> 6) as u8, 0x80 | (unit & 0x3F) as u8]);
+            }
+            _ => bytes.extend_from_slice(&[
+                0xE0 | (unit >> 12) as u8,
+                0x80 | ((unit >> 6) & 0x3F) as u8,
+                0x80 | (unit & 0x3F) as u8,
+            ]),
+        }
+    }
+    Cow::Owned(bytes)
+}
+
+/// Returns the number of bytes a string takes when encoded as Modified
+/// UTF-8.
+#[must_use]
+pub fn encoded_len(value: &str) -> usize {
+    value
+        .chars()
+        .map(|c| match c {
+            '\0' => 2,
+            // Encoded as two 3-byte surrogates
+            c if c.len_utf8() == 4 => 6,
+            c => c.len_utf8(),
+        })
+        .sum()
+}
+
+/// Decode a Modified UTF-8 string.
+///
+/// Like Java, overlong two and three byte sequences are accepted.
+///
+/// # Errors
+///
+/// Returns an error if the input is not valid Modified UTF-8,
+/// or contains unpaired surrogates.
+pub fn decode(bytes: &[u8]) -> Result<Cow<'_, str>, Mutf8Error> {
+    if is_standard(bytes)
+        && let Ok(value) = core::str::from_utf8(bytes)
+    {
+        return Ok(Cow::Borrowed(value));
+    }
+
+    let mut string = String::with_capacity(bytes.len());
+    let mut high: Option<(u16, usize)> = None;
+    let mut index = 0;
+    while index < bytes.len() {
+        let error = Mutf8Error { valid_up_to: index };
+        let continuation = |offset: usize| match bytes.get(index + offset) {
+            Some(&byte) if byte & 0xC0 == 0x80 => Ok(u16::from(byte & 0x3F)),
+            _ => Err(error),
+        };
+
+        let (unit, len) = match bytes[index] {
+            byte @ 0x00..=0x7F => (u16::from(byte), 1),
+            byte @ 0xC0..=0xDF => ((u16::from(byte & 0x1F) << 6) | continuation(1)?, 2),
+            byte @ 0xE0..=0xEF => {
+                let unit = (u16::from(byte & 0x0F) << 12) | (continuation(1)? << 6);
+                (unit | continuation(2)?, 3)
+            }
+            _ => return Err(error),
+        };
+
+        match (high.take(), unit) {
+            (None, 0xD800..=0xDBFF) => high = Some((unit, index)),
+            (Some((high, _)), 0xDC00..=0xDFFF) => {
+                let code =
+                    0x10000 + ((u32::from(high) - 0xD800) << 10) + (u32::from(unit) - 0xDC00);
+                string.push(char::from_u32(code).ok_or(error)?);
+            }
+            (Some((_, start)), _) => return Err(Mutf8Error { valid_up_to: start }),
+            (None, _) => string.push(char::from_u32(u32::from(unit)).ok_or(error)?),
+        }
+        index += len;
+    }
+
+    match high {
+        Some((_, start)) => Err(Mutf8Error { valid_up_to: start }),
+        None => Ok(Cow::Owned(string)),
+    }
+}
+
+/// Returns `true` if the bytes contain neither zero bytes nor four-byte
+/// sequences, so standard and Modified UTF-8 are identical.
+fn is_standard(bytes: &[u8]) -> bool { bytes.iter().all(|&byte| byte != 0 && byte < 0xF0) }
+
+// -------------------------------------------------------------------------------------------------
+
+/// An error that occurred while decoding Modified UTF-8.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mutf8Error {
+    valid_up_to: usize,
+}
+
+impl Mutf8Error {
+    /// Returns the index in the input up to which valid Modified UTF-8 was
+    /// verified.
+    #[inline]
+    #[must_use]
+    pub const fn valid_up_to(&self) -> usize { self.valid_up_to }
+}
+
+impl Error for Mutf8Error {}
+impl Display for Mutf8Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid modified utf-8 sequence at index {}", self.valid_up_to)
+    }
+}
@@ -0,0 +1,314 @@
+use alloc::{borrow::Cow, string::String};
+
+use facet::{Def, Facet, Field, PtrUninit, ScalarType, Shape, StructKind, Type, UserType, Variant};
+use facet_reflect::Partial;
+use uuid::Uuid;
+
+use super::{NbtCompound, NbtError, NbtErrorKind, NbtTag, NbtValue, io::NbtReader};
+
+/// Read a root tag, with an optional name.
+///
+/// A root [`NbtTag::End`] is read as `None`.
+pub(crate) fn read_root<T: Facet<'static>>(
+    reader: &mut NbtReader<'_>,
+    named: bool,
+) -> Result<(String, T), NbtError> {
+    let (name, partial) = read_root_into(Partial::alloc_owned::<T>()?, reader, named)?;
+    let value = partial.build()?.materialize::<T>()?;
+    Ok((name, value))
+}
+
+/// Read a nameless root tag into uninitialized memory.
+///
+/// A root [`NbtTag::End`] is read as `None`.
+///
+/// # Safety
+///
+/// `ptr` must be valid for writes of a value described by `shape`.
+/// The value is only initialized if this returns `Ok`.
+pub(crate) unsafe fn read_root_raw(
+    ptr: PtrUninit,
+    shape: &'static Shape,
+    reader: &mut NbtReader<'_>,
+) -> Result<(), NbtError> {
+    // SAFETY: Guaranteed by the caller.
+    let partial = unsafe { Partial::<'static, false>::from_raw(ptr, shape)? };
+    let (_, partial) = read_root_into(partial, reader, false)?;
+    partial.finish_in_place().map_err(Into::into)
+}
+
+/// Read a root tag, with an optional name, into the current frame.
+fn read_root_into<'facet, const BORROW: bool>(
+    partial: Partial<'facet, BORROW>,
+    reader: &mut NbtReader<'_>,
+    named: bool,
+) -> Result<(String, Partial<'facet, BORROW>), NbtError> {
+    let tag = reader.read_tag()?;
+    let name = if named && tag != NbtTag::End {
+        reader.read_string()?.into_owned()
+    } else {
+        String::new()
+    };
+
+    if tag == NbtTag::End {
+        if !matches!(partial.shape().def, Def::Option(_)) {
+            return Err(NbtError::mismatch(NbtTag::Compound, NbtTag::End));
+        }
+        Ok((name, partial.set_default()?))
+    } else {
+        Ok((name, read_value(partial, tag, reader)?))
+    }
+}
+
+/// Read the payload of a tag into the current frame.
+fn read_value<'facet, const BORROW: bool>(
+    partial: Partial<'facet, BORROW>,
+    tag: NbtTag,
+    reader: &mut NbtReader<'_>,
+) -> Result<Partial<'facet, BORROW>, NbtError> {
+    let shape = partial.shape();
+    match shape.def {
+        Def::Option(_) => {
+            return read_value(partial.begin_some()?, tag, reader)?.end().map_err(Into::into);
+        }
+        Def::Pointer(_) => {
+            return read_value(partial.begin_smart_ptr()?, tag, reader)?.end().map_err(Into::into);
+        }
+        _ => {}
+    }
+
+    if shape.is_type::<NbtValue>() {
+        return partial.set(NbtValue::read(tag, reader)?).map_err(Into::into);
+    } else if shape.is_type::<NbtCompound>() {
+        expect(NbtTag::Compound, tag)?;
+        return partial.set(NbtCompound::read(reader)?).map_err(Into::into);
+    } else if shape.is_type::<Uuid>() {
+        expect(NbtTag::IntArray, tag)?;
+        let length = reader.read_length()?;
+        if length != 4 {
+            return Err(NbtError::unsupported_shape(shape));
+        }
+        let bytes = reader.read_bytes(16)?;
+        return partial.set(Uuid::from_slice(bytes).unwrap_or_default()).map_err(Into::into);
+    }
+
+    if let Some(scalar) = ScalarType::try_from_shape(shape) {
+        return read_scalar(partial, scalar, tag, reader);
+    }
+
+    match shape.def {
+        Def::List(def) => {
+            let element = expect_sequence(def.t, tag, reader)?;
+            let length = reader.read_length()?;
+
+            reader.enter()?;
+            let mut partial = partial.init_list()?;
+            for _ in 0..length {
+                partial = read_value(partial.begin_list_item()?, element, reader)?.end()?;
+            }
+            reader.exit();
+            Ok(partial)
+        }
+        Def::Array(def) => {
+            let element = expect_sequence(def.t, tag, reader)?;
+            let length = reader.read_length()?;
+            if length != def.n {
+                return Err(NbtError::unsupported_shape(shape));
+            }
+
+            reader.enter()?;
+            let mut partial = partial.init_array()?;
+            for index in 0..length {
+                partial = read_value(partial.begin_nth_field(index)?, element, reader)?.end()?;
+            }
+            reader.exit();
+            Ok(partial)
+        }
+        Def::Map(_) => {
+            expect(NbtTag::Compound, tag)?;
+
+            reader.enter()?;
+            let mut partial = partial.init_map()?;
+            loop {
+                let tag = reader.read_tag()?;
+                if tag == NbtTag::End {
+                    break;
+                }
+                let key = reader.read_string()?.into_owned();
+                partial = partial.begin_key()?.set(key)?.end()?;
+                partial = read_value(partial.begin_value()?, tag, reader)?.end()?;
+            }
+            reader.exit();
+            Ok(partial)
+        }
+        _ => match shape.ty {
+            Type::User(UserType::Struct(_)) if shape.is_transparent() => {
+                read_value(partial.begin_inner()?, tag, reader)?.end().map_err(Into::into)
+            }
+            Type::User(UserType::Struct(ty)) if ty.kind != StructKind::Tuple => {
+                expect(NbtTag::Compound, tag)?;
+                read_fields(partial, ty.fields, reader)
+            }
+            Type::User(UserType::Enum(ty)) => read_enum(partial, ty.variants, tag, reader),
+            _ => Err(NbtError::unsupported_shape(shape)),
+        },
+    }
+}
+
+/// Read an enum from either a `TAG_String` naming a unit variant,
+/// or a `TAG_Compound` containing a single entry named after the variant.
+fn read_enum<'facet, const BORROW: bool>(
+    partial: Partial<'facet, BORROW>,
+    variants: &'static [Variant],
+    tag: NbtTag,
+    reader: &mut NbtReader<'_>,
+) -> Result<Partial<'facet, BORROW>, NbtError> {
+    let find = |name: &str| {
+        variants
+            .iter()
+            .position(|v| v.effective_name() == name)
+            .ok_or_else(|| NbtError::new(NbtErrorKind::UnknownVariant(String::from(name))))
+    };
+
+    if tag == NbtTag::String {
+        let index = find(&reader.read_string()?)?;
+        return partial.select_nth_variant(index).map_err(Into::into);
+    }
+    expect(NbtTag::Compound, tag)?;
+
+    reader.enter()?;
+    let inner = reader.read_tag()?;
+    if inner == NbtTag::End {
+        return Err(NbtError::new(NbtErrorKind::UnknownVariant(String::new())));
+    }
+    let index = find(&reader.read_string()?)?;
+    let variant = &variants[index];
+
+    let mut partial = partial.select_nth_variant(index)?;
+    if is_newtype(variant.data.kind, variant.data.fields) {
+        partial = read_value(partial.begin_nth_field(0)?, inner, reader)?.end()?;
+    } else {
+        expect(NbtTag::Compound, inner)?;
+        partial = read_fields(partial, variant.data.fields, reader)?;
+    }
+
+    // Ignore any other entries
+    loop {
+        let tag = reader.read_tag()?;
+        if tag == NbtTag::End {
+            break;
+        }
+        reader.read_string()?;
+        reader.skip(tag)?;
+    }
+    reader.exit();
+    Ok(partial)
+}
+
+/// Returns `true` if a variant holds a single unnamed field.
+pub(super) const fn is_newtype(kind: StructKind, fields: &[Field]) -> bool {
+    matches!(kind, StructKind::TupleStruct | StructKind::Tuple) && fields.len() == 1
+}
+
+/// Read the entries of a compound into the fields of a struct or variant.
+///
+/// Unknown entries are skipped and missing fields are filled with their
+/// default value, if they have one.
+fn read_fields<'facet, const BORROW: bool>(
+    mut partial: Partial<'facet, BORROW>,
+    fields: &'static [Field],
+    reader: &mut NbtReader<'_>,
+) -> Result<Partial<'facet, BORROW>, NbtError> {
+    reader.enter()?;
+    loop {
+        let tag = reader.read_tag()?;
+        if tag == NbtTag::End {
+            break;
+        }
+
+        let name = reader.read_string()?;
+        if let Some(index) = fields.iter().position(|field| {
+            field.effective_name() == name || field.alias.is_some_and(|alias| alias == name)
+        }) {
+            partial = read_value(partial.begin_nth_field(index)?, tag, reader)?.end()?;
+        } else {
+            reader.skip(tag)?;
+        }
+    }
+    reader.exit();
+
+    for (index, field) in fields.iter().enumerate() {
+        if partial.is_field_set(index)? {
+            continue;
+        }
+
+        if field.has_default() || matches!(field.shape().def, Def::Option(_)) {
+            partial = partial.set_nth_field_to_default(index)?;
+        } else {
+            return Err(NbtError::new(NbtErrorKind::MissingField(field.effective_name())));
+        }
+    }
+
+    Ok(partial)
+}
+
+/// Read a scalar payload into the current frame.
+fn read_scalar<'facet, const BORROW: bool>(
+    partial: Partial<'facet, BORROW>,
+    scalar: ScalarType,
+    tag: NbtTag,
+    reader: &mut NbtReader<'_>,
+) -> Result<Partial<'facet, BORROW>, NbtError> {
+    let shape = partial.shape();
+    #[expect(clippy::cast_sign_loss, reason = "NBT is signed")]
+    let partial = match scalar {
+        ScalarType::Bool => partial.set(expect(NbtTag::Byte, tag).and(reader.read_i8())? != 0),
+        ScalarType::I8 => partial.set(expect(NbtTag::Byte, tag).and(reader.read_i8())?),
+        ScalarType::U8 => partial.set(expect(NbtTag::Byte, tag).and(reader.read_i8())? as u8),
+        ScalarType::I16 => partial.set(expect(NbtTag::Short, tag).and(reader.read_i16())?),
+        ScalarType::U16 => partial.set(expect(NbtTag::Short, tag).and(reader.read_i16())? as u16),
+        ScalarType::I32 => partial.set(expect(NbtTag::Int, tag).and(reader.read_i32())?),
+        ScalarType::U32 => partial.set(expect(NbtTag::Int, tag).and(reader.read_i32())? as u32),
+        ScalarType::I64 => partial.set(expect(NbtTag::Long, tag).and(reader.read_i64())?),
+        ScalarType::U64 => partial.set(expect(NbtTag::Long, tag).and(reader.read_i64())? as u64),
+        ScalarType::F32 => partial.set(expect(NbtTag::Float, tag).and(reader.read_f32())?),
+        ScalarType::F64 => partial.set(expect(NbtTag::Double, tag).and(reader.read_f64())?),
+        ScalarType::String => {
+            partial.set(expect(NbtTag::String, tag).and(reader.read_string())?.into_owned())
+        }
+        ScalarType::CowStr => {
+            let value = expect(NbtTag::String, tag).and(reader.read_string())?.into_owned();
+            partial.set(Cow::<'static, str>::Owned(value))
+        }
+        _ => return Err(NbtError::unsupported_shape(shape)),
+    };
+    partial.map_err(Into::into)
+}
+
+/// Check the tag of a list or array, returning the tag of its elements.
+///
+/// Sequences of `i8`/`u8`, `i32` and `i64` are read from `TAG_Byte_Array`,
+/// `TAG_Int_Array` and `TAG_Long_Array`, and anything else from `TAG_List`.
+fn expect_sequence(
+    element: &'static facet::Shape,
+    tag: NbtTag,
+    reader: &mut NbtReader<'_>,
+) -> Result<NbtTag, NbtError> {
+    match ScalarType::try_from_shape(element) {
+        Some(ScalarType::U8 | ScalarType::I8) => {
+            expect(NbtTag::ByteArray, tag).map(|()| NbtTag::Byte)
+        }
+        Some(ScalarType::I32) => expect(NbtTag::IntArray, tag).map(|()| NbtTag::Int),
+        Some(ScalarType::I64) => expect(NbtTag::LongArray, tag).map(|()| NbtTag::Long),
+        _ => {
+            // The element type is checked against each element,
+            // so empty lists of `TAG_End` are accepted.
+            expect(NbtTag::List, tag).and(reader.read_tag())
+        }
+    }
+}
+
+/// Check that the found tag matches the expected tag.
+fn expect(expected: NbtTag, found: NbtTag) -> Result<(), NbtError> {
+    if expected == found { Ok(()) } else { Err(NbtError::mismatch(expected, found)) }
+}
@@ -0,0 +1,129 @@
+use alloc::string::String;
+use core::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+use facet::Shape;
+use facet_reflect::ReflectError;
+
+use super::NbtTag;
+
+/// An error that occurred while encoding or decoding NBT.
+#[derive(Debug)]
+pub struct NbtError {
+    kind: NbtErrorKind,
+}
+
+impl NbtError {
+    /// Create a new [`NbtError`].
+    #[inline]
+    #[must_use]
+    pub const fn new(kind: NbtErrorKind) -> Self { Self { kind } }
+
+    /// Create a new [`NbtError`] for an unsupported shape.
+    #[inline]
+    #[must_use]
+    pub const fn unsupported_shape(shape: &'static Shape) -> Self {
+        Self::new(NbtErrorKind::UnsupportedType(shape))
+    }
+
+    /// Create a new [`NbtError`] for a tag that does not match the expected
+    /// tag.
+    #[inline]
+    #[must_use]
+    pub const fn mismatch(expected: NbtTag, found: NbtTag) -> Self {
+        Self::new(NbtErrorKind::TagMismatch { expected, found })
+    }
+
+    /// Get the kind of NBT error.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> &NbtErrorKind { &self.kind }
+}
+
+/// The type of NBT error.
+#[derive(Debug)]
+pub enum NbtErrorKind {
+    /// An error occurred while writing to the buffer.
+    BufferError,
+
+    /// The input ended unexpectedly.
+    UnexpectedEndOfInput {
+        /// The number of additional bytes expected.
+        expected: usize,
+        /// The number of bytes actually found.
+        found: usize,
+    },
+    /// An unknown tag id was encountered.
+    InvalidTag(u8),
+    /// A tag of a different type was expected.
+    TagMismatch {
+        /// The expected tag.
+        expected: NbtTag,
+        /// The tag actually found.
+        found: NbtTag,
+    },
+    /// A list or array had a negative length.
+    NegativeLength(i32),
+    /// A string was not valid.
+    InvalidString,
+    /// A list contained elements of different types.
+    HeterogeneousList {
+        /// The type of the first element.
+        expected: NbtTag,
+        /// The type of the mismatched element.
+        found: NbtTag,
+    },
+    /// The input was nested deeper than [`MAX_DEPTH`](super::MAX_DEPTH).
+    DepthLimit,
+
+    /// A required field was missing from a compound.
+    MissingField(&'static str),
+    /// An enum variant was not recognized.
+    UnknownVariant(String),
+    /// A string was too long to be encoded.
+    StringTooLong(usize),
+    /// An NBT path was invalid at the given byte offset.
+    InvalidPath(usize),
+
+    /// Attempted to encode or decode a type that is not supported.
+    UnsupportedType(&'static Shape),
+    /// An error occurred while building the value.
+    Reflect(ReflectError),
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Error for NbtError {}
+impl Display for NbtError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            NbtErrorKind::BufferError => f.write_str("failed to write to the buffer"),
+            NbtErrorKind::UnexpectedEndOfInput { expected, found } => {
+                write!(f, "unexpected end of input, expected {expected} bytes but found {found}")
+            }
+            NbtErrorKind::InvalidTag(id) => write!(f, "invalid tag id {id}"),
+            NbtErrorKind::TagMismatch { expected, found } => {
+                write!(f, "expected {expected}, found {found}")
+            }
+            NbtErrorKind::NegativeLength(len) => write!(f, "negative length {len}"),
+            NbtErrorKind::InvalidString => f.write_str("invalid string"),
+            NbtErrorKind::HeterogeneousList { expected, found } => {
+                write!(f, "list of {expected} contains a {found}")
+            }
+            NbtErrorKind::DepthLimit => f.write_str("maximum nesting depth exceeded"),
+            NbtErrorKind::MissingField(name) => write!(f, "missing field `{name}`"),
+            NbtErrorKind::UnknownVariant(name) => write!(f, "unknown variant `{name}`"),
+            NbtErrorKind::StringTooLong(len) => write!(f, "string of {len} bytes is too long"),
+            NbtErrorKind::InvalidPath(pos) => write!(f, "invalid NBT path at offset {pos}"),
+            NbtErrorKind::UnsupportedType(shape) => write!(f, "unsupported type `{shape}`"),
+            NbtErrorKind::Reflect(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+impl From<ReflectError> for NbtError {
+    #[inline]
+    fn from(err: ReflectError) -> Self { Self::new(NbtErrorKind::Reflect(err)) }
+}
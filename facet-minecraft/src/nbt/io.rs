@@ -0,0 +1,249 @@
+use alloc::borrow::Cow;
+
+use super::{MAX_DEPTH, NbtError, NbtErrorKind, NbtTag};
+use crate::{mutf8, serialize::SerializeBuffer};
+
+/// A reader for NBT primitives.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct NbtReader<'de> {
+    input: &'de [u8],
+    consumed: usize,
+    depth: usize,
+}
+
+impl<'de> NbtReader<'de> {
+    /// Create a new [`NbtReader`] over the given input.
+    #[inline]
+    #[must_use]
+    pub(crate) const fn new(input: &'de [u8]) -> Self { Self { input, consumed: 0, depth: 0 } }
+
+    /// Returns the number of bytes consumed so far.
+    #[inline]
+    #[must_use]
+    pub(crate) const fn consumed(&self) -> usize { self.consumed }
+
+    /// Returns the input that has not been consumed yet.
+    #[inline]
+    #[must_use]
+    pub(crate) fn remaining(&self) -> &'de [u8] { &self.input[self.consumed..] }
+
+    /// Enter a nested compound or list.
+    pub(crate) fn enter(&mut self) -> Result<(), NbtError> {
+        if self.depth >= MAX_DEPTH {
+            Err(NbtError::new(NbtErrorKind::DepthLimit))
+        } else {
+            self.depth += 1;
+            Ok(())
+        }
+    }
+
+    /// Exit a nested compound or list.
+    #[inline]
+    pub(crate) const fn exit(&mut self) { self.depth = self.depth.saturating_sub(1); }
+
+    /// Read `len` raw bytes.
+    pub(crate) fn read_bytes(&mut self, len: usize) -> Result<&'de [u8], NbtError> {
+        let remaining = self.remaining();
+        if let Some(bytes) = remaining.get(..len) {
+            self.consumed += len;
+            Ok(bytes)
+        } else {
+            Err(NbtError::new(NbtErrorKind::UnexpectedEndOfInput {
+                expected: self.consumed + len,
+                found: self.input.len(),
+            }))
+        }
+    }
+
+    /// Read a fixed number of raw bytes.
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], NbtError> {
+        self.read_bytes(N).map(|bytes| bytes.try_into().unwrap_or([0; N]))
+    }
+
+    /// Read a tag id.
+    pub(crate) fn read_tag(&mut self) -> Result<NbtTag, NbtError> {
+        let [id] = self.read_array()?;
+        NbtTag::from_id(id).ok_or(NbtError::new(NbtErrorKind::InvalidTag(id)))
+    }
+
+    /// Read a `TAG_Byte` payload.
+    pub(crate) fn read_i8(&mut self) -> Result<i8, NbtError> {
+        self.read_array().map(i8::from_be_bytes)
+    }
+
+    /// Read a `TAG_Short` payload.
+    pub(crate) fn read_i16(&mut self) -> Result<i16, NbtError> {
+        self.read_array().map(i16::from_be_bytes)
+    }
+
+    /// Read a `TAG_Int` payload.
+    pub(crate) fn read_i32(&mut self) -> Result<i32, NbtError> {
+        self.read_array().map(i32::from_be_bytes)
+    }
+
+    /// Read a `TAG_Long` payload.
+    pub(crate) fn read_i64(&mut self) -> Result<i64, NbtError> {
+        self.read_array().map(i64::from_be_bytes)
+    }
+
+    /// Read a `TAG_Float` payload.
+    pub(crate) fn read_f32(&mut self) -> Result<f32, NbtError> {
+        self.read_array().map(f32::from_be_bytes)
+    }
+
+    /// Read a `TAG_Double` payload.
+    pub(crate) fn read_f64(&mut self) -> Result<f64, NbtError> {
+        self.read_array().map(f64::from_be_bytes)
+    }
+
+    /// Read the length of a list or array.
+    pub(crate) fn read_length(&mut self) -> Result<usize, NbtError> {
+        let length = self.read_i32()?;
+        usize::try_from(length).map_err(|_| NbtError::new(NbtErrorKind::NegativeLength(length)))
+    }
+
+    /// Read a `TAG_String` payload, encoded as Modified UTF-8.
+    pub(crate) fn read_string(&mut self) -> Result<Cow<'de, str>, NbtError> {
+        let [a, b] = self.read_array()?;
+        let bytes = self.read_bytes(usize::from(u16::from_be_bytes([a, b])))?;
+        mutf8::decode(bytes).map_err(|_| NbtError::new(NbtErrorKind::InvalidString))
+    }
+
+    /// Skip the payload of a tag.
+    pub(crate) fn skip(&mut self, tag: NbtTag) -> Result<(), NbtError> {
+        match tag {
+            NbtTag::End => Ok(()),
+            NbtTag::Byte => self.read_bytes(1).map(|_| ()),
+            NbtTag::Short => self.read_bytes(2).map(|_| ()),
+            NbtTag::Int | NbtTag::Float => self.read_bytes(4).map(|_| ()),
+            NbtTag::Long | NbtTag::Double => self.read_bytes(8).map(|_| ()),
+            NbtTag::ByteArray => {
+                let length = self.read_length()?;
+                self.read_bytes(length).map(|_| ())
+            }
+            NbtTag::IntArray => {
+                let length = self.read_length()?;
+                self.read_bytes(length.saturating_mul(4)).map(|_| ())
+            }
+            NbtTag::LongArray => {
+                let length = self.read_length()?;
+                self.read_bytes(length.saturating_mul(8)).map(|_| ())
+            }
+            NbtTag::String => {
+                // Skipped strings do not need to be decoded
+                let [a, b] = self.read_array()?;
+                self.read_bytes(usize::from(u16::from_be_bytes([a, b]))).map(|_| ())
+            }
+            NbtTag::List => {
+                let element = self.read_tag()?;
+                let length = self.read_length()?;
+                if element == NbtTag::End && length != 0 {
+                    return Err(NbtError::new(NbtErrorKind::InvalidTag(NbtTag::End.id())));
+                }
+
+                self.enter()?;
+                for _ in 0..length {
+                    self.skip(element)?;
+                }
+                self.exit();
+                Ok(())
+            }
+            NbtTag::Compound => {
+                self.enter()?;
+                loop {
+                    let tag = self.read_tag()?;
+                    if tag == NbtTag::End {
+                        break;
+                    }
+                    self.read_string()?;
+                    self.skip(tag)?;
+                }
+                self.exit();
+                Ok(())
+            }
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// A writer for NBT primitives.
+pub(crate) struct NbtWriter<'buffer> {
+    buffer: &'buffer mut dyn SerializeBuffer,
+}
+
+impl<'buffer> NbtWriter<'buffer> {
+    /// Create a new [`NbtWriter`].
+    #[inline]
+    #[must_use]
+    pub(crate) const fn new(buffer: &'buffer mut dyn SerializeBuffer) -> Self { Self { buffer } }
+
+    /// Write raw bytes into the buffer.
+    pub(crate) fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), NbtError> {
+        if self.buffer.extend_buffer(bytes) {
+            Ok(())
+        } else {
+            Err(NbtError::new(NbtErrorKind::BufferError))
+        }
+    }
+
+    /// Write a tag id.
+    #[inline]
+    pub(crate) fn write_tag(&mut self, tag: NbtTag) -> Result<(), NbtError> {
+        self.write_bytes(&[tag.id()])
+    }
+
+    /// Write a `TAG_Byte` payload.
+    #[inline]
+    pub(crate) fn write_i8(&mut self, value: i8) -> Result<(), NbtError> {
+        self.write_bytes(&value.to_be_bytes())
+    }
+
+    /// Write a `TAG_Short` payload.
+    #[inline]
+    pub(crate) fn write_i16(&mut self, value: i16) -> Result<(), NbtError> {
+        self.write_bytes(&value.to_be_bytes())
+    }
+
+    /// Write a `TAG_Int` payload.
+    #[inline]
+    pub(crate) fn write_i32(&mut self, value: i32) -> Result<(), NbtError> {
+        self.write_bytes(&value.to_be_bytes())
+    }
+
+    /// Write a `TAG_Long` payload.
+    #[inline]
+    pub(crate) fn write_i64(&mut self, value: i64) -> Result<(), NbtError> {
+        self.write_bytes(&value.to_be_bytes())
+    }
+
+    /// Write a `TAG_Float` payload.
+    #[inline]
+    pub(crate) fn write_f32(&mut self, value: f32) -> Result<(), NbtError> {
+        self.write_bytes(&value.to_be_bytes())
+    }
+
+    /// Write a `TAG_Double` payload.
+    #[inline]
+    pub(crate) fn write_f64(&mut self, value: f64) -> Result<(), NbtError> {
+        self.write_bytes(&value.to_be_bytes())
+    }
+
+    /// Write the length of a list or array.
+    pub(crate) fn write_length(&mut self, length: usize) -> Result<(), NbtError> {
+        match i32::try_from(length) {
+            Ok(length) => self.write_i32(length),
+            Err(_) => Err(NbtError::new(NbtErrorKind::BufferError)),
+        }
+    }
+
+    /// Write a `TAG_String` payload, encoded as Modified UTF-8.
+    pub(crate) fn write_string(&mut self, value: &str) -> Result<(), NbtError> {
+        let bytes = mutf8::encode(value);
+        let Ok(length) = u16::try_from(bytes.len()) else {
+            return Err(NbtError::new(NbtErrorKind::StringTooLong(bytes.len())));
+        };
+        self.write_bytes(&length.to_be_bytes())?;
+        self.write_bytes(&bytes)
+    }
+}
@@ -0,0 +1,153 @@
+//! Named Binary Tag (NBT) encoding for any [`Facet`] type.
+//!
+//! Values are mapped to tags by their shape:
+//!
+//! | Type                               | Tag                                |
+//! |------------------------------------|------------------------------------|
+//! | `bool`, `i8`, `u8`                 | `TAG_Byte`                         |
+//! | `i16`, `u16`                       | `TAG_Short`                        |
+//! | `i32`, `u32`                       | `TAG_Int`                          |
+//! | `i64`, `u64`                       | `TAG_Long`                         |
+//! | `f32`                              | `TAG_Float`                        |
+//! | `f64`                              | `TAG_Double`                       |
+//! | `String`, `&str`, `Cow<str>`       | `TAG_String`                       |
+//! | `Vec<i8>`, `Vec<u8>`               | `TAG_Byte_Array`                   |
+//! | `Vec<i32>`                         | `TAG_Int_Array`                    |
+//! | `Vec<i64>`                         | `TAG_Long_Array`                   |
+//! | `Uuid`                             | `TAG_Int_Array` of length 4        |
+//! | `Vec<T>`, `[T; N]`                 | `TAG_List`                         |
+//! | structs, `HashMap<String, T>`      | `TAG_Compound`                     |
+//! | unit enum variants                 | `TAG_String` of the variant name   |
+//! | enum variants with data            | `TAG_Compound` with a single entry |
+//!
+//! Fields containing `None` are omitted, and missing fields are filled with
+//! their default value when decoding, if they have one.
+//!
+//! Values without a fixed schema can be decoded into an [`NbtValue`] tree
+//! and queried using an [`NbtPath`]. Both can be written as text using
+//! [`snbt`].
+//!
+//! Since 1.20.2 the network protocol uses nameless root tags,
+//! see [`to_nbt`] and [`from_nbt`]. Files use named root tags,
+//! see [`to_nbt_named`] and [`from_nbt_named`].
+
+use alloc::{string::String, vec::Vec};
+
+use facet::{Facet, PtrUninit, Shape};
+use facet_reflect::Peek;
+
+use crate::{codec::McReader, deserialize::DeserializeError, serialize::SerializeBuffer};
+
+mod de;
+mod ser;
+pub mod snbt;
+
+mod error;
+pub use error::{NbtError, NbtErrorKind};
+
+mod io;
+pub(crate) use io::{NbtReader, NbtWriter};
+
+mod tag;
+pub use tag::NbtTag;
+
+mod value;
+pub use value::{Iter, IterMut, NbtCompound, NbtList, NbtPath, NbtPathNode, NbtValue};
+
+/// The maximum nesting depth of compounds and lists,
+/// matching the vanilla client.
+pub const MAX_DEPTH: usize = 512;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Serialize a value of type `T` into a nameless root tag.
+///
+/// # Errors
+///
+/// This function will return an error if the type cannot be represented as
+/// NBT.
+pub fn to_nbt<'facet, T: Facet<'facet> + ?Sized>(value: &T) -> Result<Vec<u8>, NbtError> {
+    let mut buffer = Vec::new();
+    to_nbt_buffer(value, &mut buffer)?;
+    Ok(buffer)
+}
+
+/// Serialize a value of type `T` into a root tag with the given name.
+///
+/// # Errors
+///
+/// This function will return an error if the type cannot be represented as
+/// NBT.
+pub fn to_nbt_named<'facet, T: Facet<'facet> + ?Sized>(
+    name: &str,
+    value: &T,
+) -> Result<Vec<u8>, NbtError> {
+    let mut buffer = Vec::new();
+    ser::write_root(Peek::new(value), Some(name), &mut NbtWriter::new(&mut buffer))?;
+    Ok(buffer)
+}
+
+/// Serialize a value of type `T` into a nameless root tag in a buffer,
+/// returning a slice containing the serialized data.
+///
+/// # Errors
+///
+/// This function will return an error if the type cannot be represented as
+/// NBT, or if the buffer cannot be written to.
+pub fn to_nbt_buffer<'output, 'facet, T: Facet<'facet> + ?Sized, B: SerializeBuffer>(
+    value: &T,
+    buffer: &'output mut B,
+) -> Result<&'output [u8], NbtError> {
+    ser::write_root(Peek::new(value), None, &mut NbtWriter::new(buffer))?;
+    Ok(buffer.get_content())
+}
+
+/// Serialize a [`Peek`] into a nameless root tag.
+pub(crate) fn write_peek(peek: Peek<'_, '_>, writer: &mut NbtWriter<'_>) -> Result<(), NbtError> {
+    ser::write_root(peek, None, writer)
+}
+
+/// Deserialize a nameless root tag into uninitialized memory,
+/// such as a field marked with `mc::nbt`.
+///
+/// # Safety
+///
+/// `ptr` must be valid for writes of a value described by `shape`.
+/// The value is only initialized if this returns `Ok`.
+pub(crate) unsafe fn read_raw(
+    ptr: PtrUninit,
+    shape: &'static Shape,
+    reader: &mut McReader<'_>,
+) -> Result<(), DeserializeError> {
+    let mut nbt = NbtReader::new(reader.remaining());
+    // SAFETY: Guaranteed by the caller.
+    unsafe { de::read_root_raw(ptr, shape, &mut nbt)? };
+    reader.read_bytes(nbt.consumed())?;
+    Ok(())
+}
+
+/// Deserialize a value of type `T` from a nameless root tag,
+/// returning any remaining bytes.
+///
+/// # Errors
+///
+/// This function will return an error if the input is not valid NBT,
+/// or does not match the type.
+pub fn from_nbt<T: Facet<'static>>(input: &[u8]) -> Result<(T, &[u8]), NbtError> {
+    let mut reader = NbtReader::new(input);
+    let (_, value) = de::read_root::<T>(&mut reader, false)?;
+    Ok((value, reader.remaining()))
+}
+
+/// Deserialize a value of type `T` from a named root tag,
+/// returning the name and any remaining bytes.
+///
+/// # Errors
+///
+/// This function will return an error if the input is not valid NBT,
+/// or does not match the type.
+pub fn from_nbt_named<T: Facet<'static>>(input: &[u8]) -> Result<(String, T, &[u8]), NbtError> {
+    let mut reader = NbtReader::new(input);
+    let (name, value) = de::read_root::<T>(&mut reader, true)?;
+    Ok((name, value, reader.remaining()))
+}
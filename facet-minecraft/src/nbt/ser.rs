@@ -0,0 +1,257 @@
+use facet::{Def, ScalarType, StructKind, Type, UserType, Variant};
+use facet_reflect::{HasFields, Peek, PeekEnum};
+use uuid::Uuid;
+
+use super::{NbtCompound, NbtError, NbtErrorKind, NbtTag, NbtValue, io::NbtWriter};
+
+/// Write a root tag, with an optional name.
+///
+/// A root `None` is written as a single [`NbtTag::End`].
+pub(crate) fn write_root(
+    peek: Peek<'_, '_>,
+    name: Option<&str>,
+    writer: &mut NbtWriter<'_>,
+) -> Result<(), NbtError> {
+    let Some(peek) = unwrap_option(peek) else { return writer.write_tag(NbtTag::End) };
+
+    let tag = tag_of(peek)?;
+    writer.write_tag(tag)?;
+    if let Some(name) = name {
+        writer.write_string(name)?;
+    }
+    write_payload(peek, tag, writer)
+}
+
+/// Unwrap pointers, transparent wrappers and [`Option`]s,
+/// returning `None` if the value is absent.
+fn unwrap_option<'mem, 'facet>(peek: Peek<'mem, 'facet>) -> Option<Peek<'mem, 'facet>> {
+    let peek = peek.innermost_peek();
+    if let Ok(option) = peek.into_option() {
+        option.value().and_then(unwrap_option)
+    } else {
+        Some(peek)
+    }
+}
+
+/// Get the [`NbtTag`] a value will be written as.
+fn tag_of(peek: Peek<'_, '_>) -> Result<NbtTag, NbtError> {
+    let shape = peek.shape();
+    if shape.is_type::<Uuid>() {
+        return Ok(NbtTag::IntArray);
+    } else if let Ok(value) = peek.get::<NbtValue>() {
+        return Ok(value.tag());
+    } else if shape.is_type::<NbtCompound>() {
+        return Ok(NbtTag::Compound);
+    }
+
+    if let Some(scalar) = peek.scalar_type() {
+        return match scalar {
+            ScalarType::Bool | ScalarType::U8 | ScalarType::I8 => Ok(NbtTag::Byte),
+            ScalarType::U16 | ScalarType::I16 => Ok(NbtTag::Short),
+            ScalarType::U32 | ScalarType::I32 => Ok(NbtTag::Int),
+            ScalarType::U64 | ScalarType::I64 => Ok(NbtTag::Long),
+            ScalarType::F32 => Ok(NbtTag::Float),
+            ScalarType::F64 => Ok(NbtTag::Double),
+            ScalarType::Str | ScalarType::String | ScalarType::CowStr => Ok(NbtTag::String),
+            _ => Err(NbtError::unsupported_shape(shape)),
+        };
+    }
+
+    match shape.def {
+        Def::List(_) | Def::Array(_) | Def::Slice(_) => {
+            let element = peek.into_list_like()?.def().t();
+            match ScalarType::try_from_shape(element) {
+                Some(ScalarType::U8 | ScalarType::I8) => Ok(NbtTag::ByteArray),
+                Some(ScalarType::I32) => Ok(NbtTag::IntArray),
+                Some(ScalarType::I64) => Ok(NbtTag::LongArray),
+                _ => Ok(NbtTag::List),
+            }
+        }
+        Def::Map(_) => Ok(NbtTag::Compound),
+        _ => match shape.ty {
+            Type::User(UserType::Struct(ty)) if ty.kind != StructKind::Tuple => {
+                Ok(NbtTag::Compound)
+            }
+            Type::User(UserType::Enum(_)) => {
+                let (_, variant) = active_variant(peek)?;
+                if variant.data.fields.is_empty() {
+                    Ok(NbtTag::String)
+                } else {
+                    Ok(NbtTag::Compound)
+                }
+            }
+            _ => Err(NbtError::unsupported_shape(shape)),
+        },
+    }
+}
+
+/// Write the payload of a value as the given [`NbtTag`].
+fn write_payload(
+    peek: Peek<'_, '_>,
+    tag: NbtTag,
+    writer: &mut NbtWriter<'_>,
+) -> Result<(), NbtError> {
+    /// Read a scalar value and cast it into the written type.
+    macro_rules! scalar {
+        ($($ty:ty),*; $write:ident as $out:ty) => {{
+            $(
+                if let Ok(value) = peek.get::<$ty>() {
+                    #[allow(clippy::cast_possible_wrap, trivial_numeric_casts, reason = "NBT is signed")]
+                    return writer.$write(*value as $out);
+                }
+            )*
+            Err(NbtError::unsupported_shape(peek.shape()))
+        }};
+    }
+
+    if let Ok(value) = peek.get::<NbtValue>() {
+        return value.write(writer);
+    } else if let Ok(compound) = peek.get::<NbtCompound>() {
+        return compound.write(writer);
+    }
+
+    match tag {
+        NbtTag::End => Ok(()),
+        NbtTag::Byte => {
+            if let Ok(value) = peek.get::<bool>() {
+                writer.write_i8(i8::from(*value))
+            } else {
+                scalar!(i8, u8; write_i8 as i8)
+            }
+        }
+        NbtTag::Short => scalar!(i16, u16; write_i16 as i16),
+        NbtTag::Int => scalar!(i32, u32; write_i32 as i32),
+        NbtTag::Long => scalar!(i64, u64; write_i64 as i64),
+        NbtTag::Float => scalar!(f32; write_f32 as f32),
+        NbtTag::Double => scalar!(f64; write_f64 as f64),
+        NbtTag::String => {
+            if let Some(value) = peek.as_str() {
+                writer.write_string(value)
+            } else {
+                let (_, variant) = active_variant(peek)?;
+                writer.write_string(variant.effective_name())
+            }
+        }
+
+        NbtTag::ByteArray | NbtTag::IntArray | NbtTag::LongArray => write_array(peek, writer),
+        NbtTag::List => write_list(peek, writer),
+        NbtTag::Compound => write_compound(peek, writer),
+    }
+}
+
+/// Write a `TAG_Byte_Array`, `TAG_Int_Array` or `TAG_Long_Array` payload.
+fn write_array(peek: Peek<'_, '_>, writer: &mut NbtWriter<'_>) -> Result<(), NbtError> {
+    if let Ok(uuid) = peek.get::<Uuid>() {
+        writer.write_length(4)?;
+        return writer.write_bytes(uuid.as_bytes());
+    }
+
+    let list = peek.into_list_like()?;
+    writer.write_length(list.len())?;
+
+    if let Some(bytes) = list.as_bytes() {
+        return writer.write_bytes(bytes);
+    }
+    for element in list.iter() {
+        if let Ok(value) = element.get::<i8>() {
+            writer.write_i8(*value)?;
+        } else if let Ok(value) = element.get::<i32>() {
+            writer.write_i32(*value)?;
+        } else if let Ok(value) = element.get::<i64>() {
+            writer.write_i64(*value)?;
+        } else {
+            return Err(NbtError::unsupported_shape(element.shape()));
+        }
+    }
+    Ok(())
+}
+
+/// Write a `TAG_List` payload.
+///
+/// Empty lists are written as a list of [`NbtTag::End`], like the vanilla
+/// client.
+fn write_list(peek: Peek<'_, '_>, writer: &mut NbtWriter<'_>) -> Result<(), NbtError> {
+    let list = peek.into_list_like()?;
+    let Some(first) = list.get(0) else {
+        writer.write_tag(NbtTag::End)?;
+        return writer.write_length(0);
+    };
+
+    let element = tag_of(first.innermost_peek())?;
+    writer.write_tag(element)?;
+    writer.write_length(list.len())?;
+
+    for value in list.iter() {
+        let value = value.innermost_peek();
+        let found = tag_of(value)?;
+        if found != element {
+            return Err(NbtError::new(NbtErrorKind::HeterogeneousList {
+                expected: element,
+                found,
+            }));
+        }
+        write_payload(value, element, writer)?;
+    }
+    Ok(())
+}
+
+/// Write a `TAG_Compound` payload.
+///
+/// Struct fields and map entries are written in order, skipping `None`
+/// values. Enum variants with data are written as a compound containing a
+/// single entry named after the variant.
+fn write_compound(peek: Peek<'_, '_>, writer: &mut NbtWriter<'_>) -> Result<(), NbtError> {
+    if let Ok(map) = peek.into_map() {
+        for (key, value) in map.iter() {
+            let Some(key) = key.innermost_peek().as_str() else {
+                return Err(NbtError::unsupported_shape(key.shape()));
+            };
+            write_entry(key, value, writer)?;
+        }
+    } else if peek.into_enum().is_ok() {
+        let (value, variant) = active_variant(peek)?;
+        if super::de::is_newtype(variant.data.kind, variant.data.fields) {
+            if let Ok(Some(field)) = value.field(0) {
+                write_entry(variant.effective_name(), field, writer)?;
+            }
+        } else {
+            writer.write_tag(NbtTag::Compound)?;
+            writer.write_string(variant.effective_name())?;
+            for (field, value) in value.fields_for_serialize() {
+                write_entry(field.effective_name(), value, writer)?;
+            }
+            writer.write_tag(NbtTag::End)?;
+        }
+    } else {
+        for (field, value) in peek.into_struct()?.fields_for_serialize() {
+            write_entry(field.effective_name(), value, writer)?;
+        }
+    }
+
+    writer.write_tag(NbtTag::End)
+}
+
+/// Write a named entry of a compound, skipping `None` values.
+fn write_entry(
+    name: &str,
+    value: Peek<'_, '_>,
+    writer: &mut NbtWriter<'_>,
+) -> Result<(), NbtError> {
+    let Some(value) = unwrap_option(value) else { return Ok(()) };
+
+    let tag = tag_of(value)?;
+    writer.write_tag(tag)?;
+    writer.write_string(name)?;
+    write_payload(value, tag, writer)
+}
+
+/// Get the active variant of an enum.
+fn active_variant<'mem, 'facet>(
+    peek: Peek<'mem, 'facet>,
+) -> Result<(PeekEnum<'mem, 'facet>, &'static Variant), NbtError> {
+    let value = peek.into_enum()?;
+    match value.active_variant() {
+        Ok(variant) => Ok((value, variant)),
+        Err(_) => Err(NbtError::unsupported_shape(peek.shape())),
+    }
+}
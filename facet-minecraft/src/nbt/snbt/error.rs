@@ -0,0 +1,103 @@
+use core::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    ops::Range,
+};
+
+use crate::nbt::{NbtError, NbtTag};
+
+/// An error that occurred while parsing SNBT.
+#[derive(Debug)]
+pub struct SnbtError {
+    kind: SnbtErrorKind,
+    span: Range<usize>,
+    line: usize,
+    column: usize,
+}
+
+impl SnbtError {
+    /// Create a new [`SnbtError`] covering a span of the input.
+    #[must_use]
+    pub fn new(kind: SnbtErrorKind, span: Range<usize>, input: &str) -> Self {
+        let before = input.get(..span.start).unwrap_or(input);
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().map_or(0, |line| line.chars().count()) + 1;
+        Self { kind, span, line, column }
+    }
+
+    /// Get the kind of SNBT error.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> &SnbtErrorKind { &self.kind }
+
+    /// Get the byte range of the input the error occurred at.
+    #[inline]
+    #[must_use]
+    pub fn span(&self) -> Range<usize> { self.span.clone() }
+
+    /// Get the line the error occurred at, starting from `1`.
+    #[inline]
+    #[must_use]
+    pub const fn line(&self) -> usize { self.line }
+
+    /// Get the column the error occurred at, in characters starting from `1`.
+    #[inline]
+    #[must_use]
+    pub const fn column(&self) -> usize { self.column }
+}
+
+/// The type of SNBT error.
+#[derive(Debug)]
+pub enum SnbtErrorKind {
+    /// The input ended unexpectedly.
+    UnexpectedEndOfInput,
+    /// An unexpected character was encountered.
+    UnexpectedCharacter(char),
+    /// Input remained after the value.
+    TrailingCharacters,
+    /// An invalid escape sequence was encountered in a quoted string.
+    InvalidEscape,
+    /// A number was out of range for its type.
+    InvalidNumber,
+    /// A list or array contained elements of different types.
+    HeterogeneousList {
+        /// The type of the first element.
+        expected: NbtTag,
+        /// The type of the mismatched element.
+        found: NbtTag,
+    },
+    /// The input was nested deeper than [`MAX_DEPTH`](crate::nbt::MAX_DEPTH).
+    DepthLimit,
+
+    /// The parsed value could not be converted into the requested type.
+    Nbt(NbtError),
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Error for SnbtError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            SnbtErrorKind::Nbt(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for SnbtError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            SnbtErrorKind::UnexpectedEndOfInput => f.write_str("unexpected end of input")?,
+            SnbtErrorKind::UnexpectedCharacter(c) => write!(f, "unexpected character `{c}`")?,
+            SnbtErrorKind::TrailingCharacters => f.write_str("trailing characters")?,
+            SnbtErrorKind::InvalidEscape => f.write_str("invalid escape sequence")?,
+            SnbtErrorKind::InvalidNumber => f.write_str("number out of range")?,
+            SnbtErrorKind::HeterogeneousList { expected, found } => {
+                write!(f, "list of {expected} contains a {found}")?;
+            }
+            SnbtErrorKind::DepthLimit => f.write_str("maximum nesting depth exceeded")?,
+            SnbtErrorKind::Nbt(err) => Display::fmt(err, f)?,
+        }
+        write!(f, " at line {}, column {}", self.line, self.column)
+    }
+}
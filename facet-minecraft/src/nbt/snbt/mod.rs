@@ -0,0 +1,166 @@
+//! Stringified NBT (SNBT), as used by commands and data packs.
+//!
+//! ```text
+//! {name: "Bananrama", count: 1b, scores: [I; 1, 2], ratio: 0.5f}
+//! ```
+//!
+//! Numbers are typed by their suffix:
+//!
+//! | Suffix     | Tag          | Example        |
+//! |------------|--------------|----------------|
+//! | `b`        | `TAG_Byte`   | `1b`, `true`   |
+//! | `s`        | `TAG_Short`  | `1s`           |
+//! | none       | `TAG_Int`    | `1`            |
+//! | `L`        | `TAG_Long`   | `1L`           |
+//! | `f`        | `TAG_Float`  | `1.0f`         |
+//! | `d`        | `TAG_Double` | `1.0d`, `1.0`  |
+//!
+//! Keys and strings only need to be quoted if they contain characters other
+//! than `0-9`, `A-Z`, `a-z`, `_`, `-`, `.` and `+`.
+
+use alloc::string::String;
+use core::fmt::{self, Display, Formatter};
+
+use facet::Facet;
+
+use super::{NbtCompound, NbtError, NbtValue};
+
+mod error;
+pub use error::{SnbtError, SnbtErrorKind};
+
+mod parse;
+use parse::SnbtParser;
+
+mod print;
+
+/// Options for printing SNBT.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SnbtStyle {
+    indent: Option<usize>,
+    sort_keys: bool,
+}
+
+impl SnbtStyle {
+    /// Print everything on a single line without spaces,
+    /// like the vanilla client.
+    pub const COMPACT: Self = Self { indent: None, sort_keys: false };
+    /// Print compounds and nested lists over multiple lines,
+    /// indented by four spaces.
+    pub const PRETTY: Self = Self { indent: Some(4), sort_keys: false };
+
+    /// Set the number of spaces to indent by,
+    /// or `None` to print everything on a single line.
+    #[inline]
+    #[must_use]
+    pub const fn with_indent(mut self, indent: Option<usize>) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Set whether compound entries are sorted by name
+    /// instead of being printed in order.
+    #[inline]
+    #[must_use]
+    pub const fn with_sorted_keys(mut self, sort_keys: bool) -> Self {
+        self.sort_keys = sort_keys;
+        self
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Parse SNBT into an [`NbtValue`].
+///
+/// # Errors
+///
+/// This function will return an error if the input is not valid SNBT.
+pub fn parse(input: &str) -> Result<NbtValue, SnbtError> { SnbtParser::new(input).parse_root() }
+
+/// Parse SNBT into a value of type `T`.
+///
+/// # Errors
+///
+/// This function will return an error if the input is not valid SNBT,
+/// or does not match the type.
+pub fn from_str<T: Facet<'static>>(input: &str) -> Result<T, SnbtError> {
+    let value = parse(input)?;
+    let error = |err| SnbtError::new(SnbtErrorKind::Nbt(err), 0..input.len(), input);
+
+    let bytes = super::to_nbt(&value).map_err(error)?;
+    super::from_nbt::<T>(&bytes).map(|(value, _)| value).map_err(error)
+}
+
+/// Print a value of type `T` as compact SNBT.
+///
+/// # Errors
+///
+/// This function will return an error if the type cannot be represented as
+/// NBT.
+pub fn to_string<'facet, T: Facet<'facet> + ?Sized>(value: &T) -> Result<String, NbtError> {
+    to_string_with(value, SnbtStyle::COMPACT)
+}
+
+/// Print a value of type `T` as pretty-printed SNBT.
+///
+/// # Errors
+///
+/// This function will return an error if the type cannot be represented as
+/// NBT.
+pub fn to_string_pretty<'facet, T: Facet<'facet> + ?Sized>(value: &T) -> Result<String, NbtError> {
+    to_string_with(value, SnbtStyle::PRETTY)
+}
+
+/// Print a value of type `T` as SNBT using the given [`SnbtStyle`].
+///
+/// # Errors
+///
+/// This function will return an error if the type cannot be represented as
+/// NBT.
+pub fn to_string_with<'facet, T: Facet<'facet> + ?Sized>(
+    value: &T,
+    style: SnbtStyle,
+) -> Result<String, NbtError> {
+    let bytes = super::to_nbt(value)?;
+    let (value, _) = super::from_nbt::<NbtValue>(&bytes)?;
+    Ok(value_to_string(&value, style))
+}
+
+/// Print an [`NbtValue`] as SNBT using the given [`SnbtStyle`].
+#[must_use]
+pub fn value_to_string(value: &NbtValue, style: SnbtStyle) -> String {
+    let mut output = String::new();
+    let _ = print::write_value(&mut output, value, style, 0);
+    output
+}
+
+/// Returns `true` if a character can be used in an unquoted key or string.
+const fn is_unquoted(c: char) -> bool {
+    matches!(c, '0'..='9' | 'A'..='Z' | 'a'..='z' | '_' | '-' | '.' | '+')
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Print the value as SNBT, pretty-printed if the alternate flag (`{:#}`) is
+/// set.
+impl Display for NbtValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let style = if f.alternate() { SnbtStyle::PRETTY } else { SnbtStyle::COMPACT };
+        print::write_value(f, self, style, 0)
+    }
+}
+
+/// Print the compound as SNBT, pretty-printed if the alternate flag (`{:#}`)
+/// is set.
+impl Display for NbtCompound {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let style = if f.alternate() { SnbtStyle::PRETTY } else { SnbtStyle::COMPACT };
+        print::write_compound(f, self, style, 0)
+    }
+}
+
+impl core::str::FromStr for NbtValue {
+    type Err = SnbtError;
+
+    #[inline]
+    fn from_str(input: &str) -> Result<Self, Self::Err> { parse(input) }
+}
@@ -0,0 +1,324 @@
+use alloc::{string::String, vec::Vec};
+
+use super::{SnbtError, SnbtErrorKind, is_unquoted};
+use crate::nbt::{MAX_DEPTH, NbtCompound, NbtList, NbtTag, NbtValue};
+
+/// A parser for SNBT.
+pub(super) struct SnbtParser<'a> {
+    input: &'a str,
+    pos: usize,
+    depth: usize,
+}
+
+impl<'a> SnbtParser<'a> {
+    /// Create a new [`SnbtParser`] over the given input.
+    pub(super) const fn new(input: &'a str) -> Self { Self { input, pos: 0, depth: 0 } }
+
+    /// Parse a single value, failing if any input remains.
+    pub(super) fn parse_root(&mut self) -> Result<NbtValue, SnbtError> {
+        let value = self.parse_value()?;
+        self.skip_whitespace();
+        if self.pos < self.input.len() {
+            return Err(self.error(SnbtErrorKind::TrailingCharacters, self.pos..self.input.len()));
+        }
+        Ok(value)
+    }
+
+    /// Parse a value.
+    fn parse_value(&mut self) -> Result<NbtValue, SnbtError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.parse_compound().map(NbtValue::Compound),
+            Some('[') => self.parse_list(),
+            Some(quote @ ('"' | '\'')) => self.parse_quoted(quote).map(NbtValue::String),
+            Some(c) if is_unquoted(c) => {
+                let start = self.pos;
+                let token = self.take_unquoted();
+                match parse_literal(token) {
+                    Some(Ok(value)) => Ok(value),
+                    Some(Err(kind)) => Err(self.error(kind, start..self.pos)),
+                    None => Ok(NbtValue::String(String::from(token))),
+                }
+            }
+            Some(c) => Err(self.unexpected(c)),
+            None => Err(self.end_of_input()),
+        }
+    }
+
+    /// Parse a compound, `{key: value, ...}`.
+    fn parse_compound(&mut self) -> Result<NbtCompound, SnbtError> {
+        self.expect('{')?;
+        self.enter()?;
+
+        let mut compound = NbtCompound::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some('}') {
+                break;
+            }
+
+            let key = match self.peek() {
+                Some(quote @ ('"' | '\'')) => self.parse_quoted(quote)?,
+                Some(c) if is_unquoted(c) => String::from(self.take_unquoted()),
+                Some(c) => return Err(self.unexpected(c)),
+                None => return Err(self.end_of_input()),
+            };
+            self.skip_whitespace();
+            self.expect(':')?;
+            compound.insert(key, self.parse_value()?);
+
+            if !self.separator()? {
+                break;
+            }
+        }
+
+        self.expect('}')?;
+        self.depth -= 1;
+        Ok(compound)
+    }
+
+    /// Parse a list, `[value, ...]`, or an array, `[B; 1b, ...]`.
+    fn parse_list(&mut self) -> Result<NbtValue, SnbtError> {
+        self.expect('[')?;
+        self.enter()?;
+
+        let rest = &self.input[self.pos..];
+        let array = match rest.as_bytes() {
+            [b'B', b';', ..] => Some(NbtTag::Byte),
+            [b'I', b';', ..] => Some(NbtTag::Int),
+            [b'L', b';', ..] => Some(NbtTag::Long),
+            _ => None,
+        };
+        if array.is_some() {
+            self.pos += 2;
+        }
+
+        let mut tag = array.unwrap_or(NbtTag::End);
+        let mut values = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(']') {
+                break;
+            }
+
+            let start = self.pos;
+            let value = self.parse_value()?;
+            if tag == NbtTag::End {
+                tag = value.tag();
+            } else if value.tag() != tag {
+                let kind = SnbtErrorKind::HeterogeneousList { expected: tag, found: value.tag() };
+                return Err(self.error(kind, start..self.pos));
+            }
+            values.push(value);
+
+            if !self.separator()? {
+                break;
+            }
+        }
+
+        self.expect(']')?;
+        self.depth -= 1;
+
+        Ok(match array {
+            Some(NbtTag::Byte) => {
+                NbtValue::ByteArray(values.iter().filter_map(NbtValue::as_i8).collect())
+            }
+            Some(NbtTag::Int) => {
+                NbtValue::IntArray(values.iter().filter_map(NbtValue::as_i32).collect())
+            }
+            Some(NbtTag::Long) => {
+                NbtValue::LongArray(values.iter().filter_map(NbtValue::as_i64).collect())
+            }
+            // Every element has already been checked
+            _ => NbtValue::List(NbtList::try_from(values).unwrap_or_default()),
+        })
+    }
+
+    /// Parse a quoted string, resolving escape sequences.
+    fn parse_quoted(&mut self, quote: char) -> Result<String, SnbtError> {
+        self.expect(quote)?;
+
+        let mut string = String::new();
+        loop {
+            let start = self.pos;
+            match self.next() {
+                Some('\\') => {
+                    let c = match self.next() {
+                        Some(c @ ('\\' | '"' | '\'')) => Some(c),
+                        Some('n') => Some('\n'),
+                        Some('t') => Some('\t'),
+                        Some('r') => Some('\r'),
+                        Some('b') => Some('\u{8}'),
+                        Some('f') => Some('\u{c}'),
+                        Some('u') => self
+                            .input
+                            .get(self.pos..self.pos + 4)
+                            .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                            .and_then(char::from_u32)
+                            .inspect(|_| self.pos += 4),
+                        Some(_) => None,
+                        None => return Err(self.end_of_input()),
+                    };
+                    let Some(c) = c else {
+                        return Err(self.error(SnbtErrorKind::InvalidEscape, start..self.pos));
+                    };
+                    string.push(c);
+                }
+                Some(c) if c == quote => return Ok(string),
+                Some(c) => string.push(c),
+                None => return Err(self.end_of_input()),
+            }
+        }
+    }
+
+    // ---------------------------------------------------------------------------------------------
+
+    /// Consume a `,` separator, returning `false` if there is none.
+    ///
+    /// Trailing separators are allowed.
+    fn separator(&mut self) -> Result<bool, SnbtError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(',') => {
+                self.pos += 1;
+                Ok(true)
+            }
+            Some(_) => Ok(false),
+            None => Err(self.end_of_input()),
+        }
+    }
+
+    /// Enter a nested compound or list.
+    fn enter(&mut self) -> Result<(), SnbtError> {
+        if self.depth >= MAX_DEPTH {
+            Err(self.error(SnbtErrorKind::DepthLimit, self.pos - 1..self.pos))
+        } else {
+            self.depth += 1;
+            Ok(())
+        }
+    }
+
+    /// Consume a run of characters allowed in unquoted strings.
+    fn take_unquoted(&mut self) -> &'a str {
+        let rest = &self.input[self.pos..];
+        let len = rest.find(|c| !is_unquoted(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    /// Consume the expected character.
+    fn expect(&mut self, expected: char) -> Result<(), SnbtError> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.pos += c.len_utf8();
+                Ok(())
+            }
+            Some(c) => Err(self.unexpected(c)),
+            None => Err(self.end_of_input()),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&self) -> Option<char> { self.input[self.pos..].chars().next() }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn error(&self, kind: SnbtErrorKind, span: core::ops::Range<usize>) -> SnbtError {
+        SnbtError::new(kind, span, self.input)
+    }
+
+    fn unexpected(&self, c: char) -> SnbtError {
+        self.error(SnbtErrorKind::UnexpectedCharacter(c), self.pos..self.pos + c.len_utf8())
+    }
+
+    fn end_of_input(&self) -> SnbtError {
+        self.error(SnbtErrorKind::UnexpectedEndOfInput, self.input.len()..self.input.len())
+    }
+}
+
+/// Parse an unquoted token as a boolean or number.
+///
+/// Returns `None` if the token should be read as a string.
+fn parse_literal(token: &str) -> Option<Result<NbtValue, SnbtErrorKind>> {
+    match token {
+        "true" => return Some(Ok(NbtValue::Byte(1))),
+        "false" => return Some(Ok(NbtValue::Byte(0))),
+        _ => {}
+    }
+
+    let (body, suffix) = match token.as_bytes().last().map(u8::to_ascii_lowercase) {
+        Some(suffix @ (b'b' | b's' | b'l' | b'f' | b'd')) => {
+            (&token[..token.len() - 1], Some(suffix))
+        }
+        _ => (token, None),
+    };
+
+    /// Parse the body as the given type, failing if it is out of range.
+    macro_rules! number {
+        ($ty:ty, $variant:ident) => {
+            Some(
+                body.parse::<$ty>()
+                    .map(NbtValue::$variant)
+                    .map_err(|_| SnbtErrorKind::InvalidNumber),
+            )
+        };
+    }
+
+    if is_integer(body) {
+        match suffix {
+            Some(b'b') => number!(i8, Byte),
+            Some(b's') => number!(i16, Short),
+            Some(b'l') => number!(i64, Long),
+            Some(b'f') => number!(f32, Float),
+            Some(b'd') => number!(f64, Double),
+            _ => number!(i32, Int),
+        }
+    } else if is_float(body) {
+        match suffix {
+            Some(b'f') => number!(f32, Float),
+            Some(b'd') => number!(f64, Double),
+            // Doubles without a suffix require a decimal point
+            None if body.contains('.') => number!(f64, Double),
+            _ => None,
+        }
+    } else {
+        None
+    }
+}
+
+/// Returns `true` if a token is an integer without leading zeros.
+fn is_integer(token: &str) -> bool {
+    let digits = token.strip_prefix(['-', '+']).unwrap_or(token);
+    match digits.as_bytes() {
+        [b'0'] => true,
+        [] | [b'0', ..] => false,
+        bytes => bytes.iter().all(u8::is_ascii_digit),
+    }
+}
+
+/// Returns `true` if a token is a decimal number with an optional exponent.
+fn is_float(token: &str) -> bool {
+    let token = token.strip_prefix(['-', '+']).unwrap_or(token);
+    let (mantissa, exponent) = match token.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (token, None),
+    };
+
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let valid_mantissa = !(whole.is_empty() && fraction.is_empty())
+        && whole.bytes().all(|b| b.is_ascii_digit())
+        && fraction.bytes().all(|b| b.is_ascii_digit());
+    let valid_exponent = exponent.is_none_or(|exponent| {
+        let digits = exponent.strip_prefix(['-', '+']).unwrap_or(exponent);
+        !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+    });
+    valid_mantissa && valid_exponent
+}
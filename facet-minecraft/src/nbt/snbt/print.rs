@@ -0,0 +1,150 @@
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+use super::{SnbtStyle, is_unquoted};
+use crate::nbt::{NbtCompound, NbtList, NbtValue};
+
+/// Write a value as SNBT.
+pub(super) fn write_value<W: Write + ?Sized>(
+    out: &mut W,
+    value: &NbtValue,
+    style: SnbtStyle,
+    level: usize,
+) -> fmt::Result {
+    match value {
+        NbtValue::Byte(value) => write!(out, "{value}b"),
+        NbtValue::Short(value) => write!(out, "{value}s"),
+        NbtValue::Int(value) => write!(out, "{value}"),
+        NbtValue::Long(value) => write!(out, "{value}L"),
+        // `Debug` always includes a decimal point or exponent
+        NbtValue::Float(value) => write!(out, "{value:?}f"),
+        NbtValue::Double(value) => write!(out, "{value:?}d"),
+        NbtValue::String(value) => write_string(out, value),
+        NbtValue::ByteArray(values) => write_array(out, 'B', values, "b", style),
+        NbtValue::IntArray(values) => write_array(out, 'I', values, "", style),
+        NbtValue::LongArray(values) => write_array(out, 'L', values, "L", style),
+        NbtValue::List(list) => write_list(out, list, style, level),
+        NbtValue::Compound(compound) => write_compound(out, compound, style, level),
+    }
+}
+
+/// Write a compound, `{key: value, ...}`.
+pub(super) fn write_compound<W: Write + ?Sized>(
+    out: &mut W,
+    compound: &NbtCompound,
+    style: SnbtStyle,
+    level: usize,
+) -> fmt::Result {
+    let mut entries: Vec<_> = compound.iter().collect();
+    if style.sort_keys {
+        entries.sort_by_key(|(key, _)| *key);
+    }
+
+    out.write_char('{')?;
+    for (index, (key, value)) in entries.into_iter().enumerate() {
+        if index != 0 {
+            out.write_char(',')?;
+        }
+        newline(out, style, level + 1)?;
+
+        if !key.is_empty() && key.chars().all(is_unquoted) {
+            out.write_str(key)?;
+        } else {
+            write_string(out, key)?;
+        }
+        out.write_str(if style.indent.is_some() { ": " } else { ":" })?;
+        write_value(out, value, style, level + 1)?;
+    }
+    if !compound.is_empty() {
+        newline(out, style, level)?;
+    }
+    out.write_char('}')
+}
+
+/// Write a list, `[value, ...]`.
+///
+/// When pretty-printing, lists of compounds and lists are written with one
+/// element per line.
+fn write_list<W: Write + ?Sized>(
+    out: &mut W,
+    list: &NbtList,
+    style: SnbtStyle,
+    level: usize,
+) -> fmt::Result {
+    let nested =
+        list.iter().any(|value| matches!(value, NbtValue::List(_) | NbtValue::Compound(_)));
+
+    out.write_char('[')?;
+    for (index, value) in list.iter().enumerate() {
+        if index != 0 {
+            out.write_char(',')?;
+        }
+        if nested {
+            newline(out, style, level + 1)?;
+        } else if index != 0 && style.indent.is_some() {
+            out.write_char(' ')?;
+        }
+        write_value(out, value, style, level + 1)?;
+    }
+    if nested {
+        newline(out, style, level)?;
+    }
+    out.write_char(']')
+}
+
+/// Write an array, `[B; 1b, ...]`.
+fn write_array<W: Write + ?Sized, T: fmt::Display>(
+    out: &mut W,
+    prefix: char,
+    values: &[T],
+    suffix: &str,
+    style: SnbtStyle,
+) -> fmt::Result {
+    let separator = if style.indent.is_some() { " " } else { "" };
+
+    write!(out, "[{prefix};")?;
+    for (index, value) in values.iter().enumerate() {
+        if index != 0 {
+            out.write_char(',')?;
+        }
+        write!(out, "{separator}{value}{suffix}")?;
+    }
+    out.write_char(']')
+}
+
+/// Write a quoted string.
+///
+/// Double quotes are used unless the string contains double quotes but no
+/// single quotes, like the vanilla client.
+pub(super) fn write_string<W: Write + ?Sized>(out: &mut W, value: &str) -> fmt::Result {
+    let quote = if value.contains('"') && !value.contains('\'') { '\'' } else { '"' };
+
+    out.write_char(quote)?;
+    for c in value.chars() {
+        match c {
+            '\\' => out.write_str("\\\\")?,
+            '\n' => out.write_str("\\n")?,
+            '\t' => out.write_str("\\t")?,
+            '\r' => out.write_str("\\r")?,
+            '\u{8}' => out.write_str("\\b")?,
+            '\u{c}' => out.write_str("\\f")?,
+            c if c == quote => {
+                out.write_char('\\')?;
+                out.write_char(c)?;
+            }
+            c => out.write_char(c)?,
+        }
+    }
+    out.write_char(quote)
+}
+
+/// Start a new line at the given indentation level, if pretty-printing.
+fn newline<W: Write + ?Sized>(out: &mut W, style: SnbtStyle, level: usize) -> fmt::Result {
+    if let Some(indent) = style.indent {
+        out.write_char('\n')?;
+        for _ in 0..indent * level {
+            out.write_char(' ')?;
+        }
+    }
+    Ok(())
+}
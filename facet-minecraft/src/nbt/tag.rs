@@ -0,0 +1,85 @@
+use core::fmt::{self, Display, Formatter};
+
+/// The type of an NBT tag.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NbtTag {
+    /// Marks the end of a compound, or an empty list or root.
+    End = 0,
+    /// A signed 8-bit integer.
+    Byte = 1,
+    /// A signed 16-bit integer.
+    Short = 2,
+    /// A signed 32-bit integer.
+    Int = 3,
+    /// A signed 64-bit integer.
+    Long = 4,
+    /// A 32-bit floating point number.
+    Float = 5,
+    /// A 64-bit floating point number.
+    Double = 6,
+    /// A length-prefixed array of signed 8-bit integers.
+    ByteArray = 7,
+    /// A length-prefixed modified UTF-8 string.
+    String = 8,
+    /// A length-prefixed list of unnamed tags of a single type.
+    List = 9,
+    /// A collection of named tags, terminated by [`NbtTag::End`].
+    Compound = 10,
+    /// A length-prefixed array of signed 32-bit integers.
+    IntArray = 11,
+    /// A length-prefixed array of signed 64-bit integers.
+    LongArray = 12,
+}
+
+impl NbtTag {
+    /// Get the [`NbtTag`] for a tag id, if it is valid.
+    #[must_use]
+    pub const fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Self::End),
+            1 => Some(Self::Byte),
+            2 => Some(Self::Short),
+            3 => Some(Self::Int),
+            4 => Some(Self::Long),
+            5 => Some(Self::Float),
+            6 => Some(Self::Double),
+            7 => Some(Self::ByteArray),
+            8 => Some(Self::String),
+            9 => Some(Self::List),
+            10 => Some(Self::Compound),
+            11 => Some(Self::IntArray),
+            12 => Some(Self::LongArray),
+            _ => None,
+        }
+    }
+
+    /// Get the tag id of this [`NbtTag`].
+    #[inline]
+    #[must_use]
+    pub const fn id(self) -> u8 { self as u8 }
+
+    /// Get the name of this [`NbtTag`], as used by the vanilla client.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::End => "TAG_End",
+            Self::Byte => "TAG_Byte",
+            Self::Short => "TAG_Short",
+            Self::Int => "TAG_Int",
+            Self::Long => "TAG_Long",
+            Self::Float => "TAG_Float",
+            Self::Double => "TAG_Double",
+            Self::ByteArray => "TAG_Byte_Array",
+            Self::String => "TAG_String",
+            Self::List => "TAG_List",
+            Self::Compound => "TAG_Compound",
+            Self::IntArray => "TAG_Int_Array",
+            Self::LongArray => "TAG_Long_Array",
+        }
+    }
+}
+
+impl Display for NbtTag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result { f.write_str(self.name()) }
+}
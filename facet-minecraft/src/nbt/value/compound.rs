@@ -0,0 +1,177 @@
+use alloc::{string::String, vec::Vec};
+use core::slice;
+
+use super::NbtValue;
+
+/// A collection of named [`NbtValue`]s.
+///
+/// Entries are kept in the order they were inserted or decoded in,
+/// so compounds are always re-encoded exactly as they were decoded.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct NbtCompound {
+    entries: Vec<(String, NbtValue)>,
+}
+
+impl NbtCompound {
+    /// Create a new, empty [`NbtCompound`].
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self { Self { entries: Vec::new() } }
+
+    /// Returns the number of entries in the compound.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize { self.entries.len() }
+
+    /// Returns `true` if the compound contains no entries.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool { self.entries.is_empty() }
+
+    /// Returns `true` if the compound contains an entry with the given name.
+    #[inline]
+    #[must_use]
+    pub fn contains_key(&self, key: &str) -> bool { self.position(key).is_some() }
+
+    /// Get a reference to the value of the entry with the given name.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&NbtValue> {
+        self.position(key).map(|index| &self.entries[index].1)
+    }
+
+    /// Get a mutable reference to the value of the entry with the given name.
+    #[must_use]
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut NbtValue> {
+        self.position(key).map(|index| &mut self.entries[index].1)
+    }
+
+    /// Insert an entry into the compound, returning the previous value.
+    ///
+    /// Existing entries keep their position, new entries are appended.
+    pub fn insert(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<NbtValue>,
+    ) -> Option<NbtValue> {
+        let key = key.into();
+        let value = value.into();
+        if let Some(index) = self.position(&key) {
+            Some(core::mem::replace(&mut self.entries[index].1, value))
+        } else {
+            self.entries.push((key, value));
+            None
+        }
+    }
+
+    /// Remove an entry from the compound, returning its value.
+    ///
+    /// The order of the remaining entries is preserved.
+    pub fn remove(&mut self, key: &str) -> Option<NbtValue> {
+        self.position(key).map(|index| self.entries.remove(index).1)
+    }
+
+    /// Returns an iterator over the entries of the compound.
+    #[inline]
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_> { Iter(self.entries.iter()) }
+
+    /// Returns a mutable iterator over the entries of the compound.
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_> { IterMut(self.entries.iter_mut()) }
+
+    /// Returns an iterator over the names of the entries.
+    #[inline]
+    pub fn keys(&self) -> impl Iterator<Item = &str> { self.iter().map(|(key, _)| key) }
+
+    /// Returns an iterator over the values of the entries.
+    #[inline]
+    pub fn values(&self) -> impl Iterator<Item = &NbtValue> { self.iter().map(|(_, value)| value) }
+
+    /// Get the index of the entry with the given name.
+    fn position(&self, key: &str) -> Option<usize> {
+        self.entries.iter().position(|(name, _)| name == key)
+    }
+
+    /// Append an entry without checking for duplicates.
+    pub(super) fn push_unchecked(&mut self, key: String, value: NbtValue) {
+        self.entries.push((key, value));
+    }
+}
+
+impl<K: Into<String>, V: Into<NbtValue>> FromIterator<(K, V)> for NbtCompound {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut compound = Self::new();
+        compound.extend(iter);
+        compound
+    }
+}
+
+impl<K: Into<String>, V: Into<NbtValue>> Extend<(K, V)> for NbtCompound {
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl IntoIterator for NbtCompound {
+    type IntoIter = alloc::vec::IntoIter<(String, NbtValue)>;
+    type Item = (String, NbtValue);
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter { self.entries.into_iter() }
+}
+
+impl<'a> IntoIterator for &'a NbtCompound {
+    type IntoIter = Iter<'a>;
+    type Item = (&'a str, &'a NbtValue);
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter { self.iter() }
+}
+
+impl<'a> IntoIterator for &'a mut NbtCompound {
+    type IntoIter = IterMut<'a>;
+    type Item = (&'a str, &'a mut NbtValue);
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter { self.iter_mut() }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// An iterator over the entries of an [`NbtCompound`].
+#[derive(Debug, Clone)]
+pub struct Iter<'a>(slice::Iter<'a, (String, NbtValue)>);
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (&'a str, &'a NbtValue);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, value)| (key.as_str(), value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { self.0.size_hint() }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+/// A mutable iterator over the entries of an [`NbtCompound`].
+#[derive(Debug)]
+pub struct IterMut<'a>(slice::IterMut<'a, (String, NbtValue)>);
+
+impl<'a> Iterator for IterMut<'a> {
+    type Item = (&'a str, &'a mut NbtValue);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, value)| (key.as_str(), value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { self.0.size_hint() }
+}
+
+impl ExactSizeIterator for IterMut<'_> {}
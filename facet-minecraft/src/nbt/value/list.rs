@@ -0,0 +1,185 @@
+use alloc::vec::Vec;
+use core::slice;
+
+use super::NbtValue;
+use crate::nbt::{NbtError, NbtErrorKind, NbtTag};
+
+/// A list of [`NbtValue`]s sharing the same [`NbtTag`].
+///
+/// The element tag is kept even when the list is empty,
+/// so lists are always re-encoded exactly as they were decoded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NbtList {
+    tag: NbtTag,
+    values: Vec<NbtValue>,
+}
+
+impl NbtList {
+    /// Create a new, empty [`NbtList`] of [`NbtTag::End`].
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self { Self::with_tag(NbtTag::End) }
+
+    /// Create a new, empty [`NbtList`] of the given [`NbtTag`].
+    #[inline]
+    #[must_use]
+    pub const fn with_tag(tag: NbtTag) -> Self { Self { tag, values: Vec::new() } }
+
+    /// Get the [`NbtTag`] of the elements in this list.
+    #[inline]
+    #[must_use]
+    pub const fn tag(&self) -> NbtTag { self.tag }
+
+    /// Returns the number of elements in the list.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize { self.values.len() }
+
+    /// Returns `true` if the list contains no elements.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool { self.values.is_empty() }
+
+    /// Get a reference to the element at the given index.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&NbtValue> { self.values.get(index) }
+
+    /// Get a mutable reference to the element at the given index.
+    ///
+    /// Replacing the element with a value of a different type will cause
+    /// encoding to fail.
+    #[inline]
+    #[must_use]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut NbtValue> { self.values.get_mut(index) }
+
+    /// Append a value to the end of the list.
+    ///
+    /// Pushing into an empty list of [`NbtTag::End`] sets the element type.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value does not match the element type.
+    pub fn push(&mut self, value: NbtValue) -> Result<(), NbtError> {
+        self.check(&value)?;
+        self.values.push(value);
+        Ok(())
+    }
+
+    /// Insert a value at the given index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value does not match the element type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, value: NbtValue) -> Result<(), NbtError> {
+        self.check(&value)?;
+        self.values.insert(index, value);
+        Ok(())
+    }
+
+    /// Replace the value at the given index, returning the previous value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value does not match the element type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    pub fn replace(&mut self, index: usize, value: NbtValue) -> Result<NbtValue, NbtError> {
+        self.check(&value)?;
+        Ok(core::mem::replace(&mut self.values[index], value))
+    }
+
+    /// Remove and return the element at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    #[inline]
+    pub fn remove(&mut self, index: usize) -> NbtValue { self.values.remove(index) }
+
+    /// Remove every element from the list, keeping its element type.
+    #[inline]
+    pub fn clear(&mut self) { self.values.clear(); }
+
+    /// Returns an iterator over the elements of the list.
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, NbtValue> { self.values.iter() }
+
+    /// Returns a mutable iterator over the elements of the list.
+    ///
+    /// Replacing an element with a value of a different type will cause
+    /// encoding to fail.
+    #[inline]
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, NbtValue> { self.values.iter_mut() }
+
+    /// Check that a value can be added to this list,
+    /// setting the element type if the list is untyped.
+    fn check(&mut self, value: &NbtValue) -> Result<(), NbtError> {
+        let found = value.tag();
+        if self.tag == found {
+            Ok(())
+        } else if self.values.is_empty() && self.tag == NbtTag::End {
+            self.tag = found;
+            Ok(())
+        } else {
+            Err(NbtError::new(NbtErrorKind::HeterogeneousList { expected: self.tag, found }))
+        }
+    }
+
+    /// Build a list from already validated parts.
+    pub(super) const fn from_parts(tag: NbtTag, values: Vec<NbtValue>) -> Self {
+        Self { tag, values }
+    }
+}
+
+impl Default for NbtList {
+    #[inline]
+    fn default() -> Self { Self::new() }
+}
+
+impl TryFrom<Vec<NbtValue>> for NbtList {
+    type Error = NbtError;
+
+    fn try_from(values: Vec<NbtValue>) -> Result<Self, Self::Error> {
+        let mut list = Self::new();
+        for value in values {
+            list.push(value)?;
+        }
+        Ok(list)
+    }
+}
+
+impl From<NbtList> for Vec<NbtValue> {
+    #[inline]
+    fn from(list: NbtList) -> Self { list.values }
+}
+
+impl IntoIterator for NbtList {
+    type IntoIter = alloc::vec::IntoIter<NbtValue>;
+    type Item = NbtValue;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter { self.values.into_iter() }
+}
+
+impl<'a> IntoIterator for &'a NbtList {
+    type IntoIter = slice::Iter<'a, NbtValue>;
+    type Item = &'a NbtValue;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter { self.values.iter() }
+}
+
+impl<'a> IntoIterator for &'a mut NbtList {
+    type IntoIter = slice::IterMut<'a, NbtValue>;
+    type Item = &'a mut NbtValue;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter { self.values.iter_mut() }
+}
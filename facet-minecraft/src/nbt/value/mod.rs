@@ -0,0 +1,357 @@
+//! A dynamic NBT value tree.
+
+use alloc::{string::String, vec::Vec};
+
+use facet::{Facet, Shape};
+
+use super::{NbtError, NbtErrorKind, NbtReader, NbtTag, NbtWriter};
+use crate::{
+    codec::{McCodec, McReader, McWriter, codec_shape},
+    common::TypeSerializeHint,
+    deserialize::DeserializeError,
+    serialize::SerializeError,
+};
+
+mod compound;
+pub use compound::{Iter, IterMut, NbtCompound};
+
+mod list;
+pub use list::NbtList;
+
+mod path;
+pub use path::{NbtPath, NbtPathNode};
+
+/// A schemaless NBT value.
+///
+/// Decoding and re-encoding a value is lossless:
+/// compound entries keep their order and empty lists keep their element type.
+#[derive(Debug, Clone, PartialEq)]
+#[expect(missing_docs, reason = "Variants are named after their tags")]
+pub enum NbtValue {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<i8>),
+    String(String),
+    List(NbtList),
+    Compound(NbtCompound),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+}
+
+impl NbtValue {
+    /// Get the [`NbtTag`] of this value.
+    #[must_use]
+    pub const fn tag(&self) -> NbtTag {
+        match self {
+            Self::Byte(_) => NbtTag::Byte,
+            Self::Short(_) => NbtTag::Short,
+            Self::Int(_) => NbtTag::Int,
+            Self::Long(_) => NbtTag::Long,
+            Self::Float(_) => NbtTag::Float,
+            Self::Double(_) => NbtTag::Double,
+            Self::ByteArray(_) => NbtTag::ByteArray,
+            Self::String(_) => NbtTag::String,
+            Self::List(_) => NbtTag::List,
+            Self::Compound(_) => NbtTag::Compound,
+            Self::IntArray(_) => NbtTag::IntArray,
+            Self::LongArray(_) => NbtTag::LongArray,
+        }
+    }
+
+    /// Get the value as an `i8`, if it is a [`NbtValue::Byte`].
+    #[must_use]
+    pub const fn as_i8(&self) -> Option<i8> {
+        if let Self::Byte(value) = self { Some(*value) } else { None }
+    }
+
+    /// Get the value as an `i16`, if it is a [`NbtValue::Short`].
+    #[must_use]
+    pub const fn as_i16(&self) -> Option<i16> {
+        if let Self::Short(value) = self { Some(*value) } else { None }
+    }
+
+    /// Get the value as an `i32`, if it is a [`NbtValue::Int`].
+    #[must_use]
+    pub const fn as_i32(&self) -> Option<i32> {
+        if let Self::Int(value) = self { Some(*value) } else { None }
+    }
+
+    /// Get the value as an `i64`, if it is a [`NbtValue::Long`].
+    #[must_use]
+    pub const fn as_i64(&self) -> Option<i64> {
+        if let Self::Long(value) = self { Some(*value) } else { None }
+    }
+
+    /// Get the value as an `f32`, if it is a [`NbtValue::Float`].
+    #[must_use]
+    pub const fn as_f32(&self) -> Option<f32> {
+        if let Self::Float(value) = self { Some(*value) } else { None }
+    }
+
+    /// Get the value as an `f64`, if it is a [`NbtValue::Double`].
+    #[must_use]
+    pub const fn as_f64(&self) -> Option<f64> {
+        if let Self::Double(value) = self { Some(*value) } else { None }
+    }
+
+    /// Get the value as a `str`, if it is a [`NbtValue::String`].
+    #[must_use]
+    pub const fn as_str(&self) -> Option<&str> {
+        if let Self::String(value) = self { Some(value.as_str()) } else { None }
+    }
+
+    /// Get the value as an [`NbtList`], if it is a [`NbtValue::List`].
+    #[must_use]
+    pub const fn as_list(&self) -> Option<&NbtList> {
+        if let Self::List(value) = self { Some(value) } else { None }
+    }
+
+    /// Get the value as a mutable [`NbtList`], if it is a [`NbtValue::List`].
+    #[must_use]
+    pub const fn as_list_mut(&mut self) -> Option<&mut NbtList> {
+        if let Self::List(value) = self { Some(value) } else { None }
+    }
+
+    /// Get the value as an [`NbtCompound`], if it is a [`NbtValue::Compound`].
+    #[must_use]
+    pub const fn as_compound(&self) -> Option<&NbtCompound> {
+        if let Self::Compound(value) = self { Some(value) } else { None }
+    }
+
+    /// Get the value as a mutable [`NbtCompound`],
+    /// if it is a [`NbtValue::Compound`].
+    #[must_use]
+    pub const fn as_compound_mut(&mut self) -> Option<&mut NbtCompound> {
+        if let Self::Compound(value) = self { Some(value) } else { None }
+    }
+
+    /// Get the first value matching an [`NbtPath`].
+    #[must_use]
+    pub fn get_path(&self, path: &NbtPath) -> Option<&Self> { path.get(self).into_iter().next() }
+
+    /// Get the first value matching an [`NbtPath`] mutably.
+    #[must_use]
+    pub fn get_path_mut(&mut self, path: &NbtPath) -> Option<&mut Self> {
+        path.get_mut(self).into_iter().next()
+    }
+
+    // ---------------------------------------------------------------------------------------------
+
+    /// Read the payload of a tag.
+    pub(crate) fn read(tag: NbtTag, reader: &mut NbtReader<'_>) -> Result<Self, NbtError> {
+        match tag {
+            NbtTag::End => Err(NbtError::new(NbtErrorKind::InvalidTag(NbtTag::End.id()))),
+            NbtTag::Byte => reader.read_i8().map(Self::Byte),
+            NbtTag::Short => reader.read_i16().map(Self::Short),
+            NbtTag::Int => reader.read_i32().map(Self::Int),
+            NbtTag::Long => reader.read_i64().map(Self::Long),
+            NbtTag::Float => reader.read_f32().map(Self::Float),
+            NbtTag::Double => reader.read_f64().map(Self::Double),
+            NbtTag::String => reader.read_string().map(|value| Self::String(value.into_owned())),
+            NbtTag::ByteArray => {
+                let length = reader.read_length()?;
+                let bytes = reader.read_bytes(length)?;
+                Ok(Self::ByteArray(bytes.iter().map(|&byte| i8::from_be_bytes([byte])).collect()))
+            }
+            NbtTag::IntArray => {
+                let length = reader.read_length()?;
+                let bytes = reader.read_bytes(length.saturating_mul(4))?;
+                Ok(Self::IntArray(
+                    bytes
+                        .chunks_exact(4)
+                        .map(|chunk| i32::from_be_bytes(chunk.try_into().unwrap_or_default()))
+                        .collect(),
+                ))
+            }
+            NbtTag::LongArray => {
+                let length = reader.read_length()?;
+                let bytes = reader.read_bytes(length.saturating_mul(8))?;
+                Ok(Self::LongArray(
+                    bytes
+                        .chunks_exact(8)
+                        .map(|chunk| i64::from_be_bytes(chunk.try_into().unwrap_or_default()))
+                        .collect(),
+                ))
+            }
+            NbtTag::List => {
+                let element = reader.read_tag()?;
+                let length = reader.read_length()?;
+                if element == NbtTag::End && length != 0 {
+                    return Err(NbtError::new(NbtErrorKind::InvalidTag(NbtTag::End.id())));
+                }
+
+                // Every element consumes at least one byte,
+                // so the list cannot be longer than the remaining input.
+                let mut values = Vec::with_capacity(length.min(reader.remaining().len()));
+                reader.enter()?;
+                for _ in 0..length {
+                    values.push(Self::read(element, reader)?);
+                }
+                reader.exit();
+                Ok(Self::List(NbtList::from_parts(element, values)))
+            }
+            NbtTag::Compound => NbtCompound::read(reader).map(Self::Compound),
+        }
+    }
+
+    /// Write the payload of this value.
+    pub(crate) fn write(&self, writer: &mut NbtWriter<'_>) -> Result<(), NbtError> {
+        match self {
+            Self::Byte(value) => writer.write_i8(*value),
+            Self::Short(value) => writer.write_i16(*value),
+            Self::Int(value) => writer.write_i32(*value),
+            Self::Long(value) => writer.write_i64(*value),
+            Self::Float(value) => writer.write_f32(*value),
+            Self::Double(value) => writer.write_f64(*value),
+            Self::String(value) => writer.write_string(value),
+            Self::ByteArray(values) => {
+                writer.write_length(values.len())?;
+                values.iter().try_for_each(|value| writer.write_i8(*value))
+            }
+            Self::IntArray(values) => {
+                writer.write_length(values.len())?;
+                values.iter().try_for_each(|value| writer.write_i32(*value))
+            }
+            Self::LongArray(values) => {
+                writer.write_length(values.len())?;
+                values.iter().try_for_each(|value| writer.write_i64(*value))
+            }
+            Self::List(list) => {
+                writer.write_tag(list.tag())?;
+                writer.write_length(list.len())?;
+                for value in list {
+                    // Elements may have been replaced using `get_mut`
+                    if value.tag() != list.tag() {
+                        return Err(NbtError::new(NbtErrorKind::HeterogeneousList {
+                            expected: list.tag(),
+                            found: value.tag(),
+                        }));
+                    }
+                    value.write(writer)?;
+                }
+                Ok(())
+            }
+            Self::Compound(compound) => compound.write(writer),
+        }
+    }
+}
+
+impl NbtCompound {
+    /// Read the payload of a `TAG_Compound`.
+    pub(crate) fn read(reader: &mut NbtReader<'_>) -> Result<Self, NbtError> {
+        let mut compound = Self::new();
+        reader.enter()?;
+        loop {
+            let tag = reader.read_tag()?;
+            if tag == NbtTag::End {
+                break;
+            }
+            let key = reader.read_string()?.into_owned();
+            compound.push_unchecked(key, NbtValue::read(tag, reader)?);
+        }
+        reader.exit();
+        Ok(compound)
+    }
+
+    /// Write the payload of a `TAG_Compound`.
+    pub(crate) fn write(&self, writer: &mut NbtWriter<'_>) -> Result<(), NbtError> {
+        for (key, value) in self {
+            writer.write_tag(value.tag())?;
+            writer.write_string(key)?;
+            value.write(writer)?;
+        }
+        writer.write_tag(NbtTag::End)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl McCodec for NbtValue {
+    // Tag + payload, at least `TAG_Byte` or an empty `TAG_Compound`
+    const SERIALIZE_HINT: TypeSerializeHint = TypeSerializeHint::Range { min: 2, max: None };
+
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        let mut nbt = NbtWriter::new(writer.buffer());
+        nbt.write_tag(self.tag())?;
+        self.write(&mut nbt).map_err(Into::into)
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        let mut nbt = NbtReader::new(reader.remaining());
+        let tag = nbt.read_tag()?;
+        let value = Self::read(tag, &mut nbt)?;
+        reader.read_bytes(nbt.consumed())?;
+        Ok(value)
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for NbtValue {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("NbtValue").build() };
+}
+
+impl McCodec for NbtCompound {
+    // `TAG_Compound` + at least `TAG_End`
+    const SERIALIZE_HINT: TypeSerializeHint = TypeSerializeHint::Range { min: 2, max: None };
+
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        let mut nbt = NbtWriter::new(writer.buffer());
+        nbt.write_tag(NbtTag::Compound)?;
+        self.write(&mut nbt).map_err(Into::into)
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        let mut nbt = NbtReader::new(reader.remaining());
+        let tag = nbt.read_tag()?;
+        if tag != NbtTag::Compound {
+            return Err(NbtError::mismatch(NbtTag::Compound, tag).into());
+        }
+        let value = Self::read(&mut nbt)?;
+        reader.read_bytes(nbt.consumed())?;
+        Ok(value)
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for NbtCompound {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("NbtCompound").build() };
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Implement `From` for a variant of [`NbtValue`].
+macro_rules! impl_from {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl From<$ty> for NbtValue {
+                #[inline]
+                fn from(value: $ty) -> Self { Self::$variant(value.into()) }
+            }
+        )*
+    };
+}
+
+impl_from! {
+    i8 => Byte,
+    i16 => Short,
+    i32 => Int,
+    i64 => Long,
+    f32 => Float,
+    f64 => Double,
+    Vec<i8> => ByteArray,
+    String => String,
+    &str => String,
+    NbtList => List,
+    NbtCompound => Compound,
+    Vec<i32> => IntArray,
+    Vec<i64> => LongArray,
+}
+
+impl From<bool> for NbtValue {
+    #[inline]
+    fn from(value: bool) -> Self { Self::Byte(i8::from(value)) }
+}
@@ -0,0 +1,301 @@
+use alloc::{string::String, vec, vec::Vec};
+use core::{
+    fmt::{self, Display, Formatter, Write},
+    str::FromStr,
+};
+
+use super::{NbtCompound, NbtValue};
+use crate::nbt::{NbtError, NbtErrorKind};
+
+/// A path selecting values inside of an [`NbtValue`].
+///
+/// Paths use the same syntax as the vanilla `/data` command,
+/// for example `Items[0].components."minecraft:custom_name"`:
+///
+/// - `name` or `"name"` selects the entry of a compound,
+/// - `[n]` selects the `n`th element of a list, counting from the end if
+///   negative,
+/// - `[]` selects every element of a list.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct NbtPath {
+    nodes: Vec<NbtPathNode>,
+}
+
+/// A single node of an [`NbtPath`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum NbtPathNode {
+    /// Select the entry of a compound with the given name.
+    Key(String),
+    /// Select the element of a list at the given index,
+    /// counting from the end if negative.
+    Index(i32),
+    /// Select every element of a list.
+    All,
+}
+
+impl NbtPath {
+    /// Create a new, empty [`NbtPath`] selecting the root value.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self { Self { nodes: Vec::new() } }
+
+    /// Get the nodes of this path.
+    #[inline]
+    #[must_use]
+    pub fn nodes(&self) -> &[NbtPathNode] { &self.nodes }
+
+    /// Append a node to the end of this path.
+    #[inline]
+    pub fn push(&mut self, node: NbtPathNode) { self.nodes.push(node); }
+
+    /// Get every value matching this path.
+    #[must_use]
+    pub fn get<'a>(&self, root: &'a NbtValue) -> Vec<&'a NbtValue> {
+        let mut values = vec![root];
+        for node in &self.nodes {
+            let mut next = Vec::new();
+            for value in values {
+                match (node, value) {
+                    (NbtPathNode::Key(key), NbtValue::Compound(compound)) => {
+                        next.extend(compound.get(key));
+                    }
+                    (NbtPathNode::Index(index), NbtValue::List(list)) => {
+                        next.extend(resolve(*index, list.len()).and_then(|index| list.get(index)));
+                    }
+                    (NbtPathNode::All, NbtValue::List(list)) => next.extend(list),
+                    _ => {}
+                }
+            }
+            values = next;
+        }
+        values
+    }
+
+    /// Get every value matching this path mutably.
+    #[must_use]
+    pub fn get_mut<'a>(&self, root: &'a mut NbtValue) -> Vec<&'a mut NbtValue> {
+        walk_mut(&self.nodes, root, false)
+    }
+
+    /// Set every value matching this path, returning the number of values
+    /// that were set.
+    ///
+    /// Missing compound entries leading up to the final node are created.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value does not match the element type of a
+    /// list it would be placed in.
+    pub fn set(&self, root: &mut NbtValue, value: NbtValue) -> Result<usize, NbtError> {
+        let Some((node, parents)) = self.nodes.split_last() else {
+            *root = value;
+            return Ok(1);
+        };
+
+        let mut count = 0;
+        for parent in walk_mut(parents, root, matches!(node, NbtPathNode::Key(_))) {
+            match (node, parent) {
+                (NbtPathNode::Key(key), NbtValue::Compound(compound)) => {
+                    compound.insert(key.as_str(), value.clone());
+                    count += 1;
+                }
+                (NbtPathNode::Index(index), NbtValue::List(list)) => {
+                    if let Some(index) = resolve(*index, list.len()) {
+                        list.replace(index, value.clone())?;
+                        count += 1;
+                    }
+                }
+                (NbtPathNode::All, NbtValue::List(list)) => {
+                    for index in 0..list.len() {
+                        list.replace(index, value.clone())?;
+                        count += 1;
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(count)
+    }
+
+    /// Remove every value matching this path, returning the number of values
+    /// that were removed.
+    ///
+    /// The root value itself cannot be removed.
+    pub fn remove(&self, root: &mut NbtValue) -> usize {
+        let Some((node, parents)) = self.nodes.split_last() else { return 0 };
+
+        let mut count = 0;
+        for parent in walk_mut(parents, root, false) {
+            match (node, parent) {
+                (NbtPathNode::Key(key), NbtValue::Compound(compound)) => {
+                    count += usize::from(compound.remove(key).is_some());
+                }
+                (NbtPathNode::Index(index), NbtValue::List(list)) => {
+                    if let Some(index) = resolve(*index, list.len()) {
+                        list.remove(index);
+                        count += 1;
+                    }
+                }
+                (NbtPathNode::All, NbtValue::List(list)) => {
+                    count += list.len();
+                    list.clear();
+                }
+                _ => {}
+            }
+        }
+        count
+    }
+}
+
+/// Follow a list of nodes, optionally creating missing compound entries
+/// that are followed by another key.
+fn walk_mut<'a>(
+    nodes: &[NbtPathNode],
+    root: &'a mut NbtValue,
+    create: bool,
+) -> Vec<&'a mut NbtValue> {
+    let mut values = vec![root];
+    for (index, node) in nodes.iter().enumerate() {
+        let create = create
+            && matches!(nodes.get(index + 1), Some(NbtPathNode::Key(_)) | None)
+            && matches!(node, NbtPathNode::Key(_));
+
+        let mut next = Vec::new();
+        for value in values {
+            match (node, value) {
+                (NbtPathNode::Key(key), NbtValue::Compound(compound)) => {
+                    if create && !compound.contains_key(key) {
+                        compound.insert(key.as_str(), NbtCompound::new());
+                    }
+                    next.extend(compound.get_mut(key));
+                }
+                (NbtPathNode::Index(index), NbtValue::List(list)) => {
+                    next.extend(resolve(*index, list.len()).and_then(|index| list.get_mut(index)));
+                }
+                (NbtPathNode::All, NbtValue::List(list)) => next.extend(list),
+                _ => {}
+            }
+        }
+        values = next;
+    }
+    values
+}
+
+/// Resolve a possibly negative index into a list of the given length.
+fn resolve(index: i32, len: usize) -> Option<usize> {
+    let index = if index < 0 {
+        len.checked_sub(usize::try_from(index.unsigned_abs()).ok()?)?
+    } else {
+        usize::try_from(index).ok()?
+    };
+    (index < len).then_some(index)
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl FromStr for NbtPath {
+    type Err = NbtError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let error = |pos: usize| NbtError::new(NbtErrorKind::InvalidPath(pos));
+        let bytes = input.as_bytes();
+
+        let mut nodes = Vec::new();
+        let mut pos = 0;
+        loop {
+            // Compound keys are required after a `.` and optional at the start
+            if pos != 0 || bytes.first() != Some(&b'[') {
+                let (key, end) = parse_key(input, pos).ok_or_else(|| error(pos))?;
+                nodes.push(NbtPathNode::Key(key));
+                pos = end;
+            }
+
+            while bytes.get(pos) == Some(&b'[') {
+                let end = input[pos..].find(']').ok_or_else(|| error(input.len()))? + pos;
+                let inner = &input[pos + 1..end];
+                if inner.is_empty() {
+                    nodes.push(NbtPathNode::All);
+                } else {
+                    let index = inner.parse::<i32>().map_err(|_| error(pos + 1))?;
+                    nodes.push(NbtPathNode::Index(index));
+                }
+                pos = end + 1;
+            }
+
+            match bytes.get(pos) {
+                None => return Ok(Self { nodes }),
+                Some(b'.') => pos += 1,
+                Some(_) => return Err(error(pos)),
+            }
+        }
+    }
+}
+
+/// Parse a bare or quoted key starting at `pos`,
+/// returning the key and the position after it.
+fn parse_key(input: &str, pos: usize) -> Option<(String, usize)> {
+    let rest = &input[pos..];
+    let quote = rest.chars().next().filter(|&c| c == '"' || c == '\'');
+
+    let Some(quote) = quote else {
+        let len = rest.find(|c| !is_bare(c)).unwrap_or(rest.len());
+        return (len != 0).then(|| (String::from(&rest[..len]), pos + len));
+    };
+
+    let mut key = String::new();
+    let mut chars = rest.char_indices().skip(1);
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some((_, c)) if c == quote || c == '\\' => key.push(c),
+                _ => return None,
+            },
+            c if c == quote => return Some((key, pos + index + 1)),
+            c => key.push(c),
+        }
+    }
+    None
+}
+
+/// Returns `true` if a character can be used in an unquoted key.
+const fn is_bare(c: char) -> bool { !matches!(c, ' ' | '"' | '\'' | '[' | ']' | '.' | '{' | '}') }
+
+impl Display for NbtPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (index, node) in self.nodes.iter().enumerate() {
+            match node {
+                NbtPathNode::Key(key) => {
+                    if index != 0 {
+                        f.write_char('.')?;
+                    }
+                    if !key.is_empty() && key.chars().all(is_bare) {
+                        f.write_str(key)?;
+                    } else {
+                        f.write_char('"')?;
+                        for c in key.chars() {
+                            if matches!(c, '"' | '\\') {
+                                f.write_char('\\')?;
+                            }
+                            f.write_char(c)?;
+                        }
+                        f.write_char('"')?;
+                    }
+                }
+                NbtPathNode::Index(index) => write!(f, "[{index}]")?,
+                NbtPathNode::All => f.write_str("[]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<Vec<NbtPathNode>> for NbtPath {
+    #[inline]
+    fn from(nodes: Vec<NbtPathNode>) -> Self { Self { nodes } }
+}
+
+impl FromIterator<NbtPathNode> for NbtPath {
+    fn from_iter<T: IntoIterator<Item = NbtPathNode>>(iter: T) -> Self {
+        Self { nodes: iter.into_iter().collect() }
+    }
+}
@@ -0,0 +1,112 @@
+use alloc::vec::Vec;
+use core::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+use facet_format::{DeserializeError as FDError, SerializeError as FSError};
+
+use super::{Direction, ProtocolState, ProtocolVersion};
+use crate::{deserialize::DeserializeError, serialize::SerializeError};
+
+/// An error that occurred while encoding or decoding a packet.
+#[derive(Debug)]
+pub struct PacketError {
+    kind: PacketErrorKind,
+}
+
+impl PacketError {
+    /// Create a new [`PacketError`].
+    #[inline]
+    #[must_use]
+    pub const fn new(kind: PacketErrorKind) -> Self { Self { kind } }
+
+    /// Get the kind of packet error.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> &PacketErrorKind { &self.kind }
+
+    /// Consume the error and return its kind.
+    #[inline]
+    #[must_use]
+    pub fn into_kind(self) -> PacketErrorKind { self.kind }
+
+    /// Returns `true` if the packet ID was not recognized.
+    ///
+    /// The packet can be skipped and the connection can continue.
+    #[inline]
+    #[must_use]
+    pub const fn is_unknown(&self) -> bool {
+        matches!(self.kind, PacketErrorKind::UnknownId { .. })
+    }
+}
+
+/// The type of packet error.
+#[derive(Debug)]
+pub enum PacketErrorKind {
+    /// No packet with this ID exists in the state and direction.
+    UnknownId {
+        /// The protocol state.
+        state: ProtocolState,
+        /// The packet direction.
+        direction: Direction,
+        /// The packet ID.
+        id: u32,
+        /// The raw packet body.
+        body: Vec<u8>,
+    },
+    /// The packets do not belong to the state and direction.
+    WrongState {
+        /// The protocol state.
+        state: ProtocolState,
+        /// The packet direction.
+        direction: Direction,
+    },
+    /// The packet has no ID in the protocol version.
+    UnsupportedVersion {
+        /// The name of the packet type.
+        packet: &'static str,
+        /// The protocol version.
+        version: ProtocolVersion,
+    },
+    /// Bytes were left over after decoding the packet body.
+    TrailingBytes(usize),
+    /// The packet body could not be deserialized.
+    Deserialize(FDError<DeserializeError>),
+    /// The packet body could not be serialized.
+    Serialize(FSError<SerializeError>),
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Error for PacketError {}
+impl Display for PacketError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            PacketErrorKind::UnknownId { state, direction, id, body } => {
+                write!(f, "unknown {direction} {state} packet {id:#04x} with {} bytes", body.len())
+            }
+            PacketErrorKind::WrongState { state, direction } => {
+                write!(f, "packets are not {direction} {state} packets")
+            }
+            PacketErrorKind::UnsupportedVersion { packet, version } => {
+                write!(f, "packet {packet} has no ID in version {version}")
+            }
+            PacketErrorKind::TrailingBytes(len) => write!(f, "{len} bytes left after packet body"),
+            PacketErrorKind::Deserialize(err) => write!(f, "failed to decode packet: {err}"),
+            PacketErrorKind::Serialize(err) => write!(f, "failed to encode packet: {err}"),
+        }
+    }
+}
+
+impl From<FDError<DeserializeError>> for PacketError {
+    #[inline]
+    fn from(err: FDError<DeserializeError>) -> Self {
+        Self { kind: PacketErrorKind::Deserialize(err) }
+    }
+}
+
+impl From<FSError<SerializeError>> for PacketError {
+    #[inline]
+    fn from(err: FSError<SerializeError>) -> Self { Self { kind: PacketErrorKind::Serialize(err) } }
+}
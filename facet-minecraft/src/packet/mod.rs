@@ -0,0 +1,496 @@
+//! Packet IDs, protocol states and directions.
+//!
+//! A packet is any type implementing [`Packet`], which ties it to an ID in a
+//! [`ProtocolState`] and [`Direction`]. The [`packet_set!`](crate::packet_set)
+//! macro groups the packets of one state and direction into an enum
+//! implementing [`PacketSet`], which decodes a packet ID and body into the
+//! matching variant and encodes a variant back into its ID and body.
+//!
+//! ```rust
+//! use facet::Facet;
+//! use facet_minecraft::packet::{Direction, Packet, PacketSet, ProtocolState};
+//!
+//! #[derive(Debug, PartialEq, Facet)]
+//! struct StatusRequest;
+//!
+//! impl Packet for StatusRequest {
+//!     const DIRECTION: Direction = Direction::Serverbound;
+//!     const ID: u32 = 0x00;
+//!     const STATE: ProtocolState = ProtocolState::Status;
+//! }
+//!
+//! #[derive(Debug, PartialEq, Facet)]
+//! struct PingRequest {
+//!     timestamp: u64,
+//! }
+//!
+//! impl Packet for PingRequest {
+//!     const DIRECTION: Direction = Direction::Serverbound;
+//!     const ID: u32 = 0x01;
+//!     const STATE: ProtocolState = ProtocolState::Status;
+//! }
+//!
+//! facet_minecraft::packet_set! {
+//!     /// Packets sent by the client during the status state.
+//!     #[derive(Debug, PartialEq)]
+//!     pub enum StatusServerbound(Status, Serverbound) {
+//!         StatusRequest(StatusRequest),
+//!         PingRequest(PingRequest),
+//!     }
+//! }
+//!
+//! let packet = StatusServerbound::decode_packet(0x01, &[0, 0, 0, 0, 0, 0, 0, 42]).unwrap();
+//! assert_eq!(packet, StatusServerbound::PingRequest(PingRequest { timestamp: 42 }));
+//!
+//! let mut buffer = Vec::new();
+//! packet.encode(&mut buffer).unwrap();
+//! assert_eq!(buffer, [0x01, 0, 0, 0, 0, 0, 0, 0, 42]);
+//! ```
+
+use alloc::vec::Vec;
+use core::{
+    any::type_name,
+    fmt::{self, Display, Formatter},
+};
+
+use facet_format::SerializeError as FSError;
+
+use crate::{
+    Deserializable, Serializable, codec::McWriter, connection::Transition,
+    serialize::SerializeBuffer,
+};
+
+mod error;
+pub use error::{PacketError, PacketErrorKind};
+
+mod version;
+pub use version::ProtocolVersion;
+
+/// The state of a connection, which determines the meaning of packet IDs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProtocolState {
+    /// The first state of every connection.
+    Handshake,
+    /// Querying the server's status for the server list.
+    Status,
+    /// Authenticating and enabling encryption and compression.
+    Login,
+    /// Exchanging registries and resource packs before joining.
+    Configuration,
+    /// Playing in the world.
+    Play,
+}
+
+/// The direction a packet is sent in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Sent from the client to the server.
+    Serverbound,
+    /// Sent from the server to the client.
+    Clientbound,
+}
+
+impl Direction {
+    /// Get the opposite direction.
+    #[must_use]
+    pub const fn opposite(self) -> Self {
+        match self {
+            Self::Serverbound => Self::Clientbound,
+            Self::Clientbound => Self::Serverbound,
+        }
+    }
+}
+
+impl Display for ProtocolState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Handshake => "handshake",
+            Self::Status => "status",
+            Self::Login => "login",
+            Self::Configuration => "configuration",
+            Self::Play => "play",
+        })
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Serverbound => "serverbound",
+            Self::Clientbound => "clientbound",
+        })
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// A packet with an ID in a [`ProtocolState`] and [`Direction`].
+pub trait Packet {
+    /// The ID of the packet.
+    ///
+    /// Used when no protocol version is given.
+    const ID: u32;
+    /// The state the packet is sent in.
+    const STATE: ProtocolState;
+    /// The direction the packet is sent in.
+    const DIRECTION: Direction;
+    /// The ID of the packet in each protocol version.
+    ///
+    /// Each entry gives the ID used from that version onwards, oldest first.
+    /// Snapshots only use entries for snapshots, and releases only entries
+    /// for releases. If empty, [`Packet::ID`] is used in every version.
+    ///
+    /// ```rust
+    /// use facet_minecraft::packet::{Direction, Packet, ProtocolState, ProtocolVersion};
+    ///
+    /// struct BundleDelimiter;
+    ///
+    /// impl Packet for BundleDelimiter {
+    ///     const DIRECTION: Direction = Direction::Clientbound;
+    ///     const ID: u32 = 0x00;
+    ///     const IDS: &'static [(ProtocolVersion, u32)] = &[(ProtocolVersion::V1_20_2, 0x00)];
+    ///     const STATE: ProtocolState = ProtocolState::Play;
+    /// }
+    ///
+    /// assert_eq!(BundleDelimiter::id_for(ProtocolVersion::V1_21_4).unwrap(), 0x00);
+    /// assert!(BundleDelimiter::id_for(ProtocolVersion::new(763)).is_err());
+    /// ```
+    const IDS: &'static [(ProtocolVersion, u32)] = &[];
+
+    /// Get the [`Transition`] this packet causes, if any.
+    #[inline]
+    #[must_use]
+    fn transition(&self) -> Option<Transition> { None }
+
+    /// Get the ID of the packet in a protocol version.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PacketErrorKind::UnsupportedVersion`]
+    /// if the packet does not exist in the version.
+    fn id_for(version: ProtocolVersion) -> Result<u32, PacketError> {
+        if Self::IDS.is_empty() {
+            return Ok(Self::ID);
+        }
+
+        Self::IDS
+            .iter()
+            .rev()
+            .find(|(since, _)| since.is_snapshot() == version.is_snapshot() && *since <= version)
+            .map(|&(_, id)| id)
+            .ok_or_else(|| {
+                PacketError::new(PacketErrorKind::UnsupportedVersion {
+                    packet: type_name::<Self>(),
+                    version,
+                })
+            })
+    }
+}
+
+/// All packets of a [`ProtocolState`] and [`Direction`].
+///
+/// Implemented by enums created with [`packet_set!`](crate::packet_set).
+pub trait PacketSet<'de>: Sized {
+    /// The state the packets are sent in.
+    const STATE: ProtocolState;
+    /// The direction the packets are sent in.
+    const DIRECTION: Direction;
+
+    /// Get the ID of this packet.
+    fn id(&self) -> u32;
+
+    /// Get the ID of this packet in a protocol version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the packet does not exist in the version.
+    fn id_for(&self, version: ProtocolVersion) -> Result<u32, PacketError>;
+
+    /// Get the [`Transition`] this packet causes, if any.
+    fn transition(&self) -> Option<Transition>;
+
+    /// Decode a packet from its ID and body.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ID is unknown or the body is invalid.
+    /// Unknown IDs return [`PacketErrorKind::UnknownId`] with the raw body.
+    fn decode_packet(id: u32, body: &'de [u8]) -> Result<Self, PacketError>;
+
+    /// Encode the body of this packet, without its ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    fn encode_body<B: SerializeBuffer>(&self, buffer: &mut B) -> Result<(), PacketError>;
+
+    /// Decode a packet from its ID and body in a protocol version.
+    ///
+    /// See [`Packet::id_for`] and
+    /// [`from_slice_versioned`](crate::from_slice_versioned).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ID is unknown in the version
+    /// or the body is invalid.
+    fn decode_packet_versioned(
+        version: ProtocolVersion,
+        id: u32,
+        body: &'de [u8],
+    ) -> Result<Self, PacketError>;
+
+    /// Encode the body of this packet in a protocol version, without its ID.
+    ///
+    /// See [`to_vec_versioned`](crate::to_vec_versioned).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    fn encode_body_versioned<B: SerializeBuffer>(
+        &self,
+        version: ProtocolVersion,
+        buffer: &mut B,
+    ) -> Result<(), PacketError>;
+
+    /// Decode a packet, checking that it belongs to this set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the state or direction do not match,
+    /// the ID is unknown or the body is invalid.
+    fn decode(
+        state: ProtocolState,
+        direction: Direction,
+        id: u32,
+        body: &'de [u8],
+    ) -> Result<Self, PacketError> {
+        if state == Self::STATE && direction == Self::DIRECTION {
+            Self::decode_packet(id, body)
+        } else {
+            Err(PacketError::new(PacketErrorKind::WrongState { state, direction }))
+        }
+    }
+
+    /// Encode the ID and body of this packet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    fn encode<B: SerializeBuffer>(&self, buffer: &mut B) -> Result<(), PacketError> {
+        McWriter::new(buffer)
+            .write_var_u32(self.id())
+            .map_err(|err| PacketError::from(FSError::Backend(err)))?;
+        self.encode_body(buffer)
+    }
+
+    /// Encode the ID and body of this packet in a protocol version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the packet does not exist in the version
+    /// or serialization fails.
+    fn encode_versioned<B: SerializeBuffer>(
+        &self,
+        version: ProtocolVersion,
+        buffer: &mut B,
+    ) -> Result<(), PacketError> {
+        McWriter::new(buffer)
+            .write_var_u32(self.id_for(version)?)
+            .map_err(|err| PacketError::from(FSError::Backend(err)))?;
+        self.encode_body_versioned(version, buffer)
+    }
+}
+
+/// Decode a packet body, failing if any bytes are left over.
+///
+/// # Errors
+///
+/// Returns an error if the body is invalid or has trailing bytes.
+pub fn decode_body<'de: 'facet, 'facet, T: Deserializable<'facet>>(
+    body: &'de [u8],
+) -> Result<T, PacketError> {
+    let (value, remaining) = crate::from_slice_borrowed::<T>(body)?;
+    if remaining.is_empty() {
+        Ok(value)
+    } else {
+        Err(PacketError::new(PacketErrorKind::TrailingBytes(remaining.len())))
+    }
+}
+
+/// Encode a packet body into a buffer.
+///
+/// # Errors
+///
+/// Returns an error if serialization fails.
+pub fn encode_body<'facet, T: Serializable<'facet>, B: SerializeBuffer>(
+    value: &T,
+    buffer: &mut B,
+) -> Result<(), PacketError> {
+    crate::to_buffer(value, buffer).map(|_| ()).map_err(PacketError::from)
+}
+
+/// Decode a packet body in a protocol version,
+/// failing if any bytes are left over.
+///
+/// # Errors
+///
+/// Returns an error if the body is invalid or has trailing bytes.
+pub fn decode_body_versioned<'de: 'facet, 'facet, T: Deserializable<'facet>>(
+    version: ProtocolVersion,
+    body: &'de [u8],
+) -> Result<T, PacketError> {
+    let (value, remaining) = crate::from_slice_versioned::<T>(body, version.protocol())?;
+    if remaining.is_empty() {
+        Ok(value)
+    } else {
+        Err(PacketError::new(PacketErrorKind::TrailingBytes(remaining.len())))
+    }
+}
+
+/// Encode a packet body in a protocol version into a buffer.
+///
+/// # Errors
+///
+/// Returns an error if serialization fails.
+pub fn encode_body_versioned<'facet, T: Serializable<'facet>, B: SerializeBuffer>(
+    version: ProtocolVersion,
+    value: &T,
+    buffer: &mut B,
+) -> Result<(), PacketError> {
+    let body = crate::to_vec_versioned(value, version.protocol())?;
+    McWriter::new(buffer).write_bytes(&body).map_err(|err| PacketError::from(FSError::Backend(err)))
+}
+
+/// Create the error returned for an unknown packet ID.
+#[must_use]
+pub fn unknown_packet(
+    state: ProtocolState,
+    direction: Direction,
+    id: u32,
+    body: &[u8],
+) -> PacketError {
+    PacketError::new(PacketErrorKind::UnknownId { state, direction, id, body: Vec::from(body) })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Create an enum of all packets in a [`ProtocolState`] and [`Direction`].
+///
+/// Implements [`PacketSet`] for the enum, and [`From`] for every packet.
+/// Every packet must implement [`Packet`] with the same state and direction,
+/// which is checked at compile time.
+///
+/// See the [`packet`](crate::packet) module for an example.
+#[macro_export]
+macro_rules! packet_set {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident $(<$lt:lifetime>)? ($state:ident, $direction:ident) {
+            $( $(#[$vmeta:meta])* $variant:ident($ty:ty) ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name $(<$lt>)? {
+            $( $(#[$vmeta])* $variant($ty), )*
+        }
+
+        impl<'de $(: $lt, $lt)?> $crate::packet::PacketSet<'de> for $name $(<$lt>)? {
+            const STATE: $crate::packet::ProtocolState = $crate::packet::ProtocolState::$state;
+            const DIRECTION: $crate::packet::Direction = $crate::packet::Direction::$direction;
+
+            fn id(&self) -> u32 {
+                match *self {
+                    $( Self::$variant(_) => <$ty as $crate::packet::Packet>::ID, )*
+                }
+            }
+
+            fn id_for(
+                &self,
+                version: $crate::packet::ProtocolVersion,
+            ) -> ::core::result::Result<u32, $crate::packet::PacketError> {
+                match *self {
+                    $( Self::$variant(_) => <$ty as $crate::packet::Packet>::id_for(version), )*
+                }
+            }
+
+            fn transition(&self) -> ::core::option::Option<$crate::connection::Transition> {
+                match *self {
+                    $( Self::$variant(ref packet) => $crate::packet::Packet::transition(packet), )*
+                }
+            }
+
+            fn decode_packet(
+                id: u32,
+                body: &'de [u8],
+            ) -> ::core::result::Result<Self, $crate::packet::PacketError> {
+                $(
+                    const {
+                        assert!(
+                            matches!(
+                                <$ty as $crate::packet::Packet>::STATE,
+                                $crate::packet::ProtocolState::$state
+                            ) && matches!(
+                                <$ty as $crate::packet::Packet>::DIRECTION,
+                                $crate::packet::Direction::$direction
+                            ),
+                            concat!(stringify!($ty), " is not a ", stringify!($direction), " ", stringify!($state), " packet"),
+                        );
+                    }
+                    if id == <$ty as $crate::packet::Packet>::ID {
+                        return $crate::packet::decode_body::<$ty>(body).map(Self::$variant);
+                    }
+                )*
+                Err($crate::packet::unknown_packet(Self::STATE, Self::DIRECTION, id, body))
+            }
+
+            fn encode_body<B: $crate::serialize::SerializeBuffer>(
+                &self,
+                buffer: &mut B,
+            ) -> ::core::result::Result<(), $crate::packet::PacketError> {
+                match *self {
+                    $( Self::$variant(ref packet) => $crate::packet::encode_body(packet, buffer), )*
+                }
+            }
+
+            fn decode_packet_versioned(
+                version: $crate::packet::ProtocolVersion,
+                id: u32,
+                body: &'de [u8],
+            ) -> ::core::result::Result<Self, $crate::packet::PacketError> {
+                $(
+                    if <$ty as $crate::packet::Packet>::id_for(version).ok() == Some(id) {
+                        return $crate::packet::decode_body_versioned::<$ty>(version, body)
+                            .map(Self::$variant);
+                    }
+                )*
+                Err($crate::packet::unknown_packet(Self::STATE, Self::DIRECTION, id, body))
+            }
+
+            fn encode_body_versioned<B: $crate::serialize::SerializeBuffer>(
+                &self,
+                version: $crate::packet::ProtocolVersion,
+                buffer: &mut B,
+            ) -> ::core::result::Result<(), $crate::packet::PacketError> {
+                match *self {
+                    $(
+                        Self::$variant(ref packet) => {
+                            $crate::packet::encode_body_versioned(version, packet, buffer)
+                        }
+                    )*
+                }
+            }
+        }
+
+        $crate::packet_set!(@from ($($lt)?) $name; $( $variant($ty), )*);
+    };
+
+    (@from ($($lt:lifetime)?) $name:ident; ) => {};
+    (@from ($($lt:lifetime)?) $name:ident; $variant:ident($ty:ty), $($rest:tt)*) => {
+        impl<$($lt)?> ::core::convert::From<$ty> for $name<$($lt)?> {
+            #[inline]
+            fn from(packet: $ty) -> Self { Self::$variant(packet) }
+        }
+
+        $crate::packet_set!(@from ($($lt)?) $name; $($rest)*);
+    };
+}
@@ -0,0 +1,137 @@
+//! Protocol versions and the releases using them.
+
+use core::fmt::{self, Display, Formatter};
+
+/// A protocol version number, as sent in the Intention packet.
+///
+/// Releases sharing a protocol version are interchangeable on the wire.
+/// Snapshots use a separate numbering with [`ProtocolVersion::SNAPSHOT_BIT`]
+/// set, so every snapshot compares greater than every release.
+///
+/// ```rust
+/// use facet_minecraft::packet::ProtocolVersion;
+///
+/// let version = ProtocolVersion::from_name("1.21.4").unwrap();
+/// assert_eq!(version, ProtocolVersion::V1_21_4);
+/// assert_eq!(version.protocol(), 769);
+/// assert_eq!(ProtocolVersion::new(772).name(), Some("1.21.8"));
+///
+/// let snapshot = ProtocolVersion::snapshot(250);
+/// assert!(snapshot.is_snapshot() && snapshot > ProtocolVersion::LATEST);
+/// assert_eq!(snapshot.to_string(), "snapshot 250");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProtocolVersion(u32);
+
+impl ProtocolVersion {
+    /// The newest known release.
+    pub const LATEST: Self = Self::V1_21_9;
+    /// Every known release name and its protocol version, newest first.
+    pub const RELEASES: &'static [(&'static str, Self)] = &[
+        ("1.21.10", Self::V1_21_9),
+        ("1.21.9", Self::V1_21_9),
+        ("1.21.8", Self::V1_21_7),
+        ("1.21.7", Self::V1_21_7),
+        ("1.21.6", Self::V1_21_6),
+        ("1.21.5", Self::V1_21_5),
+        ("1.21.4", Self::V1_21_4),
+        ("1.21.3", Self::V1_21_2),
+        ("1.21.2", Self::V1_21_2),
+        ("1.21.1", Self::V1_21),
+        ("1.21", Self::V1_21),
+        ("1.20.6", Self::V1_20_5),
+        ("1.20.5", Self::V1_20_5),
+        ("1.20.4", Self::V1_20_3),
+        ("1.20.3", Self::V1_20_3),
+        ("1.20.2", Self::V1_20_2),
+    ];
+    /// The bit set in the protocol version of every snapshot.
+    pub const SNAPSHOT_BIT: u32 = 1 << 30;
+    /// The protocol version of 1.20.2.
+    pub const V1_20_2: Self = Self(764);
+    /// The protocol version of 1.20.3 and 1.20.4.
+    pub const V1_20_3: Self = Self(765);
+    /// The protocol version of 1.20.5 and 1.20.6.
+    pub const V1_20_5: Self = Self(766);
+    /// The protocol version of 1.21 and 1.21.1.
+    pub const V1_21: Self = Self(767);
+    /// The protocol version of 1.21.2 and 1.21.3.
+    pub const V1_21_2: Self = Self(768);
+    /// The protocol version of 1.21.4.
+    pub const V1_21_4: Self = Self(769);
+    /// The protocol version of 1.21.5.
+    pub const V1_21_5: Self = Self(770);
+    /// The protocol version of 1.21.6.
+    pub const V1_21_6: Self = Self(771);
+    /// The protocol version of 1.21.7 and 1.21.8.
+    pub const V1_21_7: Self = Self(772);
+    /// The protocol version of 1.21.9 and 1.21.10.
+    pub const V1_21_9: Self = Self(773);
+
+    /// Create a [`ProtocolVersion`] from a protocol version number.
+    #[inline]
+    #[must_use]
+    pub const fn new(protocol: u32) -> Self { Self(protocol) }
+
+    /// Create the [`ProtocolVersion`] of a snapshot from its snapshot number.
+    #[inline]
+    #[must_use]
+    pub const fn snapshot(number: u32) -> Self { Self(Self::SNAPSHOT_BIT | number) }
+
+    /// Get the protocol version number.
+    #[inline]
+    #[must_use]
+    pub const fn protocol(self) -> u32 { self.0 }
+
+    /// Returns `true` if this is the protocol version of a snapshot.
+    #[inline]
+    #[must_use]
+    pub const fn is_snapshot(self) -> bool { self.0 & Self::SNAPSHOT_BIT != 0 }
+
+    /// Get the snapshot number, if this is the protocol version of a snapshot.
+    #[inline]
+    #[must_use]
+    pub const fn snapshot_number(self) -> Option<u32> {
+        if self.is_snapshot() { Some(self.0 & !Self::SNAPSHOT_BIT) } else { None }
+    }
+
+    /// Get the protocol version of a release by name, such as `"1.21.4"`.
+    ///
+    /// Snapshots can be named by their snapshot number,
+    /// such as `"snapshot 250"`.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        if let Some(number) = name.strip_prefix("snapshot ") {
+            return number.parse().ok().map(Self::snapshot);
+        }
+        Self::RELEASES.iter().find(|(release, _)| *release == name).map(|&(_, version)| version)
+    }
+
+    /// Get the name of the newest release using this protocol version.
+    #[must_use]
+    pub fn name(self) -> Option<&'static str> {
+        Self::RELEASES.iter().find(|(_, version)| *version == self).map(|&(name, _)| name)
+    }
+}
+
+impl From<u32> for ProtocolVersion {
+    #[inline]
+    fn from(protocol: u32) -> Self { Self(protocol) }
+}
+
+impl From<ProtocolVersion> for u32 {
+    #[inline]
+    fn from(version: ProtocolVersion) -> Self { version.0 }
+}
+
+impl Display for ProtocolVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(number) = self.snapshot_number() {
+            write!(f, "snapshot {number}")
+        } else if let Some(name) = self.name() {
+            write!(f, "{name} ({})", self.0)
+        } else {
+            write!(f, "protocol {}", self.0)
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use alloc::{string::String, vec::Vec};
+
+use facet::{Facet, Shape};
+
+use crate::{
+    codec::{McCodec, McReader, McWriter, codec_shape},
+    common::TypeSerializeHint,
+    deserialize::{DeserializeError, DeserializeErrorKind},
+    serialize::SerializeError,
+    types::Identifier,
+};
+
+/// The name of a client or server implementation, such as `vanilla`.
+///
+/// Sent on `minecraft:brand` as a `VarInt`-prefixed string.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Brand(pub String);
+
+impl Brand {
+    /// The brand of the vanilla client and server.
+    pub const VANILLA: &'static str = "vanilla";
+
+    /// Create a new [`Brand`].
+    #[inline]
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self { Self(name.into()) }
+
+    /// Get the brand as a string slice.
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> &str { &self.0 }
+}
+
+impl McCodec for Brand {
+    // VarInt length repr + unknown max
+    const SERIALIZE_HINT: TypeSerializeHint = TypeSerializeHint::Range { min: 1, max: None };
+
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        writer.write_str(&self.0)
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        reader.read_str().map(|name| Self(name.into()))
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for Brand {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("Brand").build() };
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// A list of channels, sent on `minecraft:register` and `minecraft:unregister`.
+///
+/// Channels are separated by `NUL` bytes and fill the rest of the input,
+/// so a [`ChannelList`] must be the last field of a packet.
+/// Empty entries, such as from a trailing `NUL`, are skipped.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ChannelList(pub Vec<Identifier<'static>>);
+
+impl ChannelList {
+    /// The byte separating channels.
+    const SEPARATOR: u8 = 0;
+}
+
+impl FromIterator<Identifier<'static>> for ChannelList {
+    fn from_iter<I: IntoIterator<Item = Identifier<'static>>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl McCodec for ChannelList {
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        for (index, channel) in self.0.iter().enumerate() {
+            if index != 0 {
+                writer.write_u8(Self::SEPARATOR)?;
+            }
+            writer.write_bytes(channel.as_str().as_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        let data = reader.read_bytes(reader.remaining().len())?;
+        data.split(|byte| *byte == Self::SEPARATOR)
+            .filter(|channel| !channel.is_empty())
+            .map(|channel| {
+                let channel = core::str::from_utf8(channel)
+                    .map_err(|_| DeserializeError::new(DeserializeErrorKind::InvalidUtf8))?;
+                Ok(Identifier::new(channel)?.into_owned())
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for ChannelList {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("ChannelList").build() };
+}
@@ -0,0 +1,308 @@
+//! Typed custom payloads, as sent in the Plugin Message packets.
+//!
+//! A custom payload is a channel [`Identifier`] followed by data that fills
+//! the rest of the packet. A [`ChannelRegistry`] maps channels to the types
+//! their data is decoded into, and keeps the data of unknown channels as raw
+//! bytes.
+//!
+//! ```rust
+//! use facet::Facet;
+//! use facet_minecraft::{
+//!     payload::{Brand, ChannelRegistry, CustomPayload},
+//!     types::Identifier,
+//! };
+//!
+//! #[derive(Debug, PartialEq, Facet)]
+//! struct Handshake {
+//!     version: u8,
+//! }
+//!
+//! let mut registry = ChannelRegistry::new();
+//! registry.register::<Handshake>(Identifier::new_unchecked("example:handshake"));
+//!
+//! let mut body = Vec::new();
+//! CustomPayload::new(Identifier::new_unchecked("example:handshake"), Handshake { version: 2 })
+//!     .encode_body(&mut body)
+//!     .unwrap();
+//!
+//! let payload = registry.decode(&body).unwrap();
+//! assert_eq!(payload.downcast_ref::<Handshake>(), Some(&Handshake { version: 2 }));
+//!
+//! let payload = registry.decode(b"\x0fminecraft:brand\x07vanilla").unwrap();
+//! assert_eq!(payload.downcast_ref::<Brand>().map(Brand::as_str), Some("vanilla"));
+//! ```
+
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    any::Any,
+    fmt::{self, Debug, Formatter},
+};
+
+use facet::{Facet, Shape};
+use facet_format::{DeserializeError as FDError, SerializeError as FSError};
+
+use crate::{
+    codec::{McReader, McWriter},
+    packet::PacketError,
+    serialize::SerializeBuffer,
+    types::Identifier,
+};
+
+mod builtin;
+pub use builtin::{Brand, ChannelList};
+
+/// The data of a [`CustomPayload`].
+#[derive(Debug)]
+pub enum Payload {
+    /// Data decoded into the type registered for the channel.
+    Typed(TypedPayload),
+    /// The data of a channel without a registered type.
+    Raw(Vec<u8>),
+}
+
+/// A custom payload sent on a plugin channel.
+#[derive(Debug)]
+pub struct CustomPayload {
+    /// The channel the payload was sent on.
+    pub channel: Identifier<'static>,
+    /// The data of the payload.
+    pub payload: Payload,
+}
+
+impl CustomPayload {
+    /// Create a new [`CustomPayload`] containing a typed value.
+    #[must_use]
+    pub fn new<T: for<'a> Facet<'a> + Send + Sync>(channel: Identifier<'static>, value: T) -> Self {
+        Self { channel, payload: Payload::Typed(TypedPayload::new(value)) }
+    }
+
+    /// Create a new [`CustomPayload`] containing raw data.
+    #[must_use]
+    pub const fn raw(channel: Identifier<'static>, data: Vec<u8>) -> Self {
+        Self { channel, payload: Payload::Raw(data) }
+    }
+
+    /// Get a reference to the typed value, if it is a `T`.
+    #[must_use]
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        match &self.payload {
+            Payload::Typed(payload) => payload.downcast_ref(),
+            Payload::Raw(_) => None,
+        }
+    }
+
+    /// Get the raw data, if the channel has no registered type.
+    #[must_use]
+    pub fn raw_data(&self) -> Option<&[u8]> {
+        match &self.payload {
+            Payload::Typed(_) => None,
+            Payload::Raw(data) => Some(data),
+        }
+    }
+
+    /// Encode the channel and the data of the payload into a buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data cannot be serialized.
+    pub fn encode_body<B: SerializeBuffer>(&self, buffer: &mut B) -> Result<(), PacketError> {
+        let mut writer = McWriter::new(buffer);
+        writer.write_identifier(&self.channel).map_err(FSError::Backend)?;
+        match &self.payload {
+            Payload::Typed(payload) => writer.write_bytes(&payload.encode()?),
+            Payload::Raw(data) => writer.write_bytes(data),
+        }
+        .map_err(|err| PacketError::from(FSError::Backend(err)))
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// A type-erased value decoded from a [`CustomPayload`].
+pub struct TypedPayload {
+    value: Box<dyn Any + Send + Sync>,
+    shape: &'static Shape,
+    encode: fn(&(dyn Any + Send + Sync)) -> Result<Vec<u8>, PacketError>,
+}
+
+impl TypedPayload {
+    /// Create a new [`TypedPayload`] from a value.
+    #[must_use]
+    pub fn new<T: for<'a> Facet<'a> + Send + Sync>(value: T) -> Self {
+        /// Serialize the value, which is always a `T`.
+        fn encode<T: for<'a> Facet<'a>>(
+            value: &(dyn Any + Send + Sync),
+        ) -> Result<Vec<u8>, PacketError> {
+            let value = value.downcast_ref::<T>().expect("payload value has the wrong type");
+            crate::to_vec(value).map_err(PacketError::from)
+        }
+
+        Self { value: Box::new(value), shape: T::SHAPE, encode: encode::<T> }
+    }
+
+    /// Get the [`Shape`] of the value.
+    #[inline]
+    #[must_use]
+    pub const fn shape(&self) -> &'static Shape { self.shape }
+
+    /// Returns `true` if the value is a `T`.
+    #[inline]
+    #[must_use]
+    pub fn is<T: 'static>(&self) -> bool { self.value.is::<T>() }
+
+    /// Get a reference to the value, if it is a `T`.
+    #[inline]
+    #[must_use]
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> { self.value.downcast_ref() }
+
+    /// Take the value, if it is a `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the payload unchanged if the value is not a `T`.
+    pub fn downcast<T: 'static>(self) -> Result<T, Self> {
+        if self.is::<T>() {
+            Ok(*self.value.downcast().unwrap_or_else(|_| unreachable!()))
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Serialize the value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value cannot be serialized.
+    pub fn encode(&self) -> Result<Vec<u8>, PacketError> { (self.encode)(&*self.value) }
+}
+
+impl Debug for TypedPayload {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedPayload")
+            .field("type", &self.shape.type_identifier)
+            .finish_non_exhaustive()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// A function decoding the data of a channel.
+type DecodeFn = fn(&[u8]) -> Result<TypedPayload, PacketError>;
+
+/// A registry of the types sent on plugin channels.
+///
+/// Channels are compared as [`Identifier`]s,
+/// so `brand` and `minecraft:brand` are the same channel.
+#[derive(Debug, Clone)]
+pub struct ChannelRegistry {
+    channels: Vec<(Identifier<'static>, DecodeFn)>,
+}
+
+impl ChannelRegistry {
+    /// The channel the client and server send their [`Brand`] on.
+    pub const BRAND: Identifier<'static> = Identifier::new_unchecked("minecraft:brand");
+    /// The channel used to announce the channels a client or server listens on.
+    pub const REGISTER: Identifier<'static> = Identifier::new_unchecked("minecraft:register");
+    /// The channel used to announce the channels a client or server stopped
+    /// listening on.
+    pub const UNREGISTER: Identifier<'static> = Identifier::new_unchecked("minecraft:unregister");
+
+    /// Create a new [`ChannelRegistry`] with the built-in channels.
+    ///
+    /// | Channel                | Type            |
+    /// |------------------------|-----------------|
+    /// | `minecraft:brand`      | [`Brand`]       |
+    /// | `minecraft:register`   | [`ChannelList`] |
+    /// | `minecraft:unregister` | [`ChannelList`] |
+    #[must_use]
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        registry
+            .register::<Brand>(Self::BRAND)
+            .register::<ChannelList>(Self::REGISTER)
+            .register::<ChannelList>(Self::UNREGISTER);
+        registry
+    }
+
+    /// Create a new [`ChannelRegistry`] without any channels.
+    #[inline]
+    #[must_use]
+    pub const fn empty() -> Self { Self { channels: Vec::new() } }
+
+    /// Register the type sent on a channel,
+    /// replacing any type already registered for it.
+    pub fn register<T: for<'a> Facet<'a> + Send + Sync>(
+        &mut self,
+        channel: Identifier<'static>,
+    ) -> &mut Self {
+        /// Deserialize the data as a `T`.
+        fn decode<T: for<'a> Facet<'a> + Send + Sync>(
+            data: &[u8],
+        ) -> Result<TypedPayload, PacketError> {
+            crate::packet::decode_body::<T>(data).map(TypedPayload::new)
+        }
+
+        match self.channels.iter_mut().find(|(id, _)| *id == channel) {
+            Some((_, function)) => *function = decode::<T>,
+            None => self.channels.push((channel, decode::<T>)),
+        }
+        self
+    }
+
+    /// Remove the type registered for a channel,
+    /// returning `true` if one was registered.
+    pub fn unregister(&mut self, channel: &Identifier<'_>) -> bool {
+        let len = self.channels.len();
+        self.channels.retain(|(id, _)| id != channel);
+        self.channels.len() != len
+    }
+
+    /// Returns `true` if a type is registered for the channel.
+    #[must_use]
+    pub fn contains(&self, channel: &Identifier<'_>) -> bool {
+        self.channels.iter().any(|(id, _)| id == channel)
+    }
+
+    /// Get the channels with a registered type, in registration order.
+    pub fn channels(&self) -> impl Iterator<Item = &Identifier<'static>> {
+        self.channels.iter().map(|(id, _)| id)
+    }
+
+    /// Decode the body of a Plugin Message packet.
+    ///
+    /// Data sent on unknown channels is kept as [`Payload::Raw`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the channel is not a valid identifier,
+    /// or the data is invalid for the registered type.
+    pub fn decode(&self, body: &[u8]) -> Result<CustomPayload, PacketError> {
+        let mut reader = McReader::new(body);
+        let channel = reader.read_identifier().map_err(FDError::Parser)?.into_owned();
+        self.decode_data(channel, reader.remaining())
+    }
+
+    /// Decode the data sent on a channel.
+    ///
+    /// Data sent on unknown channels is kept as [`Payload::Raw`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is invalid for the registered type,
+    /// including if any bytes are left over.
+    pub fn decode_data(
+        &self,
+        channel: Identifier<'static>,
+        data: &[u8],
+    ) -> Result<CustomPayload, PacketError> {
+        let payload = match self.channels.iter().find(|(id, _)| *id == channel) {
+            Some((_, decode)) => Payload::Typed(decode(data)?),
+            None => Payload::Raw(data.to_vec()),
+        };
+        Ok(CustomPayload { channel, payload })
+    }
+}
+
+impl Default for ChannelRegistry {
+    fn default() -> Self { Self::new() }
+}
@@ -0,0 +1,56 @@
+use core::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// An error that occurred while encoding or decoding a game profile.
+#[derive(Debug)]
+pub struct ProfileError {
+    kind: ProfileErrorKind,
+}
+
+impl ProfileError {
+    /// Create a new [`ProfileError`].
+    #[inline]
+    #[must_use]
+    pub const fn new(kind: ProfileErrorKind) -> Self { Self { kind } }
+
+    /// Get the kind of profile error.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> &ProfileErrorKind { &self.kind }
+}
+
+/// The type of profile error.
+#[derive(Debug)]
+pub enum ProfileErrorKind {
+    /// A player name was longer than [`GameProfile::MAX_NAME_LENGTH`].
+    ///
+    /// [`GameProfile::MAX_NAME_LENGTH`]: super::GameProfile::MAX_NAME_LENGTH
+    NameTooLong(usize),
+    /// A profile had more than [`GameProfile::MAX_PROPERTIES`] properties.
+    ///
+    /// [`GameProfile::MAX_PROPERTIES`]: super::GameProfile::MAX_PROPERTIES
+    TooManyProperties(usize),
+    /// A property's name, value or signature was longer than its limit.
+    PropertyTooLong(usize),
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Error for ProfileError {}
+impl Display for ProfileError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ProfileErrorKind::NameTooLong(len) => {
+                write!(f, "player name is {len} characters long")
+            }
+            ProfileErrorKind::TooManyProperties(count) => {
+                write!(f, "game profile has {count} properties")
+            }
+            ProfileErrorKind::PropertyTooLong(len) => {
+                write!(f, "game profile property is {len} characters long")
+            }
+        }
+    }
+}
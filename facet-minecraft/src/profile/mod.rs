@@ -0,0 +1,366 @@
+//! Game profiles, as sent in the Login Success and Player Info Update packets.
+//!
+//! A [`GameProfile`] is a player's UUID and name, along with
+//! [`ProfileProperty`]s such as their skin. Servers running without
+//! authentication use [`offline_uuid`] to give players a stable UUID.
+//! A [`ResolvableProfile`] is a complete or partial profile looked up by the
+//! client, such as in entity metadata since 1.21.9.
+//!
+//! ```rust
+//! use facet_minecraft::profile::{GameProfile, ProfileProperty};
+//! use uuid::Uuid;
+//!
+//! let id = Uuid::from_u128(0x069a_79f4_44e9_4726_a5be_fca9_0e38_aaf5);
+//! let mut profile = GameProfile::new(id, "Notch");
+//!
+//! profile.properties.push(ProfileProperty::new("textures", "e30="));
+//! let bytes = facet_minecraft::to_vec(&profile).unwrap();
+//! let (decoded, _) = facet_minecraft::from_slice::<GameProfile>(&bytes).unwrap();
+//! assert_eq!(decoded, profile);
+//! ```
+
+use alloc::{string::String, vec::Vec};
+
+use facet::{Facet, Shape};
+use uuid::Uuid;
+
+use crate::{
+    codec::{McCodec, McReader, McWriter, codec_shape},
+    deserialize::DeserializeError,
+    serialize::SerializeError,
+    types::Identifier,
+};
+
+mod error;
+pub use error::{ProfileError, ProfileErrorKind};
+
+/// A player's profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameProfile {
+    /// The player's UUID.
+    pub id: Uuid,
+    /// The player's name.
+    pub name: String,
+    /// The profile's properties.
+    pub properties: Vec<ProfileProperty>,
+}
+
+/// A property of a [`GameProfile`], such as `textures`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileProperty {
+    /// The name of the property.
+    pub name: String,
+    /// The value of the property, usually base64-encoded JSON.
+    pub value: String,
+    /// The base64-encoded signature of the value, if signed by Mojang.
+    pub signature: Option<String>,
+}
+
+impl GameProfile {
+    /// The longest player name, in characters.
+    pub const MAX_NAME_LENGTH: usize = 16;
+    /// The largest number of properties.
+    pub const MAX_PROPERTIES: usize = 16;
+
+    /// Create a new [`GameProfile`] without properties.
+    #[must_use]
+    pub fn new(id: Uuid, name: impl Into<String>) -> Self {
+        Self { id, name: name.into(), properties: Vec::new() }
+    }
+
+    /// Create a new [`GameProfile`] for a server running without
+    /// authentication, using the player's [`offline_uuid`].
+    ///
+    /// ```rust
+    /// use facet_minecraft::profile::GameProfile;
+    ///
+    /// let profile = GameProfile::offline("Notch");
+    /// assert_eq!(profile.id.to_string(), "b50ad385-829d-3141-a216-7e7d7539ba7f");
+    /// ```
+    #[cfg(feature = "offline")]
+    #[must_use]
+    pub fn offline(name: impl Into<String>) -> Self {
+        let name = name.into();
+        Self::new(offline_uuid(&name), name)
+    }
+
+    /// Get a property by name, if present.
+    #[must_use]
+    pub fn property(&self, name: &str) -> Option<&ProfileProperty> {
+        self.properties.iter().find(|property| property.name == name)
+    }
+}
+
+impl McCodec for GameProfile {
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        check_length(&self.name, Self::MAX_NAME_LENGTH, ProfileErrorKind::NameTooLong)?;
+        writer.write_uuid(&self.id)?;
+        writer.write_str(&self.name)?;
+        encode_properties(&self.properties, writer)
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        let id = reader.read_uuid()?;
+        let name = read_string(reader, Self::MAX_NAME_LENGTH, ProfileErrorKind::NameTooLong)?;
+        let properties = decode_properties(reader)?;
+        Ok(Self { id, name, properties })
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for GameProfile {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("GameProfile").build() };
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl ProfileProperty {
+    /// The longest property name, in characters.
+    pub const MAX_NAME_LENGTH: usize = 64;
+    /// The longest property signature, in characters.
+    pub const MAX_SIGNATURE_LENGTH: usize = 1024;
+    /// The longest property value, in characters.
+    pub const MAX_VALUE_LENGTH: usize = 32767;
+
+    /// Create a new, unsigned [`ProfileProperty`].
+    #[must_use]
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self { name: name.into(), value: value.into(), signature: None }
+    }
+
+    /// Create a new, signed [`ProfileProperty`].
+    #[must_use]
+    pub fn signed(
+        name: impl Into<String>,
+        value: impl Into<String>,
+        signature: impl Into<String>,
+    ) -> Self {
+        Self { name: name.into(), value: value.into(), signature: Some(signature.into()) }
+    }
+}
+
+impl McCodec for ProfileProperty {
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        let too_long = ProfileErrorKind::PropertyTooLong;
+        check_length(&self.name, Self::MAX_NAME_LENGTH, too_long)?;
+        check_length(&self.value, Self::MAX_VALUE_LENGTH, too_long)?;
+        if let Some(signature) = &self.signature {
+            check_length(signature, Self::MAX_SIGNATURE_LENGTH, too_long)?;
+        }
+
+        writer.write_str(&self.name)?;
+        writer.write_str(&self.value)?;
+        writer.write_bool(self.signature.is_some())?;
+        self.signature.as_ref().map_or(Ok(()), |signature| writer.write_str(signature))
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        let too_long = ProfileErrorKind::PropertyTooLong;
+        Ok(Self {
+            name: read_string(reader, Self::MAX_NAME_LENGTH, too_long)?,
+            value: read_string(reader, Self::MAX_VALUE_LENGTH, too_long)?,
+            signature: if reader.read_bool()? {
+                Some(read_string(reader, Self::MAX_SIGNATURE_LENGTH, too_long)?)
+            } else {
+                None
+            },
+        })
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for ProfileProperty {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("ProfileProperty").build() };
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// A profile resolved by the client, such as the one shown by a mannequin.
+///
+/// Sent since 1.21.9.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvableProfile {
+    /// The profile, or the parts of it used to look it up.
+    pub profile: ProfileSource,
+    /// The textures replacing those of the profile.
+    pub skin: SkinPatch,
+}
+
+/// The profile of a [`ResolvableProfile`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProfileSource {
+    /// A complete profile, used as is.
+    Complete(GameProfile),
+    /// A partial profile, looked up by name or UUID.
+    Partial {
+        /// The player's name.
+        name: Option<String>,
+        /// The player's UUID.
+        id: Option<Uuid>,
+        /// The profile's properties.
+        properties: Vec<ProfileProperty>,
+    },
+}
+
+/// Textures replacing the skin of a [`ResolvableProfile`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SkinPatch {
+    /// The texture of the body.
+    pub body: Option<Identifier<'static>>,
+    /// The texture of the cape.
+    pub cape: Option<Identifier<'static>>,
+    /// The texture of the elytra.
+    pub elytra: Option<Identifier<'static>>,
+    /// The model of the body.
+    pub model: Option<PlayerModel>,
+}
+
+/// The model of a player's body.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PlayerModel {
+    /// Arms four pixels wide.
+    #[default]
+    Wide,
+    /// Arms three pixels wide.
+    Slim,
+}
+
+impl ResolvableProfile {
+    /// Create a new [`ResolvableProfile`] from a complete profile,
+    /// without changing its skin.
+    #[must_use]
+    pub fn new(profile: GameProfile) -> Self {
+        Self { profile: ProfileSource::Complete(profile), skin: SkinPatch::default() }
+    }
+}
+
+impl McCodec for ResolvableProfile {
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        match &self.profile {
+            ProfileSource::Complete(profile) => {
+                writer.write_bool(true)?;
+                profile.encode(writer)?;
+            }
+            ProfileSource::Partial { name, id, properties } => {
+                writer.write_bool(false)?;
+                writer.write_bool(name.is_some())?;
+                if let Some(name) = name {
+                    check_length(
+                        name,
+                        GameProfile::MAX_NAME_LENGTH,
+                        ProfileErrorKind::NameTooLong,
+                    )?;
+                    writer.write_str(name)?;
+                }
+                writer.write_bool(id.is_some())?;
+                id.as_ref().map_or(Ok(()), |id| writer.write_uuid(id))?;
+                encode_properties(properties, writer)?;
+            }
+        }
+
+        for texture in [&self.skin.body, &self.skin.cape, &self.skin.elytra] {
+            writer.write_bool(texture.is_some())?;
+            texture.as_ref().map_or(Ok(()), |texture| writer.write_identifier(texture))?;
+        }
+        writer.write_bool(self.skin.model.is_some())?;
+        self.skin.model.map_or(Ok(()), |model| writer.write_bool(model == PlayerModel::Slim))
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        let profile = if reader.read_bool()? {
+            ProfileSource::Complete(GameProfile::decode(reader)?)
+        } else {
+            let too_long = ProfileErrorKind::NameTooLong;
+            ProfileSource::Partial {
+                name: if reader.read_bool()? {
+                    Some(read_string(reader, GameProfile::MAX_NAME_LENGTH, too_long)?)
+                } else {
+                    None
+                },
+                id: if reader.read_bool()? { Some(reader.read_uuid()?) } else { None },
+                properties: decode_properties(reader)?,
+            }
+        };
+
+        let mut texture = || -> Result<_, DeserializeError> {
+            if reader.read_bool()? {
+                Ok(Some(reader.read_identifier()?.into_owned()))
+            } else {
+                Ok(None)
+            }
+        };
+        let (body, cape, elytra) = (texture()?, texture()?, texture()?);
+        let model = if reader.read_bool()? {
+            Some(if reader.read_bool()? { PlayerModel::Slim } else { PlayerModel::Wide })
+        } else {
+            None
+        };
+
+        Ok(Self { profile, skin: SkinPatch { body, cape, elytra, model } })
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for ResolvableProfile {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("ResolvableProfile").build() };
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Compute the UUID of a player on a server running without authentication.
+///
+/// This is a version 3 UUID of the MD5 hash of `"OfflinePlayer:" + name`,
+/// the same as Java's `UUID.nameUUIDFromBytes`.
+#[cfg(feature = "offline")]
+#[must_use]
+pub fn offline_uuid(name: &str) -> Uuid {
+    use md5::{Digest, Md5};
+
+    let hash = Md5::new().chain_update(b"OfflinePlayer:").chain_update(name).finalize();
+    uuid::Builder::from_md5_bytes(hash.into()).into_uuid()
+}
+
+/// Write a list of properties, checking its length.
+fn encode_properties(
+    properties: &[ProfileProperty],
+    writer: &mut McWriter<'_>,
+) -> Result<(), SerializeError> {
+    if properties.len() > GameProfile::MAX_PROPERTIES {
+        let count = properties.len();
+        return Err(ProfileError::new(ProfileErrorKind::TooManyProperties(count)).into());
+    }
+
+    writer.write_var_u32(u32::try_from(properties.len()).unwrap_or(u32::MAX))?;
+    properties.iter().try_for_each(|property| property.encode(writer))
+}
+
+/// Read a list of properties, checking its length.
+fn decode_properties(reader: &mut McReader<'_>) -> Result<Vec<ProfileProperty>, DeserializeError> {
+    let count = reader.read_var_u32()? as usize;
+    if count > GameProfile::MAX_PROPERTIES {
+        return Err(ProfileError::new(ProfileErrorKind::TooManyProperties(count)).into());
+    }
+
+    (0..count).map(|_| ProfileProperty::decode(reader)).collect()
+}
+
+/// Check a string's length, in UTF-16 code units as counted by Java.
+fn check_length(
+    value: &str,
+    max: usize,
+    error: fn(usize) -> ProfileErrorKind,
+) -> Result<(), ProfileError> {
+    let len = value.encode_utf16().count();
+    if len > max { Err(ProfileError::new(error(len))) } else { Ok(()) }
+}
+
+/// Read a string, checking its length.
+fn read_string(
+    reader: &mut McReader<'_>,
+    max: usize,
+    error: fn(usize) -> ProfileErrorKind,
+) -> Result<String, DeserializeError> {
+    let value = reader.read_str()?;
+    check_length(value, max, error)?;
+    Ok(value.into())
+}
@@ -7,6 +7,11 @@ use core::{
 use facet::{Facet, Shape};
 use facet_format::ScalarValue;
 
+use crate::{
+    chat::ChatError, chunk::ChunkError, command::CommandError, entity::EntityError,
+    frame::FrameError, item::ItemError, nbt::NbtError, profile::ProfileError,
+};
+
 /// An error that occurred during serialization.
 #[derive(Debug)]
 pub struct SerializeError {
@@ -69,11 +74,46 @@ pub enum SerializeErrorKind {
     BufferError,
     /// Could not get the discriminant of an enum variant.
     DiscriminantMissing,
+    /// A struct field is not present in the protocol version.
+    UnavailableField {
+        /// The name of the field.
+        field: &'static str,
+        /// The protocol version.
+        version: u32,
+    },
+    /// An enum variant is not present in the protocol version.
+    UnavailableVariant {
+        /// The name of the variant.
+        variant: &'static str,
+        /// The protocol version.
+        version: u32,
+    },
 
     /// Attempted to serialize a type that is not supported.
     UnsupportedType(&'static Shape),
     /// Attempted to variable-length serialize a type that does not support it.
     VariableLength(&'static str),
+    /// A string was too long to be encoded with a `u16` length prefix.
+    StringTooLong(usize),
+    /// A registry ID was too large to be encoded offset by one,
+    /// such as in an [`IdOr`](crate::types::IdOr).
+    IdTooLarge(u32),
+    /// An error occurred while encoding a packet frame.
+    Frame(FrameError),
+    /// An error occurred while serializing a field as NBT.
+    Nbt(NbtError),
+    /// An error occurred while encoding chunk data.
+    Chunk(ChunkError),
+    /// An error occurred while encoding entity metadata.
+    Entity(EntityError),
+    /// An error occurred while encoding an item stack.
+    Item(ItemError),
+    /// An error occurred while encoding a command graph.
+    Command(CommandError),
+    /// An error occurred while encoding signed chat data.
+    Chat(ChatError),
+    /// An error occurred while encoding a game profile.
+    Profile(ProfileError),
 
     /// An I/O error occurred.
     #[cfg(feature = "std")]
@@ -87,6 +127,46 @@ impl Display for SerializeError {
     fn fmt(&self, _f: &mut Formatter<'_>) -> fmt::Result { todo!() }
 }
 
+impl From<FrameError> for SerializeError {
+    #[inline]
+    fn from(err: FrameError) -> Self { Self { kind: SerializeErrorKind::Frame(err) } }
+}
+
+impl From<ChunkError> for SerializeError {
+    #[inline]
+    fn from(err: ChunkError) -> Self { Self { kind: SerializeErrorKind::Chunk(err) } }
+}
+
+impl From<EntityError> for SerializeError {
+    #[inline]
+    fn from(err: EntityError) -> Self { Self { kind: SerializeErrorKind::Entity(err) } }
+}
+
+impl From<ItemError> for SerializeError {
+    #[inline]
+    fn from(err: ItemError) -> Self { Self { kind: SerializeErrorKind::Item(err) } }
+}
+
+impl From<CommandError> for SerializeError {
+    #[inline]
+    fn from(err: CommandError) -> Self { Self { kind: SerializeErrorKind::Command(err) } }
+}
+
+impl From<ChatError> for SerializeError {
+    #[inline]
+    fn from(err: ChatError) -> Self { Self { kind: SerializeErrorKind::Chat(err) } }
+}
+
+impl From<ProfileError> for SerializeError {
+    #[inline]
+    fn from(err: ProfileError) -> Self { Self { kind: SerializeErrorKind::Profile(err) } }
+}
+
+impl From<NbtError> for SerializeError {
+    #[inline]
+    fn from(err: NbtError) -> Self { Self { kind: SerializeErrorKind::Nbt(err) } }
+}
+
 #[cfg(feature = "std")]
 impl From<std::io::Error> for SerializeError {
     #[inline]
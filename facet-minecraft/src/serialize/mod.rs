@@ -2,7 +2,7 @@
 
 use alloc::{borrow::Cow, vec::Vec};
 
-use facet::{Facet, Shape, ShapeLayout, Variant};
+use facet::{Facet, Shape, ShapeLayout, Type, UserType, Variant};
 use facet_format::{
     DynamicValueEncoding, DynamicValueTag, EnumVariantEncoding, FieldOrdering, FormatSerializer,
     MapEncoding, ScalarValue, SerializeError as FSError, StructFieldMode,
@@ -10,6 +10,13 @@ use facet_format::{
 use facet_reflect::{FieldItem, Peek};
 use uuid::Uuid;
 
+use crate::{
+    codec::{CodecFn, McWriter},
+    common::{calculate_shape_hint_versioned, is_present},
+    nbt::{self, NbtWriter},
+    packet::ProtocolVersion,
+};
+
 mod buffer;
 pub use buffer::SerializeBuffer;
 
@@ -19,6 +26,9 @@ pub use error::{SerializeError, SerializeErrorKind};
 pub(crate) mod r#trait;
 pub use r#trait::Serializable;
 
+mod version;
+pub(crate) use version::encode_versioned;
+
 /// A function pointer to a serialization function.
 #[derive(Debug, Clone, Copy, Facet)]
 #[facet(opaque)]
@@ -78,6 +88,12 @@ pub struct McSerializer<'buffer, B: SerializeBuffer + ?Sized> {
     buffer: &'buffer mut B,
     variable_length: bool,
     value_size: usize,
+    mutf8: bool,
+    nbt: bool,
+
+    version: Option<u32>,
+    /// The shape of the last value, used to number enum variants.
+    shape: Option<&'static Shape>,
 }
 
 impl<'buffer, B: SerializeBuffer + ?Sized> McSerializer<'buffer, B> {
@@ -85,9 +101,33 @@ impl<'buffer, B: SerializeBuffer + ?Sized> McSerializer<'buffer, B> {
     #[inline]
     #[must_use]
     pub const fn new(buffer: &'buffer mut B) -> Self {
-        Self { buffer, variable_length: false, value_size: 0 }
+        Self {
+            buffer,
+            variable_length: false,
+            value_size: 0,
+            mutf8: false,
+            nbt: false,
+            version: None,
+            shape: None,
+        }
     }
 
+    /// Serialize for a protocol version,
+    /// rejecting fields and variants not present in it.
+    ///
+    /// See [`to_vec_versioned`] to skip absent fields instead.
+    #[inline]
+    #[must_use]
+    pub const fn with_version(mut self, version: u32) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Get the protocol version being serialized for, if any.
+    #[inline]
+    #[must_use]
+    pub const fn version(&self) -> Option<u32> { self.version }
+
     /// Reborrow the serializer with a shorter lifetime.
     #[inline]
     #[must_use]
@@ -96,6 +136,10 @@ impl<'buffer, B: SerializeBuffer + ?Sized> McSerializer<'buffer, B> {
             buffer: self.buffer,
             variable_length: self.variable_length,
             value_size: self.value_size,
+            mutf8: self.mutf8,
+            nbt: self.nbt,
+            version: self.version,
+            shape: self.shape,
         }
     }
 
@@ -110,6 +154,10 @@ impl<'buffer, B: SerializeBuffer + ?Sized> McSerializer<'buffer, B> {
             buffer: self.buffer,
             variable_length: self.variable_length,
             value_size: self.value_size,
+            mutf8: self.mutf8,
+            nbt: self.nbt,
+            version: self.version,
+            shape: self.shape,
         }
     }
 
@@ -135,8 +183,23 @@ impl<B: SerializeBuffer> FormatSerializer for McSerializer<'_, B> {
     fn end_struct(&mut self) -> Result<(), Self::Error> { Ok(()) }
 
     fn variant_metadata(&mut self, variant: &'static Variant) -> Result<(), Self::Error> {
-        if let Some(disciminant) = variant.discriminant {
-            self.scalar_variable(ScalarValue::I64(disciminant), true)
+        if !is_present(variant.attributes, self.version) {
+            return Err(SerializeError::new(SerializeErrorKind::UnavailableVariant {
+                variant: variant.name,
+                version: self.version.unwrap_or_default(),
+            }));
+        }
+
+        // Number variants by their position among those present in the version
+        if let Some(shape) = self.shape
+            && let Type::User(UserType::Enum(ty)) = shape.ty
+            && let Some(index) = ty.variants.iter().position(|v| core::ptr::eq(v, variant))
+        {
+            let index = ty.variants[..index]
+                .iter()
+                .filter(|v| is_present(v.attributes, self.version))
+                .count();
+            self.scalar_variable(ScalarValue::U64(index as u64), true)
         } else {
             Err(SerializeError::new(SerializeErrorKind::DiscriminantMissing))
         }
@@ -147,12 +210,25 @@ impl<B: SerializeBuffer> FormatSerializer for McSerializer<'_, B> {
         field: &FieldItem,
         _value: Peek<'_, '_>,
     ) -> Result<bool, Self::Error> {
+        // Absent fields are never reached by `to_vec_versioned`,
+        // only when driving a versioned serializer directly.
+        if let Some(field) = field.field.as_ref()
+            && !is_present(field.attributes, self.version)
+        {
+            return Err(SerializeError::new(SerializeErrorKind::UnavailableField {
+                field: field.name,
+                version: self.version.unwrap_or_default(),
+            }));
+        }
+
         if let Some(field) = field.field.as_ref()
             && let Some(attr) = field.get_attr(Some("mc"), "serialize")
             && let Some(serialize) = attr.get_as::<SerializeFn>()
         {
             serialize.call(&mut self.as_dyn()).map(|()| true)
         } else {
+            // Encoded by `serialize_opaque_scalar`, which is called first for every value.
+            self.nbt = field.field.as_ref().is_some_and(|field| field.has_attr(Some("mc"), "nbt"));
             Ok(false)
         }
     }
@@ -162,6 +238,9 @@ impl<B: SerializeBuffer> FormatSerializer for McSerializer<'_, B> {
             if field.has_attr(Some("mc"), "variable") {
                 self.variable_length = true;
             }
+            if field.has_attr(Some("mc"), "mutf8") {
+                self.mutf8 = true;
+            }
 
             if let ShapeLayout::Sized(layout) = field.shape().layout {
                 self.value_size = layout.size();
@@ -175,7 +254,13 @@ impl<B: SerializeBuffer> FormatSerializer for McSerializer<'_, B> {
 
     fn scalar(&mut self, val: ScalarValue<'_>) -> Result<(), Self::Error> {
         let variable_length = core::mem::take(&mut self.variable_length);
-        self.scalar_variable(val, variable_length)
+        if core::mem::take(&mut self.mutf8)
+            && let ScalarValue::Str(value) = &val
+        {
+            McWriter::new(self.buffer).write_mutf8(value)
+        } else {
+            self.scalar_variable(val, variable_length)
+        }
     }
 
     fn is_self_describing(&self) -> bool { false }
@@ -185,9 +270,23 @@ impl<B: SerializeBuffer> FormatSerializer for McSerializer<'_, B> {
         shape: &'static Shape,
         value: Peek<'_, '_>,
     ) -> Result<bool, Self::Error> {
-        if shape.is_type::<Uuid>() {
+        self.shape = Some(shape);
+        if let ShapeLayout::Sized(layout) = shape.layout {
+            self.value_size = layout.size();
+        }
+
+        if core::mem::take(&mut self.nbt) {
+            nbt::write_peek(value, &mut NbtWriter::new(self.buffer))?;
+            Ok(true)
+        } else if shape.is_type::<Uuid>() {
             self.scalar_variable(ScalarValue::U128(value.get::<Uuid>().unwrap().as_u128()), false)?;
             Ok(true)
+        } else if let Some(codec) = CodecFn::from_shape(shape) {
+            let version = self.version.map_or(ProtocolVersion::LATEST, ProtocolVersion::new);
+            let mut writer = McWriter::new(self.buffer).with_version(version);
+            // SAFETY: The `CodecFn` was created for the type described by `shape`.
+            unsafe { codec.encode(value.data(), &mut writer)? };
+            Ok(true)
         } else {
             Ok(false)
         }
@@ -253,12 +352,12 @@ impl<B: SerializeBuffer + ?Sized> McSerializer<'_, B> {
             }
 
             (ScalarValue::I64(v), false) => {
-                let bytes = v.to_le_bytes();
-                self.buffer.extend_buffer(&bytes[..self.value_size])
+                let bytes = v.to_be_bytes();
+                self.buffer.extend_buffer(&bytes[bytes.len().saturating_sub(self.value_size)..])
             }
             (ScalarValue::U64(v), false) => {
-                let bytes = v.to_le_bytes();
-                self.buffer.extend_buffer(&bytes[..self.value_size])
+                let bytes = v.to_be_bytes();
+                self.buffer.extend_buffer(&bytes[bytes.len().saturating_sub(self.value_size)..])
             }
             (ScalarValue::I64(v), true) => {
                 let mut buffer = [0; _];
@@ -272,12 +371,12 @@ impl<B: SerializeBuffer + ?Sized> McSerializer<'_, B> {
             }
 
             (ScalarValue::I128(v), false) => {
-                let bytes = v.to_le_bytes();
-                self.buffer.extend_buffer(&bytes[..self.value_size])
+                let bytes = v.to_be_bytes();
+                self.buffer.extend_buffer(&bytes[bytes.len().saturating_sub(self.value_size)..])
             }
             (ScalarValue::U128(v), false) => {
-                let bytes = v.to_le_bytes();
-                self.buffer.extend_buffer(&bytes[..self.value_size])
+                let bytes = v.to_be_bytes();
+                self.buffer.extend_buffer(&bytes[bytes.len().saturating_sub(self.value_size)..])
             }
             (ScalarValue::I128(v), true) => {
                 let mut buffer = [0; _];
@@ -290,10 +389,11 @@ impl<B: SerializeBuffer + ?Sized> McSerializer<'_, B> {
                 self.buffer.extend_buffer(&buffer[..len])
             }
 
-            (ScalarValue::F64(v), false) => {
-                let bytes = v.to_le_bytes();
-                self.buffer.extend_buffer(&bytes[..self.value_size])
+            #[expect(clippy::cast_possible_truncation, reason = "Only for `f32` values")]
+            (ScalarValue::F64(v), false) if self.value_size == 4 => {
+                self.buffer.extend_buffer(&(v as f32).to_be_bytes())
             }
+            (ScalarValue::F64(v), false) => self.buffer.extend_buffer(&v.to_be_bytes()),
             (ScalarValue::Str(v), false) => {
                 let mut buffer = [0; _];
                 let len = Self::var_u64(v.len() as u64, &mut buffer);
@@ -390,6 +490,47 @@ pub fn to_buffer<'output, 'facet, T: Serializable<'facet> + ?Sized, B: Serialize
     Ok(buffer.get_content())
 }
 
+/// Serialize a value of type `T` for a protocol version into a byte vector.
+///
+/// Fields and enum variants marked with `#[facet(mc::since = ..)]` or
+/// `#[facet(mc::until = ..)]` are only written if the version is within
+/// their range, inclusive. Enums with versioned variants are numbered by the
+/// position of each variant among those present in the version.
+///
+/// ```rust
+/// use facet::Facet;
+/// use facet_minecraft as mc;
+///
+/// #[derive(Facet)]
+/// struct Example {
+///     id: u8,
+///     #[facet(mc::since = 767)]
+///     added: u8,
+///     #[facet(mc::until = 766)]
+///     removed: u8,
+/// }
+///
+/// let value = Example { id: 1, added: 2, removed: 3 };
+/// assert_eq!(mc::to_vec_versioned(&value, 766).unwrap(), [1, 3]);
+/// assert_eq!(mc::to_vec_versioned(&value, 767).unwrap(), [1, 2]);
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if serialization fails,
+/// or if an enum variant is not present in the version.
+pub fn to_vec_versioned<'facet, T: Serializable<'facet> + ?Sized>(
+    value: &T,
+    version: u32,
+) -> Result<Vec<u8>, FSError<SerializeError>> {
+    let hint = calculate_shape_hint_versioned(T::SHAPE, None, version);
+    let mut buffer = hint.maximum().or(hint.minimum()).map_or_else(Vec::new, Vec::with_capacity);
+
+    let mut format = McSerializer::new(&mut buffer).with_version(version);
+    version::serialize_versioned(&mut format, Peek::new(value), version)?;
+    Ok(buffer)
+}
+
 // -------------------------------------------------------------------------------------------------
 
 /// Serialize a value of type `T` into a [`Writer`](std::io::Write).
@@ -7,7 +7,10 @@ use facet::{Facet, Shape};
 use facet_format::SerializeError as FSError;
 
 use crate::{
-    common::{TypeSerializeHint, TypeSerializeResult, calculate_shape_hint},
+    common::{
+        TypeSerializeHint, TypeSerializeResult, calculate_shape_hint,
+        calculate_shape_hint_versioned,
+    },
     serialize::{self, SerializeBuffer, SerializeError},
 };
 
@@ -18,6 +21,19 @@ pub trait Serializable<'facet>: Facet<'facet> {
     /// A hint for the size of this type after serialization.
     const SERIALIZE_HINT: &'static TypeSerializeHint = &calculate_shape_hint(Self::SHAPE, None);
 
+    /// A hint for the size of this type after serialization
+    /// for a protocol version.
+    ///
+    /// Ignores fields and variants not present in the version.
+    /// Unlike [`Serializable::SERIALIZE_HINT`], this uses the
+    /// [`McCodec::SERIALIZE_HINT`](crate::codec::McCodec::SERIALIZE_HINT)
+    /// of types with a custom encoding.
+    #[inline]
+    #[must_use]
+    fn serialize_hint_versioned(version: u32) -> TypeSerializeHint {
+        calculate_shape_hint_versioned(Self::SHAPE, None, version)
+    }
+
     /// Serialize a value into a buffer,
     /// returning a slice containing the serialized data.
     ///
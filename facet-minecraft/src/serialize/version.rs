@@ -0,0 +1,147 @@
+//! Serialization of fields and variants gated by protocol version.
+
+use alloc::{borrow::Cow, vec::Vec};
+
+use facet::{Def, Facet, StructKind};
+use facet_format::{FormatSerializer, SerializeError as FSError};
+use facet_reflect::{FieldItem, HasFields, Peek};
+
+use super::{McSerializer, SerializeBuffer, SerializeError, SerializeErrorKind};
+use crate::{
+    codec::McWriter,
+    common::{contains_versioned, is_present},
+};
+
+/// Serialize a value, never writing fields and variants not present in the
+/// version.
+///
+/// Types without any versioned fields or variants are passed to
+/// [`facet_format`] as a whole.
+pub(crate) fn serialize_versioned<B: SerializeBuffer>(
+    serializer: &mut McSerializer<'_, B>,
+    value: Peek<'_, '_>,
+    version: u32,
+) -> Result<(), FSError<SerializeError>> {
+    // Dereference pointers, as `facet_format` does.
+    let mut value = value;
+    while let Ok(pointer) = value.into_pointer()
+        && let Some(inner) = pointer.borrow_inner()
+    {
+        value = inner;
+    }
+
+    if !contains_versioned(value.shape()) {
+        return facet_format::serialize_root(serializer, value);
+    }
+    if serializer.serialize_opaque_scalar(value.shape(), value).map_err(FSError::Backend)? {
+        return Ok(());
+    }
+
+    let value = value.innermost_peek();
+    let present = |item: &FieldItem| {
+        item.field.is_none_or(|field| is_present(field.attributes, Some(version)))
+    };
+
+    match value.shape().def {
+        Def::Option(_) => {
+            let option = value.into_option().map_err(FSError::Reflect)?;
+            return match option.value() {
+                Some(inner) => {
+                    serializer.begin_option_some().map_err(FSError::Backend)?;
+                    serialize_versioned(serializer, inner, version)
+                }
+                None => serializer.serialize_none().map_err(FSError::Backend),
+            };
+        }
+        Def::List(_) | Def::Array(_) => {
+            let list = value.into_list_like().map_err(FSError::Reflect)?;
+            if matches!(value.shape().def, Def::List(_)) {
+                serializer.begin_seq_with_len(list.len()).map_err(FSError::Backend)?;
+            } else {
+                serializer.begin_seq().map_err(FSError::Backend)?;
+            }
+            for item in list.iter() {
+                serialize_versioned(serializer, item, version)?;
+            }
+            return serializer.end_seq().map_err(FSError::Backend);
+        }
+        Def::Set(_) => {
+            let set = value.into_set().map_err(FSError::Reflect)?;
+            serializer.begin_seq_with_len(set.len()).map_err(FSError::Backend)?;
+            for item in set.iter() {
+                serialize_versioned(serializer, item, version)?;
+            }
+            return serializer.end_seq().map_err(FSError::Backend);
+        }
+        Def::Map(_) => {
+            let map = value.into_map().map_err(FSError::Reflect)?;
+            serializer.begin_map_with_len(map.len()).map_err(FSError::Backend)?;
+            for (key, item) in map.iter() {
+                serialize_versioned(serializer, key, version)?;
+                serialize_versioned(serializer, item, version)?;
+            }
+            return serializer.end_map().map_err(FSError::Backend);
+        }
+        _ => {}
+    }
+
+    if let Ok(struct_) = value.into_struct() {
+        let fields = struct_.fields_for_binary_serialize().filter(|(item, _)| present(item));
+
+        // Tuples are written without any field metadata, as `facet_format` does.
+        if matches!(struct_.ty().kind, StructKind::Tuple | StructKind::TupleStruct) {
+            for (_, field_value) in fields {
+                serialize_versioned(serializer, field_value, version)?;
+            }
+            return Ok(());
+        }
+
+        serializer.struct_metadata(value.shape()).map_err(FSError::Backend)?;
+        serializer.begin_struct().map_err(FSError::Backend)?;
+        for (item, field_value) in fields {
+            if !serializer
+                .field_metadata_with_value(&item, field_value)
+                .map_err(FSError::Backend)?
+            {
+                serializer.field_metadata(&item).map_err(FSError::Backend)?;
+            }
+            serialize_versioned(serializer, field_value, version)?;
+        }
+        return serializer.end_struct().map_err(FSError::Backend);
+    }
+
+    if let Ok(enum_) = value.into_enum() {
+        let variant = enum_.active_variant().map_err(|_| {
+            FSError::Unsupported(Cow::Borrowed("opaque enum layout is unsupported"))
+        })?;
+        serializer.variant_metadata(variant).map_err(FSError::Backend)?;
+
+        for (item, field_value) in enum_.fields_for_binary_serialize() {
+            if present(&item) {
+                serialize_versioned(serializer, field_value, version)?;
+            }
+        }
+        return Ok(());
+    }
+
+    facet_format::serialize_root(serializer, value)
+}
+
+/// Serialize a value of type `T` into a [`McWriter`],
+/// for the writer's protocol version.
+///
+/// Used by [`McCodec`](crate::codec::McCodec) implementations containing
+/// values described by their fields.
+pub(crate) fn encode_versioned<'facet, T: Facet<'facet>>(
+    value: &T,
+    writer: &mut McWriter<'_>,
+) -> Result<(), SerializeError> {
+    let version = writer.version().protocol();
+    let mut buffer = Vec::new();
+    let mut format = McSerializer::new(&mut buffer).with_version(version);
+    serialize_versioned(&mut format, Peek::new(value), version).map_err(|err| match err {
+        FSError::Backend(err) => err,
+        _ => SerializeError::new(SerializeErrorKind::UnsupportedType(T::SHAPE)),
+    })?;
+    writer.write_bytes(&buffer)
+}
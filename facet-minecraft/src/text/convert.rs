@@ -0,0 +1,506 @@
+//! Conversion between [`TextComponent`]s and [`NbtValue`] trees.
+//!
+//! JSON is parsed into and written from the same tree,
+//! so both encodings share a single implementation.
+
+use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use uuid::Uuid;
+
+use super::{
+    ClickEvent, HoverEvent, NbtSource, Style, TextColor, TextComponent, TextContent, TextEncoding,
+    TextError, TextErrorKind, list_of, unwrap_entry,
+};
+use crate::nbt::{MAX_DEPTH, NbtCompound, NbtValue, snbt};
+
+/// Convert a component into a value tree.
+pub(super) fn to_value(component: &TextComponent, encoding: TextEncoding) -> NbtValue {
+    if let TextContent::Text(text) = &component.content
+        && component.style.is_empty()
+        && component.extra.is_empty()
+    {
+        return NbtValue::from(text.as_str());
+    }
+
+    let mut compound = NbtCompound::new();
+    match &component.content {
+        TextContent::Text(text) => {
+            compound.insert("text", text.as_str());
+        }
+        TextContent::Translatable { key, fallback, with } => {
+            compound.insert("translate", key.as_str());
+            if let Some(fallback) = fallback {
+                compound.insert("fallback", fallback.as_str());
+            }
+            if !with.is_empty() {
+                compound.insert("with", components_to_value(with, encoding));
+            }
+        }
+        TextContent::Score { name, objective } => {
+            let score: NbtCompound =
+                [("name", name.as_str()), ("objective", objective.as_str())].into_iter().collect();
+            compound.insert("score", score);
+        }
+        TextContent::Selector { pattern, separator } => {
+            compound.insert("selector", pattern.as_str());
+            if let Some(separator) = separator {
+                compound.insert("separator", to_value(separator, encoding));
+            }
+        }
+        TextContent::Keybind(key) => {
+            compound.insert("keybind", key.as_str());
+        }
+        TextContent::Nbt { path, interpret, separator, source } => {
+            compound.insert("nbt", path.as_str());
+            if let Some(interpret) = interpret {
+                compound.insert("interpret", *interpret);
+            }
+            if let Some(separator) = separator {
+                compound.insert("separator", to_value(separator, encoding));
+            }
+            match source {
+                NbtSource::Block(pos) => compound.insert("block", pos.as_str()),
+                NbtSource::Entity(selector) => compound.insert("entity", selector.as_str()),
+                NbtSource::Storage(id) => compound.insert("storage", id.as_str()),
+            };
+        }
+    }
+
+    style_to_value(&component.style, encoding, &mut compound);
+    if !component.extra.is_empty() {
+        compound.insert("extra", components_to_value(&component.extra, encoding));
+    }
+    NbtValue::Compound(compound)
+}
+
+fn components_to_value(components: &[TextComponent], encoding: TextEncoding) -> NbtValue {
+    NbtValue::List(list_of(components.iter().map(|c| to_value(c, encoding)).collect()))
+}
+
+fn style_to_value(style: &Style, encoding: TextEncoding, compound: &mut NbtCompound) {
+    if let Some(color) = style.color {
+        compound.insert("color", color.to_string());
+    }
+    if let Some(shadow_color) = style.shadow_color {
+        compound.insert("shadow_color", shadow_color.cast_signed());
+    }
+    for (key, flag) in [
+        ("bold", style.bold),
+        ("italic", style.italic),
+        ("underlined", style.underlined),
+        ("strikethrough", style.strikethrough),
+        ("obfuscated", style.obfuscated),
+    ] {
+        if let Some(flag) = flag {
+            compound.insert(key, flag);
+        }
+    }
+
+    let snake_case = matches!(encoding, TextEncoding::Nbt);
+    if let Some(event) = &style.click_event {
+        let key = if snake_case { "click_event" } else { "clickEvent" };
+        compound.insert(key, click_to_value(event, snake_case));
+    }
+    if let Some(event) = &style.hover_event {
+        let key = if snake_case { "hover_event" } else { "hoverEvent" };
+        compound.insert(key, hover_to_value(event, encoding, snake_case));
+    }
+
+    if let Some(insertion) = &style.insertion {
+        compound.insert("insertion", insertion.as_str());
+    }
+    if let Some(font) = &style.font {
+        compound.insert("font", font.as_str());
+    }
+}
+
+fn click_to_value(event: &ClickEvent, snake_case: bool) -> NbtCompound {
+    let mut compound = NbtCompound::new();
+    compound.insert("action", event.action());
+
+    // Since 1.21.5 each action has its own field name and type
+    match event {
+        ClickEvent::ChangePage(page) if snake_case => compound.insert("page", *page),
+        ClickEvent::ChangePage(page) => compound.insert("value", page.to_string()),
+        ClickEvent::OpenUrl(value)
+        | ClickEvent::OpenFile(value)
+        | ClickEvent::RunCommand(value)
+        | ClickEvent::SuggestCommand(value)
+        | ClickEvent::CopyToClipboard(value) => {
+            let key = match event {
+                _ if !snake_case => "value",
+                ClickEvent::OpenUrl(_) => "url",
+                ClickEvent::OpenFile(_) => "path",
+                ClickEvent::RunCommand(_) | ClickEvent::SuggestCommand(_) => "command",
+                _ => "value",
+            };
+            compound.insert(key, value.as_str())
+        }
+    };
+    compound
+}
+
+fn hover_to_value(event: &HoverEvent, encoding: TextEncoding, snake_case: bool) -> NbtCompound {
+    let mut compound = NbtCompound::new();
+    compound.insert("action", event.action());
+
+    if let HoverEvent::ShowText(text) = event {
+        compound.insert(if snake_case { "value" } else { "contents" }, to_value(text, encoding));
+        return compound;
+    }
+
+    // Since 1.21.5 the contents are inlined into the event
+    let mut contents = NbtCompound::new();
+    let target = if snake_case { &mut compound } else { &mut contents };
+    match event {
+        HoverEvent::ShowText(_) => {}
+        HoverEvent::ShowItem { id, count, components } => {
+            target.insert("id", id.as_str());
+            target.insert("count", *count);
+            if let Some(components) = components {
+                target.insert("components", components.clone());
+            }
+        }
+        HoverEvent::ShowEntity { entity_type, uuid, name } => {
+            target.insert(if snake_case { "id" } else { "type" }, entity_type.as_str());
+            target.insert(if snake_case { "uuid" } else { "id" }, uuid_to_value(uuid));
+            if let Some(name) = name {
+                target.insert("name", to_value(name, encoding));
+            }
+        }
+    }
+
+    if !snake_case {
+        compound.insert("contents", contents);
+    }
+    compound
+}
+
+/// Convert a [`Uuid`] into four big-endian integers.
+fn uuid_to_value(uuid: &Uuid) -> NbtValue {
+    let bytes = uuid.as_bytes();
+    NbtValue::IntArray(
+        bytes.chunks_exact(4).map(|c| i32::from_be_bytes([c[0], c[1], c[2], c[3]])).collect(),
+    )
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Convert a value tree into a component.
+pub(super) fn from_value(value: &NbtValue, depth: usize) -> Result<TextComponent, TextError> {
+    if depth >= MAX_DEPTH {
+        return Err(TextError::new(TextErrorKind::DepthLimit));
+    }
+
+    match unwrap_entry(value) {
+        NbtValue::String(text) => Ok(TextComponent::text(text.as_str())),
+        // The first element of a list is the parent of the rest
+        NbtValue::List(list) => {
+            let mut iter = list.iter();
+            let first = iter.next().ok_or(TextError::new(TextErrorKind::InvalidComponent))?;
+            let mut component = from_value(first, depth + 1)?;
+            for value in iter {
+                component.extra.push(from_value(value, depth + 1)?);
+            }
+            Ok(component)
+        }
+        NbtValue::Compound(compound) => from_compound(compound, depth),
+        _ => Err(TextError::new(TextErrorKind::InvalidComponent)),
+    }
+}
+
+fn from_compound(compound: &NbtCompound, depth: usize) -> Result<TextComponent, TextError> {
+    let content = content_from_compound(compound, depth)?;
+    let style = style_from_compound(compound, depth)?;
+
+    let extra = match compound.get("extra") {
+        Some(NbtValue::List(list)) => {
+            list.iter().map(|value| from_value(value, depth + 1)).collect::<Result<_, _>>()?
+        }
+        Some(_) => return Err(invalid("extra")),
+        None => Vec::new(),
+    };
+
+    Ok(TextComponent { content, style, extra })
+}
+
+fn content_from_compound(compound: &NbtCompound, depth: usize) -> Result<TextContent, TextError> {
+    /// Content types in the order the vanilla client checks for them.
+    const CONTENTS: [(&str, &str); 6] = [
+        ("text", "text"),
+        ("translatable", "translate"),
+        ("score", "score"),
+        ("selector", "selector"),
+        ("keybind", "keybind"),
+        ("nbt", "nbt"),
+    ];
+
+    let kind = match compound.get("type") {
+        Some(NbtValue::String(kind)) => CONTENTS
+            .iter()
+            .find(|(name, _)| name == kind)
+            .map(|(name, _)| *name)
+            .ok_or(TextError::new(TextErrorKind::UnknownContent))?,
+        Some(_) => return Err(invalid("type")),
+        None => CONTENTS
+            .iter()
+            .find(|(_, key)| compound.contains_key(key))
+            .map(|(name, _)| *name)
+            .ok_or(TextError::new(TextErrorKind::UnknownContent))?,
+    };
+
+    let separator = |compound: &NbtCompound| {
+        compound
+            .get("separator")
+            .map(|value| from_value(value, depth + 1).map(Box::new))
+            .transpose()
+    };
+
+    Ok(match kind {
+        "text" => TextContent::Text(get_str(compound, "text")?.to_owned()),
+        "translatable" => TextContent::Translatable {
+            key: get_str(compound, "translate")?.to_owned(),
+            fallback: get_opt_str(compound, "fallback")?,
+            with: match compound.get("with") {
+                Some(NbtValue::List(list)) => list
+                    .iter()
+                    .map(|value| argument_from_value(value, depth + 1))
+                    .collect::<Result<_, _>>()?,
+                Some(_) => return Err(invalid("with")),
+                None => Vec::new(),
+            },
+        },
+        "score" => {
+            let Some(NbtValue::Compound(score)) = compound.get("score") else {
+                return Err(invalid("score"));
+            };
+            TextContent::Score {
+                name: get_str(score, "name")?.to_owned(),
+                objective: get_str(score, "objective")?.to_owned(),
+            }
+        }
+        "selector" => TextContent::Selector {
+            pattern: get_str(compound, "selector")?.to_owned(),
+            separator: separator(compound)?,
+        },
+        "keybind" => TextContent::Keybind(get_str(compound, "keybind")?.to_owned()),
+        _ => TextContent::Nbt {
+            path: get_str(compound, "nbt")?.to_owned(),
+            interpret: get_opt_bool(compound, "interpret")?,
+            separator: separator(compound)?,
+            source: if let Some(pos) = get_opt_str(compound, "block")? {
+                NbtSource::Block(pos)
+            } else if let Some(selector) = get_opt_str(compound, "entity")? {
+                NbtSource::Entity(selector)
+            } else if let Some(id) = get_opt_str(compound, "storage")? {
+                NbtSource::Storage(id)
+            } else {
+                return Err(TextError::new(TextErrorKind::MissingField("source")));
+            },
+        },
+    })
+}
+
+/// Translation arguments may also be plain numbers and booleans.
+fn argument_from_value(value: &NbtValue, depth: usize) -> Result<TextComponent, TextError> {
+    let text = match unwrap_entry(value) {
+        NbtValue::Byte(0) => String::from("false"),
+        NbtValue::Byte(1) => String::from("true"),
+        NbtValue::Byte(value) => value.to_string(),
+        NbtValue::Short(value) => value.to_string(),
+        NbtValue::Int(value) => value.to_string(),
+        NbtValue::Long(value) => value.to_string(),
+        NbtValue::Float(value) => value.to_string(),
+        NbtValue::Double(value) => value.to_string(),
+        value => return from_value(value, depth),
+    };
+    Ok(TextComponent::text(text))
+}
+
+fn style_from_compound(compound: &NbtCompound, depth: usize) -> Result<Style, TextError> {
+    let color = match get_opt_str(compound, "color")? {
+        Some(name) => {
+            Some(TextColor::parse(&name).ok_or(TextError::new(TextErrorKind::UnknownColor(name)))?)
+        }
+        None => None,
+    };
+
+    let click_event = match compound.get("click_event").or_else(|| compound.get("clickEvent")) {
+        Some(NbtValue::Compound(event)) => Some(click_from_compound(event)?),
+        Some(_) => return Err(invalid("click_event")),
+        None => None,
+    };
+    let hover_event = match compound.get("hover_event").or_else(|| compound.get("hoverEvent")) {
+        Some(NbtValue::Compound(event)) => Some(hover_from_compound(event, depth)?),
+        Some(_) => return Err(invalid("hover_event")),
+        None => None,
+    };
+
+    Ok(Style {
+        color,
+        shadow_color: get_opt_int(compound, "shadow_color")?.map(i32::cast_unsigned),
+        bold: get_opt_bool(compound, "bold")?,
+        italic: get_opt_bool(compound, "italic")?,
+        underlined: get_opt_bool(compound, "underlined")?,
+        strikethrough: get_opt_bool(compound, "strikethrough")?,
+        obfuscated: get_opt_bool(compound, "obfuscated")?,
+        click_event,
+        hover_event,
+        insertion: get_opt_str(compound, "insertion")?,
+        font: get_opt_str(compound, "font")?,
+    })
+}
+
+fn click_from_compound(event: &NbtCompound) -> Result<ClickEvent, TextError> {
+    let action = get_str(event, "action")?;
+
+    // Accept both the 1.21.5 field names and the older `value` field
+    let value = |key: &'static str| match get_opt_str(event, key)? {
+        Some(value) => Ok(value),
+        None => get_str(event, "value").map(ToOwned::to_owned),
+    };
+
+    match action {
+        "open_url" => value("url").map(ClickEvent::OpenUrl),
+        "open_file" => value("path").map(ClickEvent::OpenFile),
+        "run_command" => value("command").map(ClickEvent::RunCommand),
+        "suggest_command" => value("command").map(ClickEvent::SuggestCommand),
+        "copy_to_clipboard" => value("value").map(ClickEvent::CopyToClipboard),
+        "change_page" => match get_opt_int(event, "page")? {
+            Some(page) => Ok(ClickEvent::ChangePage(page)),
+            None => match event.get("value") {
+                Some(NbtValue::String(page)) => {
+                    page.parse().map(ClickEvent::ChangePage).map_err(|_| invalid("value"))
+                }
+                _ => get_opt_int(event, "value")?
+                    .map(ClickEvent::ChangePage)
+                    .ok_or(TextError::new(TextErrorKind::MissingField("page"))),
+            },
+        },
+        _ => Err(TextError::new(TextErrorKind::UnknownAction(action.to_owned()))),
+    }
+}
+
+fn hover_from_compound(event: &NbtCompound, depth: usize) -> Result<HoverEvent, TextError> {
+    let action = get_str(event, "action")?;
+
+    // Before 1.21.5 the contents are stored in a separate field
+    let contents = event.get("contents");
+    let inline = match contents {
+        Some(NbtValue::Compound(contents)) => contents,
+        _ => event,
+    };
+
+    match action {
+        "show_text" => {
+            let value = contents
+                .or_else(|| event.get("value"))
+                .ok_or(TextError::new(TextErrorKind::MissingField("value")))?;
+            Ok(HoverEvent::ShowText(Box::new(from_value(value, depth + 1)?)))
+        }
+        "show_item" => {
+            // Items with only an ID may be a plain string
+            if let Some(NbtValue::String(id)) = contents {
+                return Ok(HoverEvent::ShowItem { id: id.clone(), count: 1, components: None });
+            }
+
+            let components = match inline.get("components").or_else(|| inline.get("tag")) {
+                Some(NbtValue::Compound(components)) => Some(components.clone()),
+                Some(NbtValue::String(tag)) => match snbt::parse(tag) {
+                    Ok(NbtValue::Compound(components)) => Some(components),
+                    _ => return Err(invalid("tag")),
+                },
+                Some(_) => return Err(invalid("components")),
+                None => None,
+            };
+            Ok(HoverEvent::ShowItem {
+                id: get_str(inline, "id")?.to_owned(),
+                count: get_opt_int(inline, "count")?.unwrap_or(1),
+                components,
+            })
+        }
+        "show_entity" => {
+            let (type_key, uuid_key) =
+                if contents.is_some() { ("type", "id") } else { ("id", "uuid") };
+            Ok(HoverEvent::ShowEntity {
+                entity_type: get_str(inline, type_key)?.to_owned(),
+                uuid: uuid_from_value(
+                    inline
+                        .get(uuid_key)
+                        .ok_or(TextError::new(TextErrorKind::MissingField("uuid")))?,
+                )?,
+                name: inline
+                    .get("name")
+                    .map(|name| from_value(name, depth + 1).map(Box::new))
+                    .transpose()?,
+            })
+        }
+        _ => Err(TextError::new(TextErrorKind::UnknownAction(action.to_owned()))),
+    }
+}
+
+/// Read a [`Uuid`] from four integers or a hyphenated string.
+fn uuid_from_value(value: &NbtValue) -> Result<Uuid, TextError> {
+    let ints: Vec<i32> = match value {
+        NbtValue::String(uuid) => return Uuid::parse_str(uuid).map_err(|_| invalid("uuid")),
+        NbtValue::IntArray(ints) => ints.clone(),
+        NbtValue::List(list) => list.iter().filter_map(NbtValue::as_i32).collect(),
+        _ => Vec::new(),
+    };
+
+    let [a, b, c, d] = ints[..] else { return Err(invalid("uuid")) };
+    let mut bytes = [0; 16];
+    for (chunk, int) in bytes.chunks_exact_mut(4).zip([a, b, c, d]) {
+        chunk.copy_from_slice(&int.to_be_bytes());
+    }
+    Ok(Uuid::from_bytes(bytes))
+}
+
+// -------------------------------------------------------------------------------------------------
+
+const fn invalid(field: &'static str) -> TextError {
+    TextError::new(TextErrorKind::InvalidField(field))
+}
+
+fn get_str<'a>(compound: &'a NbtCompound, key: &'static str) -> Result<&'a str, TextError> {
+    match compound.get(key) {
+        Some(NbtValue::String(value)) => Ok(value),
+        Some(_) => Err(invalid(key)),
+        None => Err(TextError::new(TextErrorKind::MissingField(key))),
+    }
+}
+
+fn get_opt_str(compound: &NbtCompound, key: &'static str) -> Result<Option<String>, TextError> {
+    match compound.get(key) {
+        Some(NbtValue::String(value)) => Ok(Some(value.clone())),
+        Some(_) => Err(invalid(key)),
+        None => Ok(None),
+    }
+}
+
+fn get_opt_bool(compound: &NbtCompound, key: &'static str) -> Result<Option<bool>, TextError> {
+    match compound.get(key) {
+        Some(NbtValue::Byte(value)) => Ok(Some(*value != 0)),
+        Some(_) => Err(invalid(key)),
+        None => Ok(None),
+    }
+}
+
+fn get_opt_int(compound: &NbtCompound, key: &'static str) -> Result<Option<i32>, TextError> {
+    match compound.get(key) {
+        Some(NbtValue::Byte(value)) => Ok(Some(i32::from(*value))),
+        Some(NbtValue::Short(value)) => Ok(Some(i32::from(*value))),
+        Some(NbtValue::Int(value)) => Ok(Some(*value)),
+        // JSON numbers larger than `i32::MAX`, such as opaque shadow colors
+        Some(NbtValue::Long(value)) => {
+            u32::try_from(*value).map(|value| Some(value.cast_signed())).map_err(|_| invalid(key))
+        }
+        Some(_) => Err(invalid(key)),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,62 @@
+use alloc::string::String;
+use core::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// An error that occurred while decoding a text component.
+#[derive(Debug)]
+pub struct TextError {
+    kind: TextErrorKind,
+}
+
+impl TextError {
+    /// Create a new [`TextError`].
+    #[inline]
+    #[must_use]
+    pub const fn new(kind: TextErrorKind) -> Self { Self { kind } }
+
+    /// Get the kind of text error.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> &TextErrorKind { &self.kind }
+}
+
+/// The type of text error.
+#[derive(Debug)]
+pub enum TextErrorKind {
+    /// The input was not valid JSON at the given byte offset.
+    InvalidJson(usize),
+    /// A value was not a string, list or compound.
+    InvalidComponent,
+    /// A component had no recognized content.
+    UnknownContent,
+    /// A required field was missing.
+    MissingField(&'static str),
+    /// A field had an unexpected type or value.
+    InvalidField(&'static str),
+    /// A color was neither a named color nor `#RRGGBB`.
+    UnknownColor(String),
+    /// A click or hover event had an unknown action.
+    UnknownAction(String),
+    /// The input was nested deeper than [`MAX_DEPTH`](crate::nbt::MAX_DEPTH).
+    DepthLimit,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Error for TextError {}
+impl Display for TextError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            TextErrorKind::InvalidJson(pos) => write!(f, "invalid JSON at offset {pos}"),
+            TextErrorKind::InvalidComponent => f.write_str("expected a string, list or compound"),
+            TextErrorKind::UnknownContent => f.write_str("component has no known content"),
+            TextErrorKind::MissingField(name) => write!(f, "missing field `{name}`"),
+            TextErrorKind::InvalidField(name) => write!(f, "invalid field `{name}`"),
+            TextErrorKind::UnknownColor(name) => write!(f, "unknown color `{name}`"),
+            TextErrorKind::UnknownAction(name) => write!(f, "unknown action `{name}`"),
+            TextErrorKind::DepthLimit => f.write_str("maximum nesting depth exceeded"),
+        }
+    }
+}
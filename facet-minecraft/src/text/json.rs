@@ -0,0 +1,306 @@
+//! A minimal JSON reader and writer using [`NbtValue`] as the value tree.
+//!
+//! Text components only use booleans for flags, so `true` and `false` are
+//! read as `TAG_Byte`, and bytes of `0` and `1` are written as booleans.
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+
+use super::{TextError, TextErrorKind, list_of, unwrap_entry};
+use crate::nbt::{MAX_DEPTH, NbtCompound, NbtValue};
+
+/// Parse a JSON document.
+pub(super) fn parse(input: &str) -> Result<NbtValue, TextError> {
+    let mut parser = JsonParser { input: input.as_bytes(), pos: 0, depth: 0 };
+    let value = parser.parse_value()?;
+    parser.skip_whitespace();
+    if parser.pos == parser.input.len() { Ok(value) } else { Err(parser.error()) }
+}
+
+struct JsonParser<'a> {
+    input: &'a [u8],
+    pos: usize,
+    depth: usize,
+}
+
+impl JsonParser<'_> {
+    fn error(&self) -> TextError { TextError::new(TextErrorKind::InvalidJson(self.pos)) }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.input.get(self.pos) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.input.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), TextError> {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<NbtValue, TextError> {
+        match self.peek() {
+            Some(b'{') => self.nested(Self::parse_object),
+            Some(b'[') => self.nested(Self::parse_array),
+            Some(b'"') => self.parse_string().map(NbtValue::String),
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            Some(_) if self.input[self.pos..].starts_with(b"true") => {
+                self.pos += 4;
+                Ok(NbtValue::from(true))
+            }
+            Some(_) if self.input[self.pos..].starts_with(b"false") => {
+                self.pos += 5;
+                Ok(NbtValue::from(false))
+            }
+            _ => Err(self.error()),
+        }
+    }
+
+    fn nested(
+        &mut self,
+        f: fn(&mut Self) -> Result<NbtValue, TextError>,
+    ) -> Result<NbtValue, TextError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(TextError::new(TextErrorKind::DepthLimit));
+        }
+        self.depth += 1;
+        let value = f(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn parse_object(&mut self) -> Result<NbtValue, TextError> {
+        self.expect(b'{')?;
+        let mut compound = NbtCompound::new();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(NbtValue::Compound(compound));
+        }
+        loop {
+            if self.peek() != Some(b'"') {
+                return Err(self.error());
+            }
+            let key = self.parse_string()?;
+            self.expect(b':')?;
+            compound.insert(key, self.parse_value()?);
+
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(NbtValue::Compound(compound));
+                }
+                _ => return Err(self.error()),
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<NbtValue, TextError> {
+        self.expect(b'[')?;
+        let mut values = Vec::new();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(NbtValue::List(list_of(values)));
+        }
+        loop {
+            values.push(self.parse_value()?);
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(NbtValue::List(list_of(values)));
+                }
+                _ => return Err(self.error()),
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, TextError> {
+        self.expect(b'"')?;
+        let mut string = String::new();
+        loop {
+            let start = self.pos;
+            while let Some(&byte) = self.input.get(self.pos)
+                && byte != b'"'
+                && byte != b'\\'
+                && byte >= 0x20
+            {
+                self.pos += 1;
+            }
+            // The input is a `str` and the run ends on an ASCII byte.
+            string.push_str(core::str::from_utf8(&self.input[start..self.pos]).unwrap_or_default());
+
+            match self.input.get(self.pos) {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(string);
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    string.push(self.parse_escape()?);
+                }
+                _ => return Err(self.error()),
+            }
+        }
+    }
+
+    fn parse_escape(&mut self) -> Result<char, TextError> {
+        let Some(&byte) = self.input.get(self.pos) else { return Err(self.error()) };
+        self.pos += 1;
+        match byte {
+            b'"' => Ok('"'),
+            b'\\' => Ok('\\'),
+            b'/' => Ok('/'),
+            b'b' => Ok('\u{8}'),
+            b'f' => Ok('\u{c}'),
+            b'n' => Ok('\n'),
+            b'r' => Ok('\r'),
+            b't' => Ok('\t'),
+            b'u' => {
+                let high = self.parse_hex()?;
+                if (0xD800..0xDC00).contains(&high) && self.input[self.pos..].starts_with(b"\\u") {
+                    self.pos += 2;
+                    let low = self.parse_hex()?;
+                    let code = 0x10000 + ((high - 0xD800) << 10) + (low.wrapping_sub(0xDC00));
+                    return char::from_u32(code)
+                        .filter(|_| (0xDC00..0xE000).contains(&low))
+                        .ok_or_else(|| self.error());
+                }
+                char::from_u32(high).ok_or_else(|| self.error())
+            }
+            _ => Err(self.error()),
+        }
+    }
+
+    fn parse_hex(&mut self) -> Result<u32, TextError> {
+        let hex = self.input.get(self.pos..self.pos + 4).ok_or_else(|| self.error())?;
+        let value = core::str::from_utf8(hex)
+            .ok()
+            .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+            .ok_or_else(|| self.error())?;
+        self.pos += 4;
+        Ok(value)
+    }
+
+    fn parse_number(&mut self) -> Result<NbtValue, TextError> {
+        let start = self.pos;
+        let mut integer = true;
+        while let Some(&byte) = self.input.get(self.pos) {
+            match byte {
+                b'0'..=b'9' | b'-' | b'+' => {}
+                b'.' | b'e' | b'E' => integer = false,
+                _ => break,
+            }
+            self.pos += 1;
+        }
+
+        let number = core::str::from_utf8(&self.input[start..self.pos]).unwrap_or_default();
+        if integer {
+            if let Ok(value) = number.parse::<i32>() {
+                return Ok(NbtValue::Int(value));
+            } else if let Ok(value) = number.parse::<i64>() {
+                return Ok(NbtValue::Long(value));
+            }
+        }
+        match number.parse::<f64>() {
+            Ok(value) if value.is_finite() => Ok(NbtValue::Double(value)),
+            _ => {
+                self.pos = start;
+                Err(self.error())
+            }
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Write a value as compact JSON.
+pub(super) fn write(out: &mut String, value: &NbtValue) {
+    match value {
+        NbtValue::Byte(0) => out.push_str("false"),
+        NbtValue::Byte(1) => out.push_str("true"),
+        NbtValue::Byte(value) => write_number(out, value),
+        NbtValue::Short(value) => write_number(out, value),
+        NbtValue::Int(value) => write_number(out, value),
+        NbtValue::Long(value) => write_number(out, value),
+        NbtValue::Float(value) => write_float(out, f64::from(*value)),
+        NbtValue::Double(value) => write_float(out, *value),
+        NbtValue::String(value) => write_string(out, value),
+        NbtValue::ByteArray(values) => write_array(out, values.iter().map(|v| NbtValue::Byte(*v))),
+        NbtValue::IntArray(values) => write_array(out, values.iter().map(|v| NbtValue::Int(*v))),
+        NbtValue::LongArray(values) => write_array(out, values.iter().map(|v| NbtValue::Long(*v))),
+        NbtValue::List(list) => {
+            out.push('[');
+            for (index, value) in list.iter().enumerate() {
+                if index != 0 {
+                    out.push(',');
+                }
+                write(out, unwrap_entry(value));
+            }
+            out.push(']');
+        }
+        NbtValue::Compound(compound) => {
+            out.push('{');
+            for (index, (key, value)) in compound.iter().enumerate() {
+                if index != 0 {
+                    out.push(',');
+                }
+                write_string(out, key);
+                out.push(':');
+                write(out, value);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_number(out: &mut String, value: impl core::fmt::Display) {
+    let _ = write!(out, "{value}");
+}
+
+fn write_float(out: &mut String, value: f64) {
+    // `Debug` always includes a decimal point or exponent
+    if value.is_finite() {
+        let _ = write!(out, "{value:?}");
+    } else {
+        out.push('0');
+    }
+}
+
+fn write_array(out: &mut String, values: impl Iterator<Item = NbtValue>) {
+    out.push('[');
+    for (index, value) in values.enumerate() {
+        if index != 0 {
+            out.push(',');
+        }
+        write(out, &value);
+    }
+    out.push(']');
+}
+
+fn write_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if u32::from(c) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
@@ -0,0 +1,186 @@
+//! Conversion between components and legacy formatting codes,
+//! such as `§aHello §lworld`.
+
+use alloc::{string::String, vec::Vec};
+
+use super::{
+    NamedColor, TextColor, TextComponent,
+    render::{self, Format},
+};
+
+/// The character that starts a legacy formatting code.
+pub const LEGACY_PREFIX: char = '§';
+
+impl TextComponent {
+    /// Parse a string containing legacy `§` formatting codes.
+    ///
+    /// See [`TextComponent::from_legacy_with`].
+    #[must_use]
+    pub fn from_legacy(input: &str) -> Self { Self::from_legacy_with(input, LEGACY_PREFIX) }
+
+    /// Parse a string containing legacy formatting codes started by `prefix`,
+    /// such as `&` for configuration files.
+    ///
+    /// | Code             | Effect                                 |
+    /// |------------------|----------------------------------------|
+    /// | `0-9`, `a-f`     | Set a named color and reset formatting |
+    /// | `x` + 6 digits   | Set a hex color and reset formatting   |
+    /// | `k`              | Obfuscated                             |
+    /// | `l`              | Bold                                   |
+    /// | `m`              | Strikethrough                          |
+    /// | `n`              | Underlined                             |
+    /// | `o`              | Italic                                 |
+    /// | `r`              | Reset color and formatting             |
+    ///
+    /// Hex colors are written as `§x§R§R§G§G§B§B`, with each digit prefixed.
+    ///
+    /// Codes are case-insensitive, unknown codes are removed.
+    ///
+    /// Each run of text becomes a child of an empty component,
+    /// unless the string has no formatting at all.
+    #[must_use]
+    pub fn from_legacy_with(input: &str, prefix: char) -> Self {
+        let mut runs: Vec<(String, Format)> = Vec::new();
+        let mut text = String::new();
+        let mut format = Format::default();
+
+        let mut chars = input.chars();
+        while let Some(c) = chars.next() {
+            if c != prefix {
+                text.push(c);
+                continue;
+            }
+
+            let mut next = format;
+            match chars.next().map(|c| c.to_ascii_lowercase()) {
+                Some('k') => next.obfuscated = true,
+                Some('l') => next.bold = true,
+                Some('m') => next.strikethrough = true,
+                Some('n') => next.underlined = true,
+                Some('o') => next.italic = true,
+                Some('r') => next = Format::default(),
+                Some('x') => {
+                    if let Some(rgb) = parse_hex(chars.as_str(), prefix) {
+                        next = Format { color: Some(TextColor::Rgb(rgb)), ..Format::default() };
+                        // Skip the six prefixed hex digits
+                        chars.nth(11);
+                    }
+                }
+                Some(code) => {
+                    if let Some(color) = NamedColor::from_code(code) {
+                        next = Format { color: Some(TextColor::Named(color)), ..Format::default() };
+                    }
+                }
+                None => {}
+            }
+
+            if next != format && !text.is_empty() {
+                runs.push((core::mem::take(&mut text), format));
+            }
+            format = next;
+        }
+        if !text.is_empty() {
+            runs.push((text, format));
+        }
+
+        match runs.as_slice() {
+            [] => Self::text(""),
+            [(_, format)] if *format == Format::default() => Self::text(runs.remove(0).0),
+            _ => {
+                let mut root = Self::text("");
+                root.extra = runs
+                    .into_iter()
+                    .map(|(text, format)| Self::text(text).with_style(format.to_style()))
+                    .collect();
+                root
+            }
+        }
+    }
+
+    /// Render this component as a string with legacy `§` formatting codes.
+    ///
+    /// See [`TextComponent::to_legacy_with`].
+    #[must_use]
+    pub fn to_legacy(&self) -> String { self.to_legacy_with(LEGACY_PREFIX) }
+
+    /// Render this component as a string with legacy formatting codes
+    /// started by `prefix`.
+    ///
+    /// Hex colors are written as `§x§R§R§G§G§B§B`.
+    /// Events, fonts and shadow colors cannot be represented and are dropped.
+    #[must_use]
+    pub fn to_legacy_with(&self, prefix: char) -> String {
+        let mut output = String::new();
+        let mut current = Format::default();
+        render::visit(self, Format::default(), &mut |text, format| {
+            if text.is_empty() {
+                return;
+            }
+            if format != current {
+                write_format(&mut output, prefix, current, format);
+                current = format;
+            }
+            output.push_str(text);
+        });
+        output
+    }
+}
+
+/// Parse six prefixed hex digits, such as `§1§2§3§4§5§6`.
+fn parse_hex(input: &str, prefix: char) -> Option<u32> {
+    let mut chars = input.chars();
+    let mut rgb = 0;
+    for _ in 0..6 {
+        if chars.next() != Some(prefix) {
+            return None;
+        }
+        rgb = rgb << 4 | chars.next()?.to_digit(16)?;
+    }
+    Some(rgb)
+}
+
+/// Write the codes needed to change from one format to another.
+fn write_format(output: &mut String, prefix: char, current: Format, next: Format) {
+    let removed = (current.obfuscated && !next.obfuscated)
+        || (current.bold && !next.bold)
+        || (current.strikethrough && !next.strikethrough)
+        || (current.underlined && !next.underlined)
+        || (current.italic && !next.italic);
+
+    // Colors reset formatting, so flags are written after them
+    let mut current = current;
+    if removed || current.color != next.color {
+        match next.color {
+            Some(TextColor::Named(color)) => {
+                output.push(prefix);
+                output.push(color.code());
+            }
+            Some(TextColor::Rgb(rgb)) => {
+                output.push(prefix);
+                output.push('x');
+                for shift in (0..6).rev() {
+                    output.push(prefix);
+                    output.extend(char::from_digit((rgb >> (shift * 4)) & 0xF, 16));
+                }
+            }
+            None => {
+                output.push(prefix);
+                output.push('r');
+            }
+        }
+        current = Format { color: next.color, ..Format::default() };
+    }
+
+    for (set, was_set, code) in [
+        (next.obfuscated, current.obfuscated, 'k'),
+        (next.bold, current.bold, 'l'),
+        (next.strikethrough, current.strikethrough, 'm'),
+        (next.underlined, current.underlined, 'n'),
+        (next.italic, current.italic, 'o'),
+    ] {
+        if set && !was_set {
+            output.push(prefix);
+            output.push(code);
+        }
+    }
+}
@@ -0,0 +1,353 @@
+//! Text components, used for chat messages, titles, item names and more.
+//!
+//! A [`TextComponent`] has some [`TextContent`], a [`Style`] and a list of
+//! children that inherit its style.
+//!
+//! Components are sent as JSON strings before 1.20.3 and as NBT since,
+//! see [`TextEncoding::for_protocol`]. [`TextComponent`] uses the encoding of
+//! the protocol version being written or read,
+//! wrap it in a [`JsonTextComponent`] to always use JSON.
+//!
+//! Components can also be converted to and from strings with legacy `§`
+//! formatting codes using [`TextComponent::from_legacy`] and
+//! [`TextComponent::to_legacy`]. For logging, [`Display`](core::fmt::Display)
+//! renders plain text and [`TextComponent::to_ansi_string`] renders text for
+//! terminals.
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use facet::{Facet, Shape};
+
+use crate::{
+    codec::{McCodec, McReader, McWriter, codec_shape},
+    common::TypeSerializeHint,
+    deserialize::DeserializeError,
+    nbt::{NbtCompound, NbtList, NbtValue},
+    serialize::SerializeError,
+};
+
+mod convert;
+mod json;
+mod render;
+
+mod legacy;
+pub use legacy::LEGACY_PREFIX;
+
+mod error;
+pub use error::{TextError, TextErrorKind};
+
+mod style;
+pub use style::{ClickEvent, HoverEvent, NamedColor, Style, TextColor};
+
+/// A formatted piece of text.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TextComponent {
+    /// The content of this component.
+    pub content: TextContent,
+    /// The style of this component, inherited by its children.
+    pub style: Style,
+    /// Components appended after this one.
+    pub extra: Vec<TextComponent>,
+}
+
+impl TextComponent {
+    /// Create a new [`TextComponent`] with the given content.
+    #[inline]
+    #[must_use]
+    pub const fn new(content: TextContent) -> Self {
+        Self { content, style: Style::EMPTY, extra: Vec::new() }
+    }
+
+    /// Create a component containing plain text.
+    #[inline]
+    #[must_use]
+    pub fn text(text: impl Into<String>) -> Self { Self::new(TextContent::Text(text.into())) }
+
+    /// Create a component translated by the client.
+    #[must_use]
+    pub fn translatable(
+        key: impl Into<String>,
+        with: impl IntoIterator<Item = impl Into<TextComponent>>,
+    ) -> Self {
+        Self::new(TextContent::Translatable {
+            key: key.into(),
+            fallback: None,
+            with: with.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    /// Create a component showing the key bound to an action.
+    #[inline]
+    #[must_use]
+    pub fn keybind(key: impl Into<String>) -> Self { Self::new(TextContent::Keybind(key.into())) }
+
+    /// Set the style of this component.
+    #[inline]
+    #[must_use]
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Append a child component.
+    #[inline]
+    #[must_use]
+    pub fn with_extra(mut self, child: impl Into<TextComponent>) -> Self {
+        self.extra.push(child.into());
+        self
+    }
+
+    /// Convert this component into a JSON string.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        json::write(&mut json, &self.to_nbt(TextEncoding::Json));
+        json
+    }
+
+    /// Parse a component from a JSON string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is not valid JSON,
+    /// or does not describe a component.
+    pub fn from_json(json: &str) -> Result<Self, TextError> {
+        convert::from_value(&json::parse(json)?, 0)
+    }
+
+    /// Convert this component into an [`NbtValue`],
+    /// using the field names of the given encoding.
+    #[must_use]
+    pub fn to_nbt(&self, encoding: TextEncoding) -> NbtValue { convert::to_value(self, encoding) }
+
+    /// Read a component from an [`NbtValue`].
+    ///
+    /// Field names of all versions are accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value does not describe a component.
+    pub fn from_nbt(value: &NbtValue) -> Result<Self, TextError> { convert::from_value(value, 0) }
+
+    /// Write this component using the given encoding.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer cannot be written to.
+    pub fn encode_with(
+        &self,
+        writer: &mut McWriter<'_>,
+        encoding: TextEncoding,
+    ) -> Result<(), SerializeError> {
+        match encoding {
+            TextEncoding::Json => writer.write_str(&self.to_json()),
+            TextEncoding::LegacyNbt | TextEncoding::Nbt => writer.write(&self.to_nbt(encoding)),
+        }
+    }
+
+    /// Read a component using the given encoding.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input ends unexpectedly,
+    /// or does not contain a valid component.
+    pub fn decode_with(
+        reader: &mut McReader<'_>,
+        encoding: TextEncoding,
+    ) -> Result<Self, DeserializeError> {
+        match encoding {
+            TextEncoding::Json => Self::from_json(reader.read_str()?).map_err(Into::into),
+            TextEncoding::LegacyNbt | TextEncoding::Nbt => {
+                Self::from_nbt(&reader.read::<NbtValue>()?).map_err(Into::into)
+            }
+        }
+    }
+}
+
+impl From<&str> for TextComponent {
+    #[inline]
+    fn from(text: &str) -> Self { Self::text(text) }
+}
+
+impl From<String> for TextComponent {
+    #[inline]
+    fn from(text: String) -> Self { Self::text(text) }
+}
+
+impl McCodec for TextComponent {
+    // At least an empty `TAG_String`
+    const SERIALIZE_HINT: TypeSerializeHint = TypeSerializeHint::Range { min: 3, max: None };
+
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        self.encode_with(writer, TextEncoding::for_protocol(writer.version().protocol()))
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        Self::decode_with(reader, TextEncoding::for_protocol(reader.version().protocol()))
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for TextComponent {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("TextComponent").build() };
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// The content of a [`TextComponent`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextContent {
+    /// Plain text.
+    Text(String),
+    /// Text translated by the client, with arguments replacing `%s` and
+    /// `%1$s`.
+    Translatable {
+        /// The translation key.
+        key: String,
+        /// The text used if the key is not known by the client.
+        fallback: Option<String>,
+        /// The translation arguments.
+        with: Vec<TextComponent>,
+    },
+    /// The score of an entity, resolved by the server.
+    Score {
+        /// The name or selector of the score holder.
+        name: String,
+        /// The name of the objective.
+        objective: String,
+    },
+    /// The names of entities matching a selector, resolved by the server.
+    Selector {
+        /// The entity selector.
+        pattern: String,
+        /// The separator placed between names, `", "` by default.
+        separator: Option<Box<TextComponent>>,
+    },
+    /// The key bound to an action, such as `key.jump`.
+    Keybind(String),
+    /// NBT data at a path, resolved by the server.
+    Nbt {
+        /// The NBT path to read.
+        path: String,
+        /// Whether the data is itself a text component.
+        interpret: Option<bool>,
+        /// The separator placed between values, `", "` by default.
+        separator: Option<Box<TextComponent>>,
+        /// Where the data is read from.
+        source: NbtSource,
+    },
+}
+
+impl Default for TextContent {
+    #[inline]
+    fn default() -> Self { Self::Text(String::new()) }
+}
+
+/// Where the data of a [`TextContent::Nbt`] is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NbtSource {
+    /// A block entity at a position, such as `~ ~-1 ~`.
+    Block(String),
+    /// The entities matching a selector.
+    Entity(String),
+    /// A command storage with the given resource location.
+    Storage(String),
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// The wire encoding of a [`TextComponent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextEncoding {
+    /// A JSON string, used before 1.20.3.
+    Json,
+    /// NBT with `camelCase` event fields, used from 1.20.3 until 1.21.5.
+    LegacyNbt,
+    /// NBT with `snake_case` event fields, used since 1.21.5.
+    Nbt,
+}
+
+impl TextEncoding {
+    /// The protocol version of 1.20.3, the first to use NBT.
+    pub const NBT_PROTOCOL: u32 = 765;
+    /// The protocol version of 1.21.5, the first to use `snake_case` events.
+    pub const SNAKE_CASE_PROTOCOL: u32 = 770;
+
+    /// Get the encoding used by a protocol version.
+    #[must_use]
+    pub const fn for_protocol(protocol: u32) -> Self {
+        if protocol >= Self::SNAKE_CASE_PROTOCOL {
+            Self::Nbt
+        } else if protocol >= Self::NBT_PROTOCOL {
+            Self::LegacyNbt
+        } else {
+            Self::Json
+        }
+    }
+}
+
+/// A [`TextComponent`] sent as a JSON string, as used before 1.20.3.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct JsonTextComponent(pub TextComponent);
+
+impl From<TextComponent> for JsonTextComponent {
+    #[inline]
+    fn from(component: TextComponent) -> Self { Self(component) }
+}
+
+impl From<JsonTextComponent> for TextComponent {
+    #[inline]
+    fn from(component: JsonTextComponent) -> Self { component.0 }
+}
+
+impl McCodec for JsonTextComponent {
+    // At least an empty JSON string `""`
+    const SERIALIZE_HINT: TypeSerializeHint = TypeSerializeHint::Range { min: 3, max: None };
+
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        self.0.encode_with(writer, TextEncoding::Json)
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        TextComponent::decode_with(reader, TextEncoding::Json).map(Self)
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for JsonTextComponent {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("JsonTextComponent").build() };
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Collect values into an [`NbtList`].
+///
+/// Like the vanilla client, lists of mixed types are stored as compounds,
+/// with other values wrapped in a compound with a single empty key.
+fn list_of(values: Vec<NbtValue>) -> NbtList {
+    let mixed = values.windows(2).any(|pair| pair[0].tag() != pair[1].tag());
+    let values = values.into_iter().map(|value| match value {
+        NbtValue::Compound(compound) if !mixed || !is_wrapped(&compound) => {
+            NbtValue::Compound(compound)
+        }
+        value if mixed => NbtValue::Compound([(String::new(), value)].into_iter().collect()),
+        value => value,
+    });
+    // Values either share the same tag or are all compounds.
+    values.collect::<Vec<_>>().try_into().unwrap_or_default()
+}
+
+/// Unwrap a value stored in a list of mixed types.
+fn unwrap_entry(value: &NbtValue) -> &NbtValue {
+    match value {
+        NbtValue::Compound(compound) if is_wrapped(compound) => {
+            compound.values().next().unwrap_or(value)
+        }
+        value => value,
+    }
+}
+
+/// Returns `true` if the compound contains a single entry with an empty key.
+fn is_wrapped(compound: &NbtCompound) -> bool {
+    compound.len() == 1 && compound.keys().all(str::is_empty)
+}
@@ -0,0 +1,211 @@
+//! Flattening components into styled runs of text,
+//! used by the plain text, ANSI and legacy renderers.
+
+use alloc::string::String;
+use core::fmt::{self, Display, Formatter, Write};
+
+use super::{NamedColor, Style, TextColor, TextComponent, TextContent};
+
+/// Translations for common chat messages,
+/// used when a translatable component has no fallback.
+const TRANSLATIONS: &[(&str, &str)] = &[
+    ("chat.type.text", "<%s> %s"),
+    ("chat.type.emote", "* %s %s"),
+    ("chat.type.announcement", "[%s] %s"),
+    ("chat.type.admin", "[%s: %s]"),
+    ("chat.type.team.text", "%s <%s> %s"),
+    ("chat.type.team.sent", "-> %s <%s> %s"),
+    ("commands.message.display.incoming", "%s whispers to you: %s"),
+    ("commands.message.display.outgoing", "You whisper to %s: %s"),
+    ("multiplayer.player.joined", "%s joined the game"),
+    ("multiplayer.player.joined.renamed", "%s (formerly known as %s) joined the game"),
+    ("multiplayer.player.left", "%s left the game"),
+    ("multiplayer.disconnect.kicked", "Kicked by an operator"),
+];
+
+/// The formatting of a run of text, after inheriting from its parents.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[expect(clippy::struct_excessive_bools, reason = "Mirrors the flags of `Style`")]
+pub(super) struct Format {
+    pub(super) color: Option<TextColor>,
+    pub(super) bold: bool,
+    pub(super) italic: bool,
+    pub(super) underlined: bool,
+    pub(super) strikethrough: bool,
+    pub(super) obfuscated: bool,
+}
+
+impl Format {
+    /// Apply a style on top of this format.
+    fn inherit(self, style: &Style) -> Self {
+        Self {
+            color: style.color.or(self.color),
+            bold: style.bold.unwrap_or(self.bold),
+            italic: style.italic.unwrap_or(self.italic),
+            underlined: style.underlined.unwrap_or(self.underlined),
+            strikethrough: style.strikethrough.unwrap_or(self.strikethrough),
+            obfuscated: style.obfuscated.unwrap_or(self.obfuscated),
+        }
+    }
+
+    /// Convert this format into a [`Style`], leaving unset flags as `None`.
+    pub(super) fn to_style(self) -> Style {
+        let flag = |set: bool| set.then_some(true);
+        Style {
+            color: self.color,
+            bold: flag(self.bold),
+            italic: flag(self.italic),
+            underlined: flag(self.underlined),
+            strikethrough: flag(self.strikethrough),
+            obfuscated: flag(self.obfuscated),
+            ..Style::EMPTY
+        }
+    }
+}
+
+/// Visit each run of text in a component and its children.
+pub(super) fn visit(component: &TextComponent, parent: Format, f: &mut impl FnMut(&str, Format)) {
+    let format = parent.inherit(&component.style);
+    match &component.content {
+        TextContent::Text(text) => f(text, format),
+        TextContent::Translatable { key, fallback, with } => {
+            let template = fallback.as_deref().unwrap_or_else(|| {
+                TRANSLATIONS.iter().find(|(k, _)| k == key).map_or(key.as_str(), |(_, v)| v)
+            });
+            visit_translation(template, with, format, f);
+        }
+        TextContent::Selector { pattern, .. } => f(pattern, format),
+        TextContent::Keybind(key) => f(key, format),
+        // Resolved by the server before being sent
+        TextContent::Score { .. } | TextContent::Nbt { .. } => {}
+    }
+    for child in &component.extra {
+        visit(child, format, f);
+    }
+}
+
+/// Visit a translation template, replacing `%s` and `%1$s` with arguments.
+fn visit_translation(
+    mut template: &str,
+    with: &[TextComponent],
+    format: Format,
+    f: &mut impl FnMut(&str, Format),
+) {
+    let mut next = 0;
+    while let Some(index) = template.find('%') {
+        f(&template[..index], format);
+        let rest = &template[index + 1..];
+        let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+
+        let (argument, rest) = if let Some(rest) = rest.strip_prefix('s') {
+            next += 1;
+            (Some(next - 1), rest)
+        } else if digits > 0
+            && let Some(after) = rest[digits..].strip_prefix("$s")
+        {
+            // Positional arguments start from `1`
+            (rest[..digits].parse::<usize>().ok().and_then(|n| n.checked_sub(1)), after)
+        } else {
+            // `%%` is an escaped percent sign, anything else is kept as-is
+            f("%", format);
+            (None, rest.strip_prefix('%').unwrap_or(rest))
+        };
+
+        if let Some(component) = argument.and_then(|index| with.get(index)) {
+            visit(component, format, f);
+        }
+        template = rest;
+    }
+    f(template, format);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Plain text, without any formatting.
+impl Display for TextComponent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut result = Ok(());
+        visit(self, Format::default(), &mut |text, _| {
+            if result.is_ok() {
+                result = f.write_str(text);
+            }
+        });
+        result
+    }
+}
+
+impl TextComponent {
+    /// Render this component as text with ANSI escape codes,
+    /// for printing to a terminal.
+    ///
+    /// Named colors use the terminal's palette,
+    /// other colors are written as 24-bit colors.
+    /// Obfuscated text is printed as-is.
+    #[must_use]
+    pub fn to_ansi_string(&self) -> String {
+        let mut output = String::new();
+        let mut current = Format::default();
+        visit(self, Format::default(), &mut |text, format| {
+            if text.is_empty() {
+                return;
+            }
+            if format != current {
+                write_ansi(&mut output, format);
+                current = format;
+            }
+            output.push_str(text);
+        });
+        if current != Format::default() {
+            output.push_str("\x1b[0m");
+        }
+        output
+    }
+}
+
+/// Write the escape sequence for a format, resetting the previous one.
+fn write_ansi(output: &mut String, format: Format) {
+    output.push_str("\x1b[0");
+    for (set, code) in [
+        (format.bold, ";1"),
+        (format.italic, ";3"),
+        (format.underlined, ";4"),
+        (format.strikethrough, ";9"),
+    ] {
+        if set {
+            output.push_str(code);
+        }
+    }
+    match format.color {
+        Some(TextColor::Named(color)) => {
+            let _ = write!(output, ";{}", ansi_color(color));
+        }
+        Some(TextColor::Rgb(rgb)) => {
+            let [_, r, g, b] = rgb.to_be_bytes();
+            let _ = write!(output, ";38;2;{r};{g};{b}");
+        }
+        None => {}
+    }
+    output.push('m');
+}
+
+/// Get the ANSI foreground color code for a named color.
+const fn ansi_color(color: NamedColor) -> u8 {
+    match color {
+        NamedColor::Black => 30,
+        NamedColor::DarkRed => 31,
+        NamedColor::DarkGreen => 32,
+        NamedColor::Gold => 33,
+        NamedColor::DarkBlue => 34,
+        NamedColor::DarkPurple => 35,
+        NamedColor::DarkAqua => 36,
+        NamedColor::Gray => 37,
+        NamedColor::DarkGray => 90,
+        NamedColor::Red => 91,
+        NamedColor::Green => 92,
+        NamedColor::Yellow => 93,
+        NamedColor::Blue => 94,
+        NamedColor::LightPurple => 95,
+        NamedColor::Aqua => 96,
+        NamedColor::White => 97,
+    }
+}
@@ -0,0 +1,357 @@
+use alloc::{boxed::Box, string::String};
+use core::fmt::{self, Display, Formatter};
+
+use uuid::Uuid;
+
+use super::TextComponent;
+use crate::nbt::NbtCompound;
+
+/// The formatting applied to a [`TextComponent`] and inherited by its
+/// children.
+///
+/// Fields set to `None` are inherited from the parent component.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Style {
+    /// The color of the text.
+    pub color: Option<TextColor>,
+    /// The color of the text shadow, as `0xAARRGGBB`.
+    ///
+    /// Added in 1.21.4.
+    pub shadow_color: Option<u32>,
+    /// Whether the text is bold.
+    pub bold: Option<bool>,
+    /// Whether the text is italic.
+    pub italic: Option<bool>,
+    /// Whether the text is underlined.
+    pub underlined: Option<bool>,
+    /// Whether the text is struck through.
+    pub strikethrough: Option<bool>,
+    /// Whether the text is obfuscated.
+    pub obfuscated: Option<bool>,
+    /// The action performed when the text is clicked.
+    pub click_event: Option<ClickEvent>,
+    /// The tooltip shown when the text is hovered over.
+    pub hover_event: Option<HoverEvent>,
+    /// The text inserted into chat when the text is shift-clicked.
+    pub insertion: Option<String>,
+    /// The resource location of the font used to render the text.
+    pub font: Option<String>,
+}
+
+impl Style {
+    /// A style that does not change anything.
+    pub const EMPTY: Self = Self {
+        color: None,
+        shadow_color: None,
+        bold: None,
+        italic: None,
+        underlined: None,
+        strikethrough: None,
+        obfuscated: None,
+        click_event: None,
+        hover_event: None,
+        insertion: None,
+        font: None,
+    };
+
+    /// Returns `true` if no fields are set.
+    #[must_use]
+    pub fn is_empty(&self) -> bool { *self == Self::EMPTY }
+
+    /// Set the color of the text.
+    #[inline]
+    #[must_use]
+    pub fn with_color(mut self, color: impl Into<TextColor>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Set whether the text is bold.
+    #[inline]
+    #[must_use]
+    pub const fn with_bold(mut self, bold: bool) -> Self {
+        self.bold = Some(bold);
+        self
+    }
+
+    /// Set whether the text is italic.
+    #[inline]
+    #[must_use]
+    pub const fn with_italic(mut self, italic: bool) -> Self {
+        self.italic = Some(italic);
+        self
+    }
+
+    /// Set whether the text is underlined.
+    #[inline]
+    #[must_use]
+    pub const fn with_underlined(mut self, underlined: bool) -> Self {
+        self.underlined = Some(underlined);
+        self
+    }
+
+    /// Set whether the text is struck through.
+    #[inline]
+    #[must_use]
+    pub const fn with_strikethrough(mut self, strikethrough: bool) -> Self {
+        self.strikethrough = Some(strikethrough);
+        self
+    }
+
+    /// Set whether the text is obfuscated.
+    #[inline]
+    #[must_use]
+    pub const fn with_obfuscated(mut self, obfuscated: bool) -> Self {
+        self.obfuscated = Some(obfuscated);
+        self
+    }
+
+    /// Set the action performed when the text is clicked.
+    #[inline]
+    #[must_use]
+    pub fn with_click_event(mut self, event: ClickEvent) -> Self {
+        self.click_event = Some(event);
+        self
+    }
+
+    /// Set the tooltip shown when the text is hovered over.
+    #[inline]
+    #[must_use]
+    pub fn with_hover_event(mut self, event: HoverEvent) -> Self {
+        self.hover_event = Some(event);
+        self
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// The color of a [`TextComponent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextColor {
+    /// One of the sixteen named colors.
+    Named(NamedColor),
+    /// An arbitrary color, as `0xRRGGBB`.
+    Rgb(u32),
+}
+
+impl TextColor {
+    /// Parse a color name or a `#RRGGBB` hex code.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.strip_prefix('#') {
+            Some(hex) if hex.len() == 6 && hex.bytes().all(|b| b.is_ascii_hexdigit()) => {
+                u32::from_str_radix(hex, 16).ok().map(Self::Rgb)
+            }
+            Some(_) => None,
+            None => NamedColor::from_name(value).map(Self::Named),
+        }
+    }
+
+    /// Get the color as `0xRRGGBB`.
+    #[must_use]
+    pub const fn rgb(self) -> u32 {
+        match self {
+            Self::Named(color) => color.rgb(),
+            Self::Rgb(rgb) => rgb & 0x00FF_FFFF,
+        }
+    }
+}
+
+impl From<NamedColor> for TextColor {
+    #[inline]
+    fn from(color: NamedColor) -> Self { Self::Named(color) }
+}
+
+impl Display for TextColor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Named(color) => f.write_str(color.name()),
+            Self::Rgb(rgb) => write!(f, "#{:06X}", rgb & 0x00FF_FFFF),
+        }
+    }
+}
+
+/// One of the sixteen named colors,
+/// each of which has a legacy formatting code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[expect(missing_docs, reason = "Variants are named after their colors")]
+pub enum NamedColor {
+    Black,
+    DarkBlue,
+    DarkGreen,
+    DarkAqua,
+    DarkRed,
+    DarkPurple,
+    Gold,
+    Gray,
+    DarkGray,
+    Blue,
+    Green,
+    Aqua,
+    Red,
+    LightPurple,
+    Yellow,
+    White,
+}
+
+impl NamedColor {
+    /// All named colors, ordered by their formatting code.
+    pub const ALL: [Self; 16] = [
+        Self::Black,
+        Self::DarkBlue,
+        Self::DarkGreen,
+        Self::DarkAqua,
+        Self::DarkRed,
+        Self::DarkPurple,
+        Self::Gold,
+        Self::Gray,
+        Self::DarkGray,
+        Self::Blue,
+        Self::Green,
+        Self::Aqua,
+        Self::Red,
+        Self::LightPurple,
+        Self::Yellow,
+        Self::White,
+    ];
+
+    /// Get the name of the color, as used by text components.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Black => "black",
+            Self::DarkBlue => "dark_blue",
+            Self::DarkGreen => "dark_green",
+            Self::DarkAqua => "dark_aqua",
+            Self::DarkRed => "dark_red",
+            Self::DarkPurple => "dark_purple",
+            Self::Gold => "gold",
+            Self::Gray => "gray",
+            Self::DarkGray => "dark_gray",
+            Self::Blue => "blue",
+            Self::Green => "green",
+            Self::Aqua => "aqua",
+            Self::Red => "red",
+            Self::LightPurple => "light_purple",
+            Self::Yellow => "yellow",
+            Self::White => "white",
+        }
+    }
+
+    /// Get the color with the given name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|color| color.name() == name)
+    }
+
+    /// Get the legacy formatting code of the color, `0-9` or `a-f`.
+    #[must_use]
+    pub const fn code(self) -> char {
+        match char::from_digit(self as u32, 16) {
+            Some(code) => code,
+            None => unreachable!(),
+        }
+    }
+
+    /// Get the color with the given legacy formatting code.
+    ///
+    /// Codes are case-insensitive.
+    #[must_use]
+    pub fn from_code(code: char) -> Option<Self> {
+        code.to_digit(16).and_then(|index| Self::ALL.get(index as usize).copied())
+    }
+
+    /// Get the color as `0xRRGGBB`.
+    #[must_use]
+    pub const fn rgb(self) -> u32 {
+        match self {
+            Self::Black => 0x00_0000,
+            Self::DarkBlue => 0x00_00AA,
+            Self::DarkGreen => 0x00_AA00,
+            Self::DarkAqua => 0x00_AAAA,
+            Self::DarkRed => 0xAA_0000,
+            Self::DarkPurple => 0xAA_00AA,
+            Self::Gold => 0xFF_AA00,
+            Self::Gray => 0xAA_AAAA,
+            Self::DarkGray => 0x55_5555,
+            Self::Blue => 0x55_55FF,
+            Self::Green => 0x55_FF55,
+            Self::Aqua => 0x55_FFFF,
+            Self::Red => 0xFF_5555,
+            Self::LightPurple => 0xFF_55FF,
+            Self::Yellow => 0xFF_FF55,
+            Self::White => 0xFF_FFFF,
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// An action performed when a [`TextComponent`] is clicked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClickEvent {
+    /// Open a URL in the browser.
+    OpenUrl(String),
+    /// Open a file on the client, only used by the client itself.
+    OpenFile(String),
+    /// Run a command, or send a chat message if it does not start with `/`.
+    RunCommand(String),
+    /// Replace the contents of the chat input.
+    SuggestCommand(String),
+    /// Change the page of a written book.
+    ChangePage(i32),
+    /// Copy text to the clipboard.
+    CopyToClipboard(String),
+}
+
+impl ClickEvent {
+    /// Get the name of the action.
+    #[must_use]
+    pub const fn action(&self) -> &'static str {
+        match self {
+            Self::OpenUrl(_) => "open_url",
+            Self::OpenFile(_) => "open_file",
+            Self::RunCommand(_) => "run_command",
+            Self::SuggestCommand(_) => "suggest_command",
+            Self::ChangePage(_) => "change_page",
+            Self::CopyToClipboard(_) => "copy_to_clipboard",
+        }
+    }
+}
+
+/// A tooltip shown when a [`TextComponent`] is hovered over.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HoverEvent {
+    /// Show a text component.
+    ShowText(Box<TextComponent>),
+    /// Show the tooltip of an item.
+    ShowItem {
+        /// The item's resource location.
+        id: String,
+        /// The number of items in the stack.
+        count: i32,
+        /// The item's components, or its tag before 1.20.5.
+        components: Option<NbtCompound>,
+    },
+    /// Show the type, UUID and name of an entity.
+    ShowEntity {
+        /// The entity type's resource location.
+        entity_type: String,
+        /// The entity's UUID.
+        uuid: Uuid,
+        /// The entity's name.
+        name: Option<Box<TextComponent>>,
+    },
+}
+
+impl HoverEvent {
+    /// Get the name of the action.
+    #[must_use]
+    pub const fn action(&self) -> &'static str {
+        match self {
+            Self::ShowText(_) => "show_text",
+            Self::ShowItem { .. } => "show_item",
+            Self::ShowEntity { .. } => "show_entity",
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use alloc::vec::Vec;
+
+use facet::{Facet, Shape};
+
+use crate::{
+    codec::{McCodec, McReader, McWriter, codec_shape},
+    deserialize::DeserializeError,
+    serialize::SerializeError,
+};
+
+/// A growable set of bits, sent as a `VarInt`-prefixed array of longs.
+///
+/// Bit `n` is stored in long `n / 64` at bit `n % 64`.
+/// Trailing empty longs are not sent.
+///
+/// ```rust
+/// use facet_minecraft::types::BitSet;
+///
+/// let mut bits = BitSet::new();
+/// bits.set(1, true);
+/// bits.set(70, true);
+/// assert!(bits.get(70) && !bits.get(69));
+/// assert_eq!(bits.iter().collect::<Vec<_>>(), [1, 70]);
+/// assert_eq!(bits.as_longs(), [0b10, 0b100_0000]);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    longs: Vec<u64>,
+}
+
+impl BitSet {
+    /// Create a new, empty [`BitSet`].
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self { Self { longs: Vec::new() } }
+
+    /// Create a [`BitSet`] from its longs.
+    #[must_use]
+    pub fn from_longs(longs: Vec<u64>) -> Self {
+        let mut set = Self { longs };
+        set.trim();
+        set
+    }
+
+    /// Get the longs storing the bits.
+    #[inline]
+    #[must_use]
+    pub fn as_longs(&self) -> &[u64] { &self.longs }
+
+    /// Returns `true` if no bits are set.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool { self.longs.is_empty() }
+
+    /// Returns `true` if a bit is set.
+    #[must_use]
+    pub fn get(&self, index: usize) -> bool {
+        self.longs.get(index / 64).is_some_and(|long| long & 1 << (index % 64) != 0)
+    }
+
+    /// Set or clear a bit.
+    pub fn set(&mut self, index: usize, value: bool) {
+        if value {
+            if self.longs.len() <= index / 64 {
+                self.longs.resize(index / 64 + 1, 0);
+            }
+            self.longs[index / 64] |= 1 << (index % 64);
+        } else if let Some(long) = self.longs.get_mut(index / 64) {
+            *long &= !(1 << (index % 64));
+            self.trim();
+        }
+    }
+
+    /// Iterate over the indices of the set bits, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.longs.len() * 64).filter(|index| self.get(*index))
+    }
+
+    /// Remove trailing empty longs.
+    fn trim(&mut self) {
+        while self.longs.last() == Some(&0) {
+            self.longs.pop();
+        }
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<T: IntoIterator<Item = usize>>(iter: T) -> Self {
+        let mut set = Self::new();
+        iter.into_iter().for_each(|index| set.set(index, true));
+        set
+    }
+}
+
+impl McCodec for BitSet {
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        writer.write_var_u32(u32::try_from(self.longs.len()).unwrap_or(u32::MAX))?;
+        self.longs.iter().try_for_each(|long| writer.write_u64(*long))
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        let len = reader.read_var_u32()? as usize;
+        let longs = (0..len).map(|_| reader.read_u64()).collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::from_longs(longs))
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for BitSet {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("BitSet").build() };
+}
@@ -0,0 +1,93 @@
+use facet::{Facet, Shape};
+
+use crate::{
+    codec::{McCodec, McReader, McWriter, codec_shape},
+    deserialize::DeserializeError,
+    serialize::SerializeError,
+    types::Identifier,
+};
+
+/// The position of a block, packed into a single long.
+///
+/// The `x` and `z` coordinates use 26 bits and the `y` coordinate 12 bits,
+/// coordinates outside of that range wrap around when encoded.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BlockPos {
+    /// The `x` coordinate.
+    pub x: i32,
+    /// The `y` coordinate.
+    pub y: i32,
+    /// The `z` coordinate.
+    pub z: i32,
+}
+
+impl BlockPos {
+    /// The position at the origin of the world.
+    pub const ZERO: Self = Self::new(0, 0, 0);
+
+    /// Create a new [`BlockPos`].
+    #[inline]
+    #[must_use]
+    pub const fn new(x: i32, y: i32, z: i32) -> Self { Self { x, y, z } }
+
+    /// Pack the position into a long.
+    #[must_use]
+    pub const fn to_long(self) -> i64 {
+        (self.x as i64 & 0x3FF_FFFF) << 38
+            | (self.z as i64 & 0x3FF_FFFF) << 12
+            | self.y as i64 & 0xFFF
+    }
+
+    /// Unpack a position from a long.
+    #[must_use]
+    pub const fn from_long(value: i64) -> Self {
+        Self {
+            x: (value >> 38) as i32,
+            y: (value << 52 >> 52) as i32,
+            z: (value << 26 >> 38) as i32,
+        }
+    }
+}
+
+impl McCodec for BlockPos {
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        writer.write_u64(self.to_long().cast_unsigned())
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        reader.read_u64().map(|value| Self::from_long(value.cast_signed()))
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for BlockPos {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("BlockPos").build() };
+}
+
+/// A [`BlockPos`] in a dimension.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GlobalPos {
+    /// The dimension, such as `minecraft:overworld`.
+    pub dimension: Identifier<'static>,
+    /// The position in the dimension.
+    pub position: BlockPos,
+}
+
+impl McCodec for GlobalPos {
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        writer.write_identifier(&self.dimension)?;
+        self.position.encode(writer)
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        Ok(Self {
+            dimension: reader.read_identifier()?.into_owned(),
+            position: BlockPos::decode(reader)?,
+        })
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for GlobalPos {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("GlobalPos").build() };
+}
@@ -0,0 +1,89 @@
+use facet::{Facet, Shape};
+
+use crate::{
+    codec::{McCodec, McReader, McWriter, codec_shape},
+    common::TypeSerializeHint,
+    deserialize::{self, DeserializeError},
+    serialize::{self, SerializeError, SerializeErrorKind},
+};
+
+/// A registry ID or an inline value, such as a sound event or a trim material.
+///
+/// Sent as a `VarInt` of the ID plus one, or `0` followed by the inline value,
+/// so [`u32::MAX`] cannot be sent as an ID. The inline value is encoded like
+/// any other [`Facet`] type, for the same protocol version.
+///
+/// ```rust
+/// use facet_minecraft::types::{IdOr, Identifier};
+///
+/// let sound: IdOr<Identifier<'static>> = IdOr::Id(5);
+/// assert_eq!(facet_minecraft::to_vec(&sound).unwrap(), [6]);
+///
+/// let sound = IdOr::Inline(Identifier::new_unchecked("minecraft:a"));
+/// assert_eq!(facet_minecraft::to_vec(&sound).unwrap(), b"\0\x0bminecraft:a");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum IdOr<T> {
+    /// An ID in the registry.
+    Id(u32),
+    /// A value defined inline.
+    Inline(T),
+}
+
+impl<T> IdOr<T> {
+    /// Get the registry ID, if this is not an inline value.
+    #[inline]
+    #[must_use]
+    pub const fn id(&self) -> Option<u32> {
+        match self {
+            Self::Id(id) => Some(*id),
+            Self::Inline(_) => None,
+        }
+    }
+
+    /// Get the inline value, if this is not a registry ID.
+    #[inline]
+    #[must_use]
+    pub const fn inline(&self) -> Option<&T> {
+        match self {
+            Self::Id(_) => None,
+            Self::Inline(value) => Some(value),
+        }
+    }
+}
+
+impl<T> From<T> for IdOr<T> {
+    fn from(value: T) -> Self { Self::Inline(value) }
+}
+
+impl<T: Facet<'static> + Clone> McCodec for IdOr<T> {
+    // VarInt ID, or `0` + inline value
+    const SERIALIZE_HINT: TypeSerializeHint = TypeSerializeHint::Range { min: 1, max: None };
+
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        match self {
+            Self::Id(id) => {
+                let encoded = id
+                    .checked_add(1)
+                    .ok_or_else(|| SerializeError::new(SerializeErrorKind::IdTooLarge(*id)))?;
+                writer.write_var_u32(encoded)
+            }
+            Self::Inline(value) => {
+                writer.write_var_u32(0)?;
+                serialize::encode_versioned(value, writer)
+            }
+        }
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        match reader.read_var_u32()? {
+            0 => deserialize::decode_versioned(reader).map(Self::Inline),
+            id => Ok(Self::Id(id - 1)),
+        }
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl<T: Facet<'static> + Clone> Facet<'_> for IdOr<T> {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("IdOr").build() };
+}
@@ -0,0 +1,101 @@
+use alloc::vec::Vec;
+
+use facet::{Facet, Shape};
+
+use crate::{
+    codec::{McCodec, McReader, McWriter, codec_shape},
+    common::TypeSerializeHint,
+    deserialize::DeserializeError,
+    serialize::SerializeError,
+    types::Identifier,
+};
+
+/// A set of registry entries, either a tag or a list of IDs.
+///
+/// Sent as a `VarInt` `0` followed by the tag identifier,
+/// or the number of IDs plus one followed by the IDs.
+///
+/// ```rust
+/// use facet_minecraft::types::{IdSet, Identifier};
+///
+/// let blocks = IdSet::Ids(vec![1, 300]);
+/// assert_eq!(facet_minecraft::to_vec(&blocks).unwrap(), [3, 1, 0xAC, 0x02]);
+///
+/// let blocks = IdSet::Tag(Identifier::new_unchecked("minecraft:logs"));
+/// assert_eq!(facet_minecraft::to_vec(&blocks).unwrap(), b"\0\x0eminecraft:logs");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum IdSet {
+    /// A tag, without the leading `#`.
+    Tag(Identifier<'static>),
+    /// A list of registry IDs.
+    Ids(Vec<u32>),
+}
+
+impl IdSet {
+    /// An empty list of IDs.
+    pub const EMPTY: Self = Self::Ids(Vec::new());
+
+    /// Get the tag, if this is not a list of IDs.
+    #[inline]
+    #[must_use]
+    pub const fn tag(&self) -> Option<&Identifier<'static>> {
+        match self {
+            Self::Tag(tag) => Some(tag),
+            Self::Ids(_) => None,
+        }
+    }
+
+    /// Get the IDs, if this is not a tag.
+    #[inline]
+    #[must_use]
+    pub const fn ids(&self) -> Option<&[u32]> {
+        match self {
+            Self::Tag(_) => None,
+            Self::Ids(ids) => Some(ids.as_slice()),
+        }
+    }
+}
+
+impl Default for IdSet {
+    fn default() -> Self { Self::EMPTY }
+}
+
+impl FromIterator<u32> for IdSet {
+    fn from_iter<T: IntoIterator<Item = u32>>(iter: T) -> Self {
+        Self::Ids(iter.into_iter().collect())
+    }
+}
+
+impl McCodec for IdSet {
+    // VarInt `0` + tag, or VarInt length + VarInt IDs
+    const SERIALIZE_HINT: TypeSerializeHint = TypeSerializeHint::Range { min: 1, max: None };
+
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        match self {
+            Self::Tag(tag) => {
+                writer.write_var_u32(0)?;
+                writer.write_identifier(tag)
+            }
+            Self::Ids(ids) => {
+                writer.write_var_u32(u32::try_from(ids.len() + 1).unwrap_or(u32::MAX))?;
+                ids.iter().try_for_each(|id| writer.write_var_u32(*id))
+            }
+        }
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        match reader.read_var_u32()? {
+            0 => Ok(Self::Tag(reader.read_identifier()?.into_owned())),
+            len => {
+                let ids = (1..len).map(|_| reader.read_var_u32()).collect::<Result<_, _>>()?;
+                Ok(Self::Ids(ids))
+            }
+        }
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for IdSet {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("IdSet").build() };
+}
@@ -0,0 +1,344 @@
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+};
+use core::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+use facet::{
+    Attr, Def, Facet, OxPtrMut, OxPtrUninit, PtrConst, Shape, ShapeBuilder, TryFromOutcome, Type,
+    UserType, VTableIndirect,
+};
+
+use crate::{
+    codec::{CodecFn, McCodec, McReader, McWriter},
+    common::TypeSerializeHint,
+    deserialize::DeserializeError,
+    serialize::SerializeError,
+};
+
+/// A namespaced identifier, such as `minecraft:stone`.
+///
+/// Identifiers without a namespace, such as `stone`,
+/// use the [`DEFAULT_NAMESPACE`](Self::DEFAULT_NAMESPACE).
+///
+/// The original string is kept as-is, so `stone` and `minecraft:stone` are
+/// equal but are encoded differently.
+///
+/// When deserialized using
+/// [`from_slice_borrowed`](crate::Deserializable::from_slice_borrowed),
+/// the identifier borrows from the input.
+#[derive(Debug, Clone)]
+pub struct Identifier<'a> {
+    inner: Cow<'a, str>,
+}
+
+impl<'a> Identifier<'a> {
+    /// The namespace used when none is given.
+    pub const DEFAULT_NAMESPACE: &'static str = "minecraft";
+    /// The maximum length of an identifier, in bytes.
+    pub const MAX_LENGTH: usize = 32767;
+
+    /// Create a new [`Identifier`] from a string,
+    /// with or without a namespace.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is too long,
+    /// or contains characters not allowed in identifiers.
+    pub fn new(value: impl Into<Cow<'a, str>>) -> Result<Self, IdentifierError> {
+        let inner = value.into();
+        validate(&inner)?;
+        Ok(Self { inner })
+    }
+
+    /// Create a new [`Identifier`] from a string without validating it.
+    ///
+    /// Invalid identifiers are encoded as-is,
+    /// but will be rejected when decoded.
+    #[inline]
+    #[must_use]
+    pub const fn new_unchecked(value: &'a str) -> Self { Self { inner: Cow::Borrowed(value) } }
+
+    /// Create a new [`Identifier`] from a namespace and a path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the identifier is too long,
+    /// or contains characters not allowed in identifiers.
+    pub fn from_parts(namespace: &str, path: &str) -> Result<Self, IdentifierError> {
+        let mut inner = String::with_capacity(namespace.len() + path.len() + 1);
+        inner.push_str(namespace);
+        inner.push(':');
+        inner.push_str(path);
+        Self::new(inner)
+    }
+
+    /// Create a new [`Identifier`] in the
+    /// [`DEFAULT_NAMESPACE`](Self::DEFAULT_NAMESPACE).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the identifier is too long,
+    /// or if the path contains characters not allowed in paths.
+    #[inline]
+    pub fn minecraft(path: &str) -> Result<Self, IdentifierError> {
+        Self::from_parts(Self::DEFAULT_NAMESPACE, path)
+    }
+
+    /// Get the namespace of this identifier.
+    #[must_use]
+    pub fn namespace(&self) -> &str {
+        match self.inner.split_once(':') {
+            Some((namespace, _)) if !namespace.is_empty() => namespace,
+            _ => Self::DEFAULT_NAMESPACE,
+        }
+    }
+
+    /// Get the path of this identifier.
+    #[must_use]
+    pub fn path(&self) -> &str { self.inner.split_once(':').map_or(&self.inner, |(_, path)| path) }
+
+    /// Get the identifier as it was written,
+    /// which may not include a namespace.
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> &str { &self.inner }
+
+    /// Returns `true` if the identifier is in the
+    /// [`DEFAULT_NAMESPACE`](Self::DEFAULT_NAMESPACE).
+    #[inline]
+    #[must_use]
+    pub fn is_default_namespace(&self) -> bool { self.namespace() == Self::DEFAULT_NAMESPACE }
+
+    /// Returns `true` if this identifier borrows its contents.
+    #[inline]
+    #[must_use]
+    pub const fn is_borrowed(&self) -> bool { matches!(self.inner, Cow::Borrowed(_)) }
+
+    /// Convert this identifier into one that owns its contents.
+    #[must_use]
+    pub fn into_owned(self) -> Identifier<'static> {
+        Identifier { inner: Cow::Owned(self.inner.into_owned()) }
+    }
+
+    /// Borrow this identifier.
+    #[must_use]
+    pub fn borrowed(&self) -> Identifier<'_> { Identifier { inner: Cow::Borrowed(&self.inner) } }
+}
+
+impl Identifier<'_> {
+    /// Returns `true` if the character is allowed in a namespace.
+    #[must_use]
+    pub const fn is_namespace_char(c: char) -> bool {
+        matches!(c, 'a'..='z' | '0'..='9' | '_' | '-' | '.')
+    }
+
+    /// Returns `true` if the character is allowed in a path.
+    #[must_use]
+    pub const fn is_path_char(c: char) -> bool { Self::is_namespace_char(c) || c == '/' }
+}
+
+/// Check that an identifier is not too long and only uses allowed characters.
+fn validate(value: &str) -> Result<(), IdentifierError> {
+    if value.len() > Identifier::MAX_LENGTH {
+        return Err(IdentifierError::new(IdentifierErrorKind::TooLong(value.len())));
+    }
+
+    let (namespace, path) = value.split_once(':').unwrap_or(("", value));
+    if let Some(c) = namespace.chars().find(|&c| !Identifier::is_namespace_char(c)) {
+        return Err(IdentifierError::new(IdentifierErrorKind::InvalidNamespace(c)));
+    }
+    if let Some(c) = path.chars().find(|&c| !Identifier::is_path_char(c)) {
+        return Err(IdentifierError::new(IdentifierErrorKind::InvalidPath(c)));
+    }
+    Ok(())
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl PartialEq for Identifier<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.namespace() == other.namespace() && self.path() == other.path()
+    }
+}
+
+impl Eq for Identifier<'_> {}
+
+impl Hash for Identifier<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.namespace().hash(state);
+        self.path().hash(state);
+    }
+}
+
+/// Always includes the namespace, such as `minecraft:stone`.
+impl Display for Identifier<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.namespace(), self.path())
+    }
+}
+
+impl FromStr for Identifier<'static> {
+    type Err = IdentifierError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Identifier::new(String::from(s)) }
+}
+
+impl<'a> TryFrom<&'a str> for Identifier<'a> {
+    type Error = IdentifierError;
+
+    #[inline]
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> { Self::new(value) }
+}
+
+impl TryFrom<String> for Identifier<'static> {
+    type Error = IdentifierError;
+
+    #[inline]
+    fn try_from(value: String) -> Result<Self, Self::Error> { Self::new(value) }
+}
+
+impl<'a> From<Identifier<'a>> for Cow<'a, str> {
+    #[inline]
+    fn from(value: Identifier<'a>) -> Self { value.inner }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl McCodec for Identifier<'static> {
+    // VarInt length repr + up to `Identifier::MAX_LENGTH` bytes
+    const SERIALIZE_HINT: TypeSerializeHint =
+        TypeSerializeHint::Range { min: 1, max: Some(3 + Self::MAX_LENGTH) };
+
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        writer.write_identifier(self)
+    }
+
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        reader.read_identifier().map(Identifier::into_owned)
+    }
+}
+
+// SAFETY: The shape describes an opaque `Identifier`, built from a `Cow<str>`.
+unsafe impl<'a> Facet<'a> for Identifier<'a> {
+    const SHAPE: &'static Shape = &const {
+        /// Validate a `Cow<str>` or `String` and convert it into an identifier.
+        unsafe fn try_from(
+            target: OxPtrUninit,
+            source: &'static Shape,
+            ptr: PtrConst,
+        ) -> TryFromOutcome {
+            let inner = if source.id == <Cow<'_, str>>::SHAPE.id {
+                unsafe { ptr.read::<Cow<'static, str>>() }
+            } else if source.id == String::SHAPE.id {
+                Cow::Owned(unsafe { ptr.read::<String>() })
+            } else {
+                return TryFromOutcome::Unsupported;
+            };
+
+            match validate(&inner) {
+                Ok(()) => {
+                    unsafe { target.put(Identifier { inner }) };
+                    TryFromOutcome::Converted
+                }
+                Err(err) => TryFromOutcome::Failed(err.to_string().into()),
+            }
+        }
+
+        /// Drop a value in place.
+        unsafe fn drop_in_place(ox: OxPtrMut) {
+            unsafe { core::ptr::drop_in_place(ox.ptr().as_ptr::<Identifier>().cast_mut()) };
+        }
+
+        /// Clone a value into uninitialized memory.
+        unsafe fn clone_into(src: facet::OxPtrConst, dst: OxPtrMut) {
+            let value = unsafe { src.get::<Identifier<'static>>() }.clone();
+            unsafe { dst.ptr().as_uninit().put(value) };
+        }
+
+        ShapeBuilder::for_sized::<Self>("Identifier")
+            .ty(Type::User(UserType::Opaque))
+            .def(Def::Scalar)
+            .builder_shape(<Cow<'a, str>>::SHAPE)
+            .attributes(
+                &const {
+                    [Attr::new(
+                        Some("mc"),
+                        "codec",
+                        &const { CodecFn::of::<Identifier<'static>>() },
+                    )]
+                },
+            )
+            .vtable_indirect(
+                &const { VTableIndirect { try_from: Some(try_from), ..VTableIndirect::EMPTY } },
+            )
+            .type_ops_indirect(
+                &const {
+                    facet::TypeOpsIndirect {
+                        drop_in_place,
+                        default_in_place: None,
+                        clone_into: Some(clone_into),
+                        is_truthy: None,
+                    }
+                },
+            )
+            .build()
+    };
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// An error that occurred while validating an [`Identifier`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdentifierError {
+    kind: IdentifierErrorKind,
+}
+
+impl IdentifierError {
+    /// Create a new [`IdentifierError`].
+    #[inline]
+    #[must_use]
+    pub const fn new(kind: IdentifierErrorKind) -> Self { Self { kind } }
+
+    /// Get the kind of identifier error.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> &IdentifierErrorKind { &self.kind }
+}
+
+/// The type of identifier error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentifierErrorKind {
+    /// The identifier was longer than [`Identifier::MAX_LENGTH`] bytes.
+    TooLong(usize),
+    /// The namespace contained a character other than `[a-z0-9_.-]`.
+    InvalidNamespace(char),
+    /// The path contained a character other than `[a-z0-9_.-/]`.
+    InvalidPath(char),
+}
+
+impl Error for IdentifierError {}
+impl Display for IdentifierError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            IdentifierErrorKind::TooLong(len) => {
+                write!(
+                    f,
+                    "identifier is {len} bytes long, at most {} allowed",
+                    Identifier::MAX_LENGTH
+                )
+            }
+            IdentifierErrorKind::InvalidNamespace(c) => {
+                write!(f, "invalid character {c:?} in identifier namespace")
+            }
+            IdentifierErrorKind::InvalidPath(c) => {
+                write!(f, "invalid character {c:?} in identifier path")
+            }
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use facet::{Facet, Shape};
+
+use crate::{
+    codec::{McCodec, McReader, McWriter, codec_shape},
+    common::TypeSerializeHint,
+    deserialize::DeserializeError,
+    serialize::SerializeError,
+};
+
+/// A low-precision vector, used for entity velocities since `1.21.9`.
+///
+/// Each component is quantized to 15 bits and multiplied by a shared scale.
+/// Small scales are packed alongside the components,
+/// larger scales are followed by a `VarInt` continuation.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct LpVec3 {
+    /// The `x` component.
+    pub x: f64,
+    /// The `y` component.
+    pub y: f64,
+    /// The `z` component.
+    pub z: f64,
+}
+
+impl LpVec3 {
+    /// The largest absolute value a component can have.
+    pub const ABS_MAX_VALUE: f64 = 1.717_986_918_3E10;
+    /// The smallest absolute value that is not encoded as zero.
+    pub const ABS_MIN_VALUE: f64 = 3.051_944_088_384_301E-5;
+    const CONTINUATION_FLAG: u64 = 0b100;
+    const DATA_BITS_MASK: u64 = 0x7FFF;
+    const MAX_QUANTIZED_VALUE: f64 = 32766.0;
+    const SCALE_BITS_MASK: u64 = 0b11;
+    /// A vector with all components set to zero.
+    pub const ZERO: Self = Self::new(0.0, 0.0, 0.0);
+
+    /// Create a new [`LpVec3`].
+    #[inline]
+    #[must_use]
+    pub const fn new(x: f64, y: f64, z: f64) -> Self { Self { x, y, z } }
+
+    /// Returns the number of bytes this vector occupies when encoded.
+    #[must_use]
+    pub fn encoded_size(&self) -> usize {
+        let (x, y, z) = (Self::sanitize(self.x), Self::sanitize(self.y), Self::sanitize(self.z));
+        let max = x.abs().max(y.abs()).max(z.abs());
+        if max < Self::ABS_MIN_VALUE {
+            return 1;
+        }
+
+        let scale = Self::ceil(max);
+        if scale & Self::SCALE_BITS_MASK == scale {
+            6
+        } else {
+            let mut continuation = scale >> 2;
+            let mut size = 7;
+            while continuation >= 0x80 {
+                continuation >>= 7;
+                size += 1;
+            }
+            size
+        }
+    }
+
+    /// Replace `NaN` with zero and clamp to the encodable range.
+    fn sanitize(value: f64) -> f64 {
+        if value.is_nan() { 0.0 } else { value.clamp(-Self::ABS_MAX_VALUE, Self::ABS_MAX_VALUE) }
+    }
+
+    /// Round a positive value up to the nearest integer.
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss, reason = "Value is clamped")]
+    #[expect(clippy::cast_precision_loss, reason = "Value is clamped")]
+    fn ceil(value: f64) -> u64 {
+        let truncated = value as u64;
+        if (truncated as f64) < value { truncated + 1 } else { truncated }
+    }
+
+    /// Quantize a value in the range `-1.0..=1.0` into 15 bits.
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss, reason = "Value is clamped")]
+    fn pack(value: f64) -> u64 {
+        // Equivalent to Java's `Math.round`, as the value is never negative.
+        ((value * 0.5 + 0.5) * Self::MAX_QUANTIZED_VALUE + 0.5) as u64
+    }
+
+    /// Restore a 15-bit quantized value into the range `-1.0..=1.0`.
+    #[expect(clippy::cast_precision_loss, reason = "Value is masked to 15 bits")]
+    fn unpack(value: u64) -> f64 {
+        let value = ((value & Self::DATA_BITS_MASK) as f64).min(Self::MAX_QUANTIZED_VALUE);
+        value * 2.0 / Self::MAX_QUANTIZED_VALUE - 1.0
+    }
+}
+
+impl McCodec for LpVec3 {
+    // `[u8; 1]` if zero, `[u8; 6]` + optional VarInt scale
+    const SERIALIZE_HINT: TypeSerializeHint = TypeSerializeHint::Range { min: 1, max: Some(11) };
+
+    #[expect(clippy::cast_possible_truncation, reason = "Bytes are extracted individually")]
+    #[expect(clippy::cast_precision_loss, reason = "Value is clamped")]
+    fn encode(&self, writer: &mut McWriter<'_>) -> Result<(), SerializeError> {
+        let (x, y, z) = (Self::sanitize(self.x), Self::sanitize(self.y), Self::sanitize(self.z));
+        let max = x.abs().max(y.abs()).max(z.abs());
+        if max < Self::ABS_MIN_VALUE {
+            return writer.write_u8(0);
+        }
+
+        let scale = Self::ceil(max);
+        let continuation = scale & Self::SCALE_BITS_MASK != scale;
+        let header = if continuation {
+            scale & Self::SCALE_BITS_MASK | Self::CONTINUATION_FLAG
+        } else {
+            scale
+        };
+
+        let divisor = scale as f64;
+        let packed = header
+            | Self::pack(x / divisor) << 3
+            | Self::pack(y / divisor) << 18
+            | Self::pack(z / divisor) << 33;
+
+        writer.write_u8(packed as u8)?;
+        writer.write_u8((packed >> 8) as u8)?;
+        writer.write_u32((packed >> 16) as u32)?;
+        if continuation {
+            writer.write_var_u32((scale >> 2) as u32)?;
+        }
+
+        Ok(())
+    }
+
+    #[expect(clippy::cast_precision_loss, reason = "Scale is at most 34 bits")]
+    fn decode(reader: &mut McReader<'_>) -> Result<Self, DeserializeError> {
+        let first = u64::from(reader.read_u8()?);
+        if first == 0 {
+            return Ok(Self::ZERO);
+        }
+
+        let second = u64::from(reader.read_u8()?);
+        let packed = u64::from(reader.read_u32()?) << 16 | second << 8 | first;
+
+        let mut scale = first & Self::SCALE_BITS_MASK;
+        if first & Self::CONTINUATION_FLAG != 0 {
+            scale |= u64::from(reader.read_var_u32()?) << 2;
+        }
+
+        let scale = scale as f64;
+        Ok(Self::new(
+            Self::unpack(packed >> 3) * scale,
+            Self::unpack(packed >> 18) * scale,
+            Self::unpack(packed >> 33) * scale,
+        ))
+    }
+}
+
+// SAFETY: The shape is created by `codec_shape` for this type.
+unsafe impl Facet<'_> for LpVec3 {
+    const SHAPE: &'static Shape = &const { codec_shape::<Self>("LpVec3").build() };
+}
+
+impl From<[f64; 3]> for LpVec3 {
+    #[inline]
+    fn from([x, y, z]: [f64; 3]) -> Self { Self::new(x, y, z) }
+}
+
+impl From<LpVec3> for [f64; 3] {
+    #[inline]
+    fn from(value: LpVec3) -> Self { [value.x, value.y, value.z] }
+}
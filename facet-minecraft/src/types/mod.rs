@@ -0,0 +1,19 @@
+//! Common types with custom wire encodings.
+
+mod bit_set;
+pub use bit_set::BitSet;
+
+mod block_pos;
+pub use block_pos::{BlockPos, GlobalPos};
+
+mod id_or;
+pub use id_or::IdOr;
+
+mod id_set;
+pub use id_set::IdSet;
+
+mod identifier;
+pub use identifier::{Identifier, IdentifierError, IdentifierErrorKind};
+
+mod lp_vec3;
+pub use lp_vec3::LpVec3;
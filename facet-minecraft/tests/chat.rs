@@ -0,0 +1,216 @@
+//! Verify signed chat types and [`LastSeenTracker`] validation.
+
+mod common;
+
+use common::{decode, encode, roundtrip_codec as roundtrip};
+use facet::Facet;
+use facet_minecraft::{
+    chat::{
+        ChatErrorKind, ChatSession, LastSeenMessages, LastSeenTracker, LastSeenUpdate,
+        MessageSignature, PackedLastSeenMessages, PackedSignature, PlayerPublicKey,
+    },
+    codec::{McCodec, McReader, McWriter},
+    deserialize::DeserializeErrorKind,
+    packet::ProtocolVersion,
+    serialize::SerializeErrorKind,
+};
+use uuid::Uuid;
+
+/// A helper struct modelling the Chat packet.
+#[derive(Debug, PartialEq, Facet)]
+struct Chat {
+    message: String,
+    timestamp: i64,
+    salt: i64,
+    last_seen: LastSeenUpdate,
+}
+
+/// A helper struct modelling the end of the Player Chat packet.
+#[derive(Debug, PartialEq, Facet)]
+struct PlayerChat {
+    signature: MessageSignature,
+    previous: PackedLastSeenMessages,
+}
+
+/// Signatures with known checksums.
+fn signatures() -> [MessageSignature; 3] {
+    [
+        MessageSignature::new([0x01; 256]),
+        MessageSignature::new([0xFF; 256]),
+        MessageSignature::new(core::array::from_fn(|index| u8::try_from(index).unwrap())),
+    ]
+}
+
+#[test]
+fn checksums() {
+    let [a, b, c] = signatures();
+    assert_eq!(a.checksum(), 308_277_249);
+    assert_eq!(b.checksum(), -1_097_084_927);
+    assert_eq!(c.checksum(), -764_092_287);
+
+    assert_eq!(LastSeenMessages::new().checksum(), 1);
+    assert_eq!(LastSeenMessages(vec![a.clone()]).checksum(), 32);
+    assert_eq!(LastSeenMessages(vec![a.clone(), b.clone()]).checksum(), 225);
+    assert_eq!(LastSeenMessages(vec![a, b, c]).checksum(), 192);
+}
+
+#[test]
+fn wire() {
+    let [a, b, _] = signatures();
+
+    let bytes = roundtrip(&a);
+    assert_eq!(bytes, [0x01; 256]);
+
+    let packed: PackedSignature = PackedSignature::Id(2);
+    assert_eq!(roundtrip(&packed), [3]);
+    let packed = PackedLastSeenMessages(vec![PackedSignature::Id(0), PackedSignature::Inline(b)]);
+    let bytes = roundtrip(&packed);
+    assert_eq!(&bytes[..3], [2, 1, 0]);
+    assert_eq!(bytes.len(), 3 + 256);
+
+    // Bit `n` of the window is bit `n % 8` of byte `n / 8`
+    let update = LastSeenUpdate { offset: 3, acknowledged: 1 << 19 | 1 << 8 | 1, checksum: 225 };
+    assert_eq!(roundtrip(&update), [3, 0x01, 0x01, 0x08, 225]);
+    assert!(update.is_acknowledged(19) && update.is_acknowledged(8) && !update.is_acknowledged(7));
+
+    // The checksum was added in 1.21.5
+    let bytes = encode(&update, ProtocolVersion::V1_21_4);
+    assert_eq!(bytes, [3, 0x01, 0x01, 0x08]);
+    let decoded = decode::<LastSeenUpdate>(&bytes, ProtocolVersion::V1_21_4);
+    assert_eq!(decoded, LastSeenUpdate { checksum: LastSeenUpdate::IGNORE_CHECKSUM, ..update });
+
+    let chat = Chat { message: String::from("hi"), timestamp: 1, salt: -1, last_seen: update };
+    let bytes = facet_minecraft::to_vec(&chat).unwrap();
+    assert_eq!(bytes.len(), 3 + 8 + 8 + 5);
+    let (decoded, remaining) = facet_minecraft::from_slice::<Chat>(&bytes).unwrap();
+    assert_eq!(decoded, chat);
+    assert!(remaining.is_empty());
+
+    let [a, ..] = signatures();
+    let chat = PlayerChat { signature: a, previous: packed };
+    let bytes = facet_minecraft::to_vec(&chat).unwrap();
+    let (decoded, remaining) = facet_minecraft::from_slice::<PlayerChat>(&bytes).unwrap();
+    assert_eq!(decoded, chat);
+    assert!(remaining.is_empty());
+}
+
+#[test]
+fn session() {
+    let session = ChatSession {
+        session_id: Uuid::from_u128(0x0011_2233_4455_6677_8899_AABB_CCDD_EEFF),
+        public_key: PlayerPublicKey {
+            expires_at: 1_700_000_000_000,
+            key: vec![0x30; 4],
+            signature: vec![0xAB; 2],
+        },
+    };
+    assert!(!session.public_key.is_expired(1_700_000_000_000));
+    assert!(session.public_key.is_expired(1_700_000_000_001));
+
+    let bytes = facet_minecraft::to_vec(&session).unwrap();
+    assert_eq!(&bytes[16..24], 1_700_000_000_000i64.to_be_bytes());
+    assert_eq!(&bytes[24..], [4, 0x30, 0x30, 0x30, 0x30, 2, 0xAB, 0xAB]);
+    let (decoded, remaining) = facet_minecraft::from_slice::<ChatSession>(&bytes).unwrap();
+    assert_eq!(decoded, session);
+    assert!(remaining.is_empty());
+}
+
+#[test]
+fn tracker() {
+    let [a, b, c] = signatures();
+    let mut tracker = LastSeenTracker::new();
+    assert_eq!(tracker.tracked_count(), LastSeenMessages::WINDOW);
+
+    // Nothing seen yet
+    let seen = tracker.apply_update(&LastSeenUpdate { offset: 0, acknowledged: 0, checksum: 1 });
+    assert_eq!(seen.unwrap(), LastSeenMessages::new());
+
+    // Two messages are sent, a duplicate is not tracked
+    tracker.add_pending(a.clone());
+    tracker.add_pending(b.clone());
+    tracker.add_pending(b.clone());
+    assert_eq!(tracker.tracked_count(), 22);
+
+    let update = LastSeenUpdate { offset: 2, acknowledged: 0b11 << 18, checksum: 225 };
+    let seen = tracker.apply_update(&update).unwrap();
+    assert_eq!(seen.0, [a.clone(), b.clone()]);
+    assert_eq!(tracker.tracked_count(), LastSeenMessages::WINDOW);
+
+    // A third message shifts the window again
+    tracker.add_pending(c.clone());
+    let update = LastSeenUpdate { offset: 1, acknowledged: 0b111 << 17, checksum: 192 };
+    let seen = tracker.apply_update(&update).unwrap();
+    assert_eq!(seen.0, [a.clone(), b, c]);
+
+    // The same window can be acknowledged again, ignoring the checksum
+    let update = LastSeenUpdate { offset: 0, acknowledged: 0b111 << 17, checksum: 0 };
+    assert_eq!(tracker.apply_update(&update).unwrap().0.len(), 3);
+
+    // Pending messages may be ignored
+    let mut tracker = LastSeenTracker::new();
+    tracker.add_pending(a);
+    let update = LastSeenUpdate { offset: 1, acknowledged: 0, checksum: 1 };
+    assert_eq!(tracker.apply_update(&update).unwrap(), LastSeenMessages::new());
+    let update = LastSeenUpdate { offset: 0, acknowledged: 1 << 19, checksum: 0 };
+    let error = tracker.apply_update(&update).unwrap_err();
+    assert!(matches!(error.kind(), ChatErrorKind::UnknownMessage(19)));
+}
+
+#[test]
+fn errors() {
+    let [a, b, _] = signatures();
+    let mut tracker = LastSeenTracker::new();
+    tracker.add_pending(a.clone());
+
+    // The window cannot advance past the tracked messages
+    let update = LastSeenUpdate { offset: 2, acknowledged: 0, checksum: 0 };
+    let error = tracker.apply_update(&update).unwrap_err();
+    assert!(matches!(error.kind(), ChatErrorKind::InvalidOffset { offset: 2, max: 1 }));
+
+    // Messages must be tracked to be acknowledged
+    let update = LastSeenUpdate { offset: 0, acknowledged: 1, checksum: 0 };
+    let error = tracker.apply_update(&update).unwrap_err();
+    assert!(matches!(error.kind(), ChatErrorKind::UnknownMessage(0)));
+
+    let update = LastSeenUpdate { offset: 0, acknowledged: 1 << 20, checksum: 0 };
+    let error = tracker.apply_update(&update).unwrap_err();
+    assert!(matches!(error.kind(), ChatErrorKind::OutsideWindow));
+
+    // The checksum must match
+    let mut tracker = LastSeenTracker::new();
+    tracker.add_pending(a.clone());
+    let update = LastSeenUpdate { offset: 1, acknowledged: 1 << 19, checksum: 7 };
+    let error = tracker.apply_update(&update).unwrap_err();
+    assert!(matches!(error.kind(), ChatErrorKind::ChecksumMismatch { expected: 32, found: 7 }));
+
+    // Acknowledged messages cannot be ignored later
+    let mut tracker = LastSeenTracker::new();
+    tracker.add_pending(a);
+    let update = LastSeenUpdate { offset: 1, acknowledged: 1 << 19, checksum: 32 };
+    tracker.apply_update(&update).unwrap();
+    let update = LastSeenUpdate { offset: 0, acknowledged: 0, checksum: 0 };
+    let error = tracker.apply_update(&update).unwrap_err();
+    assert!(matches!(error.kind(), ChatErrorKind::IgnoredMessage(19)));
+
+    // Public keys are limited in length
+    let key = PlayerPublicKey { expires_at: 0, key: vec![0; 513], signature: Vec::new() };
+    let error = key.encode(&mut McWriter::new(&mut Vec::new())).unwrap_err();
+    let SerializeErrorKind::Chat(error) = error.kind() else { panic!() };
+    assert!(matches!(error.kind(), ChatErrorKind::KeyTooLong(513)));
+
+    let mut bytes = vec![0; 8];
+    bytes.extend([0, 0x81, 0x20]);
+    let error = PlayerPublicKey::decode(&mut McReader::new(&bytes)).unwrap_err();
+    let DeserializeErrorKind::Chat(error) = error.kind() else { panic!() };
+    assert!(matches!(error.kind(), ChatErrorKind::SignatureTooLong(4097)));
+
+    // At most a window of messages can be sent
+    let packed = PackedLastSeenMessages(vec![PackedSignature::Inline(b); 21]);
+    let error = packed.encode(&mut McWriter::new(&mut Vec::new())).unwrap_err();
+    let SerializeErrorKind::Chat(error) = error.kind() else { panic!() };
+    assert!(matches!(error.kind(), ChatErrorKind::TooManyEntries(21)));
+
+    let error = PackedLastSeenMessages::decode(&mut McReader::new(&[21])).unwrap_err();
+    let DeserializeErrorKind::Chat(error) = error.kind() else { panic!() };
+    assert!(matches!(error.kind(), ChatErrorKind::TooManyEntries(21)));
+}
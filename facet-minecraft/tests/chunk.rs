@@ -0,0 +1,291 @@
+//! Verify [`PalettedContainer`] encodings for every palette kind,
+//! and the structures of the Chunk Data and Update Light packet.
+
+mod common;
+
+use common::{decode, encode};
+use facet::Facet;
+use facet_minecraft::{
+    chunk::{
+        Biomes, BlockEntities, BlockEntity, BlockStates, ChunkData, ChunkDataAndLight,
+        ChunkErrorKind, ChunkSection, Heightmap, HeightmapKind, Heightmaps, LightArray, LightData,
+    },
+    codec::{McCodec, McReader, McWriter},
+    deserialize::DeserializeErrorKind,
+    nbt::NbtCompound,
+    packet::ProtocolVersion,
+    serialize::SerializeErrorKind,
+    types::BitSet,
+};
+
+/// A helper struct containing a [`PalettedContainer`] field.
+#[derive(Debug, PartialEq, Facet)]
+struct Section {
+    count: u16,
+    states: BlockStates,
+    biomes: Biomes,
+}
+
+#[test]
+fn single() {
+    let states = BlockStates::new(1);
+    assert_eq!(states.single(), Some(1));
+    assert_eq!(encode(&states, ProtocolVersion::LATEST), [0, 1]);
+    assert_eq!(encode(&states, ProtocolVersion::V1_21_4), [0, 1, 0]);
+
+    assert_eq!(decode::<BlockStates>(&[0, 1], ProtocolVersion::LATEST), states);
+    assert_eq!(decode::<BlockStates>(&[0, 1, 0], ProtocolVersion::V1_21_4), states);
+}
+
+#[test]
+fn indirect() {
+    let mut states = BlockStates::new(0);
+    assert_eq!(states.set(1, 9), 0);
+    assert_eq!(states.set(BlockStates::index(15, 15, 15), 9), 0);
+
+    // Block states use at least 4 bits, 16 entries to a long
+    let bytes = encode(&states, ProtocolVersion::LATEST);
+    assert_eq!(bytes[..3], [4, 2, 0]);
+    assert_eq!(bytes[3], 9);
+    assert_eq!(bytes.len(), 4 + 256 * 8);
+    assert_eq!(bytes[4..12], 0x10u64.to_be_bytes());
+    assert_eq!(bytes[bytes.len() - 8..], (1u64 << 60).to_be_bytes());
+    assert_eq!(decode::<BlockStates>(&bytes, ProtocolVersion::LATEST), states);
+
+    // Before 1.21.5 the data is prefixed by its length
+    let legacy = encode(&states, ProtocolVersion::V1_21_4);
+    assert_eq!(legacy[4..6], [0x80, 0x02]);
+    assert_eq!(legacy[6..], bytes[4..]);
+    assert_eq!(decode::<BlockStates>(&legacy, ProtocolVersion::V1_21_4), states);
+
+    // Biomes use a single bit for two values
+    let mut biomes = Biomes::new(3);
+    biomes.set(63, 4);
+    let bytes = encode(&biomes, ProtocolVersion::LATEST);
+    assert_eq!(bytes, [[1, 2, 3, 4].as_slice(), &(1u64 << 63).to_be_bytes()].concat());
+    assert_eq!(decode::<Biomes>(&bytes, ProtocolVersion::LATEST), biomes);
+}
+
+#[test]
+fn direct() {
+    let values = (0..4096).map(|value| value % 300).collect::<Vec<_>>();
+    let states = BlockStates::from_values(values.clone()).unwrap();
+    assert_eq!(states.palette().len(), 300);
+    assert!(states.iter().eq(values.iter().copied()));
+
+    // Without a registry size the smallest direct size is used
+    let bytes = encode(&states, ProtocolVersion::LATEST);
+    assert_eq!(bytes[0], 9);
+    assert_eq!(bytes.len(), 1 + 4096_usize.div_ceil(7) * 8);
+    assert_eq!(decode::<BlockStates>(&bytes, ProtocolVersion::LATEST), states);
+
+    let states = states.with_direct_bits(15);
+    let bytes = encode(&states, ProtocolVersion::V1_21_4);
+    assert_eq!(bytes[..3], [15, 0x80, 0x08]);
+    assert_eq!(bytes.len(), 3 + 1024 * 8);
+    let decoded = decode::<BlockStates>(&bytes, ProtocolVersion::V1_21_4);
+    assert_eq!(decoded.direct_bits(), Some(15));
+    assert_eq!(decoded, states);
+
+    // Biomes use a direct palette above 3 bits
+    let biomes = Biomes::from_values((0..64).collect()).unwrap();
+    let bytes = encode(&biomes, ProtocolVersion::LATEST);
+    assert_eq!(bytes[0], 6);
+    assert_eq!(decode::<Biomes>(&bytes, ProtocolVersion::LATEST), biomes);
+}
+
+#[test]
+fn codec() {
+    let mut section = Section { count: 1, states: BlockStates::new(0), biomes: Biomes::new(2) };
+    section.states.set(0, 1);
+
+    let bytes = facet_minecraft::to_vec(&section).unwrap();
+    assert_eq!(bytes[..3], [0, 1, 4]);
+    assert_eq!(bytes[bytes.len() - 2..], [0, 2]);
+
+    let (decoded, remaining) = facet_minecraft::from_slice::<Section>(&bytes).unwrap();
+    assert!(remaining.is_empty());
+    assert_eq!(decoded, section);
+
+    // Fields follow the version, which prefixes the longs before 1.21.5
+    let version = ProtocolVersion::V1_21_4;
+    let bytes = facet_minecraft::to_vec_versioned(&section, version.protocol()).unwrap();
+    assert_eq!(bytes[..4], [0, 1, 4, 2]);
+    assert_eq!(bytes[bytes.len() - 3..], [0, 2, 0]);
+
+    let (decoded, remaining) =
+        facet_minecraft::from_slice_versioned::<Section>(&bytes, version.protocol()).unwrap();
+    assert!(remaining.is_empty());
+    assert_eq!(decoded, section);
+}
+
+#[test]
+fn errors() {
+    assert!(BlockStates::from_values(vec![0; 64]).is_none());
+    assert_eq!(BlockStates::new(0).get(4096), None);
+
+    // A palette index past the end of the palette
+    let mut bytes = vec![4, 1, 7];
+    bytes.extend(std::iter::repeat_n(0x11, 256 * 8));
+    let error =
+        BlockStates::decode_for(&mut McReader::new(&bytes), ProtocolVersion::LATEST).unwrap_err();
+    let DeserializeErrorKind::Chunk(error) = error.kind() else { panic!() };
+    assert!(matches!(error.kind(), ChunkErrorKind::InvalidPaletteIndex { index: 1, len: 1 }));
+
+    // A data length not matching the container size
+    let error =
+        BlockStates::decode_for(&mut McReader::new(&[4, 1, 7, 3]), ProtocolVersion::V1_21_4)
+            .unwrap_err();
+    let DeserializeErrorKind::Chunk(error) = error.kind() else { panic!() };
+    assert!(matches!(error.kind(), ChunkErrorKind::InvalidDataLength { expected: 256, found: 3 }));
+    assert_eq!(error.to_string(), "expected 256 longs of data, found 3");
+
+    // A value too large for the registry size
+    let mut states = BlockStates::from_values((0..4096).collect()).unwrap().with_direct_bits(11);
+    let mut buffer = Vec::new();
+    let error =
+        states.encode_for(&mut McWriter::new(&mut buffer), ProtocolVersion::LATEST).unwrap_err();
+    let SerializeErrorKind::Chunk(error) = error.kind() else { panic!() };
+    assert!(matches!(error.kind(), ChunkErrorKind::ValueTooLarge { value: 4095, bits: 11 }));
+
+    states.fill(5);
+    assert_eq!(states.single(), Some(5));
+}
+
+/// Create a chunk with two sections, heightmaps, a block entity and light.
+fn chunk(version: ProtocolVersion) -> ChunkDataAndLight<'static> {
+    let mut section = ChunkSection { block_count: 2, ..ChunkSection::default() };
+    section.block_states.set(BlockStates::index(0, 0, 0), 1);
+    section.block_states.set(BlockStates::index(15, 15, 15), 2);
+    section.biomes.fill(7);
+
+    let mut data = NbtCompound::new();
+    data.insert("id", "minecraft:chest");
+    let entity = BlockEntity { x: 15, z: 1, y: -60, kind: 2, data: Some(data) };
+
+    let mut heightmap = Heightmap { kind: HeightmapKind::MotionBlocking, data: vec![0; 37] };
+    heightmap.data[0] = 5 | 7 << 9;
+
+    let mut light = LightArray::new(15);
+    light.set(1, 2, 3, 4);
+
+    let mut chunk = ChunkData {
+        heightmaps: Heightmaps(vec![heightmap]),
+        block_entities: BlockEntities(vec![entity, BlockEntity::default()]),
+        ..ChunkData::default()
+    };
+    chunk.set_sections(&[section, ChunkSection::default()], version).unwrap();
+
+    ChunkDataAndLight {
+        x: -2,
+        z: 3,
+        chunk,
+        light: LightData {
+            sky_mask: BitSet::from_iter([1]),
+            empty_sky_mask: BitSet::from_iter([0, 2]),
+            empty_block_mask: BitSet::from_iter([0, 1, 2]),
+            sky_light: Box::new([light]),
+            ..LightData::default()
+        },
+    }
+}
+
+#[test]
+fn chunk_data() {
+    let packet = chunk(ProtocolVersion::LATEST);
+    let body = facet_minecraft::to_vec(&packet).unwrap();
+    assert_eq!(body[..8], [0xFF, 0xFF, 0xFF, 0xFE, 0, 0, 0, 3]);
+
+    let (decoded, remaining) =
+        facet_minecraft::from_slice_borrowed::<ChunkDataAndLight>(&body).unwrap();
+    assert!(remaining.is_empty());
+    assert!(body.as_ptr_range().contains(&decoded.chunk.data.as_ptr()));
+    assert_eq!(decoded, packet);
+
+    let sections = decoded.chunk.sections(ProtocolVersion::LATEST).unwrap();
+    assert_eq!(sections.len(), 2);
+    assert_eq!(sections[0].block_states.get(4095), Some(2));
+    assert_eq!(sections[0].biomes.single(), Some(7));
+    assert!(sections[1].is_empty());
+
+    let heightmap = decoded.chunk.heightmaps.get(HeightmapKind::MotionBlocking).unwrap();
+    assert_eq!(heightmap.get(0, 0, 384), Some(5));
+    assert_eq!(heightmap.get(1, 0, 384), Some(7));
+    assert_eq!(decoded.chunk.heightmaps.get(HeightmapKind::WorldSurface), None);
+
+    let entity = &decoded.chunk.block_entities.0[0];
+    assert_eq!((entity.x, entity.z, entity.y, entity.packed_xz()), (15, 1, -60, 0xF1));
+    assert!(decoded.chunk.block_entities.0[1].data.is_none());
+
+    let light = &decoded.light;
+    assert_eq!(light.sky_mask.iter().collect::<Vec<_>>(), [1]);
+    assert_eq!(light.sky_light[0].get(1, 2, 3), 4);
+    assert_eq!(light.sky_light[0].get(0, 2, 3), 15);
+    assert_eq!(light.sky_light[0].as_bytes()[0], 0xFF);
+    assert!(light.sky_light[0].is_borrowed());
+}
+
+#[test]
+fn legacy_chunk_data() {
+    let version = ProtocolVersion::V1_21_4;
+    let packet = chunk(version);
+    let sections = packet.chunk.sections(version).unwrap();
+    assert!(packet.chunk.sections(ProtocolVersion::LATEST).is_err());
+
+    // Heightmaps were sent as NBT before 1.21.5
+    let mut buffer = Vec::new();
+    packet.chunk.heightmaps.encode_for(&mut McWriter::new(&mut buffer), version).unwrap();
+    assert_eq!(buffer[0], 0x0A);
+    let mut reader = McReader::new(&buffer);
+    let compound = NbtCompound::decode(&mut reader).unwrap();
+    assert!(compound.contains_key("MOTION_BLOCKING"));
+
+    let decoded = Heightmaps::decode(&mut McReader::new(&buffer)).unwrap();
+    assert_eq!(decoded, packet.chunk.heightmaps);
+
+    // The packet follows the version as well
+    let body = facet_minecraft::to_vec_versioned(&packet, version.protocol()).unwrap();
+    assert_eq!(body[8..8 + buffer.len()], buffer);
+    let (decoded, remaining) =
+        facet_minecraft::from_slice_versioned::<ChunkDataAndLight>(&body, version.protocol())
+            .unwrap();
+    assert!(remaining.is_empty());
+    assert_eq!(decoded, packet);
+
+    let mut section = Vec::new();
+    sections[0].encode_for(&mut McWriter::new(&mut section), version).unwrap();
+    assert_eq!(section[..9], [0, 2, 4, 3, 1, 0, 2, 0x80, 0x02]);
+    assert_eq!(
+        ChunkSection::decode_for(&mut McReader::new(&section), version).unwrap(),
+        sections[0]
+    );
+}
+
+#[test]
+fn chunk_errors() {
+    let error = Heightmaps::decode(&mut McReader::new(&[1, 6, 0])).unwrap_err();
+    let DeserializeErrorKind::Chunk(error) = error.kind() else { panic!() };
+    assert!(matches!(error.kind(), ChunkErrorKind::UnknownHeightmap(6)));
+
+    let mut compound = NbtCompound::new();
+    compound.insert("WORLD_SURFACE", 0i32);
+    let mut buffer = Vec::new();
+    compound.encode(&mut McWriter::new(&mut buffer)).unwrap();
+    let error = Heightmaps::decode(&mut McReader::new(&buffer)).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        r#"DeserializeError { kind: Chunk(ChunkError { kind: InvalidHeightmap("WORLD_SURFACE") }) }"#
+    );
+
+    let error = LightArray::decode(&mut McReader::new(&[3, 1, 2, 3])).unwrap_err();
+    let DeserializeErrorKind::Chunk(error) = error.kind() else { panic!() };
+    assert!(matches!(error.kind(), ChunkErrorKind::InvalidLightLength(3)));
+    let light = [&[0u8; 4][..], &[1, 3, 1, 2, 3], &[0]].concat();
+    assert!(facet_minecraft::from_slice::<LightData>(&light).is_err());
+    assert!(LightArray::from_bytes(&[0; 2047]).is_none());
+
+    let mut bits = BitSet::from_longs(vec![1, 0, 0]);
+    assert_eq!(bits.as_longs(), [1]);
+    bits.set(0, false);
+    assert!(bits.is_empty());
+}
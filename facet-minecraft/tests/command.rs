@@ -0,0 +1,219 @@
+//! Verify [`CommandGraph`] encodings and index validation.
+
+mod common;
+
+use common::{decode, encode, roundtrip_codec as roundtrip};
+use facet::Facet;
+use facet_minecraft::{
+    codec::{McCodec, McReader, McWriter},
+    command::{ArgumentParser, CommandErrorKind, CommandGraph, CommandNode, NodeKind, StringKind},
+    deserialize::DeserializeErrorKind,
+    packet::ProtocolVersion,
+    serialize::SerializeErrorKind,
+    types::Identifier,
+};
+
+/// A helper struct containing a [`CommandGraph`] field.
+#[derive(Debug, PartialEq, Facet)]
+struct Commands {
+    graph: CommandGraph,
+    id: u16,
+}
+
+#[test]
+fn graph() {
+    let mut graph = CommandGraph::new();
+    let root = graph.root();
+    let time = graph.add(root, CommandNode::literal("time"));
+    let set = graph.add(time, CommandNode::literal("set"));
+    graph.add(
+        set,
+        CommandNode::argument("time", ArgumentParser::Time { min: 0 }).executable().restricted(),
+    );
+    graph.add(root, CommandNode::literal("t").redirect(time));
+
+    assert_eq!(graph.nodes().len(), 5);
+    assert_eq!(graph.node(root).unwrap().children, [1, 4]);
+    assert_eq!(graph.node(set).and_then(CommandNode::name), Some("set"));
+    assert_eq!(graph.node(root).and_then(CommandNode::name), None);
+    assert_eq!(graph.node(5), None);
+
+    let expected = [
+        &[5][..],
+        // Root
+        &[0, 2, 1, 4],
+        // Literal `time`
+        &[1, 1, 2, 4],
+        b"time",
+        // Literal `set`
+        &[1, 1, 3, 3],
+        b"set",
+        // Argument `time`, executable and restricted
+        &[0x26, 0, 4],
+        b"time",
+        &[43, 0, 0, 0, 0],
+        // Literal `t`, redirecting to `time`
+        &[0x09, 0, 1, 1],
+        b"t",
+        // Root index
+        &[0],
+    ]
+    .concat();
+    assert_eq!(roundtrip(&graph), expected);
+
+    let commands = Commands { graph, id: 7 };
+    let bytes = facet_minecraft::to_vec(&commands).unwrap();
+    let (decoded, remaining) = facet_minecraft::from_slice::<Commands>(&bytes).unwrap();
+    assert_eq!(decoded, commands);
+    assert!(remaining.is_empty());
+}
+
+#[test]
+fn parsers() {
+    let parsers = [
+        ArgumentParser::Bool,
+        ArgumentParser::Float { min: Some(-1.5), max: None },
+        ArgumentParser::Double { min: None, max: Some(2.0) },
+        ArgumentParser::Integer { min: Some(0), max: Some(100) },
+        ArgumentParser::Long { min: None, max: None },
+        ArgumentParser::String(StringKind::GreedyPhrase),
+        ArgumentParser::Entity { single: true, players_only: true },
+        ArgumentParser::ScoreHolder { allow_multiple: true },
+        ArgumentParser::Time { min: 20 },
+        ArgumentParser::Resource { registry: Identifier::new_unchecked("minecraft:mob_effect") },
+        ArgumentParser::ResourceOrTag { registry: Identifier::new_unchecked("minecraft:biome") },
+        ArgumentParser::ResourceSelector { registry: Identifier::new_unchecked("minecraft:test") },
+        ArgumentParser::BlockPos,
+        ArgumentParser::HexColor,
+        ArgumentParser::Dialog,
+        ArgumentParser::Uuid,
+    ];
+
+    let mut graph = CommandGraph::new();
+    for (index, parser) in parsers.into_iter().enumerate() {
+        let node = CommandNode::argument(format!("arg{index}"), parser)
+            .suggestions(Identifier::new_unchecked("minecraft:ask_server"));
+        graph.add(graph.root(), node);
+    }
+    roundtrip(&graph);
+
+    let NodeKind::Argument { suggestions, .. } = &graph.node(1).unwrap().kind else { panic!() };
+    assert_eq!(suggestions.as_ref().map(Identifier::as_str), Some("minecraft:ask_server"));
+
+    // Suggestions are ignored for literals
+    let literal = CommandNode::literal("a").suggestions(Identifier::new_unchecked("minecraft:x"));
+    assert_eq!(literal, CommandNode::literal("a"));
+
+    // Argument properties
+    let mut graph = CommandGraph::new();
+    graph.add(0, CommandNode::argument("n", ArgumentParser::Integer { min: Some(1), max: None }));
+    graph.add(
+        0,
+        CommandNode::argument("e", ArgumentParser::Entity { single: false, players_only: true }),
+    );
+    let bytes = roundtrip(&graph);
+    assert_eq!(&bytes[8..15], [b'n', 3, 0x01, 0, 0, 0, 1]);
+    assert_eq!(&bytes[18..], [b'e', 6, 0x02, 0]);
+}
+
+#[test]
+fn versions() {
+    let latest = ProtocolVersion::LATEST;
+    let legacy = ProtocolVersion::V1_21_5;
+    assert_eq!(ArgumentParser::Color.id(legacy), Some(16));
+    assert_eq!(ArgumentParser::Component.id(legacy), Some(17));
+    assert_eq!(ArgumentParser::Uuid.id(legacy), Some(54));
+    assert_eq!(ArgumentParser::HexColor.id(legacy), None);
+    assert_eq!(ArgumentParser::HexColor.id(latest), Some(17));
+    assert_eq!(ArgumentParser::Component.id(latest), Some(18));
+    assert_eq!(ArgumentParser::Dialog.id(latest), Some(55));
+    assert_eq!(ArgumentParser::Uuid.id(latest), Some(56));
+    assert_eq!(ArgumentParser::table(legacy).len(), 55);
+    assert_eq!(ArgumentParser::table(latest).len(), 57);
+
+    // The restricted flag is left out before 1.21.6
+    let mut graph = CommandGraph::new();
+    let node = CommandNode::argument("t", ArgumentParser::Time { min: 0 }).restricted();
+    graph.add(graph.root(), node);
+    let bytes = encode(&graph, legacy);
+    assert_eq!(bytes[4..], [0x02, 0, 1, b't', 42, 0, 0, 0, 0, 0]);
+
+    let decoded = decode::<CommandGraph>(&bytes, legacy);
+    assert!(!decoded.node(1).unwrap().restricted);
+    let error = CommandGraph::decode_for(&mut McReader::new(&[1, 0x20, 0, 0]), legacy).unwrap_err();
+    let DeserializeErrorKind::Command(error) = error.kind() else { panic!() };
+    assert!(matches!(error.kind(), CommandErrorKind::InvalidFlags(0x20)));
+
+    // Fields follow the version
+    let commands = Commands { graph, id: 7 };
+    let versioned = facet_minecraft::to_vec_versioned(&commands, legacy.protocol()).unwrap();
+    assert_eq!(versioned[..bytes.len()], bytes);
+    let (decoded, _) =
+        facet_minecraft::from_slice_versioned::<Commands>(&versioned, legacy.protocol()).unwrap();
+    assert_eq!(decoded.graph.node(1).unwrap().kind, commands.graph.node(1).unwrap().kind);
+
+    // Parsers added in 1.21.6 cannot be sent to older clients
+    let mut graph = CommandGraph::new();
+    graph.add(graph.root(), CommandNode::argument("c", ArgumentParser::HexColor));
+    let error = graph.encode_for(&mut McWriter::new(&mut Vec::new()), legacy).unwrap_err();
+    let SerializeErrorKind::Command(error) = error.kind() else { panic!() };
+    assert!(matches!(
+        error.kind(),
+        CommandErrorKind::UnsupportedParser { parser: "minecraft:hex_color", .. }
+    ));
+
+    // Parser IDs before 1.21.4 are not known
+    let old = ProtocolVersion::V1_21_2;
+    assert!(ArgumentParser::table(old).is_empty());
+    assert_eq!(ArgumentParser::Bool.id(old), None);
+    assert_eq!(ArgumentParser::table(ProtocolVersion::V1_21_4).len(), 55);
+    let error =
+        CommandGraph::new().encode_for(&mut McWriter::new(&mut Vec::new()), old).unwrap_err();
+    let SerializeErrorKind::Command(error) = error.kind() else { panic!() };
+    assert!(
+        matches!(error.kind(), CommandErrorKind::UnsupportedVersion(version) if *version == old)
+    );
+    let error = CommandGraph::decode_for(&mut McReader::new(&[1, 0, 0]), old).unwrap_err();
+    let DeserializeErrorKind::Command(error) = error.kind() else { panic!() };
+    assert!(
+        matches!(error.kind(), CommandErrorKind::UnsupportedVersion(version) if *version == old)
+    );
+}
+
+#[test]
+fn errors() {
+    let check = |bytes: &[u8], expected: fn(&CommandErrorKind) -> bool| {
+        let error = CommandGraph::decode(&mut McReader::new(bytes)).unwrap_err();
+        let DeserializeErrorKind::Command(error) = error.kind() else { panic!() };
+        assert!(expected(error.kind()), "{:?}", error.kind());
+    };
+
+    // Dangling children and redirects
+    check(&[1, 0, 1, 1, 0], |kind| {
+        matches!(kind, CommandErrorKind::InvalidChild { node: 0, child: 1 })
+    });
+    check(&[2, 0, 1, 1, 0x09, 0, 5, 1, b'a', 0], |kind| {
+        matches!(kind, CommandErrorKind::InvalidRedirect { node: 1, redirect: 5 })
+    });
+    check(&[1, 0, 0, 3], |kind| matches!(kind, CommandErrorKind::InvalidRoot(3)));
+    check(&[0, 0], |kind| matches!(kind, CommandErrorKind::InvalidRoot(0)));
+    assert!(CommandGraph::from_parts(vec![CommandNode::root()], 0).is_ok());
+
+    // Flags, parsers and properties
+    check(&[1, 0x03, 0, 0], |kind| matches!(kind, CommandErrorKind::InvalidFlags(0x03)));
+    check(&[1, 0x40, 0, 0], |kind| matches!(kind, CommandErrorKind::InvalidFlags(0x40)));
+    check(&[1, 0x11, 0, 1, b'a', 0], |kind| matches!(kind, CommandErrorKind::InvalidFlags(0x11)));
+    check(&[1, 0x02, 0, 1, b'a', 99, 0], |kind| {
+        matches!(kind, CommandErrorKind::UnknownParser(99))
+    });
+    check(&[1, 0x02, 0, 1, b'a', 5, 3, 0], |kind| {
+        matches!(kind, CommandErrorKind::InvalidStringKind(3))
+    });
+
+    // Graphs are validated before encoding
+    let mut graph = CommandGraph::new();
+    graph.node_mut(0).unwrap().children.push(1);
+    let error = graph.encode(&mut McWriter::new(&mut Vec::new())).unwrap_err();
+    let SerializeErrorKind::Command(error) = error.kind() else { panic!() };
+    assert!(matches!(error.kind(), CommandErrorKind::InvalidChild { node: 0, child: 1 }));
+}
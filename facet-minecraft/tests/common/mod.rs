@@ -0,0 +1,40 @@
+//! Encoding helpers shared between tests.
+#![allow(dead_code, reason = "Not every test uses every helper")]
+
+use facet::Facet;
+use facet_minecraft::{
+    codec::{McCodec, McReader, McWriter},
+    packet::ProtocolVersion,
+};
+
+/// Serialize a value and deserialize it again, checking all input is used.
+pub(crate) fn roundtrip<T: for<'a> Facet<'a> + PartialEq + core::fmt::Debug>(value: &T) -> Vec<u8> {
+    let bytes = facet_minecraft::to_vec(value).unwrap();
+    let (decoded, remaining) = facet_minecraft::from_slice::<T>(&bytes).unwrap();
+    assert_eq!(&decoded, value);
+    assert!(remaining.is_empty());
+    bytes
+}
+
+/// Encode a value with its [`McCodec`] and check it decodes to the same value.
+pub(crate) fn roundtrip_codec<T: McCodec + PartialEq + core::fmt::Debug>(value: &T) -> Vec<u8> {
+    let bytes = encode(value, ProtocolVersion::LATEST);
+    assert_eq!(&decode::<T>(&bytes, ProtocolVersion::LATEST), value);
+    bytes
+}
+
+/// Encode a value with its [`McCodec`] for a version.
+pub(crate) fn encode<T: McCodec>(value: &T, version: ProtocolVersion) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    value.encode(&mut McWriter::new(&mut buffer).with_version(version)).unwrap();
+    buffer
+}
+
+/// Decode a value with its [`McCodec`] for a version,
+/// checking all input is used.
+pub(crate) fn decode<T: McCodec>(bytes: &[u8], version: ProtocolVersion) -> T {
+    let mut reader = McReader::new(bytes).with_version(version);
+    let value = T::decode(&mut reader).unwrap();
+    assert!(reader.remaining().is_empty());
+    value
+}
@@ -0,0 +1,207 @@
+//! Verify the [`Connection`] state machine by running both sides in memory.
+
+use facet::Facet;
+use facet_minecraft::{
+    connection::{Action, Connection, ConnectionErrorKind, Transition},
+    packet::{Direction, Packet, PacketSet, ProtocolState},
+    packet_set,
+};
+
+/// Define a packet causing an optional transition.
+macro_rules! packet {
+    ($name:ident { $($field:ident: $ty:ty),* } = $id:literal, $state:ident, $direction:ident $(, |$this:ident| $transition:expr)?) => {
+        #[derive(Debug, Clone, PartialEq, Facet)]
+        struct $name { $($field: $ty),* }
+
+        impl Packet for $name {
+            const DIRECTION: Direction = Direction::$direction;
+            const ID: u32 = $id;
+            const STATE: ProtocolState = ProtocolState::$state;
+
+            $(
+                fn transition(&self) -> Option<Transition> {
+                    let $this = self;
+                    $transition
+                }
+            )?
+        }
+    };
+}
+
+packet!(Intention { intent: u8 } = 0x00, Handshake, Serverbound, |this| match this.intent {
+    1 => Some(Transition::Status),
+    _ => Some(Transition::Login),
+});
+packet!(StatusRequest {} = 0x00, Status, Serverbound);
+packet!(Hello { name: String } = 0x00, Login, Serverbound);
+packet!(EncryptionResponse { secret: u8 } = 0x01, Login, Serverbound, |_this| Some(
+    Transition::EnableEncryption
+));
+packet!(SetCompression { threshold: i32 } = 0x03, Login, Clientbound, |this| Some(
+    Transition::SetCompression(this.threshold)
+));
+packet!(LoginSuccess {} = 0x02, Login, Clientbound, |_this| Some(Transition::LoginSuccess));
+packet!(LoginAcknowledged {} = 0x03, Login, Serverbound, |_this| Some(
+    Transition::LoginAcknowledged
+));
+packet!(FinishConfiguration {} = 0x03, Configuration, Clientbound, |_this| Some(
+    Transition::FinishConfiguration
+));
+packet!(AcknowledgeFinish {} = 0x03, Configuration, Serverbound, |_this| Some(
+    Transition::AcknowledgeFinishConfiguration
+));
+packet!(KeepAlive { id: u64 } = 0x1A, Play, Serverbound);
+packet!(StartConfiguration {} = 0x6F, Play, Clientbound, |_this| Some(
+    Transition::StartConfiguration
+));
+packet!(ConfigurationAcknowledged {} = 0x0E, Play, Serverbound, |_this| Some(
+    Transition::ConfigurationAcknowledged
+));
+
+packet_set! {
+    /// Login packets received by the server.
+    #[derive(Debug)]
+    pub enum LoginServerbound(Login, Serverbound) {
+        /// Start logging in.
+        Hello(Hello),
+        /// Respond to the encryption request.
+        EncryptionResponse(EncryptionResponse),
+        /// Acknowledge the login success.
+        LoginAcknowledged(LoginAcknowledged),
+    }
+}
+
+/// Send a packet from one side and receive it on the other.
+fn transfer<P: Packet>(
+    from: &mut Connection,
+    to: &mut Connection,
+    packet: &P,
+) -> (Option<Action>, Option<Action>) {
+    (from.send(packet).unwrap(), to.receive_packet(packet).unwrap())
+}
+
+#[test]
+fn login() {
+    let mut client = Connection::client();
+    let mut server = Connection::server();
+    assert_eq!(
+        (client.outgoing(), server.outgoing()),
+        (Direction::Serverbound, Direction::Clientbound)
+    );
+
+    transfer(&mut client, &mut server, &Intention { intent: 2 });
+    assert_eq!((client.state(), server.state()), (ProtocolState::Login, ProtocolState::Login));
+
+    // The server receives a decoded packet set
+    let hello = LoginServerbound::decode_packet(0x00, b"\x05Steve").unwrap();
+    client.send(&Hello { name: String::from("Steve") }).unwrap();
+    assert_eq!(server.receive(&hello).unwrap(), None);
+
+    let actions = transfer(&mut client, &mut server, &EncryptionResponse { secret: 0 });
+    assert_eq!(actions, (Some(Action::EnableEncryption), Some(Action::EnableEncryption)));
+    assert!(client.is_encrypted() && server.is_encrypted());
+
+    let actions = transfer(&mut server, &mut client, &SetCompression { threshold: 256 });
+    let action = Some(Action::SetCompression(Some(256)));
+    assert_eq!(actions, (action, action));
+    assert_eq!(client.compression(), Some(256));
+
+    // Login Success waits for the client to acknowledge it
+    transfer(&mut server, &mut client, &LoginSuccess {});
+    assert_eq!(server.pending(), Some(Transition::LoginSuccess));
+    assert_eq!(client.state(), ProtocolState::Login);
+    transfer(&mut client, &mut server, &LoginAcknowledged {});
+    assert_eq!(server.pending(), None);
+    assert_eq!(
+        (client.state(), server.state()),
+        (ProtocolState::Configuration, ProtocolState::Configuration)
+    );
+
+    transfer(&mut server, &mut client, &FinishConfiguration {});
+    transfer(&mut client, &mut server, &AcknowledgeFinish {});
+    assert_eq!((client.state(), server.state()), (ProtocolState::Play, ProtocolState::Play));
+
+    // The client may keep sending play packets until it acknowledges
+    transfer(&mut server, &mut client, &StartConfiguration {});
+    transfer(&mut client, &mut server, &KeepAlive { id: 1 });
+    transfer(&mut client, &mut server, &ConfigurationAcknowledged {});
+    assert_eq!(
+        (client.state(), server.state()),
+        (ProtocolState::Configuration, ProtocolState::Configuration)
+    );
+}
+
+#[test]
+fn status() {
+    let mut client = Connection::client();
+    let mut server = Connection::server();
+    transfer(&mut client, &mut server, &Intention { intent: 1 });
+    assert_eq!(server.state(), ProtocolState::Status);
+    transfer(&mut client, &mut server, &StatusRequest {});
+
+    let error = client.send(&Hello { name: String::new() }).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ConnectionErrorKind::UnexpectedPacket {
+            current: ProtocolState::Status,
+            state: ProtocolState::Login,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn errors() {
+    // Packets in the wrong direction
+    let mut server = Connection::server_in(ProtocolState::Login);
+    let error = server.send(&Hello { name: String::new() }).unwrap_err();
+    assert!(matches!(error.kind(), ConnectionErrorKind::UnexpectedPacket { id: 0x00, .. }));
+    assert_eq!(error.to_string(), "unexpected serverbound login packet 0x00 in login state");
+
+    // No packets may be sent while waiting for an acknowledgement
+    server.send(&LoginSuccess {}).unwrap();
+    let error = server.send(&SetCompression { threshold: -1 }).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ConnectionErrorKind::AwaitingAcknowledgement(Transition::LoginSuccess)
+    ));
+
+    // Acknowledgements must follow the transition they acknowledge
+    let mut client = Connection::client_in(ProtocolState::Login);
+    let error = client.send(&LoginAcknowledged {}).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ConnectionErrorKind::UnexpectedAcknowledgement(Transition::LoginAcknowledged)
+    ));
+    assert_eq!(client.state(), ProtocolState::Login);
+
+    // Encryption can only be enabled once
+    assert_eq!(
+        client.send(&EncryptionResponse { secret: 0 }).unwrap(),
+        Some(Action::EnableEncryption)
+    );
+    let error = client.send(&EncryptionResponse { secret: 0 }).unwrap_err();
+    assert!(matches!(error.kind(), ConnectionErrorKind::AlreadyEncrypted));
+
+    // A negative threshold disables compression
+    assert_eq!(
+        client.receive_packet(&SetCompression { threshold: -1 }).unwrap(),
+        Some(Action::SetCompression(None))
+    );
+}
+
+#[test]
+fn invalid_transition() {
+    packet!(Invalid {} = 0x10, Play, Serverbound, |_this| Some(Transition::LoginSuccess));
+
+    let mut client = Connection::client_in(ProtocolState::Play);
+    let error = client.send(&Invalid {}).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ConnectionErrorKind::InvalidTransition {
+            transition: Transition::LoginSuccess,
+            state: ProtocolState::Play,
+            direction: Direction::Serverbound,
+        }
+    ));
+}
@@ -3,7 +3,11 @@
 use std::io::Cursor;
 
 use facet_format::DeserializeError as FDError;
-use facet_minecraft::{Deserializable, deserialize::DeserializeError};
+use facet_minecraft::{
+    Deserializable,
+    deserialize::DeserializeError,
+    nbt::{NbtCompound, NbtValue},
+};
 
 #[repr(transparent)]
 struct TestCursor(Cursor<&'static [u8]>);
@@ -234,3 +238,17 @@ fn f64() {
     assert!((cursor.read::<f64>().unwrap() - 6.0f64).abs() < f64::EPSILON);
     assert!((cursor.read::<f64>().unwrap() - 7.0f64).abs() < f64::EPSILON);
 }
+
+#[test]
+fn nbt() {
+    let mut compound = NbtCompound::new();
+    compound.insert("name", "Bananrama");
+    compound.insert("count", 3i32);
+    let value = NbtValue::Compound(compound);
+
+    // Values read from NBT request more input until they are complete
+    let bytes = facet_minecraft::to_vec(&value).unwrap().leak();
+    let mut cursor = TestCursor(Cursor::new(bytes));
+    assert_eq!(cursor.read::<NbtValue>().unwrap(), value);
+    assert!(cursor.read::<NbtValue>().is_err());
+}
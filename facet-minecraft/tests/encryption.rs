@@ -0,0 +1,133 @@
+//! Verify [`Cfb8`] against known vectors and [`CipherStream`] encryption.
+#![allow(clippy::std_instead_of_alloc, reason = "`std` example")]
+
+use std::io::{BufReader, Cursor, Read, Write};
+
+use facet_minecraft::encryption::{Cfb8, CipherStream};
+
+/// The shared secret used as both the key and the IV.
+const SECRET: [u8; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+/// `Hello, Minecraft!` encrypted using [`SECRET`].
+const CIPHERTEXT: [u8; 17] = [
+    0x42, 0xEA, 0x5E, 0xD4, 0xDA, 0xF8, 0x64, 0xEA, 0xE7, 0xEF, 0x5C, 0x17, 0x72, 0x83, 0x10, 0xD5,
+    0xED,
+];
+
+/// A writer that accepts at most three bytes at a time.
+struct Trickle(Vec<u8>);
+
+impl Write for Trickle {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let len = buf.len().min(3);
+        self.0.extend_from_slice(&buf[..len]);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> { Ok(()) }
+}
+
+#[test]
+fn vectors() {
+    // NIST SP 800-38A, F.3.7 CFB8-AES128.Encrypt
+    let key = [
+        0x2B, 0x7E, 0x15, 0x16, 0x28, 0xAE, 0xD2, 0xA6, 0xAB, 0xF7, 0x15, 0x88, 0x09, 0xCF, 0x4F,
+        0x3C,
+    ];
+    let plaintext = [
+        0x6B, 0xC1, 0xBE, 0xE2, 0x2E, 0x40, 0x9F, 0x96, 0xE9, 0x3D, 0x7E, 0x11, 0x73, 0x93, 0x17,
+        0x2A, 0xAE, 0x2D,
+    ];
+    let ciphertext = [
+        0x3B, 0x79, 0x42, 0x4C, 0x9C, 0x0D, 0xD4, 0x36, 0xBA, 0xCE, 0x9E, 0x0E, 0xD4, 0x58, 0x6A,
+        0x4F, 0x32, 0xB9,
+    ];
+
+    let mut data = plaintext;
+    Cfb8::new(&key, &SECRET).encrypt(&mut data);
+    assert_eq!(data, ciphertext);
+    Cfb8::new(&key, &SECRET).decrypt(&mut data);
+    assert_eq!(data, plaintext);
+
+    // Key and IV are the same
+    let mut data = *b"Hello, Minecraft!";
+    Cfb8::from_secret(&SECRET).encrypt(&mut data);
+    assert_eq!(data, CIPHERTEXT);
+
+    // Split across calls
+    let mut data = CIPHERTEXT;
+    let mut cipher = Cfb8::from_secret(&SECRET);
+    for chunk in data.chunks_mut(5) {
+        cipher.decrypt(chunk);
+    }
+    assert_eq!(&data, b"Hello, Minecraft!");
+}
+
+#[test]
+fn stream() {
+    let mut stream = CipherStream::encrypted(Vec::new(), &SECRET);
+    stream.write_all(b"Hello, Minecraft!").unwrap();
+    assert_eq!(stream.get_ref(), &CIPHERTEXT);
+
+    // Partial writes only advance the cipher by the bytes written
+    let mut stream = CipherStream::encrypted(Trickle(Vec::new()), &SECRET);
+    stream.write_all(b"Hello, Minecraft!").unwrap();
+    assert_eq!(stream.into_inner().0, CIPHERTEXT);
+
+    let mut stream = CipherStream::encrypted(CIPHERTEXT.as_slice(), &SECRET);
+    let mut data = String::new();
+    stream.read_to_string(&mut data).unwrap();
+    assert_eq!(data, "Hello, Minecraft!");
+}
+
+#[test]
+fn mid_stream() {
+    let mut stream = CipherStream::new(Vec::new());
+    facet_minecraft::to_writer(&"plain", &mut stream).unwrap();
+    assert!(!stream.is_enabled());
+    stream.enable(&SECRET);
+    facet_minecraft::to_writer(&"hidden", &mut stream).unwrap();
+
+    let buffer = stream.into_inner();
+    assert_eq!(&buffer[..6], b"\x05plain");
+    let mut hidden = buffer[6..].to_vec();
+    Cfb8::from_secret(&SECRET).decrypt(&mut hidden);
+    assert_eq!(hidden, b"\x06hidden");
+
+    // A `u8` in plaintext, followed by an encrypted `u32` and `u8`
+    let mut buffer = vec![0x01, 0x00, 0x00, 0x01, 0x00, 0x02];
+    Cfb8::from_secret(&SECRET).encrypt(&mut buffer[1..]);
+
+    // The inner reader reads ahead, but bytes are only decrypted when used
+    let mut stream = CipherStream::new(BufReader::new(Cursor::new(buffer.clone())));
+    assert_eq!(facet_minecraft::from_reader::<u8, _>(&mut stream).unwrap(), 1);
+    stream.enable(&SECRET);
+    assert_eq!(facet_minecraft::from_reader::<u32, _>(&mut stream).unwrap(), 256);
+    assert_eq!(facet_minecraft::from_reader::<u8, _>(&mut stream).unwrap(), 2);
+
+    // Bytes read ahead of the stream are decrypted when enabling
+    let mut stream = CipherStream::new(Cursor::new(buffer));
+    let mut ahead = [0; 3];
+    stream.read_exact(&mut ahead).unwrap();
+    stream.enable_with_buffered(&SECRET, &mut ahead[1..]);
+    assert_eq!(ahead, [0x01, 0x00, 0x00]);
+    let mut rest = Vec::new();
+    stream.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, [0x01, 0x00, 0x02]);
+}
+
+#[test]
+#[cfg(feature = "futures-lite")]
+fn future() {
+    futures_lite::future::block_on(async {
+        let mut stream = CipherStream::encrypted(Vec::new(), &SECRET);
+        facet_minecraft::to_async_writer(&"Hello, Minecraft!", &mut stream).await.unwrap();
+        let mut buffer = stream.into_inner();
+        Cfb8::from_secret(&SECRET).decrypt(&mut buffer);
+        assert_eq!(buffer, b"\x11Hello, Minecraft!");
+
+        let buffer = [0x42, 0xEA, 0x5E, 0xD4];
+        let mut stream = CipherStream::encrypted(buffer.as_slice(), &SECRET);
+        let value = facet_minecraft::from_async_reader::<u32, _>(&mut stream).await.unwrap();
+        assert_eq!(value.to_be_bytes(), *b"Hell");
+    });
+}
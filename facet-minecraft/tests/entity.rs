@@ -0,0 +1,522 @@
+//! Verify [`EntityMetadata`] encodings across protocol versions.
+
+mod common;
+
+use common::{decode, encode};
+use facet::Facet;
+use facet_minecraft::{
+    codec::{McCodec, McReader, McWriter},
+    deserialize::DeserializeErrorKind,
+    entity::{
+        EntityErrorKind, EntityMetadata, Facing, MetadataType, MetadataValue, Particle,
+        ParticleKind, ParticleOptions, Pose, PositionSource, VillagerData,
+    },
+    item::{DataComponentKind, ItemStack, OptionalItemStack},
+    nbt::{NbtCompound, NbtValue},
+    packet::ProtocolVersion,
+    profile::{
+        GameProfile, PlayerModel, ProfileProperty, ProfileSource, ResolvableProfile, SkinPatch,
+    },
+    serialize::SerializeErrorKind,
+    text::TextComponent,
+    types::{BlockPos, GlobalPos, Identifier},
+};
+use uuid::Uuid;
+
+/// A helper struct containing an [`EntityMetadata`] field.
+#[derive(Debug, PartialEq, Facet)]
+struct Entity {
+    id: u16,
+    metadata: EntityMetadata,
+}
+
+#[test]
+fn values() {
+    let values = [
+        MetadataValue::Byte(-1),
+        MetadataValue::VarInt(-5),
+        MetadataValue::VarLong(1 << 40),
+        MetadataValue::Float(0.5),
+        MetadataValue::String(String::from("name")),
+        MetadataValue::Text(TextComponent::text("Steve")),
+        MetadataValue::OptionalText(Some(TextComponent::text("Alex"))),
+        MetadataValue::OptionalText(None),
+        MetadataValue::Slot(OptionalItemStack::from(ItemStack::new(1, 2))),
+        MetadataValue::Slot(OptionalItemStack::EMPTY),
+        MetadataValue::Boolean(true),
+        MetadataValue::Rotations([0.0, 90.0, -45.0]),
+        MetadataValue::Position(BlockPos::new(1, -64, -1)),
+        MetadataValue::OptionalPosition(Some(BlockPos::new(0, 319, 0))),
+        MetadataValue::OptionalPosition(None),
+        MetadataValue::Direction(Facing::West),
+        MetadataValue::OptionalUuid(Some(Uuid::from_u128(0x1234))),
+        MetadataValue::OptionalUuid(None),
+        MetadataValue::BlockState(1),
+        MetadataValue::OptionalBlockState(Some(9)),
+        MetadataValue::OptionalBlockState(None),
+        MetadataValue::Particle(Particle::new(ParticleKind::Heart)),
+        MetadataValue::Particles(vec![
+            Particle {
+                kind: ParticleKind::Dust,
+                options: ParticleOptions::Dust { color: 0xFF_0000, scale: 1.5 },
+            },
+            Particle::new(ParticleKind::Firefly),
+        ]),
+        MetadataValue::VillagerData(VillagerData { kind: 2, profession: 5, level: 1 }),
+        MetadataValue::OptionalVarInt(Some(0)),
+        MetadataValue::OptionalVarInt(None),
+        MetadataValue::Pose(Pose::Sleeping),
+        MetadataValue::CatVariant(3),
+        MetadataValue::CowVariant(1),
+        MetadataValue::WolfVariant(4),
+        MetadataValue::WolfSoundVariant(2),
+        MetadataValue::FrogVariant(1),
+        MetadataValue::PigVariant(2),
+        MetadataValue::ChickenVariant(0),
+        MetadataValue::OptionalGlobalPosition(Some(GlobalPos {
+            dimension: Identifier::new_unchecked("minecraft:the_nether"),
+            position: BlockPos::new(8, 64, -8),
+        })),
+        MetadataValue::OptionalGlobalPosition(None),
+        MetadataValue::PaintingVariant(7),
+        MetadataValue::SnifferState(3),
+        MetadataValue::ArmadilloState(1),
+        MetadataValue::CopperGolemState(2),
+        MetadataValue::WeatheringCopperState(3),
+        MetadataValue::Vector3([1.0, 2.0, 3.0]),
+        MetadataValue::Quaternion([0.0, 0.0, 0.0, 1.0]),
+        MetadataValue::ResolvableProfile(ResolvableProfile::new(GameProfile::new(
+            Uuid::from_u128(0x5678),
+            "Steve",
+        ))),
+        MetadataValue::HumanoidArm(0),
+    ];
+
+    let mut metadata = EntityMetadata::new();
+    for (index, value) in values.into_iter().enumerate() {
+        metadata.insert(u8::try_from(index).unwrap(), value);
+    }
+
+    let bytes = encode(&metadata, ProtocolVersion::LATEST);
+    assert_eq!(bytes.last(), Some(&EntityMetadata::TERMINATOR));
+    assert_eq!(decode::<EntityMetadata>(&bytes, ProtocolVersion::LATEST), metadata);
+
+    let entity = Entity { id: 300, metadata };
+    let bytes = facet_minecraft::to_vec(&entity).unwrap();
+    let (decoded, remaining) = facet_minecraft::from_slice::<Entity>(&bytes).unwrap();
+    assert_eq!(decoded, entity);
+    assert!(remaining.is_empty());
+}
+
+#[test]
+fn entries() {
+    let mut metadata = EntityMetadata::new();
+    assert_eq!(encode(&metadata, ProtocolVersion::LATEST), [0xFF]);
+
+    metadata.insert(0, MetadataValue::Byte(0x20));
+    metadata.insert(2, MetadataValue::OptionalText(None));
+    metadata.insert(0, MetadataValue::Byte(0x02));
+    assert_eq!(metadata.0.len(), 2);
+    assert_eq!(metadata.get(0), Some(&MetadataValue::Byte(0x02)));
+    assert_eq!(metadata.get(1), None);
+    assert_eq!(metadata.get(2).map(MetadataValue::kind), Some(MetadataType::OptionalText));
+
+    assert_eq!(encode(&metadata, ProtocolVersion::LATEST), [0, 0, 0x02, 2, 6, 0, 0xFF]);
+}
+
+#[test]
+fn versions() {
+    let ids = |kind: MetadataType| {
+        [
+            ProtocolVersion::V1_20_2,
+            ProtocolVersion::V1_20_5,
+            ProtocolVersion::V1_21_4,
+            ProtocolVersion::V1_21_5,
+            ProtocolVersion::V1_21_9,
+            ProtocolVersion::snapshot(1),
+        ]
+        .map(|version| kind.id(version))
+    };
+
+    assert_eq!(ids(MetadataType::Byte), [Some(0); 6]);
+    assert_eq!(
+        ids(MetadataType::Particles),
+        [None, Some(18), Some(18), Some(18), Some(17), Some(17)]
+    );
+    assert_eq!(ids(MetadataType::Nbt), [Some(16), Some(16), Some(16), Some(16), None, None]);
+    assert_eq!(
+        ids(MetadataType::Pose),
+        [Some(20), Some(21), Some(21), Some(21), Some(20), Some(20)]
+    );
+    assert_eq!(ids(MetadataType::CowVariant), [None, None, None, Some(23), Some(22), Some(22)]);
+    assert_eq!(
+        ids(MetadataType::Quaternion),
+        [Some(27), Some(30), Some(30), Some(34), Some(35), Some(35)]
+    );
+    assert_eq!(ids(MetadataType::HumanoidArm), [None, None, None, None, Some(37), Some(37)]);
+
+    for version in [ProtocolVersion::V1_20_2, ProtocolVersion::V1_21_4, ProtocolVersion::LATEST] {
+        for (id, kind) in MetadataType::table(version).iter().enumerate() {
+            let id = u32::try_from(id).unwrap();
+            assert_eq!(MetadataType::from_id(id, version), Some(*kind));
+            assert_eq!(kind.id(version), Some(id));
+        }
+    }
+
+    // The same pose has a different type ID
+    let mut metadata = EntityMetadata::new();
+    metadata.insert(6, MetadataValue::Pose(Pose::Swimming));
+    assert_eq!(encode(&metadata, ProtocolVersion::V1_20_2), [6, 20, 3, 0xFF]);
+    assert_eq!(encode(&metadata, ProtocolVersion::V1_21_4), [6, 21, 3, 0xFF]);
+    assert_eq!(decode::<EntityMetadata>(&[6, 20, 3, 0xFF], ProtocolVersion::V1_20_2), metadata);
+    assert_eq!(encode(&metadata, ProtocolVersion::LATEST), [6, 20, 3, 0xFF]);
+
+    // NBT values were removed in 1.21.9
+    let mut nbt = NbtCompound::new();
+    nbt.insert("Count", 3i32);
+    let mut metadata = EntityMetadata::new();
+    metadata.insert(19, MetadataValue::Nbt(NbtValue::Compound(nbt)));
+    let bytes = encode(&metadata, ProtocolVersion::V1_21_5);
+    assert_eq!(bytes[..2], [19, 16]);
+    assert_eq!(decode::<EntityMetadata>(&bytes, ProtocolVersion::V1_21_5), metadata);
+    let mut buffer = Vec::new();
+    let mut writer = McWriter::new(&mut buffer);
+    let error = metadata.encode_for(&mut writer, ProtocolVersion::V1_21_9).unwrap_err();
+    let SerializeErrorKind::Entity(error) = error.kind() else { panic!() };
+    assert!(matches!(
+        error.kind(),
+        EntityErrorKind::UnsupportedType { kind: MetadataType::Nbt, .. }
+    ));
+
+    // Text is sent as JSON before 1.20.3
+    let mut metadata = EntityMetadata::new();
+    metadata.insert(2, MetadataValue::Text(TextComponent::text("Hi")));
+    let json = encode(&metadata, ProtocolVersion::V1_20_2);
+    assert_eq!(json, [&[2, 5, 4][..], br#""Hi""#, &[0xFF]].concat());
+    assert_eq!(decode::<EntityMetadata>(&json, ProtocolVersion::V1_20_2), metadata);
+    let nbt = encode(&metadata, ProtocolVersion::LATEST);
+    assert_eq!(nbt, [&[2, 5, 8, 0, 2][..], b"Hi", &[0xFF]].concat());
+
+    // Slots use the component type IDs of the version
+    let mut stack = ItemStack::new(1, 1);
+    stack.components.remove(DataComponentKind::Food);
+    let mut metadata = EntityMetadata::new();
+    metadata.insert(8, MetadataValue::Slot(OptionalItemStack::from(stack)));
+    let legacy = encode(&metadata, ProtocolVersion::V1_21_4);
+    assert_eq!(legacy, [8, 7, 1, 1, 0, 1, 21, 0xFF]);
+    assert_eq!(decode::<EntityMetadata>(&legacy, ProtocolVersion::V1_21_4), metadata);
+    assert_eq!(encode(&metadata, ProtocolVersion::LATEST), [8, 7, 1, 1, 0, 1, 20, 0xFF]);
+    let mut buffer = Vec::new();
+    metadata.encode_for(&mut McWriter::new(&mut buffer), ProtocolVersion::V1_21_4).unwrap();
+    assert_eq!(buffer, legacy);
+    let mut reader = McReader::new(&legacy);
+    assert_eq!(
+        EntityMetadata::decode_for(&mut reader, ProtocolVersion::V1_21_4).unwrap(),
+        metadata
+    );
+    assert_eq!(reader.version(), ProtocolVersion::LATEST);
+}
+
+#[test]
+fn particles() {
+    let particles = [
+        Particle::new(ParticleKind::AngryVillager),
+        Particle { kind: ParticleKind::FallingDust, options: ParticleOptions::Block(10) },
+        Particle {
+            kind: ParticleKind::DustColorTransition,
+            options: ParticleOptions::DustColorTransition { from: 0xFF, to: 0xFF00, scale: 2.0 },
+        },
+        Particle { kind: ParticleKind::Flash, options: ParticleOptions::Color(0xFFFF_FFFF) },
+        Particle {
+            kind: ParticleKind::InstantEffect,
+            options: ParticleOptions::Spell { color: 0x80_8080, power: 1.0 },
+        },
+        Particle { kind: ParticleKind::DragonBreath, options: ParticleOptions::Power(0.5) },
+        Particle { kind: ParticleKind::Item, options: ParticleOptions::Item(ItemStack::new(5, 1)) },
+        Particle {
+            kind: ParticleKind::Vibration,
+            options: ParticleOptions::Vibration {
+                destination: PositionSource::Block(BlockPos::new(1, 2, 3)),
+                ticks: 20,
+            },
+        },
+        Particle {
+            kind: ParticleKind::Vibration,
+            options: ParticleOptions::Vibration {
+                destination: PositionSource::Entity { id: 42, y_offset: 1.5 },
+                ticks: 10,
+            },
+        },
+        Particle {
+            kind: ParticleKind::Trail,
+            options: ParticleOptions::Trail {
+                target: [0.5, 64.0, -0.5],
+                color: 0xFF,
+                duration: 40,
+            },
+        },
+        Particle { kind: ParticleKind::SculkCharge, options: ParticleOptions::SculkCharge(1.5) },
+        Particle { kind: ParticleKind::Shriek, options: ParticleOptions::Shriek(5) },
+    ];
+
+    let mut metadata = EntityMetadata::new();
+    for (index, particle) in particles.iter().enumerate() {
+        metadata.insert(u8::try_from(index).unwrap(), MetadataValue::Particle(particle.clone()));
+    }
+    assert_eq!(
+        decode::<EntityMetadata>(
+            &encode(&metadata, ProtocolVersion::LATEST),
+            ProtocolVersion::LATEST
+        ),
+        metadata
+    );
+
+    // Particles are sent by their ID in the version and their options
+    let mut metadata = EntityMetadata::new();
+    let particle = Particle { kind: ParticleKind::Block, options: ParticleOptions::Block(300) };
+    metadata.insert(0, MetadataValue::Particle(particle));
+    assert_eq!(encode(&metadata, ProtocolVersion::LATEST), [0, 16, 1, 0xAC, 0x02, 0xFF]);
+
+    assert_eq!(ParticleKind::Firefly.id(ProtocolVersion::LATEST), Some(114));
+    assert_eq!(ParticleKind::Firefly.id(ProtocolVersion::V1_21_5), Some(113));
+    assert_eq!(ParticleKind::Firefly.id(ProtocolVersion::V1_21_4), None);
+    assert_eq!(ParticleKind::AmbientEntityEffect.id(ProtocolVersion::V1_20_2), Some(0));
+    assert_eq!(ParticleKind::AmbientEntityEffect.id(ProtocolVersion::V1_20_5), None);
+    assert_eq!(ParticleKind::GustEmitter.id(ProtocolVersion::V1_20_3), Some(25));
+    assert_eq!(
+        ParticleKind::from_name("minecraft:copper_fire_flame"),
+        Some(ParticleKind::CopperFireFlame)
+    );
+    for version in [
+        ProtocolVersion::V1_20_2,
+        ProtocolVersion::V1_20_3,
+        ProtocolVersion::V1_20_5,
+        ProtocolVersion::V1_21_2,
+        ProtocolVersion::V1_21_4,
+        ProtocolVersion::V1_21_5,
+        ProtocolVersion::LATEST,
+    ] {
+        for kind in ParticleKind::ALL {
+            if let Some(id) = kind.id(version) {
+                assert_eq!(ParticleKind::from_id(id, version), Some(*kind));
+            }
+        }
+    }
+}
+
+#[test]
+fn legacy_particles() {
+    // An area effect cloud in 1.21.4, with a colored `entity_effect`
+    let mut metadata = EntityMetadata::new();
+    let particle =
+        Particle { kind: ParticleKind::EntityEffect, options: ParticleOptions::Color(0xFF00_FF00) };
+    metadata.insert(11, MetadataValue::Particle(particle));
+    let bytes = encode(&metadata, ProtocolVersion::V1_21_4);
+    assert_eq!(bytes, [11, 17, 20, 0xFF, 0, 0xFF, 0, 0xFF]);
+    assert_eq!(decode::<EntityMetadata>(&bytes, ProtocolVersion::V1_21_4), metadata);
+
+    // Dust colors are sent as floats before 1.21.2
+    let mut metadata = EntityMetadata::new();
+    let particle = Particle {
+        kind: ParticleKind::Dust,
+        options: ParticleOptions::Dust { color: 0xFF_0000, scale: 1.0 },
+    };
+    metadata.insert(0, MetadataValue::Particle(particle));
+    let bytes = encode(&metadata, ProtocolVersion::V1_20_2);
+    assert_eq!(
+        bytes,
+        [0, 17, 14, 0x3F, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x3F, 0x80, 0, 0, 0xFF]
+    );
+    assert_eq!(decode::<EntityMetadata>(&bytes, ProtocolVersion::V1_20_2), metadata);
+
+    let particles = [
+        Particle::new(ParticleKind::AmbientEntityEffect),
+        Particle::new(ParticleKind::Flash),
+        Particle::new(ParticleKind::DragonBreath),
+        Particle {
+            kind: ParticleKind::DustColorTransition,
+            options: ParticleOptions::DustColorTransition { from: 0xFF, to: 0xFF00, scale: 2.0 },
+        },
+        Particle {
+            kind: ParticleKind::Vibration,
+            options: ParticleOptions::Vibration {
+                destination: PositionSource::Entity { id: 42, y_offset: 1.5 },
+                ticks: 10,
+            },
+        },
+    ];
+    let mut metadata = EntityMetadata::new();
+    for (index, particle) in particles.iter().enumerate() {
+        metadata.insert(u8::try_from(index).unwrap(), MetadataValue::Particle(particle.clone()));
+    }
+    let bytes = encode(&metadata, ProtocolVersion::V1_20_3);
+    assert_eq!(decode::<EntityMetadata>(&bytes, ProtocolVersion::V1_20_3), metadata);
+
+    // Vibration sources are sent by name before 1.20.5
+    let mut metadata = EntityMetadata::new();
+    metadata.insert(0, MetadataValue::Particle(particles[4].clone()));
+    let bytes = encode(&metadata, ProtocolVersion::V1_20_3);
+    assert_eq!(bytes[..4], [0, 17, 43, 16]);
+    assert_eq!(&bytes[4..20], b"minecraft:entity");
+
+    // Items use the components of the version
+    let mut metadata = EntityMetadata::new();
+    let particle =
+        Particle { kind: ParticleKind::Item, options: ParticleOptions::Item(ItemStack::new(5, 1)) };
+    metadata.insert(0, MetadataValue::Particle(particle));
+    let bytes = encode(&metadata, ProtocolVersion::V1_21_4);
+    assert_eq!(bytes[..3], [0, 17, 45]);
+    assert_eq!(decode::<EntityMetadata>(&bytes, ProtocolVersion::V1_21_4), metadata);
+
+    // Trails have no duration before 1.21.4
+    let mut metadata = EntityMetadata::new();
+    let particle = Particle {
+        kind: ParticleKind::Trail,
+        options: ParticleOptions::Trail { target: [0.0; 3], color: 0xFF, duration: 0 },
+    };
+    metadata.insert(0, MetadataValue::Particle(particle));
+    let bytes = encode(&metadata, ProtocolVersion::V1_21_2);
+    assert_eq!(bytes.len(), 3 + 24 + 4 + 1);
+    assert_eq!(decode::<EntityMetadata>(&bytes, ProtocolVersion::V1_21_2), metadata);
+}
+
+#[test]
+fn profiles() {
+    let profiles = [
+        ResolvableProfile::new(GameProfile::new(Uuid::from_u128(1), "Alex")),
+        ResolvableProfile {
+            profile: ProfileSource::Partial {
+                name: Some(String::from("Notch")),
+                id: None,
+                properties: vec![ProfileProperty::new("textures", "e30=")],
+            },
+            skin: SkinPatch {
+                body: Some(Identifier::new_unchecked("minecraft:entity/player/slim/alex")),
+                cape: None,
+                elytra: None,
+                model: Some(PlayerModel::Slim),
+            },
+        },
+    ];
+
+    for profile in profiles {
+        let mut metadata = EntityMetadata::new();
+        metadata.insert(17, MetadataValue::ResolvableProfile(profile));
+        let bytes = encode(&metadata, ProtocolVersion::LATEST);
+        assert_eq!(bytes[..2], [17, 36]);
+        assert_eq!(decode::<EntityMetadata>(&bytes, ProtocolVersion::LATEST), metadata);
+    }
+
+    // A partial profile without a name, UUID, properties or skin
+    let mut metadata = EntityMetadata::new();
+    let profile = ResolvableProfile {
+        profile: ProfileSource::Partial { name: None, id: None, properties: Vec::new() },
+        skin: SkinPatch::default(),
+    };
+    metadata.insert(17, MetadataValue::ResolvableProfile(profile));
+    assert_eq!(encode(&metadata, ProtocolVersion::LATEST), [17, 36, 0, 0, 0, 0, 0, 0, 0, 0, 0xFF]);
+}
+
+#[test]
+fn block_pos() {
+    let position = BlockPos::new(18_357_644, 831, -20_882_616);
+    assert_eq!(position.to_long(), 0x4607_632C_15B4_833F);
+    assert_eq!(BlockPos::from_long(0x4607_632C_15B4_833F), position);
+
+    let position = BlockPos::new(-1, -2048, -33_554_432);
+    assert_eq!(BlockPos::from_long(position.to_long()), position);
+
+    let mut buffer = Vec::new();
+    BlockPos::new(0, 1, 0).encode(&mut McWriter::new(&mut buffer)).unwrap();
+    assert_eq!(buffer, [0, 0, 0, 0, 0, 0, 0, 1]);
+}
+
+#[test]
+fn errors() {
+    // Particle types and options must exist in the version
+    let mut buffer = Vec::new();
+    let mut metadata = EntityMetadata::new();
+    metadata.insert(0, MetadataValue::Particle(Particle::new(ParticleKind::Firefly)));
+    let mut writer = McWriter::new(&mut buffer);
+    let error = metadata.encode_for(&mut writer, ProtocolVersion::V1_21_4).unwrap_err();
+    let SerializeErrorKind::Entity(error) = error.kind() else { panic!() };
+    assert!(matches!(
+        error.kind(),
+        EntityErrorKind::UnsupportedParticle { kind: ParticleKind::Firefly, .. }
+    ));
+
+    let mut metadata = EntityMetadata::new();
+    let particle = Particle { kind: ParticleKind::Flash, options: ParticleOptions::Color(0) };
+    metadata.insert(0, MetadataValue::Particle(particle));
+    let mut writer = McWriter::new(&mut buffer);
+    let error = metadata.encode_for(&mut writer, ProtocolVersion::V1_21_4).unwrap_err();
+    let SerializeErrorKind::Entity(error) = error.kind() else { panic!() };
+    assert!(matches!(error.kind(), EntityErrorKind::InvalidParticle(ParticleKind::Flash)));
+
+    let mut bytes = vec![0, 17, 43, 14];
+    bytes.extend_from_slice(b"minecraft:sign");
+    let mut reader = McReader::new(&bytes).with_version(ProtocolVersion::V1_20_3);
+    let error = EntityMetadata::decode(&mut reader).unwrap_err();
+    let DeserializeErrorKind::Entity(error) = error.kind() else { panic!() };
+    assert!(
+        matches!(error.kind(), EntityErrorKind::InvalidName { name, .. } if name == "minecraft:sign")
+    );
+
+    let error = EntityMetadata::decode(&mut McReader::new(&[0, 16, 120, 0xFF])).unwrap_err();
+    let DeserializeErrorKind::Entity(error) = error.kind() else { panic!() };
+    assert!(matches!(
+        error.kind(),
+        EntityErrorKind::InvalidValue { kind: MetadataType::Particle, value: 120 }
+    ));
+
+    // Particle options must match the particle type
+    let mut metadata = EntityMetadata::new();
+    let particle = Particle { kind: ParticleKind::Heart, options: ParticleOptions::Block(1) };
+    metadata.insert(0, MetadataValue::Particle(particle));
+    let error = metadata.encode(&mut McWriter::new(&mut Vec::new())).unwrap_err();
+    let SerializeErrorKind::Entity(error) = error.kind() else { panic!() };
+    assert!(matches!(error.kind(), EntityErrorKind::InvalidParticle(ParticleKind::Heart)));
+
+    let error = EntityMetadata::decode(&mut McReader::new(&[0, 38, 0, 0xFF])).unwrap_err();
+    let DeserializeErrorKind::Entity(error) = error.kind() else { panic!() };
+    assert!(matches!(error.kind(), EntityErrorKind::UnknownType { id: 38, .. }));
+
+    let error = EntityMetadata::decode(&mut McReader::new(&[0, 12, 6, 0xFF])).unwrap_err();
+    let DeserializeErrorKind::Entity(error) = error.kind() else { panic!() };
+    assert!(matches!(
+        error.kind(),
+        EntityErrorKind::InvalidValue { kind: MetadataType::Direction, value: 6 }
+    ));
+
+    // Slots before 1.20.5 use a different item format
+    let mut reader = McReader::new(&[0, 7, 0, 0xFF]);
+    let error = EntityMetadata::decode_for(&mut reader, ProtocolVersion::V1_20_2).unwrap_err();
+    let DeserializeErrorKind::Entity(error) = error.kind() else { panic!() };
+    assert!(matches!(
+        error.kind(),
+        EntityErrorKind::UnsupportedType { kind: MetadataType::Slot, .. }
+    ));
+
+    // The list must end with a terminator
+    let error = EntityMetadata::decode(&mut McReader::new(&[0, 0, 1])).unwrap_err();
+    assert!(matches!(error.kind(), DeserializeErrorKind::UnexpectedEndOfInput { .. }));
+
+    // Types must exist in the version
+    let mut metadata = EntityMetadata::new();
+    metadata.insert(17, MetadataValue::CowVariant(0));
+    let mut writer_buffer = Vec::new();
+    let error = metadata
+        .encode_for(&mut McWriter::new(&mut writer_buffer), ProtocolVersion::V1_21_4)
+        .unwrap_err();
+    let SerializeErrorKind::Entity(error) = error.kind() else { panic!() };
+    assert!(matches!(
+        error.kind(),
+        EntityErrorKind::UnsupportedType { kind: MetadataType::CowVariant, .. }
+    ));
+
+    let mut metadata = EntityMetadata::new();
+    metadata.insert(EntityMetadata::TERMINATOR, MetadataValue::Boolean(true));
+    let error = metadata.encode(&mut McWriter::new(&mut Vec::new())).unwrap_err();
+    let SerializeErrorKind::Entity(error) = error.kind() else { panic!() };
+    assert!(matches!(error.kind(), EntityErrorKind::ReservedIndex));
+}
@@ -0,0 +1,185 @@
+//! Verify [`PacketCodec`] framing with and without compression.
+
+use std::io::{Cursor, Write};
+
+use facet::Facet;
+use facet_minecraft::{
+    frame::{Frame, FrameErrorKind, PacketCodec},
+    types::Identifier,
+};
+use flate2::{Compression, write::ZlibEncoder};
+
+/// A helper struct used as a packet body.
+#[derive(Debug, PartialEq, Facet)]
+struct Body<'a> {
+    id: Identifier<'a>,
+    data: u8,
+}
+
+/// Compress data using zlib.
+fn zlib(data: &[u8]) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[test]
+fn uncompressed() {
+    let codec = PacketCodec::new();
+
+    let mut buffer = Vec::new();
+    codec.encode_frame(0x00, &[1, 2, 3], &mut buffer).unwrap();
+    codec.encode_frame(0x80, &[], &mut buffer).unwrap();
+    assert_eq!(buffer, [4, 0x00, 1, 2, 3, 2, 0x80, 0x01]);
+
+    let (frame, consumed) = codec.decode_frame(&buffer).unwrap().unwrap();
+    assert_eq!((frame.id(), frame.body(), consumed), (0x00, &[1, 2, 3][..], 5));
+    assert!(frame.is_borrowed());
+
+    let (frame, consumed) = codec.decode_frame(&buffer[5..]).unwrap().unwrap();
+    assert_eq!((frame.id(), frame.body(), consumed), (0x80, &[][..], 3));
+
+    // Incomplete frames
+    for len in 0..5 {
+        assert!(codec.decode_frame(&buffer[..len]).unwrap().is_none());
+    }
+}
+
+#[test]
+fn compressed() {
+    let codec = PacketCodec::with_compression(256);
+    assert_eq!(codec.compression(), Some(256));
+
+    // Below the threshold
+    let mut buffer = Vec::new();
+    codec.encode_frame(0x05, &[1, 2, 3], &mut buffer).unwrap();
+    assert_eq!(buffer, [5, 0, 0x05, 1, 2, 3]);
+
+    let (frame, _) = codec.decode_frame(&buffer).unwrap().unwrap();
+    assert_eq!((frame.id(), frame.body()), (0x05, &[1, 2, 3][..]));
+    assert!(frame.is_borrowed());
+
+    // At and above the threshold
+    for len in [255, 300, 100_000] {
+        let body: Vec<u8> = (0..7).cycle().take(len).collect();
+        let mut buffer = Vec::new();
+        codec.encode_frame(0x05, &body, &mut buffer).unwrap();
+        assert!(buffer.len() < body.len());
+
+        let (frame, consumed) = codec.decode_frame(&buffer).unwrap().unwrap();
+        assert_eq!(consumed, buffer.len());
+        assert_eq!((frame.id(), frame.body()), (0x05, body.as_slice()));
+        assert!(!frame.is_borrowed());
+    }
+
+    // Encoded by hand: data length `256` as `[0x80, 0x02]`
+    let mut data = vec![0x05];
+    data.extend(std::iter::repeat_n(0xAB, 255));
+    let mut payload = vec![0x80, 0x02];
+    payload.extend(zlib(&data));
+    let mut buffer = vec![u8::try_from(payload.len()).unwrap()];
+    buffer.extend(payload);
+
+    let (frame, _) = codec.decode_frame(&buffer).unwrap().unwrap();
+    assert_eq!((frame.id(), frame.body()), (0x05, &data[1..]));
+}
+
+#[test]
+fn limits() {
+    let codec = PacketCodec::new();
+
+    // A length wider than 21 bits
+    let error = codec.decode_frame(&[0xFF, 0xFF, 0xFF, 0x01]).unwrap_err();
+    assert!(matches!(error.kind(), FrameErrorKind::InvalidVarInt));
+
+    // The largest possible frame
+    let body = vec![0; PacketCodec::MAX_FRAME_LENGTH - 1];
+    let mut buffer = Vec::new();
+    codec.encode_frame(0x00, &body, &mut buffer).unwrap();
+    assert_eq!(buffer[..3], [0xFF, 0xFF, 0x7F]);
+
+    let body = vec![0; PacketCodec::MAX_FRAME_LENGTH];
+    let error = codec.encode_frame(0x00, &body, &mut Vec::new()).unwrap_err();
+    assert!(matches!(error.kind(), FrameErrorKind::FrameTooLarge(len) if *len == body.len() + 1));
+
+    let codec = PacketCodec::with_compression(256);
+    let body = vec![0; PacketCodec::MAX_DATA_LENGTH];
+    let error = codec.encode_frame(0x00, &body, &mut Vec::new()).unwrap_err();
+    assert!(matches!(error.kind(), FrameErrorKind::DataTooLarge(_)));
+}
+
+#[test]
+fn malformed() {
+    let codec = PacketCodec::with_compression(256);
+    let frame = |data_len: &[u8], compressed: &[u8]| {
+        let mut payload = data_len.to_vec();
+        payload.extend(compressed);
+        // Always a two-byte length
+        let len = u16::try_from(payload.len()).unwrap();
+        let mut buffer =
+            vec![0x80 | u8::try_from(len & 0x7F).unwrap(), u8::try_from(len >> 7).unwrap()];
+        buffer.extend(payload);
+        buffer
+    };
+
+    // Compressed packets below the threshold
+    let buffer = frame(&[0x10], &zlib(&[0; 16]));
+    let error = codec.decode_frame(&buffer).unwrap_err();
+    assert!(matches!(error.kind(), FrameErrorKind::BelowThreshold(16)));
+
+    // Declaring more than the protocol maximum
+    let buffer = frame(&[0x81, 0x80, 0x80, 0x04], &zlib(&[0; 16]));
+    let error = codec.decode_frame(&buffer).unwrap_err();
+    assert!(matches!(error.kind(), FrameErrorKind::DataTooLarge(8_388_609)));
+
+    // Decompressing to more than was declared
+    let buffer = frame(&[0x80, 0x02], &zlib(&vec![0; 1_000_000]));
+    let error = codec.decode_frame(&buffer).unwrap_err();
+    assert!(matches!(error.kind(), FrameErrorKind::LengthMismatch { expected: 256, found: 257 }));
+
+    // Decompressing to less than was declared
+    let buffer = frame(&[0x80, 0x04], &zlib(&[0; 300]));
+    let error = codec.decode_frame(&buffer).unwrap_err();
+    assert!(matches!(error.kind(), FrameErrorKind::LengthMismatch { expected: 512, found: 300 }));
+
+    // Invalid zlib data
+    let buffer = frame(&[0x80, 0x02], &[0xDE, 0xAD, 0xBE, 0xEF]);
+    let error = codec.decode_frame(&buffer).unwrap_err();
+    assert!(matches!(error.kind(), FrameErrorKind::InvalidCompression));
+}
+
+#[test]
+fn packets() {
+    let body = Body { id: Identifier::new("minecraft:brand").unwrap(), data: 42 };
+
+    for codec in [PacketCodec::new(), PacketCodec::with_compression(0)] {
+        let mut buffer = Vec::new();
+        codec.encode_packet(0x02, &body, &mut buffer).unwrap();
+
+        let (frame, _) = codec.decode_frame(&buffer).unwrap().unwrap();
+        assert_eq!(frame.id(), 0x02);
+        assert_eq!(frame.decode::<Body>().unwrap(), body);
+    }
+
+    // Bytes left after the body
+    let frame = Frame::new(0x02, b"\x0Fminecraft:brand\x2A\xFF".as_slice());
+    assert!(frame.decode::<Body>().is_err());
+}
+
+#[test]
+fn reader() {
+    let codec = PacketCodec::with_compression(64);
+
+    let mut buffer = Vec::new();
+    codec.write_frame(0x01, &[7; 8], &mut buffer).unwrap();
+    codec.write_frame(0x02, &[9; 128], &mut buffer).unwrap();
+
+    let mut cursor = Cursor::new(buffer);
+    let frame = codec.read_frame(&mut cursor).unwrap();
+    assert_eq!((frame.id(), frame.body()), (0x01, &[7; 8][..]));
+    let frame = codec.read_frame(&mut cursor).unwrap();
+    assert_eq!((frame.id(), frame.body()), (0x02, &[9; 128][..]));
+
+    let error = codec.read_frame(&mut cursor).unwrap_err();
+    assert!(matches!(error.kind(), FrameErrorKind::Io(_)));
+}
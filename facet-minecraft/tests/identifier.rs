@@ -0,0 +1,92 @@
+//! Verify [`Identifier`] parsing, validation and encoding.
+
+use facet::Facet;
+use facet_minecraft::{
+    Deserializable,
+    codec::{McCodec, McReader},
+    deserialize::DeserializeErrorKind,
+    types::{Identifier, IdentifierErrorKind},
+};
+
+/// A helper struct containing an [`Identifier`] field.
+#[derive(Debug, PartialEq, Facet)]
+struct Block<'a> {
+    id: Identifier<'a>,
+    count: u8,
+}
+
+#[test]
+fn parts() {
+    let stone = Identifier::new("minecraft:stone").unwrap();
+    assert_eq!(stone.namespace(), "minecraft");
+    assert_eq!(stone.path(), "stone");
+
+    let short = Identifier::new("stone").unwrap();
+    assert_eq!(short.namespace(), "minecraft");
+    assert_eq!(short.path(), "stone");
+    assert_eq!(short.as_str(), "stone");
+    assert_eq!(short.to_string(), "minecraft:stone");
+    assert_eq!(short, stone);
+
+    let empty = Identifier::new(":stone").unwrap();
+    assert_eq!(empty.namespace(), "minecraft");
+    assert_eq!(empty, stone);
+
+    let custom = Identifier::from_parts("my_mod", "textures/block.png").unwrap();
+    assert_eq!(custom.as_str(), "my_mod:textures/block.png");
+    assert_eq!(custom.namespace(), "my_mod");
+    assert_eq!(custom.path(), "textures/block.png");
+    assert!(!custom.is_default_namespace());
+    assert_ne!(custom, stone);
+
+    assert_eq!(Identifier::minecraft("stone").unwrap(), stone);
+    assert_eq!("stone".parse::<Identifier>().unwrap(), stone);
+}
+
+#[test]
+fn validation() {
+    let kind = |value: &str| *Identifier::new(value).unwrap_err().kind();
+
+    assert_eq!(kind("Minecraft:stone"), IdentifierErrorKind::InvalidNamespace('M'));
+    assert_eq!(kind("my/mod:stone"), IdentifierErrorKind::InvalidNamespace('/'));
+    assert_eq!(kind("minecraft:Stone"), IdentifierErrorKind::InvalidPath('S'));
+    assert_eq!(kind("minecraft:a:b"), IdentifierErrorKind::InvalidPath(':'));
+    assert_eq!(kind("stone block"), IdentifierErrorKind::InvalidPath(' '));
+    assert_eq!(kind("§"), IdentifierErrorKind::InvalidPath('§'));
+
+    let long = "a".repeat(Identifier::MAX_LENGTH + 1);
+    assert_eq!(kind(&long), IdentifierErrorKind::TooLong(Identifier::MAX_LENGTH + 1));
+    assert!(Identifier::new(&long[1..]).is_ok());
+}
+
+#[test]
+fn decode() {
+    let mut reader = McReader::new(b"\x0Fminecraft:stone\x05stone");
+    let stone = reader.read_identifier().unwrap();
+    assert!(stone.is_borrowed());
+    assert_eq!(stone.as_str(), "minecraft:stone");
+    assert_eq!(Identifier::decode(&mut reader).unwrap(), stone);
+    assert!(reader.remaining().is_empty());
+
+    let mut reader = McReader::new(b"\x0Fminecraft:Stone");
+    let error = Identifier::decode(&mut reader).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        DeserializeErrorKind::InvalidIdentifier(err)
+            if *err.kind() == IdentifierErrorKind::InvalidPath('S')
+    ));
+}
+
+#[test]
+fn field() {
+    let block = Block { id: Identifier::new("minecraft:stone").unwrap(), count: 64 };
+    let bytes = facet_minecraft::to_vec(&block).unwrap();
+    assert_eq!(bytes, b"\x0Fminecraft:stone\x40");
+
+    let (decoded, remaining) = Block::from_slice_borrowed(&bytes).unwrap();
+    assert!(decoded.id.is_borrowed());
+    assert_eq!(decoded, block);
+    assert!(remaining.is_empty());
+
+    assert!(Block::from_slice_borrowed(b"\x0Fminecraft:Stone\x40").is_err());
+}
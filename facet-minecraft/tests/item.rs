@@ -0,0 +1,293 @@
+//! Verify [`ItemStack`] and [`OptionalItemStack`] encodings,
+//! including nested stacks and unknown component types.
+
+mod common;
+
+use common::{decode, encode, roundtrip};
+use facet::Facet;
+use facet_minecraft::{
+    codec::{McCodec, McReader, McWriter},
+    deserialize::DeserializeErrorKind,
+    item::{
+        CustomModelData, DataComponent, DataComponentKind, Enchantments, Food, ItemErrorKind,
+        ItemStack, OptionalItemStack, Rarity,
+    },
+    nbt::NbtCompound,
+    packet::ProtocolVersion,
+    serialize::SerializeErrorKind,
+    text::{ClickEvent, Style, TextComponent},
+    types::Identifier,
+};
+
+/// A helper struct containing item stack fields.
+#[derive(Debug, PartialEq, Facet)]
+struct SetSlot {
+    slot: u16,
+    cursor: OptionalItemStack,
+    item: ItemStack,
+}
+
+/// Check that decoding an item stack fails with an item error.
+fn assert_error(bytes: &[u8], check: impl FnOnce(&ItemErrorKind) -> bool) {
+    let error = ItemStack::decode(&mut McReader::new(bytes)).unwrap_err();
+    match error.kind() {
+        DeserializeErrorKind::Item(error) => assert!(check(error.kind()), "{error:?}"),
+        other => panic!("expected an item error, got {other:?}"),
+    }
+}
+
+#[test]
+fn empty() {
+    assert_eq!(roundtrip(&OptionalItemStack::EMPTY), [0]);
+
+    // A negative count is also empty
+    let (stack, _) =
+        facet_minecraft::from_slice::<OptionalItemStack>(&[0xFF, 0xFF, 0xFF, 0xFF, 0x0F]).unwrap();
+    assert!(stack.is_empty());
+
+    assert_error(&[0], |kind| matches!(kind, ItemErrorKind::EmptyStack));
+
+    let error = ItemStack::new(1, 0).encode(&mut McWriter::new(&mut Vec::new())).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        SerializeErrorKind::Item(error) if matches!(error.kind(), ItemErrorKind::EmptyStack)
+    ));
+}
+
+#[test]
+fn simple() {
+    let stack = ItemStack::new(800, 3);
+    assert!(stack.components.is_empty());
+    assert_eq!(roundtrip(&stack), [3, 0xA0, 0x06, 0, 0]);
+    assert_eq!(roundtrip(&OptionalItemStack::from(stack)), [3, 0xA0, 0x06, 0, 0]);
+
+    let slot = SetSlot { slot: 36, cursor: OptionalItemStack::EMPTY, item: ItemStack::new(1, 64) };
+    assert_eq!(roundtrip(&slot), [0, 36, 0, 64, 1, 0, 0]);
+}
+
+#[test]
+fn components() {
+    let mut stack = ItemStack::new(42, 1);
+    stack.components.insert(DataComponent::Damage(5));
+    stack.components.insert(DataComponent::CustomName(TextComponent::text("Sword")));
+    stack.components.insert(DataComponent::Lore(vec![
+        TextComponent::text("First"),
+        TextComponent::text("Second"),
+    ]));
+    stack.components.insert(DataComponent::Rarity(Rarity::Epic));
+    stack.components.insert(DataComponent::Enchantments(Enchantments {
+        levels: vec![(12, 5), (30, 1)],
+        show_in_tooltip: true,
+    }));
+    stack.components.insert(DataComponent::CustomModelData(CustomModelData {
+        floats: vec![1.5],
+        flags: vec![true, false],
+        strings: vec![String::from("red")],
+        colors: vec![0x00FF_00FF],
+    }));
+    stack.components.insert(DataComponent::Food(Food {
+        nutrition: 4,
+        saturation: 2.4,
+        can_always_eat: true,
+    }));
+    stack.components.insert(DataComponent::ItemModel(Identifier::new_unchecked("minecraft:stick")));
+    stack.components.insert(DataComponent::CustomData({
+        let mut nbt = NbtCompound::new();
+        nbt.insert("owner", "Steve");
+        nbt
+    }));
+    stack.components.insert(DataComponent::TooltipDisplay {
+        hide_tooltip: false,
+        hidden_components: vec![DataComponentKind::Enchantments, DataComponentKind::Rarity],
+    });
+    stack.components.remove(DataComponentKind::Unbreakable);
+    roundtrip(&stack);
+
+    // Replacing a component keeps a single entry
+    stack.components.insert(DataComponent::Damage(6));
+    assert_eq!(stack.components.get(DataComponentKind::Damage), Some(&DataComponent::Damage(6)));
+    assert_eq!(stack.components.added.len(), 10);
+
+    // Adding a removed component clears the removal
+    stack.components.insert(DataComponent::Unbreakable { show_in_tooltip: true });
+    assert!(stack.components.removed.is_empty());
+    roundtrip(&stack);
+}
+
+#[test]
+fn nested() {
+    let mut inner = ItemStack::new(7, 2);
+    inner.components.insert(DataComponent::MaxStackSize(1));
+
+    let mut bundle = ItemStack::new(1000, 1);
+    bundle.components.insert(DataComponent::BundleContents(vec![inner.clone(), inner.clone()]));
+    bundle.components.insert(DataComponent::UseRemainder(Box::new(inner.clone())));
+
+    let mut chest = ItemStack::new(300, 1);
+    chest.components.insert(DataComponent::Container(vec![
+        OptionalItemStack::from(bundle),
+        OptionalItemStack::EMPTY,
+        OptionalItemStack::from(inner),
+    ]));
+    roundtrip(&chest);
+
+    // Stacks nested past the limit are rejected
+    let mut bytes = Vec::new();
+    for _ in 0..=ItemStack::MAX_DEPTH {
+        // One item with a `use_remainder` (22) component
+        bytes.extend([1, 1, 1, 0, 22]);
+    }
+    bytes.extend([1, 1, 0, 0]);
+    assert_error(&bytes, |kind| matches!(kind, ItemErrorKind::DepthLimit));
+
+    // One level less is accepted
+    let (stack, _) = facet_minecraft::from_slice::<ItemStack>(&bytes[5..]).unwrap();
+    assert_eq!(stack.item, 1);
+}
+
+#[test]
+fn kinds() {
+    let ids = |kind: DataComponentKind| {
+        [
+            ProtocolVersion::V1_20_2,
+            ProtocolVersion::V1_20_5,
+            ProtocolVersion::V1_21,
+            ProtocolVersion::V1_21_4,
+            ProtocolVersion::V1_21_5,
+            ProtocolVersion::LATEST,
+        ]
+        .map(|version| kind.id(version))
+    };
+
+    assert_eq!(
+        ids(DataComponentKind::CustomData),
+        [None, Some(0), Some(0), Some(0), Some(0), Some(0)]
+    );
+    assert_eq!(
+        ids(DataComponentKind::Food),
+        [None, Some(20), Some(20), Some(21), Some(20), Some(20)]
+    );
+    assert_eq!(
+        ids(DataComponentKind::HideTooltip),
+        [None, Some(15), Some(15), Some(16), None, None]
+    );
+    assert_eq!(ids(DataComponentKind::ItemModel), [None, None, None, Some(7), Some(7), Some(7)]);
+    assert_eq!(
+        ids(DataComponentKind::ContainerLoot),
+        [None, Some(55), Some(56), Some(66), Some(70), Some(70)]
+    );
+    assert_eq!(ids(DataComponentKind::ShulkerColor), [None, None, None, None, Some(95), Some(95)]);
+
+    for (version, len) in [
+        (ProtocolVersion::V1_20_5, 56),
+        (ProtocolVersion::V1_21, 57),
+        (ProtocolVersion::V1_21_4, 67),
+        (ProtocolVersion::LATEST, 96),
+    ] {
+        let table = DataComponentKind::table(version);
+        assert_eq!(table.len(), len);
+        for (id, kind) in table.iter().enumerate() {
+            let id = u32::try_from(id).unwrap();
+            assert_eq!(DataComponentKind::from_id(id, version), Some(*kind));
+            assert_eq!(kind.id(version), Some(id));
+        }
+        assert_eq!(DataComponentKind::from_id(u32::try_from(len).unwrap(), version), None);
+    }
+
+    for kind in DataComponentKind::ALL {
+        assert_eq!(DataComponentKind::from_name(kind.name()), Some(*kind));
+    }
+    assert_eq!(DataComponentKind::from_name("minecraft:food"), Some(DataComponentKind::Food));
+    assert_eq!(
+        DataComponentKind::from_name("minecraft:wolf/collar"),
+        Some(DataComponentKind::WolfCollar)
+    );
+}
+
+#[test]
+fn versions() {
+    // The same removed component has a different type ID
+    let mut stack = ItemStack::new(1, 1);
+    stack.components.remove(DataComponentKind::Food);
+    assert_eq!(encode(&stack, ProtocolVersion::V1_21_4), [1, 1, 0, 1, 21]);
+    assert_eq!(encode(&stack, ProtocolVersion::V1_21_5), [1, 1, 0, 1, 20]);
+    assert_eq!(decode::<ItemStack>(&[1, 1, 0, 1, 21], ProtocolVersion::V1_21_4), stack);
+
+    // Tooltip flags are only sent before 1.21.5, and shown when missing
+    let mut stack = ItemStack::new(1, 1);
+    stack.components.insert(DataComponent::Unbreakable { show_in_tooltip: false });
+    stack.components.insert(DataComponent::HideTooltip);
+    assert_eq!(encode(&stack, ProtocolVersion::V1_21_4), [1, 1, 2, 0, 4, 0, 16]);
+    assert_eq!(decode::<ItemStack>(&[1, 1, 2, 0, 4, 0, 16], ProtocolVersion::V1_21_4), stack);
+    let mut shown = ItemStack::new(1, 1);
+    shown.components.insert(DataComponent::Unbreakable { show_in_tooltip: true });
+    assert_eq!(encode(&shown, ProtocolVersion::V1_21_5), [1, 1, 1, 0, 4]);
+    assert_eq!(decode::<ItemStack>(&[1, 1, 1, 0, 4], ProtocolVersion::V1_21_5), shown);
+
+    // Text event fields are `snake_case` since 1.21.5
+    let mut stack = ItemStack::new(1, 1);
+    let text = TextComponent::text("Help")
+        .with_style(Style::EMPTY.with_click_event(ClickEvent::RunCommand(String::from("/help"))));
+    stack.components.insert(DataComponent::CustomName(text));
+    let contains = |bytes: &[u8], key: &[u8]| bytes.windows(key.len()).any(|window| window == key);
+    let legacy = encode(&stack, ProtocolVersion::V1_21_4);
+    assert!(contains(&legacy, b"clickEvent"));
+    assert_eq!(decode::<ItemStack>(&legacy, ProtocolVersion::V1_21_4), stack);
+    let current = encode(&stack, ProtocolVersion::V1_21_5);
+    assert!(contains(&current, b"click_event"));
+    assert_eq!(decode::<ItemStack>(&current, ProtocolVersion::V1_21_5), stack);
+}
+
+#[test]
+fn errors() {
+    // An unknown component type
+    assert_error(&[1, 1, 1, 0, 100], |kind| {
+        matches!(kind, ItemErrorKind::UnknownComponent { id: 100, .. })
+    });
+    // An unknown removed component type
+    assert_error(&[1, 1, 0, 1, 96], |kind| {
+        matches!(kind, ItemErrorKind::UnknownComponent { id: 96, .. })
+    });
+    // A known component type without decoding support, `attribute_modifiers` (13)
+    assert_error(&[1, 1, 1, 0, 13, 0], |kind| {
+        matches!(
+            kind,
+            ItemErrorKind::UnsupportedComponent { kind: DataComponentKind::AttributeModifiers, .. }
+        )
+    });
+    // An invalid `rarity` (9)
+    assert_error(&[1, 1, 1, 0, 9, 4], |kind| {
+        matches!(
+            kind,
+            ItemErrorKind::InvalidValue { component: DataComponentKind::Rarity, value: 4 }
+        )
+    });
+    // Truncated component data
+    let error = ItemStack::decode(&mut McReader::new(&[1, 1, 1, 0, 1])).unwrap_err();
+    assert!(matches!(error.kind(), DeserializeErrorKind::UnexpectedEndOfInput { .. }));
+
+    // Component types must exist in the version
+    let mut stack = ItemStack::new(1, 1);
+    stack.components.insert(DataComponent::HideTooltip);
+    let error = stack.encode(&mut McWriter::new(&mut Vec::new())).unwrap_err();
+    let SerializeErrorKind::Item(error) = error.kind() else { panic!() };
+    assert!(matches!(
+        error.kind(),
+        ItemErrorKind::UnsupportedComponent { kind: DataComponentKind::HideTooltip, .. }
+    ));
+
+    // Food before 1.21.2 has more fields
+    let mut reader = McReader::new(&[1, 1, 1, 0, 20, 0]).with_version(ProtocolVersion::V1_21);
+    let error = ItemStack::decode(&mut reader).unwrap_err();
+    let DeserializeErrorKind::Item(error) = error.kind() else { panic!() };
+    assert!(matches!(
+        error.kind(),
+        ItemErrorKind::UnsupportedComponent { kind: DataComponentKind::Food, .. }
+    ));
+
+    // Items before 1.20.5 have no components
+    let mut reader = McReader::new(&[0]).with_version(ProtocolVersion::V1_20_2);
+    let error = OptionalItemStack::decode(&mut reader).unwrap_err();
+    let DeserializeErrorKind::Item(error) = error.kind() else { panic!() };
+    assert!(matches!(error.kind(), ItemErrorKind::UnsupportedVersion(ProtocolVersion::V1_20_2)));
+}
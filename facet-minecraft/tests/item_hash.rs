@@ -0,0 +1,208 @@
+//! Verify [`HashedItemStack`] encodings and component hashes.
+//!
+//! Golden hashes were computed with a separate model of vanilla's `HashOps`,
+//! following the 1.21.5 component codecs, and not with this crate.
+
+use facet::Facet;
+use facet_minecraft::{
+    codec::{McCodec, McReader, McWriter},
+    deserialize::DeserializeErrorKind,
+    item::{
+        DataComponent, DataComponentKind, Enchantments, Food, HashedComponentPatch,
+        HashedItemStack, ItemErrorKind, ItemStack, OptionalHashedItemStack, OptionalItemStack,
+        Rarity,
+    },
+    nbt::NbtCompound,
+    packet::ProtocolVersion,
+    serialize::SerializeErrorKind,
+    text::{Style, TextComponent},
+    types::Identifier,
+};
+
+/// A helper struct containing the slots of a Click Container packet.
+#[derive(Debug, PartialEq, Facet)]
+struct ClickContainer {
+    window: u8,
+    changed: HashedItemStack,
+    carried: OptionalHashedItemStack,
+}
+
+#[test]
+fn golden() {
+    let hash = |component: DataComponent| component.hash().unwrap();
+
+    assert_eq!(hash(DataComponent::Glider), 0xC574_B4C8);
+    assert_eq!(hash(DataComponent::Unbreakable { show_in_tooltip: false }), 0xC574_B4C8);
+    assert_eq!(hash(DataComponent::MaxStackSize(64)), 0x2BB3_2243);
+    assert_eq!(hash(DataComponent::EnchantmentGlintOverride(true)), 0xC336_CEC2);
+    assert_eq!(hash(DataComponent::Rarity(Rarity::Epic)), 0xEE8D_827D);
+    assert_eq!(hash(DataComponent::BaseColor(14)), 0x8C64_52AA);
+    assert_eq!(
+        hash(DataComponent::DyedColor { rgb: 0xA0_6540, show_in_tooltip: true }),
+        0xB245_7611
+    );
+    // Some single values are saved as a record
+    assert_eq!(hash(DataComponent::Enchantable(15)), 0xF41C_6ED1);
+    assert_eq!(
+        hash(DataComponent::ItemModel(Identifier::new_unchecked("stick"))),
+        hash(DataComponent::ItemModel(Identifier::new_unchecked("minecraft:stick"))),
+    );
+    assert_eq!(hash(DataComponent::ItemModel(Identifier::new_unchecked("stick"))), 0xBB41_7764);
+
+    // Strings are hashed as UTF-16
+    assert_eq!(hash(DataComponent::CustomName(TextComponent::text("Sword"))), 0xF3C6_5C04);
+    assert_eq!(hash(DataComponent::ItemName(TextComponent::text("🗡 Dagger"))), 0x655B_DD2D);
+    assert_eq!(
+        hash(DataComponent::Lore(vec![
+            TextComponent::text("First"),
+            TextComponent::text("Second")
+        ])),
+        0xE1FA_0777
+    );
+    // Style flags are hashed as booleans
+    let bold = TextComponent::text("Hi").with_style(Style::EMPTY.with_bold(true));
+    assert_eq!(hash(DataComponent::CustomName(bold)), 0xCD4C_A3B1);
+
+    // Optional fields are left out when set to their default
+    let food = Food { nutrition: 4, saturation: 2.4, can_always_eat: false };
+    assert_eq!(hash(DataComponent::Food(food)), 0x57AB_4ED6);
+    let food = Food { can_always_eat: true, ..food };
+    assert_eq!(hash(DataComponent::Food(food)), 0xE2FD_3A03);
+    let display = DataComponent::TooltipDisplay {
+        hide_tooltip: false,
+        hidden_components: vec![DataComponentKind::Enchantments, DataComponentKind::DyedColor],
+    };
+    assert_eq!(hash(display), 0x08AD_ED8E);
+    let display =
+        DataComponent::TooltipDisplay { hide_tooltip: true, hidden_components: Vec::new() };
+    assert_eq!(hash(display), 0x00D5_E0D2);
+
+    // NBT bytes stay bytes, and the order of entries does not matter
+    let mut nbt = NbtCompound::new();
+    nbt.insert("owner", "Steve");
+    nbt.insert("level", 3i8);
+    assert_eq!(hash(DataComponent::CustomData(nbt)), 0xF3FB_28E5);
+    let mut reversed = NbtCompound::new();
+    reversed.insert("level", 3i8);
+    reversed.insert("owner", "Steve");
+    assert_eq!(hash(DataComponent::CustomData(reversed)), 0xF3FB_28E5);
+}
+
+#[test]
+fn hashed_stack() {
+    let mut stack = ItemStack::new(800, 2);
+    stack.components.insert(DataComponent::MaxStackSize(64));
+    stack.components.insert(DataComponent::Rarity(Rarity::Epic));
+    stack.components.remove(DataComponentKind::Food);
+
+    let hashed = HashedItemStack::new(&stack).unwrap();
+    assert_eq!(
+        hashed.components,
+        HashedComponentPatch {
+            added: vec![
+                (DataComponentKind::MaxStackSize, 0x2BB3_2243),
+                (DataComponentKind::Rarity, 0xEE8D_827D),
+            ],
+            removed: vec![DataComponentKind::Food],
+        }
+    );
+    assert!(hashed.matches(&stack));
+
+    // Components may be sent in any order
+    let mut reordered = hashed.clone();
+    reordered.components.added.reverse();
+    assert!(reordered.matches(&stack));
+
+    let mut changed = stack.clone();
+    changed.components.insert(DataComponent::MaxStackSize(16));
+    assert!(!hashed.matches(&changed));
+    changed = stack.clone();
+    changed.count = 3;
+    assert!(!hashed.matches(&changed));
+
+    let empty = OptionalHashedItemStack::new(&OptionalItemStack::EMPTY).unwrap();
+    assert!(empty.is_empty());
+    assert!(empty.matches(&OptionalItemStack::EMPTY));
+    assert!(!empty.matches(&OptionalItemStack::from(stack.clone())));
+    let optional = OptionalHashedItemStack::from(hashed.clone());
+    assert!(optional.matches(&OptionalItemStack::from(stack)));
+
+    let bytes = [
+        &[1, 0xA0, 0x06, 2][..],
+        &[2, 1, 0x2B, 0xB3, 0x22, 0x43, 9, 0xEE, 0x8D, 0x82, 0x7D],
+        &[1, 20],
+    ]
+    .concat();
+    let packet =
+        ClickContainer { window: 3, changed: hashed, carried: OptionalHashedItemStack::EMPTY };
+    let encoded = facet_minecraft::to_vec(&packet).unwrap();
+    assert_eq!(encoded, [&[3][..], &bytes, &[0]].concat());
+    let (decoded, remaining) = facet_minecraft::from_slice::<ClickContainer>(&encoded).unwrap();
+    assert_eq!(decoded, packet);
+    assert!(remaining.is_empty());
+}
+
+#[test]
+fn errors() {
+    let error =
+        DataComponent::Enchantments(Enchantments { levels: vec![(1, 1)], show_in_tooltip: true })
+            .hash()
+            .unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ItemErrorKind::UnhashableComponent(DataComponentKind::Enchantments)
+    ));
+    assert!(matches!(
+        DataComponent::CreativeSlotLock.hash().unwrap_err().kind(),
+        ItemErrorKind::UnhashableComponent(DataComponentKind::CreativeSlotLock)
+    ));
+    assert!(matches!(
+        DataComponent::BaseColor(16).hash().unwrap_err().kind(),
+        ItemErrorKind::InvalidValue { component: DataComponentKind::BaseColor, value: 16 }
+    ));
+
+    // Stacks with unhashable components never match
+    let mut stack = ItemStack::new(1, 1);
+    stack.components.insert(DataComponent::CreativeSlotLock);
+    assert!(HashedItemStack::new(&stack).is_err());
+    let hashed = HashedItemStack {
+        item: 1,
+        count: 1,
+        components: HashedComponentPatch {
+            added: vec![(DataComponentKind::CreativeSlotLock, 0)],
+            removed: Vec::new(),
+        },
+    };
+    assert!(!hashed.matches(&stack));
+
+    let error = HashedItemStack::decode(&mut McReader::new(&[0])).unwrap_err();
+    let DeserializeErrorKind::Item(error) = error.kind() else { panic!() };
+    assert!(matches!(error.kind(), ItemErrorKind::EmptyStack));
+
+    let error = HashedItemStack::decode(&mut McReader::new(&[1, 1, 1, 1, 100])).unwrap_err();
+    let DeserializeErrorKind::Item(error) = error.kind() else { panic!() };
+    assert!(matches!(error.kind(), ItemErrorKind::UnknownComponent { id: 100, .. }));
+
+    // Component types removed in 1.21.5 cannot be hashed or removed
+    assert!(matches!(
+        DataComponent::HideTooltip.hash().unwrap_err().kind(),
+        ItemErrorKind::UnsupportedComponent { kind: DataComponentKind::HideTooltip, .. }
+    ));
+    let mut stack = ItemStack::new(1, 1);
+    stack.components.remove(DataComponentKind::HideAdditionalTooltip);
+    assert!(matches!(
+        HashedItemStack::new(&stack).unwrap_err().kind(),
+        ItemErrorKind::UnsupportedComponent { kind: DataComponentKind::HideAdditionalTooltip, .. }
+    ));
+
+    // Hashed stacks are only sent since 1.21.5
+    let mut reader = McReader::new(&[0]).with_version(ProtocolVersion::V1_21_4);
+    let error = OptionalHashedItemStack::decode(&mut reader).unwrap_err();
+    let DeserializeErrorKind::Item(error) = error.kind() else { panic!() };
+    assert!(matches!(error.kind(), ItemErrorKind::UnsupportedVersion(ProtocolVersion::V1_21_4)));
+    let mut buffer = Vec::new();
+    let mut writer = McWriter::new(&mut buffer).with_version(ProtocolVersion::V1_21_4);
+    let error = OptionalHashedItemStack::EMPTY.encode(&mut writer).unwrap_err();
+    let SerializeErrorKind::Item(error) = error.kind() else { panic!() };
+    assert!(matches!(error.kind(), ItemErrorKind::UnsupportedVersion(ProtocolVersion::V1_21_4)));
+}
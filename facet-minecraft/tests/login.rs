@@ -0,0 +1,97 @@
+//! Verify the login encryption handshake helpers using fixed vectors.
+
+use facet_minecraft::{
+    Deserializable,
+    login::{
+        EncryptionRequest, EncryptionResponse, LoginErrorKind, ServerKey, generate_secret,
+        server_hash,
+    },
+};
+use rand_chacha::{ChaCha8Rng, rand_core::SeedableRng};
+
+/// A 1024-bit RSA private key in PKCS#8 DER format.
+const PRIVATE_KEY: &[u8] = include_bytes!("data/login_key.der");
+/// The public key of [`PRIVATE_KEY`] in X.509 DER format.
+const PUBLIC_KEY: &[u8] = include_bytes!("data/login_public.der");
+/// `0123456789abcdef` encrypted with [`PUBLIC_KEY`].
+const SHARED_SECRET: &[u8] = include_bytes!("data/login_secret.bin");
+/// `DEADBEEF` encrypted with [`PUBLIC_KEY`].
+const VERIFY_TOKEN: &[u8] = include_bytes!("data/login_token.bin");
+
+#[test]
+fn hash() {
+    assert_eq!(server_hash("Notch", &[], &[]), "4ed1f46bbe04bc756bcb17c0c7ce3e4632f06a48");
+    assert_eq!(server_hash("jeb_", &[], &[]), "-7c9d5b0044c130109a5d7b5fb5c317c02b4e28c1");
+    assert_eq!(server_hash("simon", &[], &[]), "88e16a1019277b15d58faf0541e11910eb756f6");
+
+    assert_eq!(
+        server_hash("", b"0123456789abcdef", PUBLIC_KEY),
+        "54b864a8d415ae2b57a5a6be065ce4f089169675"
+    );
+}
+
+#[test]
+fn server() {
+    let key = ServerKey::from_pkcs8_der(PRIVATE_KEY).unwrap();
+    assert_eq!(key.public_key_der(), PUBLIC_KEY);
+
+    let response = EncryptionResponse {
+        shared_secret: SHARED_SECRET.to_vec(),
+        verify_token: VERIFY_TOKEN.to_vec(),
+    };
+    let secret = key.decrypt_response(&response, &[0xDE, 0xAD, 0xBE, 0xEF]).unwrap();
+    assert_eq!(&secret, b"0123456789abcdef");
+
+    let error = key.decrypt_response(&response, &[0xDE, 0xAD, 0xBE, 0xEE]).unwrap_err();
+    assert!(matches!(error.kind(), LoginErrorKind::VerifyTokenMismatch));
+
+    let swapped = EncryptionResponse {
+        shared_secret: VERIFY_TOKEN.to_vec(),
+        verify_token: VERIFY_TOKEN.to_vec(),
+    };
+    let error = key.decrypt_response(&swapped, &[0xDE, 0xAD, 0xBE, 0xEF]).unwrap_err();
+    assert!(matches!(error.kind(), LoginErrorKind::InvalidSecretLength(4)));
+
+    let error = key.decrypt(&SHARED_SECRET[1..]).unwrap_err();
+    assert!(matches!(error.kind(), LoginErrorKind::Rsa(_)));
+}
+
+#[test]
+fn handshake() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+    // Server
+    let key = ServerKey::from_pkcs8_der(PRIVATE_KEY).unwrap();
+    let request = key.encryption_request("", [1, 2, 3, 4], true);
+    let bytes = facet_minecraft::to_vec(&request).unwrap();
+    assert_eq!(bytes[..3], [0x00, 0xA2, 0x01]);
+
+    // Client
+    let (request, remaining) = EncryptionRequest::from_slice(&bytes).unwrap();
+    assert!(remaining.is_empty());
+    let secret = generate_secret(&mut rng);
+    let response = request.respond(&mut rng, &secret).unwrap();
+    assert_eq!(response.shared_secret.len(), 128);
+    let bytes = facet_minecraft::to_vec(&response).unwrap();
+
+    // Server
+    let (response, _) = EncryptionResponse::from_slice(&bytes).unwrap();
+    assert_eq!(key.decrypt_response(&response, &[1, 2, 3, 4]).unwrap(), secret);
+    assert_eq!(request.server_hash(&secret), server_hash("", &secret, key.public_key_der()));
+
+    // An invalid public key
+    let invalid = EncryptionRequest { public_key: vec![0x30, 0x00], ..request };
+    let error = invalid.respond(&mut rng, &secret).unwrap_err();
+    assert!(matches!(error.kind(), LoginErrorKind::InvalidKey(_)));
+}
+
+#[test]
+fn generate() {
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+    let key = ServerKey::generate(&mut rng).unwrap();
+    assert_eq!(key.public_key_der().len(), PUBLIC_KEY.len());
+
+    let request = key.encryption_request("", [5; 4], false);
+    let response = request.respond(&mut rng, &[7; 16]).unwrap();
+    assert_eq!(key.decrypt_response(&response, &[5; 4]).unwrap(), [7; 16]);
+}
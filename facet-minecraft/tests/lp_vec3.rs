@@ -0,0 +1,93 @@
+//! Verify [`LpVec3`] against vectors produced by the vanilla encoding.
+#![allow(clippy::float_cmp, reason = "Decoded values are exact")]
+
+use std::io::Cursor;
+
+use facet::Facet;
+use facet_format::DeserializeError as FDError;
+use facet_minecraft::{
+    Deserializable,
+    codec::{McCodec, McReader},
+    deserialize::DeserializeErrorKind,
+    types::LpVec3,
+};
+
+/// A helper struct containing an [`LpVec3`] field.
+#[derive(Debug, PartialEq, Facet)]
+struct Velocity {
+    value: LpVec3,
+}
+
+#[rustfmt::skip]
+const VECTORS: &[([f64; 3], &[u8], [f64; 3])] = &[
+    ([0.0, 0.0, 0.0], &[0x00], [0.0, 0.0, 0.0]),
+    ([1.0E-6, 0.0, 0.0], &[0x00], [0.0, 0.0, 0.0]),
+    ([1.0, 0.0, -1.0], &[0xF1, 0xFF, 0x00, 0x00, 0xFF, 0xFF], [1.0, 0.0, -1.0]),
+    (
+        [0.5, -0.25, 0.125],
+        &[0xF9, 0xFF, 0x8F, 0xFE, 0xBF, 0xFE],
+        [0.500_030_519_440_883_8, -0.250_015_259_720_441_9, 0.125_007_629_860_220_96],
+    ),
+    ([2.0, 3.0, -1.5], &[0x4B, 0x55, 0x40, 0x01, 0xFF, 0xFB], [2.0, 3.0, -1.499_908_441_677_348_5]),
+    ([4.0, 0.0, 0.0], &[0xF4, 0xFF, 0x7F, 0xFE, 0xFF, 0xFF, 0x01], [4.0, 0.0, 0.0]),
+    (
+        [-10.5, 0.0, 100.25],
+        &[0xC5, 0xCA, 0xFF, 0x08, 0xFF, 0xFD, 0x19],
+        [-10.498_870_780_687_3, 0.0, 100.247_878_898_858_56],
+    ),
+    (
+        [LpVec3::ABS_MAX_VALUE, -LpVec3::ABS_MAX_VALUE, 0.0],
+        &[0xF7, 0xFF, 0x7F, 0xFE, 0x00, 0x03, 0xFF, 0xFF, 0xFF, 0xFF, 0x0F],
+        [LpVec3::ABS_MAX_VALUE, -LpVec3::ABS_MAX_VALUE, 0.0],
+    ),
+    ([f64::NAN, 1.0, 0.0], &[0xF9, 0xFF, 0x7F, 0xFF, 0xFF, 0xF9], [0.0, 1.0, 0.0]),
+];
+
+#[test]
+fn encode() {
+    for (input, bytes, _) in VECTORS {
+        let value = LpVec3::from(*input);
+        assert_eq!(facet_minecraft::to_vec(&value).unwrap(), *bytes, "Failed to encode {input:?}");
+        assert_eq!(value.encoded_size(), bytes.len(), "Wrong size for {input:?}");
+    }
+}
+
+#[test]
+fn decode() {
+    for (_, bytes, output) in VECTORS {
+        let (value, remaining) = LpVec3::from_slice(bytes).unwrap();
+        assert_eq!(<[f64; 3]>::from(value), *output, "Failed to decode {bytes:02X?}");
+        assert!(remaining.is_empty());
+
+        let value = LpVec3::from_reader(&mut Cursor::new(bytes)).unwrap();
+        assert_eq!(<[f64; 3]>::from(value), *output, "Failed to stream {bytes:02X?}");
+    }
+}
+
+#[test]
+fn field() {
+    let velocity = Velocity { value: LpVec3::new(2.0, 3.0, -1.5) };
+    let bytes = facet_minecraft::to_vec(&velocity).unwrap();
+    assert_eq!(bytes, [0x4B, 0x55, 0x40, 0x01, 0xFF, 0xFB]);
+
+    let (decoded, remaining) = Velocity::from_slice_borrowed(&[0x00, 0xFF]).unwrap();
+    assert_eq!(decoded, Velocity { value: LpVec3::ZERO });
+    assert_eq!(remaining, [0xFF]);
+}
+
+#[test]
+fn truncated() {
+    for (_, bytes, _) in VECTORS.iter().filter(|(_, bytes, _)| bytes.len() > 1) {
+        for len in 1..bytes.len() {
+            let mut reader = McReader::new(&bytes[..len]);
+            assert!(LpVec3::decode(&mut reader).is_err());
+        }
+    }
+
+    // Errors from the codec are kept when decoding a field
+    let Err(FDError::Parser(error)) = Velocity::from_slice(&[0x4B, 0x55]) else { panic!() };
+    assert!(matches!(
+        error.kind(),
+        DeserializeErrorKind::UnexpectedEndOfInput { expected: 6, found: 2 }
+    ));
+}
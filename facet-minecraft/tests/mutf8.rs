@@ -0,0 +1,139 @@
+//! Verify Modified UTF-8 against a reference implementation.
+#![allow(clippy::std_instead_of_alloc, reason = "`std` example")]
+
+use std::borrow::Cow;
+
+use facet::Facet;
+use facet_minecraft::{
+    self as mc,
+    codec::McReader,
+    mutf8,
+    nbt::{self, NbtValue},
+};
+use pretty_assertions::assert_eq;
+use proptest::prelude::*;
+
+/// A direct port of Java's `DataOutputStream.writeUTF`.
+#[expect(clippy::cast_possible_truncation, reason = "Values are masked")]
+fn reference_encode(value: &str) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for unit in value.encode_utf16() {
+        let unit = u32::from(unit);
+        if (0x0001..=0x007F).contains(&unit) {
+            bytes.push(unit as u8);
+        } else if unit <= 0x07FF {
+            bytes.push((0xC0 | ((unit >> 6) & 0x1F)) as u8);
+            bytes.push((0x80 | (unit & 0x3F)) as u8);
+        } else {
+            bytes.push((0xE0 | ((unit >> 12) & 0x0F)) as u8);
+            bytes.push((0x80 | ((unit >> 6) & 0x3F)) as u8);
+            bytes.push((0x80 | (unit & 0x3F)) as u8);
+        }
+    }
+    bytes
+}
+
+/// A direct port of Java's `DataInputStream.readUTF`,
+/// rejecting unpaired surrogates.
+fn reference_decode(bytes: &[u8]) -> Option<String> {
+    let mut units = Vec::new();
+    let mut index = 0;
+    while index < bytes.len() {
+        let c = u16::from(bytes[index]);
+        let continuation = |offset: usize| {
+            bytes.get(index + offset).filter(|&&b| b & 0xC0 == 0x80).map(|&b| u16::from(b & 0x3F))
+        };
+        match c >> 4 {
+            0..=7 => {
+                units.push(c);
+                index += 1;
+            }
+            12 | 13 => {
+                units.push(((c & 0x1F) << 6) | continuation(1)?);
+                index += 2;
+            }
+            14 => {
+                units.push(((c & 0x0F) << 12) | (continuation(1)? << 6) | continuation(2)?);
+                index += 3;
+            }
+            _ => return None,
+        }
+    }
+    String::from_utf16(&units).ok()
+}
+
+/// Strings with a bias towards characters that are encoded differently.
+fn strings() -> impl Strategy<Value = String> {
+    prop::collection::vec(prop_oneof![Just('\0'), Just('😀'), any::<char>()], 0..32)
+        .prop_map(String::from_iter)
+}
+
+proptest! {
+    #[test]
+    fn encode(value in strings()) {
+        let encoded = mutf8::encode(&value);
+        prop_assert_eq!(encoded.as_ref(), reference_encode(&value));
+        prop_assert_eq!(mutf8::encoded_len(&value), encoded.len());
+        prop_assert!(!encoded.contains(&0));
+        prop_assert_eq!(mutf8::decode(&encoded).unwrap(), value.as_str());
+    }
+
+    #[test]
+    fn decode(bytes in prop::collection::vec(any::<u8>(), 0..32)) {
+        prop_assert_eq!(mutf8::decode(&bytes).ok().map(Cow::into_owned), reference_decode(&bytes));
+    }
+}
+
+#[test]
+fn special() {
+    // `U+0000` is encoded as two bytes
+    assert_eq!(mutf8::encode("a\0b").as_ref(), b"a\xC0\x80b");
+    // Supplementary characters are encoded as surrogate pairs
+    assert_eq!(mutf8::encode("😀").as_ref(), b"\xED\xA0\xBD\xED\xB8\x80");
+    assert_eq!(mutf8::decode(b"\xED\xA0\xBD\xED\xB8\x80").unwrap(), "😀");
+
+    // Strings that are also valid UTF-8 are borrowed
+    assert!(matches!(mutf8::encode("héllo"), Cow::Borrowed(_)));
+    assert!(matches!(mutf8::decode("héllo".as_bytes()).unwrap(), Cow::Borrowed(_)));
+
+    // Standard four-byte sequences and unpaired surrogates are invalid
+    assert_eq!(mutf8::decode("😀".as_bytes()).unwrap_err().valid_up_to(), 0);
+    assert_eq!(mutf8::decode(b"ab\xED\xA0\xBD").unwrap_err().valid_up_to(), 2);
+    assert_eq!(mutf8::decode(b"ab\xED\xB8\x80").unwrap_err().valid_up_to(), 2);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq, Facet)]
+struct Legacy {
+    #[facet(mc::mutf8)]
+    name: String,
+    motd: String,
+    #[facet(mc::mutf8)]
+    kick: Option<String>,
+}
+
+#[test]
+fn fields() {
+    // Fields marked with `mc::mutf8` use a `u16` length prefix
+    let value = Legacy {
+        name: String::from("\0😀"),
+        motd: String::from("hi"),
+        kick: Some(String::from("ab")),
+    };
+    let bytes = mc::to_vec(&value).unwrap();
+    assert_eq!(bytes, b"\x00\x08\xC0\x80\xED\xA0\xBD\xED\xB8\x80\x02hi\x01\x00\x02ab");
+
+    let mut reader = McReader::new(&bytes);
+    assert_eq!(reader.read_mutf8().unwrap(), value.name);
+    assert_eq!(reader.read_str().unwrap(), value.motd);
+
+    let (decoded, remaining) = mc::from_slice::<Legacy>(&bytes).unwrap();
+    assert_eq!(decoded, value);
+    assert!(remaining.is_empty());
+
+    // NBT strings are always Modified UTF-8
+    let bytes = nbt::to_nbt(&NbtValue::from("\0😀")).unwrap();
+    assert_eq!(bytes, b"\x08\x00\x08\xC0\x80\xED\xA0\xBD\xED\xB8\x80");
+    assert_eq!(nbt::from_nbt::<String>(&bytes).unwrap().0, "\0😀");
+}
@@ -0,0 +1,308 @@
+//! Verify NBT encoding and decoding of typed values.
+#![allow(clippy::std_instead_of_alloc, reason = "`std` example")]
+
+use std::collections::BTreeMap;
+
+use facet::Facet;
+use facet_minecraft::{
+    self as mc,
+    nbt::{self, NbtErrorKind, NbtTag},
+};
+use pretty_assertions::assert_eq;
+use uuid::Uuid;
+
+#[derive(Debug, PartialEq, Facet)]
+struct HelloWorld {
+    name: String,
+}
+
+#[test]
+fn hello_world() {
+    // The `hello_world.nbt` test file from the original specification.
+    const BYTES: &[u8] = b"\x0a\x00\x0bhello world\x08\x00\x04name\x00\x09Bananrama\x00";
+
+    let value = HelloWorld { name: String::from("Bananrama") };
+    assert_eq!(nbt::to_nbt_named("hello world", &value).unwrap(), BYTES);
+
+    let (name, decoded, remaining) = nbt::from_nbt_named::<HelloWorld>(BYTES).unwrap();
+    assert_eq!(name, "hello world");
+    assert_eq!(decoded, value);
+    assert!(remaining.is_empty());
+
+    // Network roots omit the name
+    assert_eq!(nbt::to_nbt(&value).unwrap(), [&[0x0a], &BYTES[14..]].concat());
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq, Facet)]
+struct Primitives {
+    flag: bool,
+    byte: i8,
+    short: i16,
+    int: i32,
+    long: i64,
+    float: f32,
+    double: f64,
+    text: String,
+}
+
+#[test]
+fn primitives() {
+    let value = Primitives {
+        flag: true,
+        byte: -1,
+        short: 0x0102,
+        int: 0x0102_0304,
+        long: -2,
+        float: 0.5,
+        double: -0.25,
+        text: String::from("Hi"),
+    };
+
+    let bytes = nbt::to_nbt(&value).unwrap();
+    #[rustfmt::skip]
+    assert_eq!(bytes, [
+        0x0a,
+        0x01, 0x00, 0x04, b'f', b'l', b'a', b'g', 0x01,
+        0x01, 0x00, 0x04, b'b', b'y', b't', b'e', 0xff,
+        0x02, 0x00, 0x05, b's', b'h', b'o', b'r', b't', 0x01, 0x02,
+        0x03, 0x00, 0x03, b'i', b'n', b't', 0x01, 0x02, 0x03, 0x04,
+        0x04, 0x00, 0x04, b'l', b'o', b'n', b'g', 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+        0x05, 0x00, 0x05, b'f', b'l', b'o', b'a', b't', 0x3f, 0x00, 0x00, 0x00,
+        0x06, 0x00, 0x06, b'd', b'o', b'u', b'b', b'l', b'e', 0xbf, 0xd0, 0, 0, 0, 0, 0, 0,
+        0x08, 0x00, 0x04, b't', b'e', b'x', b't', 0x00, 0x02, b'H', b'i',
+        0x00,
+    ]);
+
+    let (decoded, remaining) = nbt::from_nbt::<Primitives>(&bytes).unwrap();
+    assert_eq!(decoded, value);
+    assert!(remaining.is_empty());
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq, Facet)]
+struct Sequences {
+    bytes: Vec<i8>,
+    ints: Vec<i32>,
+    longs: Vec<i64>,
+    names: Vec<String>,
+    empty: Vec<HelloWorld>,
+    nested: Vec<Vec<i16>>,
+}
+
+#[test]
+fn sequences() {
+    let value = Sequences {
+        bytes: vec![1, -1],
+        ints: vec![1],
+        longs: vec![],
+        names: vec![String::from("a"), String::from("b")],
+        empty: vec![],
+        nested: vec![vec![7], vec![]],
+    };
+
+    let bytes = nbt::to_nbt(&value).unwrap();
+    #[rustfmt::skip]
+    assert_eq!(bytes, [
+        0x0a,
+        0x07, 0x00, 0x05, b'b', b'y', b't', b'e', b's', 0, 0, 0, 2, 0x01, 0xff,
+        0x0b, 0x00, 0x04, b'i', b'n', b't', b's', 0, 0, 0, 1, 0, 0, 0, 1,
+        0x0c, 0x00, 0x05, b'l', b'o', b'n', b'g', b's', 0, 0, 0, 0,
+        0x09, 0x00, 0x05, b'n', b'a', b'm', b'e', b's', 0x08, 0, 0, 0, 2, 0, 1, b'a', 0, 1, b'b',
+        0x09, 0x00, 0x05, b'e', b'm', b'p', b't', b'y', 0x00, 0, 0, 0, 0,
+        0x09, 0x00, 0x06, b'n', b'e', b's', b't', b'e', b'd', 0x09, 0, 0, 0, 2,
+            0x02, 0, 0, 0, 1, 0x00, 0x07,
+            0x00, 0, 0, 0, 0,
+        0x00,
+    ]);
+
+    let (decoded, _) = nbt::from_nbt::<Sequences>(&bytes).unwrap();
+    assert_eq!(decoded, value);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq, Facet)]
+struct Optional {
+    present: Option<i32>,
+    absent: Option<i32>,
+    #[facet(default)]
+    defaulted: u16,
+}
+
+#[test]
+fn optional() {
+    let value = Optional { present: Some(3), absent: None, defaulted: 0 };
+
+    let bytes = nbt::to_nbt(&value).unwrap();
+    #[rustfmt::skip]
+    assert_eq!(bytes, [
+        0x0a,
+        0x03, 0x00, 0x07, b'p', b'r', b'e', b's', b'e', b'n', b't', 0, 0, 0, 3,
+        0x02, 0x00, 0x09, b'd', b'e', b'f', b'a', b'u', b'l', b't', b'e', b'd', 0, 0,
+        0x00,
+    ]);
+
+    // Missing optional and default fields are filled in,
+    // and unknown entries are skipped.
+    #[rustfmt::skip]
+    let bytes = [
+        0x0a,
+        0x08, 0x00, 0x03, b'f', b'o', b'o', 0x00, 0x01, b'x',
+        0x03, 0x00, 0x07, b'p', b'r', b'e', b's', b'e', b'n', b't', 0, 0, 0, 3,
+        0x00,
+    ];
+    let (decoded, _) = nbt::from_nbt::<Optional>(&bytes).unwrap();
+    assert_eq!(decoded, value);
+
+    // Missing required fields are an error
+    let error = nbt::from_nbt::<HelloWorld>(&[0x0a, 0x00]).unwrap_err();
+    assert!(matches!(error.kind(), NbtErrorKind::MissingField("name")));
+
+    // An empty network root is `None`
+    assert_eq!(nbt::to_nbt(&None::<HelloWorld>).unwrap(), [0x00]);
+    assert_eq!(nbt::from_nbt::<Option<HelloWorld>>(&[0x00]).unwrap().0, None);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq, Facet)]
+#[repr(u8)]
+enum Shape {
+    Empty,
+    Circle(f32),
+    Square { side: i32 },
+}
+
+#[derive(Debug, PartialEq, Facet)]
+struct Other {
+    id: Uuid,
+    shapes: Vec<Shape>,
+    map: BTreeMap<String, i8>,
+}
+
+#[test]
+fn other() {
+    let value = Other {
+        id: Uuid::from_u128(0x0001_0203_0405_0607_0809_0a0b_0c0d_0e0f),
+        shapes: vec![Shape::Circle(1.0), Shape::Square { side: 2 }],
+        map: BTreeMap::from([(String::from("a"), 1), (String::from("b"), 2)]),
+    };
+
+    let bytes = nbt::to_nbt(&value).unwrap();
+    #[rustfmt::skip]
+    assert_eq!(bytes, [
+        0x0a,
+        0x0b, 0x00, 0x02, b'i', b'd', 0, 0, 0, 4,
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+        0x09, 0x00, 0x06, b's', b'h', b'a', b'p', b'e', b's', 0x0a, 0, 0, 0, 2,
+            0x05, 0x00, 0x06, b'C', b'i', b'r', b'c', b'l', b'e', 0x3f, 0x80, 0x00, 0x00, 0x00,
+            0x0a, 0x00, 0x06, b'S', b'q', b'u', b'a', b'r', b'e',
+                0x03, 0x00, 0x04, b's', b'i', b'd', b'e', 0, 0, 0, 2,
+            0x00, 0x00,
+        0x0a, 0x00, 0x03, b'm', b'a', b'p',
+            0x01, 0x00, 0x01, b'a', 0x01,
+            0x01, 0x00, 0x01, b'b', 0x02,
+        0x00,
+        0x00,
+    ]);
+
+    let (decoded, _) = nbt::from_nbt::<Other>(&bytes).unwrap();
+    assert_eq!(decoded, value);
+
+    // Unit variants are strings
+    let bytes = nbt::to_nbt(&Shape::Empty).unwrap();
+    assert_eq!(bytes, [0x08, 0x00, 0x05, b'E', b'm', b'p', b't', b'y']);
+    assert_eq!(nbt::from_nbt::<Shape>(&bytes).unwrap().0, Shape::Empty);
+
+    // Mismatched tags are an error
+    let error = nbt::from_nbt::<i32>(&[0x01, 0x00]).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        NbtErrorKind::TagMismatch { expected: NbtTag::Int, found: NbtTag::Byte }
+    ));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq, Facet)]
+struct Nested {
+    #[facet(recursive_type)]
+    child: Option<Box<Nested>>,
+}
+
+/// Create a root compound containing `depth - 1` nested compounds.
+fn nested(depth: usize) -> Vec<u8> {
+    let mut bytes = vec![0x0a];
+    for _ in 1..depth {
+        bytes.extend_from_slice(b"\x0a\x00\x05child");
+    }
+    bytes.extend(core::iter::repeat_n(0x00, depth));
+    bytes
+}
+
+#[test]
+fn depth_limit() {
+    // Decoding deeply nested values needs more stack than the default test thread.
+    std::thread::Builder::new()
+        .stack_size(64 * 1024 * 1024)
+        .spawn(depth_limit_inner)
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
+fn depth_limit_inner() {
+    // Compounds nested up to the limit are fine
+    assert!(nbt::from_nbt::<Nested>(&nested(nbt::MAX_DEPTH)).is_ok());
+
+    // Compounds nested deeper than the limit are an error
+    let error = nbt::from_nbt::<Nested>(&nested(nbt::MAX_DEPTH + 1)).unwrap_err();
+    assert!(matches!(error.kind(), NbtErrorKind::DepthLimit));
+
+    // Skipped values are limited as well
+    let mut bytes = vec![0x0a, 0x09, 0x00, 0x01, b'x'];
+    for _ in 0..=nbt::MAX_DEPTH {
+        bytes.extend_from_slice(&[0x09, 0, 0, 0, 1]);
+    }
+    bytes.extend_from_slice(&[0x00, 0, 0, 0, 0]);
+
+    let error = nbt::from_nbt::<Optional>(&bytes).unwrap_err();
+    assert!(matches!(error.kind(), NbtErrorKind::DepthLimit));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq, Facet)]
+struct Embedded {
+    #[facet(mc::nbt)]
+    data: HelloWorld,
+    #[facet(mc::nbt)]
+    missing: Option<HelloWorld>,
+    #[facet(mc::nbt)]
+    present: Option<HelloWorld>,
+    tail: u8,
+}
+
+#[test]
+fn embedded() {
+    let data = HelloWorld { name: String::from("Bananrama") };
+    let present = HelloWorld { name: String::from("x") };
+    let value = Embedded { data, missing: None, present: Some(present), tail: 7 };
+
+    let bytes = mc::to_vec(&value).unwrap();
+    let expected = [
+        nbt::to_nbt(&value.data).unwrap(),
+        vec![0x00],
+        nbt::to_nbt(&value.present).unwrap(),
+        vec![7],
+    ];
+    assert_eq!(bytes, expected.concat());
+
+    let (decoded, remaining) = mc::from_slice::<Embedded>(&bytes).unwrap();
+    assert_eq!(decoded, value);
+    assert!(remaining.is_empty());
+}
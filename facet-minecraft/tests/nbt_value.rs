@@ -0,0 +1,202 @@
+//! Verify decoding, querying and re-encoding of dynamic NBT values.
+
+use facet::Facet;
+use facet_minecraft::{
+    self as mc,
+    nbt::{self, NbtCompound, NbtErrorKind, NbtList, NbtPath, NbtPathNode, NbtTag, NbtValue},
+};
+use pretty_assertions::assert_eq;
+
+/// A player inventory with a single named item.
+#[rustfmt::skip]
+const INVENTORY: &[u8] = &[
+    0x0a,
+    0x09, 0x00, 0x05, b'I', b't', b'e', b'm', b's', 0x0a, 0, 0, 0, 1,
+        0x08, 0x00, 0x02, b'i', b'd', 0x00, 0x05, b's', b't', b'o', b'n', b'e',
+        0x0a, 0x00, 0x0a, b'c', b'o', b'm', b'p', b'o', b'n', b'e', b'n', b't', b's',
+            0x08, 0x00, 0x15,
+                b'm', b'i', b'n', b'e', b'c', b'r', b'a', b'f', b't', b':',
+                b'c', b'u', b's', b't', b'o', b'm', b'_', b'n', b'a', b'm', b'e',
+                0x00, 0x04, b'R', b'o', b'c', b'k',
+        0x00,
+        0x00,
+    // Empty lists keep their element type
+    0x09, 0x00, 0x05, b'E', b'm', b'p', b't', b'y', 0x03, 0, 0, 0, 0,
+    0x0c, 0x00, 0x04, b'L', b'o', b'n', b'g', 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 7,
+    // Entries are not sorted
+    0x01, 0x00, 0x01, b'B', 0x01,
+    0x01, 0x00, 0x01, b'A', 0x02,
+    0x00,
+];
+
+#[test]
+fn lossless() {
+    let (value, remaining) = nbt::from_nbt::<NbtValue>(INVENTORY).unwrap();
+    assert!(remaining.is_empty());
+
+    let root = value.as_compound().unwrap();
+    assert_eq!(root.keys().collect::<Vec<_>>(), ["Items", "Empty", "Long", "B", "A"]);
+    assert_eq!(root.get("Empty").and_then(NbtValue::as_list).map(NbtList::tag), Some(NbtTag::Int));
+    assert_eq!(root.get("Long"), Some(&NbtValue::LongArray(vec![7])));
+
+    assert_eq!(nbt::to_nbt(&value).unwrap(), INVENTORY);
+
+    // Compounds can be decoded directly
+    let (compound, _) = nbt::from_nbt::<NbtCompound>(INVENTORY).unwrap();
+    assert_eq!(Some(&compound), value.as_compound());
+    assert_eq!(nbt::to_nbt(&compound).unwrap(), INVENTORY);
+}
+
+#[test]
+fn path() {
+    let (mut value, _) = nbt::from_nbt::<NbtValue>(INVENTORY).unwrap();
+
+    let path: NbtPath = r#"Items[0].components."minecraft:custom_name""#.parse().unwrap();
+    assert_eq!(
+        path.nodes(),
+        [
+            NbtPathNode::Key(String::from("Items")),
+            NbtPathNode::Index(0),
+            NbtPathNode::Key(String::from("components")),
+            NbtPathNode::Key(String::from("minecraft:custom_name")),
+        ]
+    );
+    // Keys are only quoted when needed
+    assert_eq!(path.to_string(), "Items[0].components.minecraft:custom_name");
+    assert_eq!(path.to_string().parse::<NbtPath>().unwrap(), path);
+    let quoted = NbtPath::from(vec![NbtPathNode::Key(String::from(r#"a "b""#))]);
+    assert_eq!(quoted.to_string(), r#""a \"b\"""#);
+    assert_eq!(quoted.to_string().parse::<NbtPath>().unwrap(), quoted);
+    assert_eq!(value.get_path(&path).and_then(NbtValue::as_str), Some("Rock"));
+
+    // Negative indices count from the end, and `[]` selects every element
+    let path: NbtPath = "Items[-1].id".parse().unwrap();
+    assert_eq!(value.get_path(&path).and_then(NbtValue::as_str), Some("stone"));
+    let path: NbtPath = "Items[].'id'".parse().unwrap();
+    assert_eq!(path.get(&value).len(), 1);
+    assert!(value.get_path(&"Items[1].id".parse().unwrap()).is_none());
+
+    // Values can be changed in place
+    let path: NbtPath = "Items[0].Count".parse().unwrap();
+    assert_eq!(path.set(&mut value, NbtValue::Byte(3)).unwrap(), 1);
+    *value.get_path_mut(&path).unwrap() = NbtValue::Byte(4);
+    assert_eq!(value.get_path(&path), Some(&NbtValue::Byte(4)));
+
+    // Missing compounds are created
+    let path: NbtPath = "Items[0].tag.display.Name".parse().unwrap();
+    assert_eq!(path.set(&mut value, "Pebble".into()).unwrap(), 1);
+    assert_eq!(value.get_path(&path).and_then(NbtValue::as_str), Some("Pebble"));
+
+    // List elements must keep the same type
+    let path: NbtPath = "Items[0]".parse().unwrap();
+    let error = path.set(&mut value, NbtValue::Int(0)).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        NbtErrorKind::HeterogeneousList { expected: NbtTag::Compound, found: NbtTag::Int }
+    ));
+
+    // Values can be removed
+    assert_eq!("Items[0].tag".parse::<NbtPath>().unwrap().remove(&mut value), 1);
+    assert_eq!("Items[0].Count".parse::<NbtPath>().unwrap().remove(&mut value), 1);
+    assert_eq!(nbt::to_nbt(&value).unwrap(), INVENTORY);
+    assert_eq!("Items[]".parse::<NbtPath>().unwrap().remove(&mut value), 1);
+    assert!("Items[0]".parse::<NbtPath>().unwrap().get(&value).is_empty());
+
+    // Invalid paths report the offset of the error
+    for (input, offset) in [("", 0), ("a.", 2), ("a[x]", 2), ("a[0", 3), ("'a", 0), ("a b", 1)] {
+        let error = input.parse::<NbtPath>().unwrap_err();
+        assert!(
+            matches!(error.kind(), NbtErrorKind::InvalidPath(pos) if *pos == offset),
+            "{input}"
+        );
+    }
+}
+
+#[test]
+fn mutation() {
+    let mut list = NbtList::new();
+    list.push(NbtValue::Int(1)).unwrap();
+    assert_eq!(list.tag(), NbtTag::Int);
+    assert!(list.push(NbtValue::Short(2)).is_err());
+
+    let mut compound: NbtCompound =
+        [("b", NbtValue::from(1i8)), ("a", NbtValue::from(true))].into_iter().collect();
+    compound.insert("list", list);
+    assert_eq!(compound.insert("b", 2i8), Some(NbtValue::Byte(1)));
+
+    let value = NbtValue::Compound(compound);
+    #[rustfmt::skip]
+    assert_eq!(nbt::to_nbt(&value).unwrap(), [
+        0x0a,
+        0x01, 0x00, 0x01, b'b', 0x02,
+        0x01, 0x00, 0x01, b'a', 0x01,
+        0x09, 0x00, 0x04, b'l', b'i', b's', b't', 0x03, 0, 0, 0, 1, 0, 0, 0, 1,
+        0x00,
+    ]);
+
+    // Elements replaced through `get_mut` are checked when encoding
+    let mut value = value;
+    let path = NbtPath::from(vec![NbtPathNode::Key(String::from("list")), NbtPathNode::Index(0)]);
+    *value.get_path_mut(&path).unwrap() = NbtValue::Long(1);
+    let error = nbt::to_nbt(&value).unwrap_err();
+    assert!(matches!(error.kind(), NbtErrorKind::HeterogeneousList { .. }));
+}
+
+#[test]
+fn depth_limit() {
+    // Decoding deeply nested values needs more stack than the default test thread.
+    std::thread::Builder::new()
+        .stack_size(64 * 1024 * 1024)
+        .spawn(depth_limit_inner)
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
+fn depth_limit_inner() {
+    /// Create a root list containing `depth - 1` nested lists.
+    fn nested(depth: usize) -> Vec<u8> {
+        let mut bytes = vec![0x09];
+        for _ in 1..depth {
+            bytes.extend_from_slice(&[0x09, 0, 0, 0, 1]);
+        }
+        bytes.extend_from_slice(&[0x00, 0, 0, 0, 0]);
+        bytes
+    }
+
+    assert!(nbt::from_nbt::<NbtValue>(&nested(nbt::MAX_DEPTH)).is_ok());
+    let error = nbt::from_nbt::<NbtValue>(&nested(nbt::MAX_DEPTH + 1)).unwrap_err();
+    assert!(matches!(error.kind(), NbtErrorKind::DepthLimit));
+
+    // Lists of `TAG_End` cannot contain elements
+    let error = nbt::from_nbt::<NbtValue>(&[0x09, 0x00, 0x7f, 0xff, 0xff, 0xff]).unwrap_err();
+    assert!(matches!(error.kind(), NbtErrorKind::InvalidTag(0)));
+
+    // Arrays longer than the input are rejected before allocating
+    let error = nbt::from_nbt::<NbtValue>(&[0x0c, 0x7f, 0xff, 0xff, 0xff]).unwrap_err();
+    assert!(matches!(error.kind(), NbtErrorKind::UnexpectedEndOfInput { .. }));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq, Facet)]
+struct Item {
+    id: String,
+    components: NbtCompound,
+}
+
+#[test]
+fn typed() {
+    let (value, _) = nbt::from_nbt::<NbtValue>(INVENTORY).unwrap();
+    let item = value.get_path(&"Items[0]".parse().unwrap()).unwrap();
+
+    // Dynamic values can be used inside of typed values
+    let (typed, _) = nbt::from_nbt::<Item>(&nbt::to_nbt(item).unwrap()).unwrap();
+    assert_eq!(typed.id, "stone");
+    assert_eq!(typed.components.len(), 1);
+    assert_eq!(nbt::to_nbt(&typed).unwrap(), nbt::to_nbt(item).unwrap());
+
+    // And in the network protocol
+    assert_eq!(mc::to_vec(&value).unwrap(), INVENTORY);
+    assert_eq!(mc::to_vec(&typed.components).unwrap(), nbt::to_nbt(&typed.components).unwrap());
+}
@@ -0,0 +1,231 @@
+//! Verify [`PacketSet`] dispatch created by [`packet_set!`].
+
+use facet::Facet;
+use facet_minecraft::{
+    self as mc,
+    frame::{Frame, PacketCodec},
+    packet::{Direction, Packet, PacketErrorKind, PacketSet, ProtocolState, ProtocolVersion},
+    packet_set,
+    types::Identifier,
+};
+
+/// A packet with fixed-size fields.
+#[derive(Debug, PartialEq, Facet)]
+struct Intention {
+    protocol: u32,
+    address: String,
+    port: u16,
+    intent: u8,
+}
+
+impl Packet for Intention {
+    const DIRECTION: Direction = Direction::Serverbound;
+    const ID: u32 = 0x00;
+    const STATE: ProtocolState = ProtocolState::Handshake;
+}
+
+/// A packet borrowing from its body.
+#[derive(Debug, PartialEq, Facet)]
+struct Brand<'a> {
+    channel: Identifier<'a>,
+    brand: &'a str,
+}
+
+impl Packet for Brand<'_> {
+    const DIRECTION: Direction = Direction::Serverbound;
+    const ID: u32 = 0x7F;
+    const STATE: ProtocolState = ProtocolState::Handshake;
+}
+
+packet_set! {
+    /// Packets sent by the client during the handshake.
+    #[derive(Debug, PartialEq)]
+    pub enum Handshake<'a>(Handshake, Serverbound) {
+        /// Begin the connection.
+        Intention(Intention),
+        /// Send the client brand.
+        Brand(Brand<'a>),
+    }
+}
+
+packet_set! {
+    /// An owned set of packets.
+    #[derive(Debug, PartialEq)]
+    pub enum OwnedHandshake(Handshake, Serverbound) {
+        /// Begin the connection.
+        Intention(Intention),
+    }
+}
+
+/// `Intention` for `localhost:25565` with protocol `772` and intent `2`.
+const INTENTION: &[u8] = b"\x00\x00\x03\x04\x09localhost\x63\xDD\x02";
+
+#[test]
+fn decode() {
+    let packet =
+        Handshake::decode(ProtocolState::Handshake, Direction::Serverbound, 0x00, INTENTION)
+            .unwrap();
+    let intention =
+        Intention { protocol: 772, address: String::from("localhost"), port: 25565, intent: 2 };
+    assert_eq!(packet, Handshake::Intention(intention));
+    assert_eq!(packet.id(), 0x00);
+
+    let body = b"\x0Fminecraft:brand\x07vanilla";
+    let Handshake::Brand(brand) = Handshake::decode_packet(0x7F, body).unwrap() else { panic!() };
+    assert!(brand.channel.is_borrowed());
+    assert_eq!(brand.brand, "vanilla");
+
+    let packet = OwnedHandshake::decode_packet(0x00, INTENTION).unwrap();
+    assert_eq!(packet.id(), 0x00);
+}
+
+#[test]
+fn encode() {
+    let intention =
+        Intention { protocol: 772, address: String::from("localhost"), port: 25565, intent: 2 };
+    let packet = Handshake::from(intention);
+
+    let mut buffer = Vec::new();
+    packet.encode_body(&mut buffer).unwrap();
+    assert_eq!(buffer, INTENTION);
+
+    let mut buffer = Vec::new();
+    packet.encode(&mut buffer).unwrap();
+    assert_eq!(buffer[0], 0x00);
+    assert_eq!(&buffer[1..], INTENTION);
+
+    let packet = Handshake::from(Brand { channel: Identifier::new_unchecked("brand"), brand: "a" });
+    let mut buffer = Vec::new();
+    packet.encode(&mut buffer).unwrap();
+    assert_eq!(buffer, b"\x7F\x05brand\x01a");
+}
+
+#[test]
+fn errors() {
+    // Unknown packets keep their body
+    let error = Handshake::decode_packet(0x05, &[1, 2, 3]).unwrap_err();
+    assert!(error.is_unknown());
+    let PacketErrorKind::UnknownId { state, direction, id, body } = error.into_kind() else {
+        panic!()
+    };
+    assert_eq!((state, direction, id), (ProtocolState::Handshake, Direction::Serverbound, 0x05));
+    assert_eq!(body, [1, 2, 3]);
+
+    let error = Handshake::decode(ProtocolState::Play, Direction::Serverbound, 0x00, INTENTION)
+        .unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        PacketErrorKind::WrongState {
+            state: ProtocolState::Play,
+            direction: Direction::Serverbound
+        }
+    ));
+
+    let mut body = INTENTION.to_vec();
+    body.push(0);
+    let error = Handshake::decode_packet(0x00, &body).unwrap_err();
+    assert!(matches!(error.kind(), PacketErrorKind::TrailingBytes(1)));
+
+    let error = Handshake::decode_packet(0x00, &INTENTION[..4]).unwrap_err();
+    assert!(matches!(error.kind(), PacketErrorKind::Deserialize(_)));
+}
+
+#[test]
+#[cfg(feature = "compression")]
+fn frame() {
+    let codec = PacketCodec::with_compression(0);
+    let mut buffer = Vec::new();
+    codec.encode_frame(0x00, INTENTION, &mut buffer).unwrap();
+
+    let (frame, _) = codec.decode_frame(&buffer).unwrap().unwrap();
+    assert!(matches!(frame.decode_packet::<Handshake>().unwrap(), Handshake::Intention(_)));
+
+    let frame = Frame::new(0x01, INTENTION);
+    assert!(frame.decode_packet::<Handshake>().unwrap_err().is_unknown());
+}
+
+/// A packet whose ID changed between versions.
+#[derive(Debug, PartialEq, Facet)]
+struct KeepAlive {
+    id: u64,
+    #[facet(mc::since = 770)]
+    extra: u8,
+}
+
+impl Packet for KeepAlive {
+    const DIRECTION: Direction = Direction::Serverbound;
+    const ID: u32 = 0x1B;
+    const IDS: &'static [(ProtocolVersion, u32)] = &[
+        (ProtocolVersion::V1_20_5, 0x18),
+        (ProtocolVersion::V1_21_2, 0x1A),
+        (ProtocolVersion::V1_21_6, 0x1B),
+        (ProtocolVersion::snapshot(200), 0x20),
+    ];
+    const STATE: ProtocolState = ProtocolState::Play;
+}
+
+packet_set! {
+    /// Packets sent by the client while playing.
+    #[derive(Debug, PartialEq)]
+    pub enum Play(Play, Serverbound) {
+        /// Keep the connection alive.
+        KeepAlive(KeepAlive),
+    }
+}
+
+#[test]
+fn versions() {
+    let version = |name| ProtocolVersion::from_name(name).unwrap();
+    assert_eq!(version("1.21.1"), ProtocolVersion::V1_21);
+    assert_eq!(version("1.21.3").protocol(), 768);
+    assert_eq!(version("snapshot 201"), ProtocolVersion::snapshot(201));
+    assert_eq!(ProtocolVersion::from_name("1.8.9"), None);
+    assert_eq!(ProtocolVersion::V1_21_4.to_string(), "1.21.4 (769)");
+    assert_eq!(ProtocolVersion::new(47).to_string(), "protocol 47");
+
+    assert_eq!(KeepAlive::id_for(ProtocolVersion::V1_20_5).unwrap(), 0x18);
+    assert_eq!(KeepAlive::id_for(ProtocolVersion::V1_21).unwrap(), 0x18);
+    assert_eq!(KeepAlive::id_for(version("1.21.4")).unwrap(), 0x1A);
+    assert_eq!(KeepAlive::id_for(ProtocolVersion::LATEST).unwrap(), 0x1B);
+    assert_eq!(KeepAlive::id_for(ProtocolVersion::snapshot(210)).unwrap(), 0x20);
+    assert_eq!(Intention::id_for(ProtocolVersion::new(47)).unwrap(), 0x00);
+
+    // Each version encodes its own ID and fields
+    let packet = Play::from(KeepAlive { id: 1, extra: 2 });
+    let mut buffer = Vec::new();
+    packet.encode_versioned(ProtocolVersion::V1_21_4, &mut buffer).unwrap();
+    assert_eq!(buffer, [0x1A, 0, 0, 0, 0, 0, 0, 0, 1]);
+
+    let mut buffer = Vec::new();
+    packet.encode_versioned(ProtocolVersion::V1_21_6, &mut buffer).unwrap();
+    assert_eq!(buffer, [0x1B, 0, 0, 0, 0, 0, 0, 0, 1, 2]);
+
+    let decoded = Play::decode_packet_versioned(ProtocolVersion::V1_21_6, 0x1B, &buffer[1..]);
+    assert_eq!(decoded.unwrap(), packet);
+    let decoded = Play::decode_packet_versioned(ProtocolVersion::V1_21_4, 0x1A, &buffer[1..9]);
+    assert_eq!(decoded.unwrap(), Play::from(KeepAlive { id: 1, extra: 0 }));
+    assert!(
+        Play::decode_packet_versioned(ProtocolVersion::V1_21_4, 0x1B, &buffer[1..9])
+            .unwrap_err()
+            .is_unknown()
+    );
+}
+
+#[test]
+fn version_errors() {
+    let error = KeepAlive::id_for(ProtocolVersion::V1_20_3).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        PacketErrorKind::UnsupportedVersion { version: ProtocolVersion::V1_20_3, .. }
+    ));
+    assert_eq!(error.to_string(), "packet packet::KeepAlive has no ID in version 1.20.4 (765)");
+
+    // Snapshots only use snapshot IDs
+    let error = KeepAlive::id_for(ProtocolVersion::snapshot(100)).unwrap_err();
+    assert_eq!(error.to_string(), "packet packet::KeepAlive has no ID in version snapshot 100");
+
+    let mut buffer = Vec::new();
+    let packet = Play::from(KeepAlive { id: 1, extra: 2 });
+    assert!(packet.encode_versioned(ProtocolVersion::V1_20_2, &mut buffer).is_err());
+    assert!(buffer.is_empty());
+}
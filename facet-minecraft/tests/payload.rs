@@ -0,0 +1,103 @@
+//! Verify [`ChannelRegistry`] dispatch and the built-in channels.
+
+use facet::Facet;
+use facet_format::DeserializeError as FDError;
+use facet_minecraft::{
+    deserialize::DeserializeErrorKind,
+    packet::PacketErrorKind,
+    payload::{Brand, ChannelList, ChannelRegistry, CustomPayload, Payload},
+    types::Identifier,
+};
+
+/// A helper struct modelling a plugin's handshake.
+#[derive(Debug, PartialEq, Facet)]
+struct Handshake {
+    version: u8,
+    flags: u16,
+}
+
+/// Encode a payload into a Plugin Message body.
+fn body(payload: &CustomPayload) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    payload.encode_body(&mut bytes).unwrap();
+    bytes
+}
+
+#[test]
+fn builtin() {
+    let registry = ChannelRegistry::new();
+    assert_eq!(registry.channels().count(), 3);
+    assert!(registry.contains(&Identifier::new_unchecked("brand")));
+
+    let brand = CustomPayload::new(ChannelRegistry::BRAND, Brand::new(Brand::VANILLA));
+    let bytes = body(&brand);
+    assert_eq!(bytes, [b"\x0fminecraft:brand\x07".as_slice(), b"vanilla"].concat());
+    let decoded = registry.decode(&bytes).unwrap();
+    assert_eq!(decoded.channel, ChannelRegistry::BRAND);
+    assert_eq!(decoded.downcast_ref::<Brand>().map(Brand::as_str), Some("vanilla"));
+
+    let channels: ChannelList =
+        ["example:a", "example:b"].into_iter().map(Identifier::new_unchecked).collect();
+    let bytes = body(&CustomPayload::new(ChannelRegistry::REGISTER, channels.clone()));
+    assert_eq!(&bytes[19..], b"example:a\0example:b");
+    assert_eq!(registry.decode(&bytes).unwrap().downcast_ref(), Some(&channels));
+
+    // Empty entries are skipped
+    let mut bytes = body(&CustomPayload::raw(ChannelRegistry::UNREGISTER, b"a\0\0b\0".to_vec()));
+    let expected =
+        ChannelList(vec![Identifier::new_unchecked("a"), Identifier::new_unchecked("b")]);
+    assert_eq!(registry.decode(&bytes).unwrap().downcast_ref(), Some(&expected));
+    bytes.truncate(21);
+    assert_eq!(registry.decode(&bytes).unwrap().downcast_ref(), Some(&ChannelList::default()));
+}
+
+#[test]
+fn custom() {
+    let channel = Identifier::new_unchecked("example:handshake");
+    let mut registry = ChannelRegistry::new();
+    let payload = CustomPayload::new(channel.clone(), Handshake { version: 1, flags: 0x0102 });
+    let bytes = body(&payload);
+    assert_eq!(&bytes[18..], [1, 1, 2]);
+
+    // Unknown channels are kept as raw bytes
+    let decoded = registry.decode(&bytes).unwrap();
+    assert_eq!(decoded.raw_data(), Some([1, 1, 2].as_slice()));
+    assert_eq!(decoded.downcast_ref::<Handshake>(), None);
+    assert_eq!(body(&decoded), bytes);
+
+    registry.register::<Handshake>(channel.clone());
+    let decoded = registry.decode(&bytes).unwrap();
+    assert_eq!(decoded.downcast_ref(), Some(&Handshake { version: 1, flags: 0x0102 }));
+    assert_eq!(decoded.raw_data(), None);
+    assert_eq!(body(&decoded), bytes);
+
+    let Payload::Typed(typed) = decoded.payload else { panic!() };
+    assert!(typed.is::<Handshake>());
+    let typed = typed.downcast::<Brand>().unwrap_err();
+    assert_eq!(typed.downcast::<Handshake>().unwrap().version, 1);
+
+    // Registering a channel again replaces its type
+    registry.register::<Brand>(channel.clone());
+    assert_eq!(registry.channels().count(), 4);
+    assert!(registry.unregister(&channel));
+    assert!(!registry.unregister(&channel));
+    assert!(registry.decode(&bytes).unwrap().raw_data().is_some());
+    assert_eq!(ChannelRegistry::empty().channels().count(), 0);
+}
+
+#[test]
+fn errors() {
+    let registry = ChannelRegistry::new();
+
+    let mut bytes = body(&CustomPayload::new(ChannelRegistry::BRAND, Brand::new("a")));
+    bytes.push(0);
+    let error = registry.decode(&bytes).unwrap_err();
+    assert!(matches!(error.kind(), PacketErrorKind::TrailingBytes(1)));
+
+    let error = registry.decode(b"\x03A:b").unwrap_err();
+    let PacketErrorKind::Deserialize(FDError::Parser(error)) = error.kind() else { panic!() };
+    assert!(matches!(error.kind(), DeserializeErrorKind::InvalidIdentifier(_)));
+
+    let bytes = body(&CustomPayload::raw(ChannelRegistry::REGISTER, b"a\0B".to_vec()));
+    assert!(registry.decode(&bytes).is_err());
+}
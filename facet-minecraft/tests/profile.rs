@@ -0,0 +1,107 @@
+//! Verify [`GameProfile`] encodings and offline-mode UUIDs.
+
+use facet::Facet;
+use facet_minecraft::{
+    codec::{McCodec, McReader, McWriter},
+    deserialize::DeserializeErrorKind,
+    profile::{GameProfile, ProfileErrorKind, ProfileProperty},
+    serialize::SerializeErrorKind,
+};
+use uuid::{Uuid, Version};
+
+/// A helper struct modelling the Login Success packet.
+#[derive(Debug, PartialEq, Facet)]
+struct LoginSuccess {
+    profile: GameProfile,
+}
+
+#[test]
+#[cfg(feature = "offline")]
+fn offline() {
+    use facet_minecraft::profile::offline_uuid;
+
+    // Computed with Java's `UUID.nameUUIDFromBytes`
+    assert_eq!(offline_uuid("Notch"), Uuid::from_u128(0xB50A_D385_829D_3141_A216_7E7D_7539_BA7F));
+    assert_eq!(offline_uuid("jeb_"), Uuid::from_u128(0xA762_F560_4FCE_3236_812A_B80E_FFF0_B62B));
+    assert_eq!(offline_uuid(""), Uuid::from_u128(0xFC5B_C365_AEDF_30A8_8B89_04E4_62E2_9BDE));
+    assert_eq!(offline_uuid("Notch").get_version(), Some(Version::Md5));
+
+    let profile = GameProfile::offline("Notch");
+    assert_eq!(profile, GameProfile::new(offline_uuid("Notch"), "Notch"));
+}
+
+#[test]
+fn wire() {
+    let mut profile = GameProfile::new(Uuid::from_u128(1), "Steve");
+    let bytes = facet_minecraft::to_vec(&profile).unwrap();
+    assert_eq!(&bytes[..16], Uuid::from_u128(1).as_bytes());
+    assert_eq!(&bytes[16..], [&[5][..], b"Steve", &[0]].concat());
+
+    profile.properties.push(ProfileProperty::signed("textures", "e30=", "c2ln"));
+    profile.properties.push(ProfileProperty::new("a", ""));
+    let bytes = facet_minecraft::to_vec(&profile).unwrap();
+    let expected = [
+        &[5][..],
+        b"Steve",
+        &[2, 8],
+        b"textures",
+        &[4],
+        b"e30=",
+        &[1, 4],
+        b"c2ln",
+        &[1, b'a', 0, 0],
+    ]
+    .concat();
+    assert_eq!(&bytes[16..], expected);
+
+    assert_eq!(profile.property("textures").and_then(|p| p.signature.as_deref()), Some("c2ln"));
+    assert_eq!(profile.property("b"), None);
+
+    let login = LoginSuccess { profile };
+    let bytes = facet_minecraft::to_vec(&login).unwrap();
+    let (decoded, remaining) = facet_minecraft::from_slice::<LoginSuccess>(&bytes).unwrap();
+    assert_eq!(decoded, login);
+    assert!(remaining.is_empty());
+}
+
+#[test]
+fn errors() {
+    let check = |profile: &GameProfile, expected: fn(&ProfileErrorKind) -> bool| {
+        let error = profile.encode(&mut McWriter::new(&mut Vec::new())).unwrap_err();
+        let SerializeErrorKind::Profile(error) = error.kind() else { panic!() };
+        assert!(expected(error.kind()), "{:?}", error.kind());
+    };
+
+    // Names are limited to 16 UTF-16 code units
+    let profile = GameProfile::new(Uuid::nil(), "é".repeat(16));
+    profile.encode(&mut McWriter::new(&mut Vec::new())).unwrap();
+    let profile = GameProfile::new(Uuid::nil(), "a".repeat(17));
+    check(&profile, |kind| matches!(kind, ProfileErrorKind::NameTooLong(17)));
+
+    let mut profile = GameProfile::new(Uuid::nil(), "a");
+    profile.properties = vec![ProfileProperty::new("a", "b"); 17];
+    check(&profile, |kind| matches!(kind, ProfileErrorKind::TooManyProperties(17)));
+
+    profile.properties = vec![ProfileProperty::signed("a", "b", "c".repeat(1025))];
+    check(&profile, |kind| matches!(kind, ProfileErrorKind::PropertyTooLong(1025)));
+
+    // Decoding checks the same limits
+    let mut bytes = vec![0; 16];
+    bytes.push(17);
+    bytes.extend([b'a'; 17]);
+    let error = GameProfile::decode(&mut McReader::new(&bytes)).unwrap_err();
+    let DeserializeErrorKind::Profile(error) = error.kind() else { panic!() };
+    assert!(matches!(error.kind(), ProfileErrorKind::NameTooLong(17)));
+
+    let mut bytes = vec![0; 16];
+    bytes.extend([1, b'a', 17]);
+    let error = GameProfile::decode(&mut McReader::new(&bytes)).unwrap_err();
+    let DeserializeErrorKind::Profile(error) = error.kind() else { panic!() };
+    assert!(matches!(error.kind(), ProfileErrorKind::TooManyProperties(17)));
+
+    let mut bytes = vec![65];
+    bytes.extend([b'a'; 65]);
+    let error = ProfileProperty::decode(&mut McReader::new(&bytes)).unwrap_err();
+    let DeserializeErrorKind::Profile(error) = error.kind() else { panic!() };
+    assert!(matches!(error.kind(), ProfileErrorKind::PropertyTooLong(65)));
+}
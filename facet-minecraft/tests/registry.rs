@@ -0,0 +1,125 @@
+//! Verify [`IdOr`] and [`IdSet`] registry holder encodings.
+
+mod common;
+
+use common::roundtrip;
+use facet::Facet;
+use facet_minecraft::{
+    self as mc,
+    codec::{McCodec, McReader, McWriter},
+    deserialize::DeserializeErrorKind,
+    serialize::SerializeErrorKind,
+    types::{IdOr, IdSet, Identifier},
+};
+
+/// A helper struct modelling a sound event,
+/// either from the registry or defined inline.
+#[derive(Debug, Clone, PartialEq, Facet)]
+struct PlaySound {
+    sound: IdOr<Identifier<'static>>,
+    blocks: IdSet,
+    volume: f32,
+}
+
+/// A helper struct modelling a sound event defined inline.
+#[derive(Debug, Clone, PartialEq, Facet)]
+struct SoundEvent {
+    name: Identifier<'static>,
+    fixed_range: Option<f32>,
+    #[facet(mc::since = 767)]
+    pitch: u8,
+}
+
+#[test]
+fn id_or() {
+    assert_eq!(roundtrip::<IdOr<Identifier>>(&IdOr::Id(0)), [1]);
+    assert_eq!(roundtrip::<IdOr<Identifier>>(&IdOr::Id(127)), [0x80, 0x01]);
+    let inline = IdOr::Inline(Identifier::new_unchecked("minecraft:x"));
+    assert_eq!(roundtrip::<IdOr<Identifier>>(&inline), b"\0\x0bminecraft:x");
+
+    let sound = IdOr::<Identifier>::Id(3);
+    assert_eq!(sound.id(), Some(3));
+    assert_eq!(sound.inline(), None);
+    let sound = IdOr::from(Identifier::new_unchecked("minecraft:x"));
+    assert_eq!(sound.id(), None);
+    assert_eq!(sound.inline().map(Identifier::as_str), Some("minecraft:x"));
+
+    // Inline values may themselves be holders
+    assert_eq!(roundtrip::<IdOr<IdOr<Identifier>>>(&IdOr::Inline(IdOr::Id(4))), [0, 5]);
+}
+
+#[test]
+fn id_or_struct() {
+    let name = Identifier::new_unchecked("minecraft:x");
+    assert_eq!(roundtrip::<IdOr<SoundEvent>>(&IdOr::Id(2)), [3]);
+
+    let sound = IdOr::Inline(SoundEvent { name: name.clone(), fixed_range: Some(16.0), pitch: 1 });
+    let bytes = [&b"\0\x0bminecraft:x"[..], &[1, 0x41, 0x80, 0, 0], &[1]].concat();
+    assert_eq!(roundtrip(&sound), bytes);
+    let sound = IdOr::Inline(SoundEvent { name: name.clone(), fixed_range: None, pitch: 0 });
+    assert_eq!(roundtrip(&sound), b"\0\x0bminecraft:x\0\0");
+
+    // Inline values are encoded for the same version
+    let sound = IdOr::Inline(SoundEvent { name, fixed_range: None, pitch: 0 });
+    let bytes = mc::to_vec_versioned(&sound, 766).unwrap();
+    assert_eq!(bytes, b"\0\x0bminecraft:x\0");
+    let (decoded, remaining) = mc::from_slice_versioned::<IdOr<SoundEvent>>(&bytes, 766).unwrap();
+    assert_eq!((decoded, remaining), (sound, &[][..]));
+
+    // The inline value ends early
+    let error = IdOr::<SoundEvent>::decode(&mut McReader::new(b"\0\x0bminecraft:x")).unwrap_err();
+    assert!(matches!(error.kind(), DeserializeErrorKind::UnexpectedEndOfInput { .. }));
+}
+
+#[test]
+fn id_set() {
+    assert_eq!(roundtrip(&IdSet::EMPTY), [1]);
+    assert_eq!(roundtrip(&IdSet::Ids(vec![0, 300])), [3, 0, 0xAC, 0x02]);
+    let tag = IdSet::Tag(Identifier::new_unchecked("minecraft:logs"));
+    assert_eq!(roundtrip(&tag), b"\0\x0eminecraft:logs");
+
+    let set: IdSet = [7, 8].into_iter().collect();
+    assert_eq!(set.ids(), Some(&[7, 8][..]));
+    assert_eq!(set.tag(), None);
+    assert_eq!(IdSet::default(), IdSet::EMPTY);
+}
+
+#[test]
+fn fields() {
+    let value = PlaySound {
+        sound: IdOr::Id(9),
+        blocks: IdSet::Tag(Identifier::new_unchecked("minecraft:wool")),
+        volume: 1.0,
+    };
+    assert_eq!(
+        roundtrip(&value),
+        [&[10, 0, 14][..], b"minecraft:wool", &[0x3F, 0x80, 0, 0]].concat()
+    );
+
+    let value = PlaySound {
+        sound: IdOr::Inline(Identifier::new_unchecked("minecraft:a")),
+        blocks: IdSet::Ids(vec![1]),
+        volume: 0.0,
+    };
+    assert_eq!(roundtrip(&value), [&[0, 11][..], b"minecraft:a", &[2, 1, 0, 0, 0, 0]].concat());
+}
+
+#[test]
+fn errors() {
+    // The set claims two IDs but contains one
+    let error = IdSet::decode(&mut McReader::new(&[3, 1])).unwrap_err();
+    assert!(matches!(error.kind(), DeserializeErrorKind::UnexpectedEndOfInput { .. }));
+
+    // The inline value is missing
+    let error = IdOr::<Identifier>::decode(&mut McReader::new(&[0])).unwrap_err();
+    assert!(matches!(error.kind(), DeserializeErrorKind::UnexpectedEndOfInput { .. }));
+
+    let error = IdSet::decode(&mut McReader::new(&[0, 3, b'a', b'!', b'b'])).unwrap_err();
+    assert!(matches!(error.kind(), DeserializeErrorKind::InvalidIdentifier(_)));
+
+    // The largest ID cannot be offset by one
+    let error = IdOr::<Identifier>::Id(u32::MAX).encode(&mut McWriter::new(&mut Vec::new()));
+    assert!(matches!(error.unwrap_err().kind(), SerializeErrorKind::IdTooLarge(u32::MAX)));
+    let max = IdOr::<Identifier>::Id(u32::MAX - 1);
+    assert_eq!(roundtrip(&max), [0xFF, 0xFF, 0xFF, 0xFF, 0x0F]);
+}
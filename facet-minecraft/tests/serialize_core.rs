@@ -0,0 +1,61 @@
+//! Verify fixed-width scalars are written in big-endian byte order.
+#![allow(clippy::float_cmp, reason = "Decoded values are exact")]
+
+use facet::Facet;
+
+/// A helper struct containing every fixed-width scalar type.
+#[derive(Debug, PartialEq, Facet)]
+struct Scalars {
+    a: i16,
+    b: u16,
+    c: i32,
+    d: u32,
+    e: i64,
+    f: u64,
+    g: f32,
+    h: f64,
+}
+
+#[test]
+fn byte_order() {
+    assert_eq!(facet_minecraft::to_vec(&0x0102_i16).unwrap(), [0x01, 0x02]);
+    assert_eq!(facet_minecraft::to_vec(&-2_i16).unwrap(), [0xFF, 0xFE]);
+    assert_eq!(facet_minecraft::to_vec(&0x0102_0304_i32).unwrap(), [1, 2, 3, 4]);
+    assert_eq!(facet_minecraft::to_vec(&-2_i32).unwrap(), [0xFF, 0xFF, 0xFF, 0xFE]);
+    assert_eq!(
+        facet_minecraft::to_vec(&0x0102_0304_0506_0708_i64).unwrap(),
+        [1, 2, 3, 4, 5, 6, 7, 8]
+    );
+    assert_eq!(
+        facet_minecraft::to_vec(&-2_i64).unwrap(),
+        [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE]
+    );
+
+    // `f32` values are not widened to `f64`
+    assert_eq!(facet_minecraft::to_vec(&1.5_f32).unwrap(), 1.5_f32.to_be_bytes());
+    assert_eq!(facet_minecraft::to_vec(&-0.1_f32).unwrap(), (-0.1_f32).to_be_bytes());
+    assert_eq!(facet_minecraft::to_vec(&1.5_f64).unwrap(), 1.5_f64.to_be_bytes());
+}
+
+#[test]
+fn roundtrip() {
+    let value = Scalars {
+        a: -0x0102,
+        b: 0x0304,
+        c: -0x0506_0708,
+        d: 0x090A_0B0C,
+        e: -0x0D0E_0F10_1112_1314,
+        f: 0x1516_1718_191A_1B1C,
+        g: 0.1,
+        h: -1234.5678,
+    };
+
+    let bytes = facet_minecraft::to_vec(&value).unwrap();
+    assert_eq!(bytes.len(), 2 + 2 + 4 + 4 + 8 + 8 + 4 + 8);
+    assert_eq!(&bytes[..4], [0xFE, 0xFE, 0x03, 0x04]);
+    assert_eq!(&bytes[28..32], 0.1_f32.to_be_bytes());
+
+    let (decoded, remaining) = facet_minecraft::from_slice::<Scalars>(&bytes).unwrap();
+    assert_eq!(decoded, value);
+    assert!(remaining.is_empty());
+}
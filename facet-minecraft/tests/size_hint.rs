@@ -5,7 +5,15 @@
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 use facet::Facet;
-use facet_minecraft::{self as mc, Serializable, common::TypeSerializeHint};
+use facet_minecraft::{
+    self as mc, Serializable,
+    common::TypeSerializeHint,
+    item::ItemStack,
+    nbt::{NbtCompound, NbtValue},
+    packet::ProtocolVersion,
+    text::{JsonTextComponent, TextComponent},
+    types::{IdOr, IdSet, Identifier, LpVec3},
+};
 use uuid::Uuid;
 
 #[test]
@@ -107,6 +115,30 @@ fn verify() {
     assert::<&Var<f64>>(TypeSerializeHint::None);
 }
 
+#[test]
+fn codec() {
+    // Size: Min `[u8; 1]`, Max `[u8; 11]`
+    assert_codec::<LpVec3>(TypeSerializeHint::Range { min: 1, max: Some(11) });
+    // Size: Min `[u8; 1]`, Max `[u8; 32770]`
+    assert_codec::<Identifier>(TypeSerializeHint::Range { min: 1, max: Some(32770) });
+
+    // Size: Min `[u8; 2]` or `[u8; 3]`, Max: Unbounded
+    assert_codec::<NbtValue>(TypeSerializeHint::Range { min: 2, max: None });
+    assert_codec::<NbtCompound>(TypeSerializeHint::Range { min: 2, max: None });
+    assert_codec::<TextComponent>(TypeSerializeHint::Range { min: 3, max: None });
+    assert_codec::<JsonTextComponent>(TypeSerializeHint::Range { min: 3, max: None });
+
+    // Size: Min `[u8; 1]`, Max: Unbounded
+    assert_codec::<IdOr<Identifier>>(TypeSerializeHint::Range { min: 1, max: None });
+    assert_codec::<IdSet>(TypeSerializeHint::Range { min: 1, max: None });
+
+    // Size: None (No hint provided)
+    assert_codec::<ItemStack>(TypeSerializeHint::None);
+
+    // Codec hints are not available at compile time
+    assert::<IdSet>(TypeSerializeHint::None);
+}
+
 // -------------------------------------------------------------------------------------------------
 
 /// A helper struct with a variable-size field.
@@ -124,3 +156,14 @@ fn assert<'facet, T: Serializable<'facet>>(hint: TypeSerializeHint) {
         T::SHAPE.ty,
     );
 }
+
+/// A helper function to verify the `McCodec::SERIALIZE_HINT` of a given type.
+fn assert_codec<'facet, T: Serializable<'facet>>(hint: TypeSerializeHint) {
+    let version = ProtocolVersion::LATEST.protocol();
+    assert_eq!(
+        hint,
+        T::serialize_hint_versioned(version),
+        "The size of {} does not match the expected value!",
+        T::SHAPE.type_name(),
+    );
+}
@@ -0,0 +1,153 @@
+//! Verify parsing and printing of stringified NBT.
+
+use facet::Facet;
+use facet_minecraft::nbt::{
+    NbtCompound, NbtList, NbtTag, NbtValue,
+    snbt::{self, SnbtErrorKind, SnbtStyle},
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn values() {
+    let value = snbt::parse(
+        r#"{
+            byte: 1b, short: -2S, int: 3, long: 4l, float: 0.5F, double: 1.5, exp: 1e3d,
+            yes: true, int_float: 2f, word: hello, "minecraft:key": 'it\'s',
+            bytes: [B; 1b, -1B], ints: [I;], longs: [L; 7L],
+            list: [1s, 2s,], empty: [], nested: [{}, {a: []}],
+        }"#,
+    )
+    .unwrap();
+
+    let root = value.as_compound().unwrap();
+    assert_eq!(root.get("byte"), Some(&NbtValue::Byte(1)));
+    assert_eq!(root.get("short"), Some(&NbtValue::Short(-2)));
+    assert_eq!(root.get("int"), Some(&NbtValue::Int(3)));
+    assert_eq!(root.get("long"), Some(&NbtValue::Long(4)));
+    assert_eq!(root.get("float"), Some(&NbtValue::Float(0.5)));
+    assert_eq!(root.get("double"), Some(&NbtValue::Double(1.5)));
+    assert_eq!(root.get("exp"), Some(&NbtValue::Double(1000.0)));
+    assert_eq!(root.get("yes"), Some(&NbtValue::Byte(1)));
+    assert_eq!(root.get("int_float"), Some(&NbtValue::Float(2.0)));
+    assert_eq!(root.get("word").and_then(NbtValue::as_str), Some("hello"));
+    assert_eq!(root.get("minecraft:key").and_then(NbtValue::as_str), Some("it's"));
+    assert_eq!(root.get("bytes"), Some(&NbtValue::ByteArray(vec![1, -1])));
+    assert_eq!(root.get("ints"), Some(&NbtValue::IntArray(vec![])));
+    assert_eq!(root.get("longs"), Some(&NbtValue::LongArray(vec![7])));
+    assert_eq!(root.get("list").and_then(NbtValue::as_list).map(NbtList::len), Some(2));
+    assert_eq!(root.get("empty").and_then(NbtValue::as_list).map(NbtList::tag), Some(NbtTag::End));
+
+    // Numbers that do not match a pattern are strings
+    for input in ["1e3", "01", "1.2.3", "+", "1bb"] {
+        assert_eq!(snbt::parse(input).unwrap(), NbtValue::from(input), "{input}");
+    }
+}
+
+#[test]
+fn print() {
+    let value: NbtValue =
+        r#"{b: 1b, a: [I; 1, 2], "key with spaces": "say \"hi\"", l: [{x: 1.0d}], f: 1.0f}"#
+            .parse()
+            .unwrap();
+
+    assert_eq!(
+        value.to_string(),
+        r#"{b:1b,a:[I;1,2],"key with spaces":'say "hi"',l:[{x:1.0d}],f:1.0f}"#
+    );
+    assert_eq!(
+        format!("{value:#}"),
+        r#"{
+    b: 1b,
+    a: [I; 1, 2],
+    "key with spaces": 'say "hi"',
+    l: [
+        {
+            x: 1.0d
+        }
+    ],
+    f: 1.0f
+}"#
+    );
+    assert_eq!(
+        snbt::value_to_string(&value, SnbtStyle::COMPACT.with_sorted_keys(true)),
+        r#"{a:[I;1,2],b:1b,f:1.0f,"key with spaces":'say "hi"',l:[{x:1.0d}]}"#
+    );
+
+    // Printed values parse back into the same value
+    for style in [SnbtStyle::COMPACT, SnbtStyle::PRETTY, SnbtStyle::PRETTY.with_indent(Some(1))] {
+        assert_eq!(snbt::parse(&snbt::value_to_string(&value, style)).unwrap(), value);
+    }
+
+    let tricky: NbtValue = [("", NbtValue::from("a'b\"c\\\n")), ("x", NbtValue::Long(i64::MIN))]
+        .into_iter()
+        .collect::<NbtCompound>()
+        .into();
+    assert_eq!(tricky.to_string(), r#"{"":"a'b\"c\\\n",x:-9223372036854775808L}"#);
+    assert_eq!(snbt::parse(&tricky.to_string()).unwrap(), tricky);
+}
+
+#[test]
+fn errors() {
+    let error = snbt::parse("{a: 1,\n  b: [1, 2b]}").unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        SnbtErrorKind::HeterogeneousList { expected: NbtTag::Int, found: NbtTag::Byte }
+    ));
+    assert_eq!((error.line(), error.column(), error.span()), (2, 10, 16..18));
+
+    let error = snbt::parse("{a: 128b}").unwrap_err();
+    assert!(matches!(error.kind(), SnbtErrorKind::InvalidNumber));
+    assert_eq!((error.line(), error.column()), (1, 5));
+
+    let error = snbt::parse("{a: 1").unwrap_err();
+    assert!(matches!(error.kind(), SnbtErrorKind::UnexpectedEndOfInput));
+
+    let error = snbt::parse("{a 1}").unwrap_err();
+    assert!(matches!(error.kind(), SnbtErrorKind::UnexpectedCharacter('1')));
+    assert_eq!(error.column(), 4);
+
+    let error = snbt::parse(r#""\q""#).unwrap_err();
+    assert!(matches!(error.kind(), SnbtErrorKind::InvalidEscape));
+    assert_eq!(error.span(), 1..3);
+
+    let error = snbt::parse("{} {}").unwrap_err();
+    assert!(matches!(error.kind(), SnbtErrorKind::TrailingCharacters));
+
+    let error = snbt::parse(&"[".repeat(1024)).unwrap_err();
+    assert!(matches!(error.kind(), SnbtErrorKind::DepthLimit));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq, Facet)]
+struct Item {
+    id: String,
+    count: i8,
+    damage: Option<i32>,
+    lore: Vec<String>,
+}
+
+#[test]
+fn typed() {
+    let item: Item =
+        snbt::from_str(r#"{id: "minecraft:stone", count: 3b, lore: [a, "b c"]}"#).unwrap();
+    assert_eq!(
+        item,
+        Item {
+            id: String::from("minecraft:stone"),
+            count: 3,
+            damage: None,
+            lore: vec![String::from("a"), String::from("b c")],
+        }
+    );
+
+    assert_eq!(
+        snbt::to_string(&item).unwrap(),
+        r#"{id:"minecraft:stone",count:3b,lore:["a","b c"]}"#
+    );
+    assert_eq!(snbt::from_str::<Item>(&snbt::to_string_pretty(&item).unwrap()).unwrap(), item);
+
+    // Suffixes must match the type
+    let error = snbt::from_str::<Item>(r#"{id: "stone", count: 3, lore: []}"#).unwrap_err();
+    assert!(matches!(error.kind(), SnbtErrorKind::Nbt(_)));
+}
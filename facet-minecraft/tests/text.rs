@@ -0,0 +1,330 @@
+//! Verify text components in both their JSON and NBT encodings.
+
+use facet::Facet;
+use facet_minecraft::{
+    Deserializable,
+    nbt::{self, NbtValue, snbt},
+    text::{
+        ClickEvent, HoverEvent, JsonTextComponent, NamedColor, NbtSource, Style, TextColor,
+        TextComponent, TextContent, TextEncoding, TextErrorKind,
+    },
+};
+use pretty_assertions::assert_eq;
+use uuid::Uuid;
+
+/// A component using every kind of content and style.
+fn example() -> TextComponent {
+    TextComponent::translatable(
+        "chat.type.text",
+        [
+            TextComponent::text("Steve").with_style(
+                Style::EMPTY
+                    .with_hover_event(HoverEvent::ShowEntity {
+                        entity_type: String::from("minecraft:player"),
+                        uuid: Uuid::from_u128(0x0011_2233_4455_6677_8899_AABB_CCDD_EEFF),
+                        name: Some(Box::new(TextComponent::text("Steve"))),
+                    })
+                    .with_click_event(ClickEvent::SuggestCommand(String::from("/msg Steve "))),
+            ),
+            TextComponent::keybind("key.jump"),
+        ],
+    )
+    .with_style(Style::EMPTY.with_color(NamedColor::Gold).with_bold(true))
+    .with_extra(" ")
+    .with_extra(TextComponent::new(TextContent::Score {
+        name: String::from("@s"),
+        objective: String::from("kills"),
+    }))
+    .with_extra(TextComponent::new(TextContent::Nbt {
+        path: String::from("Items[0]"),
+        interpret: Some(false),
+        separator: None,
+        source: NbtSource::Block(String::from("~ ~-1 ~")),
+    }))
+    .with_extra(
+        TextComponent::new(TextContent::Selector { pattern: String::from("@a"), separator: None })
+            .with_style(Style {
+                color: Some(TextColor::Rgb(0x12_34AB)),
+                shadow_color: Some(0xFF00_0000),
+                hover_event: Some(HoverEvent::ShowItem {
+                    id: String::from("minecraft:stone"),
+                    count: 2,
+                    components: None,
+                }),
+                click_event: Some(ClickEvent::ChangePage(3)),
+                ..Style::EMPTY
+            }),
+    )
+}
+
+#[test]
+fn json() {
+    let json = example().to_json();
+    assert_eq!(
+        json,
+        concat!(
+            r#"{"translate":"chat.type.text","with":[{"text":"Steve","#,
+            r#""clickEvent":{"action":"suggest_command","value":"/msg Steve "},"#,
+            r#""hoverEvent":{"action":"show_entity","contents":{"type":"minecraft:player","#,
+            r#""id":[1122867,1146447479,-2003195205,-857870593],"name":"Steve"}}},"#,
+            r#"{"keybind":"key.jump"}],"color":"gold","bold":true,"extra":[" ","#,
+            r#"{"score":{"name":"@s","objective":"kills"}},"#,
+            r#"{"nbt":"Items[0]","interpret":false,"block":"~ ~-1 ~"},"#,
+            r##"{"selector":"@a","color":"#1234AB","shadow_color":-16777216,"##,
+            r#""clickEvent":{"action":"change_page","value":"3"},"#,
+            r#""hoverEvent":{"action":"show_item","contents":{"id":"minecraft:stone","count":2}}}]}"#,
+        )
+    );
+    assert_eq!(TextComponent::from_json(&json).unwrap(), example());
+
+    // Plain text is a string, and lists are a parent followed by its children
+    assert_eq!(TextComponent::text("a\"\n").to_json(), r#""a\"\n""#);
+    assert_eq!(
+        TextComponent::from_json(r#" [ "a" , {"text": "b", "italic": true}, "1" ] "#).unwrap(),
+        TextComponent::text("a")
+            .with_extra(TextComponent::text("b").with_style(Style::EMPTY.with_italic(true)))
+            .with_extra("1"),
+    );
+    assert_eq!(TextComponent::from_json(r#""😀é""#).unwrap(), "😀é".into());
+
+    // Older `value` fields and string UUIDs are accepted
+    let legacy = TextComponent::from_json(
+        r#"{"text": "", "hoverEvent": {"action": "show_entity", "contents": {
+            "type": "minecraft:pig", "id": "00112233-4455-6677-8899-aabbccddeeff"
+        }}, "clickEvent": {"action": "change_page", "value": 7}}"#,
+    )
+    .unwrap();
+    assert!(matches!(
+        legacy.style.hover_event,
+        Some(HoverEvent::ShowEntity { uuid, .. }) if uuid.as_u128() == 0x0011_2233_4455_6677_8899_AABB_CCDD_EEFF
+    ));
+    assert_eq!(legacy.style.click_event, Some(ClickEvent::ChangePage(7)));
+}
+
+#[test]
+fn nbt() {
+    // Since 1.21.5 events use `snake_case` fields with inlined contents,
+    // and strings in lists of compounds are wrapped in a compound with an empty key
+    let modern = snbt::parse(
+        r#"{
+            text: "",
+            click_event: {action: "run_command", command: "/help"},
+            hover_event: {action: "show_item", id: "minecraft:apple", count: 3, components: {"minecraft:rarity": "epic"}},
+            extra: [{"": "a"}, {text: "b", bold: 1b}],
+        }"#,
+    )
+    .unwrap();
+    let component = TextComponent::from_nbt(&modern).unwrap();
+    assert_eq!(component.style.click_event, Some(ClickEvent::RunCommand(String::from("/help"))));
+    assert!(matches!(
+        &component.style.hover_event,
+        Some(HoverEvent::ShowItem { id, count: 3, components: Some(components) })
+            if id == "minecraft:apple" && components.len() == 1
+    ));
+    assert_eq!(
+        component.extra,
+        [
+            TextComponent::text("a"),
+            TextComponent::text("b").with_style(Style::EMPTY.with_bold(true))
+        ]
+    );
+    assert_eq!(component.to_nbt(TextEncoding::Nbt), modern);
+
+    // Between 1.20.3 and 1.21.5 events use `camelCase` fields
+    assert_eq!(
+        component.to_nbt(TextEncoding::LegacyNbt).to_string(),
+        concat!(
+            r#"{text:"",clickEvent:{action:"run_command",value:"/help"},"#,
+            r#"hoverEvent:{action:"show_item",contents:{id:"minecraft:apple",count:3,"#,
+            r#"components:{"minecraft:rarity":"epic"}}},"#,
+            r#"extra:[{"":"a"},{text:"b",bold:1b}]}"#,
+        )
+    );
+
+    for encoding in [TextEncoding::LegacyNbt, TextEncoding::Nbt] {
+        let value = example().to_nbt(encoding);
+        assert_eq!(TextComponent::from_nbt(&value).unwrap(), example(), "{encoding:?}");
+    }
+}
+
+#[derive(Debug, PartialEq, Facet)]
+struct Disconnect {
+    reason: TextComponent,
+}
+
+#[derive(Debug, PartialEq, Facet)]
+struct LegacyDisconnect {
+    reason: JsonTextComponent,
+}
+
+#[test]
+fn wire() {
+    assert_eq!(TextEncoding::for_protocol(764), TextEncoding::Json);
+    assert_eq!(TextEncoding::for_protocol(765), TextEncoding::LegacyNbt);
+    assert_eq!(TextEncoding::for_protocol(770), TextEncoding::Nbt);
+
+    // Plain text is a nameless `TAG_String`
+    let packet = Disconnect { reason: TextComponent::text("Bye") };
+    let bytes = facet_minecraft::to_vec(&packet).unwrap();
+    assert_eq!(bytes, b"\x08\x00\x03Bye");
+    assert_eq!(Disconnect::from_slice(&bytes).unwrap().0, packet);
+
+    let packet = Disconnect { reason: example() };
+    let bytes = facet_minecraft::to_vec(&packet).unwrap();
+    assert_eq!(nbt::from_nbt::<NbtValue>(&bytes).unwrap().0, example().to_nbt(TextEncoding::Nbt));
+    assert_eq!(Disconnect::from_slice(&bytes).unwrap().0, packet);
+
+    // Older versions use a JSON string
+    let packet = LegacyDisconnect { reason: JsonTextComponent(TextComponent::text("Bye")) };
+    let bytes = facet_minecraft::to_vec(&packet).unwrap();
+    assert_eq!(bytes, b"\x05\"Bye\"");
+    assert_eq!(LegacyDisconnect::from_slice(&bytes).unwrap().0, packet);
+
+    let packet = LegacyDisconnect { reason: JsonTextComponent(example()) };
+    let bytes = facet_minecraft::to_vec(&packet).unwrap();
+    assert_eq!(LegacyDisconnect::from_slice(&bytes).unwrap().0, packet);
+}
+
+#[test]
+fn versioned() {
+    let packet = Disconnect { reason: example() };
+    let roundtrip = |version: u32| {
+        let bytes = facet_minecraft::to_vec_versioned(&packet, version).unwrap();
+        let (decoded, remaining) =
+            facet_minecraft::from_slice_versioned::<Disconnect>(&bytes, version).unwrap();
+        assert_eq!(decoded, packet);
+        assert!(remaining.is_empty());
+        bytes
+    };
+
+    // JSON before 1.20.3
+    let bytes = roundtrip(760);
+    let json = facet_minecraft::to_vec(&JsonTextComponent(example())).unwrap();
+    assert_eq!(bytes, json);
+    assert_eq!(roundtrip(764), json);
+
+    // NBT with `camelCase` event fields until 1.21.5
+    for version in [765, 767] {
+        let bytes = roundtrip(version);
+        let value = nbt::from_nbt::<NbtValue>(&bytes).unwrap().0;
+        assert_eq!(value, example().to_nbt(TextEncoding::LegacyNbt), "{version}");
+    }
+
+    // NBT with `snake_case` event fields since
+    let bytes = roundtrip(770);
+    assert_eq!(nbt::from_nbt::<NbtValue>(&bytes).unwrap().0, example().to_nbt(TextEncoding::Nbt));
+    assert_ne!(example().to_nbt(TextEncoding::LegacyNbt), example().to_nbt(TextEncoding::Nbt));
+}
+
+#[test]
+fn errors() {
+    let kind = |json: &str| TextComponent::from_json(json).unwrap_err().to_string();
+
+    assert_eq!(kind(r#"{"text": "a""#), "invalid JSON at offset 12");
+    assert_eq!(kind(r#"{"text": "a"} x"#), "invalid JSON at offset 14");
+    assert_eq!(kind("null"), "invalid JSON at offset 0");
+    assert_eq!(kind("[]"), "expected a string, list or compound");
+    assert_eq!(kind(r#"{"bold": true}"#), "component has no known content");
+    assert_eq!(kind(r#"{"text": 1}"#), "invalid field `text`");
+    assert_eq!(kind(r#"{"translate": "a", "with": 1}"#), "invalid field `with`");
+    assert_eq!(kind(r#"{"nbt": "a"}"#), "missing field `source`");
+    assert_eq!(kind(r##"{"text": "", "color": "#12345"}"##), "unknown color `#12345`");
+    assert_eq!(
+        kind(r#"{"text": "", "clickEvent": {"action": "explode", "value": ""}}"#),
+        "unknown action `explode`"
+    );
+    assert_eq!(kind(&"[".repeat(1024)), "maximum nesting depth exceeded");
+
+    let error = TextComponent::from_nbt(&NbtValue::Int(1)).unwrap_err();
+    assert!(matches!(error.kind(), TextErrorKind::InvalidComponent));
+}
+
+#[test]
+fn colors() {
+    for (index, color) in NamedColor::ALL.into_iter().enumerate() {
+        assert_eq!(NamedColor::from_code(color.code()), Some(color));
+        assert_eq!(NamedColor::from_name(color.name()), Some(color));
+        assert_eq!(color.code().to_digit(16), u32::try_from(index).ok());
+    }
+    assert_eq!(NamedColor::from_code('A'), Some(NamedColor::Green));
+    assert_eq!(TextColor::parse("#00ff7F"), Some(TextColor::Rgb(0x00_FF7F)));
+    assert_eq!(TextColor::Rgb(0x00_FF7F).to_string(), "#00FF7F");
+    assert_eq!(TextColor::parse("light_purple").map(TextColor::rgb), Some(0xFF_55FF));
+    assert_eq!(TextColor::parse("#+0ff7F"), None);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[test]
+fn legacy() {
+    let component =
+        TextComponent::from_legacy("§6Gold §lbold§r plain §x§1§2§a§B§c§dhex §OItalic§z!");
+    assert_eq!(
+        component.extra,
+        [
+            TextComponent::text("Gold ").with_style(Style::EMPTY.with_color(NamedColor::Gold)),
+            TextComponent::text("bold")
+                .with_style(Style::EMPTY.with_color(NamedColor::Gold).with_bold(true)),
+            TextComponent::text(" plain "),
+            TextComponent::text("hex ")
+                .with_style(Style::EMPTY.with_color(TextColor::Rgb(0x12_ABCD))),
+            TextComponent::text("Italic!")
+                .with_style(Style::EMPTY.with_color(TextColor::Rgb(0x12_ABCD)).with_italic(true)),
+        ]
+    );
+    assert_eq!(component.to_string(), "Gold bold plain hex Italic!");
+    assert_eq!(component.to_legacy(), "§6Gold §lbold§r plain §x§1§2§a§b§c§dhex §oItalic!");
+
+    // Strings without formatting are plain text
+    assert_eq!(TextComponent::from_legacy("plain"), TextComponent::text("plain"));
+    assert_eq!(TextComponent::from_legacy("§r§"), TextComponent::text(""));
+    // Colors reset formatting, and an invalid hex color is ignored
+    assert_eq!(TextComponent::from_legacy("§l§cA§xB§lC").to_legacy(), "§cAB§lC");
+    assert_eq!(TextComponent::from_legacy_with("&aA&&b", '&').to_legacy_with('&'), "&aAb");
+
+    // Removing a flag requires resetting the color
+    let component = TextComponent::text("a")
+        .with_style(Style::EMPTY.with_color(NamedColor::Red).with_bold(true).with_italic(true))
+        .with_extra(TextComponent::text("b").with_style(Style::EMPTY.with_bold(false)))
+        .with_extra(TextComponent::text("c").with_style(Style { color: None, ..Style::EMPTY }));
+    assert_eq!(component.to_legacy(), "§c§l§oa§c§ob§lc");
+    assert_eq!(
+        TextComponent::from_legacy(&component.to_legacy()).to_legacy(),
+        component.to_legacy()
+    );
+}
+
+#[test]
+fn render() {
+    let component = TextComponent::translatable(
+        "chat.type.text",
+        [
+            TextComponent::text("Steve").with_style(Style::EMPTY.with_color(NamedColor::Aqua)),
+            TextComponent::text("hello ")
+                .with_extra(TextComponent::text("world").with_style(
+                    Style::EMPTY.with_bold(true).with_color(TextColor::Rgb(0x10_2030)),
+                )),
+        ],
+    );
+    assert_eq!(component.to_string(), "<Steve> hello world");
+    assert_eq!(
+        component.to_ansi_string(),
+        "<\x1b[0;96mSteve\x1b[0m> hello \x1b[0;1;38;2;16;32;48mworld\x1b[0m"
+    );
+
+    // Fallbacks support positional arguments and escaped percent signs
+    let component = TextComponent::new(TextContent::Translatable {
+        key: String::from("custom.key"),
+        fallback: Some(String::from("%2$s is 100%% %1$s%3$s %q")),
+        with: vec![TextComponent::text("great"), TextComponent::keybind("key.jump")],
+    });
+    assert_eq!(component.to_string(), "key.jump is 100% great %q");
+
+    // Unknown keys are shown as-is, and unresolved scores are empty
+    let component =
+        TextComponent::translatable("unknown.key", ["a"]).with_extra(TextComponent::new(
+            TextContent::Score { name: String::from("@s"), objective: String::new() },
+        ));
+    assert_eq!(component.to_string(), "unknown.key");
+    assert_eq!(TextComponent::text("plain").to_ansi_string(), "plain");
+}